@@ -0,0 +1,85 @@
+//! Example plugin implementing every capability, it serves a fake 32-bit process from its own
+//! memory. Build it with `cargo build --example plugin` and set `plugin_path` to the library.
+
+// The functions have the signatures of the plugin API, which YClass calls as safe `fn`s.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::{ffi::c_char, sync::Mutex};
+
+/// Address of the fake process' memory.
+const BASE: usize = 0x10000;
+const SIZE: usize = 0x100;
+
+/// Readable and writable.
+const PROTECTION: u64 = 1 | 2;
+
+static MEMORY: Mutex<[u8; SIZE]> = Mutex::new([0; SIZE]);
+// `count` and one region, read by the caller after `yc_maps` returns.
+static MAPS: [u64; 4] = [1, BASE as u64, (BASE + SIZE) as u64, PROTECTION];
+
+/// Memory at `address` if `len` bytes are in the fake process.
+fn offset(address: usize, len: usize) -> Option<usize> {
+    let offset = address.checked_sub(BASE)?;
+    (offset.checked_add(len)? <= SIZE).then_some(offset)
+}
+
+#[no_mangle]
+pub fn yc_attach(_pid: u32) -> u32 {
+    let mut memory = MEMORY.lock().unwrap();
+    for (i, b) in memory.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    0
+}
+
+#[no_mangle]
+pub fn yc_read(address: usize, buffer: *mut u8, buffer_size: usize) -> u32 {
+    let Some(offset) = offset(address, buffer_size) else {
+        return 1;
+    };
+    let memory = MEMORY.lock().unwrap();
+    // SAFETY: The caller passes a buffer of `buffer_size` bytes.
+    unsafe { std::ptr::copy_nonoverlapping(memory[offset..].as_ptr(), buffer, buffer_size) };
+    0
+}
+
+#[no_mangle]
+pub fn yc_write(address: usize, buffer: *const u8, buffer_size: usize) -> u32 {
+    let Some(offset) = offset(address, buffer_size) else {
+        return 1;
+    };
+    let mut memory = MEMORY.lock().unwrap();
+    // SAFETY: The caller passes a buffer of `buffer_size` bytes.
+    unsafe { std::ptr::copy_nonoverlapping(buffer, memory[offset..].as_mut_ptr(), buffer_size) };
+    0
+}
+
+#[no_mangle]
+pub fn yc_can_read(address: usize) -> bool {
+    offset(address, 1).is_some()
+}
+
+#[no_mangle]
+pub fn yc_maps() -> *const u8 {
+    MAPS.as_ptr().cast()
+}
+
+#[no_mangle]
+pub fn yc_name() -> *const c_char {
+    c"example".as_ptr()
+}
+
+#[no_mangle]
+pub fn yc_pointer_size() -> usize {
+    4
+}
+
+#[no_mangle]
+pub fn yc_capabilities() -> u32 {
+    1 | 2 | 4 | 8 | 16
+}
+
+#[no_mangle]
+pub fn yc_detach() {
+    MEMORY.lock().unwrap().fill(0);
+}
@@ -13,7 +13,7 @@ struct Foo {
 }
 
 fn main() {
-    #[allow(clippy::disallowed_names)]
+    #[allow(clippy::disallowed_names, clippy::manual_c_str_literals)]
     let foo = Foo {
         values: repeat_with(|| fastrand::u16(..255))
             .take(10)
@@ -1,13 +1,161 @@
 //! This module parses addresses.
 //! All numbers are parsed as hexidecimals.
 //! Expected Syntax:
-//! * `[0xAABB]` loads 8 bytes at address `0xAABB`.
-//! * `<module.exe>` loads address of the `module.exe`.
+//! * `[0xAABB]` loads a pointer at address `0xAABB`.
+//! * `game.exe` or `<game.exe>` loads base address of the `game.exe` module, names that
+//!   look like numbers or contain operators have to be wrapped in `<>`.
+//! * `Player::vtable` or `<Player::vtable>` is replaced by the named address, names take
+//!   precedence over modules.
+//! * `(expr)` groups expressions.
 //!
-//! Allowed operations are would be: `+`, `-`, `*`.
+//! Allowed operations are `+`, `-` and `*`, i.e. `[game.exe+1A3F20]+0x10`.
 
-// TODO(ItsEthra): Parse address (with `nom` crate maybe?) to allow special syntax like adding, dereferencing
-// pointers and getting modules' addresses.
+use crate::{named_addresses::NamedAddressRegistry, process::Process};
+use eyre::{bail, eyre, Result};
+
+/// Parses a plain hex number with an optional `0x` prefix.
 pub fn parse_address(addr: &str) -> Option<usize> {
-    usize::from_str_radix(addr.trim().strip_prefix("0x").unwrap_or(addr), 16).ok()
+    let addr = addr.trim();
+    usize::from_str_radix(addr.strip_prefix("0x").unwrap_or(addr), 16).ok()
+}
+
+/// Evaluates an address expression, module bases and pointers are read from `process`.
+pub fn evaluate_address(
+    expr: &str,
+    process: &Process,
+    names: &NamedAddressRegistry,
+) -> Result<usize> {
+    let mut parser = Parser {
+        src: expr,
+        pos: 0,
+        process,
+        names,
+    };
+
+    let value = parser.expr()?;
+    match parser.peek() {
+        Some(c) => bail!("Unexpected `{c}` at {}", parser.pos),
+        None => Ok(value),
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    process: &'a Process,
+    names: &'a NamedAddressRegistry,
+}
+
+impl Parser<'_> {
+    /// Skips whitespace and returns the next character.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+        self.src[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            return Ok(());
+        }
+
+        match self.peek() {
+            Some(found) => bail!("Expected `{c}` at {}, found `{found}`", self.pos),
+            None => bail!("Expected `{c}` at the end"),
+        }
+    }
+
+    /// Takes characters until `pred` fails.
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        let len = self.src[start..]
+            .find(|c| !pred(c))
+            .unwrap_or(self.src.len() - start);
+        self.pos += len;
+        &self.src[start..self.pos]
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<usize> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value = value.wrapping_add(self.term()?);
+            } else if self.eat('-') {
+                value = value.wrapping_sub(self.term()?);
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    // term := atom ('*' atom)*
+    fn term(&mut self) -> Result<usize> {
+        let mut value = self.atom()?;
+        while self.eat('*') {
+            value = value.wrapping_mul(self.atom()?);
+        }
+        Ok(value)
+    }
+
+    // atom := '[' expr ']' | '(' expr ')' | '<' name '>' | number | name
+    fn atom(&mut self) -> Result<usize> {
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                let address = self.expr()?;
+                self.expect(']')?;
+
+                if !self.process.can_read(address) {
+                    bail!("Can't read memory at address {address:#X}");
+                }
+                Ok(self.process.read_pointer(address))
+            }
+            Some('(') => {
+                self.pos += 1;
+                let value = self.expr()?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Some('<') => {
+                self.pos += 1;
+                let name = self.take_while(|c| c != '>').trim().to_owned();
+                self.expect('>')?;
+                self.name(&name)
+            }
+            Some(c) if is_word_char(c) => {
+                let word = self.take_while(is_word_char);
+                match parse_address(word) {
+                    Some(value) => Ok(value),
+                    None => {
+                        let word = word.to_owned();
+                        self.name(&word)
+                    }
+                }
+            }
+            Some(c) => bail!("Unexpected `{c}` at {}", self.pos),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+
+    /// Named address or base of the module.
+    fn name(&self, name: &str) -> Result<usize> {
+        self.names
+            .address(name)
+            .or_else(|| self.process.module_base(name))
+            .ok_or_else(|| eyre!("`{name}` is not a named address or a loaded module"))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '.' || c == '_' || c == ':'
 }
@@ -1,23 +1,32 @@
 use crate::{
+    class::Class,
     context::Selection,
-    field::allocate_padding,
-    gui::{ClassListPanel, InspectorPanel, ToolBarPanel, ToolBarResponse},
+    field::{allocate_padding, EmbeddedClassField, Field, FieldId, FieldKind, SectionField},
+    gui::{
+        size_warning, AttachTarget, ClassListPanel, InspectorPanel, ToolBarPanel, ToolBarResponse,
+    },
+    history::Command,
+    plugin_watcher::PluginWatcher,
     process::Process,
-    state::StateRef,
+    state::{GlobalState, StateRef},
 };
 use eframe::{
     egui::{Context, ViewportCommand},
-    epaint::Color32,
     App, Frame,
 };
 use std::collections::HashSet;
-use std::{sync::Once, time::Duration};
+use std::{
+    sync::Once,
+    time::{Duration, Instant},
+};
 
 pub struct YClassApp {
     class_list: ClassListPanel,
     inspector: InspectorPanel,
     tool_bar: ToolBarPanel,
     state: StateRef,
+    last_auto_save: Instant,
+    plugin_watcher: PluginWatcher,
 }
 
 impl YClassApp {
@@ -27,6 +36,8 @@ impl YClassApp {
             inspector: InspectorPanel::new(state),
             tool_bar: ToolBarPanel::new(state),
             state,
+            last_auto_save: Instant::now(),
+            plugin_watcher: PluginWatcher::default(),
         }
     }
 
@@ -45,6 +56,7 @@ impl YClassApp {
                     .map(|s| s.container_id)
                     .or_else(|| state.class_list.selected())
                 {
+                    state.record_edit(cid);
                     let class = state.class_list.by_id_mut(cid).unwrap();
                     class.fields.extend(allocate_padding(n));
 
@@ -54,16 +66,19 @@ impl YClassApp {
             Some(ToolBarResponse::Remove(n)) => {
                 let state = &mut *self.state.borrow_mut();
 
-                if let Some(Selection {
+                if !state.multi_selection.is_empty() {
+                    remove_selected_fields(state);
+                } else if let Some(Selection {
                     container_id,
                     field_id,
                     ..
                 }) = state.selection
                 {
+                    state.record_edit(container_id);
                     let class = state.class_list.by_id_mut(container_id).unwrap();
+                    let fields = class.fields_with_mut(field_id).unwrap();
                     let mut discrd_sel = false;
-                    let pos = class
-                        .fields
+                    let pos = fields
                         .iter()
                         .position(|f| {
                             discrd_sel |= state
@@ -78,10 +93,10 @@ impl YClassApp {
                         state.selection = None;
                     }
 
-                    let from = pos.min(class.fields.len());
-                    let to = (pos + n).min(class.fields.len());
+                    let from = pos.min(fields.len());
+                    let to = (pos + n).min(fields.len());
 
-                    class.fields.drain(from..to);
+                    fields.drain(from..to);
                     state.dummy = false;
                 }
             }
@@ -94,78 +109,126 @@ impl YClassApp {
                     ..
                 }) = state.selection
                 {
+                    state.record_edit(container_id);
                     let class = state.class_list.by_id_mut(container_id).unwrap();
-                    let pos = class
-                        .fields
-                        .iter()
-                        .position(|f| f.id() == field_id)
-                        .unwrap();
+                    let fields = class.fields_with_mut(field_id).unwrap();
+                    let pos = fields.iter().position(|f| f.id() == field_id).unwrap();
                     let mut padding = allocate_padding(n);
 
                     while let Some(field) = padding.pop() {
-                        class.fields.insert(pos, field);
+                        fields.insert(pos, field);
                     }
 
                     state.dummy = false;
                 }
             }
-            Some(ToolBarResponse::ChangeKind(new)) => {
+            Some(ToolBarResponse::FillToSize(target)) => {
                 let state = &mut *self.state.borrow_mut();
 
-                if let Some(Selection {
-                    container_id,
-                    field_id,
-                    ..
-                }) = state.selection
-                {
-                    let class = state.class_list.by_id_mut(container_id).unwrap();
-                    let pos = class
-                        .fields
-                        .iter()
-                        .position(|f| f.id() == field_id)
-                        .unwrap();
-
-                    let (old_size, old_name) = (class.fields[pos].size(), class.fields[pos].name());
-                    if old_size > new.size() {
-                        let mut padding = allocate_padding(old_size - new.size());
-                        class.fields[pos] = new.into_field(old_name);
-                        while let Some(pad) = padding.pop() {
-                            class.fields.insert(pos + 1, pad);
-                        }
-
-                        state.selection.as_mut().unwrap().field_id = class.fields[pos].id();
-                    } else {
-                        let (mut steal_size, mut steal_len) = (0, 0);
-                        while steal_size < new.size() {
-                            if pos >= class.fields.len() {
-                                break;
+                if let Some(cid) = state.class_list.selected() {
+                    let command = Command::edit_class(&state.class_list, cid);
+                    match state.class_list.fill_to_size(cid, target) {
+                        Ok(added) => {
+                            if let Some(command) = command.filter(|_| added > 0) {
+                                state.history.push(command);
+                                state.dummy = false;
                             }
-
-                            let index = pos + steal_len;
-                            if index >= class.fields.len() {
-                                break;
+                            // Padding reaches any size, but the class may not end up aligned.
+                            if let Some((_, warning)) =
+                                size_warning(target, state.class_list.pointer_size())
+                            {
+                                state
+                                    .toasts
+                                    .warning(format!("{warning}, 0x{target:X} bytes"));
                             }
-
-                            steal_size += class.fields[index].size();
-                            steal_len += 1;
                         }
+                        Err(e) => {
+                            state.toasts.error(e.to_string());
+                        }
+                    }
+                }
+            }
+            Some(ToolBarResponse::ChangeKind(new)) => {
+                let state = &mut *self.state.borrow_mut();
+                let pointer_size = state.class_list.pointer_size();
+                replace_selected_fields(state, new.size(pointer_size), |name| {
+                    new.clone().into_field(name, pointer_size)
+                });
+            }
+            Some(ToolBarResponse::Reorder { from_id, to_id }) => {
+                let state = &mut *self.state.borrow_mut();
+                reorder_field(state, from_id, to_id);
+            }
+            Some(ToolBarResponse::SecondaryAttach(target)) => {
+                let mut state = self.state.borrow_mut();
 
-                        if steal_size < new.size() {
-                            state.toasts.error("Not enough space for a new field");
-                        } else {
-                            class.fields.drain(pos..pos + steal_len);
-                            let mut padding = allocate_padding(steal_size - new.size());
-                            class.fields.insert(pos, new.into_field(old_name));
-
-                            while let Some(pad) = padding.pop() {
-                                class.fields.insert(pos + 1, pad);
-                            }
-
-                            state.selection.as_mut().unwrap().field_id = class.fields[pos].id();
+                let result = match &target {
+                    AttachTarget::Process(pid) => Process::attach(*pid, &state.config),
+                    AttachTarget::Remote(address) => Process::connect(address),
+                };
+                match result {
+                    Ok(proc) => {
+                        let primary_size = state.process.read().as_ref().map(|p| p.pointer_size());
+                        if primary_size.is_some_and(|size| size != proc.pointer_size()) {
+                            state
+                                .toasts
+                                .warning("Secondary process has a different pointer size");
                         }
+                        *state.secondary_process.write() = Some(proc);
                     }
+                    Err(e) => {
+                        state
+                            .toasts
+                            .error(format!("Failed to attach the secondary process.\n{e}"));
+                    }
+                }
+            }
+            Some(ToolBarResponse::SecondaryDetach) => {
+                *self.state.borrow().secondary_process.write() = None;
+            }
+            Some(ToolBarResponse::FindReferences(address)) => {
+                self.tool_bar.find_references(address);
+            }
+            Some(ToolBarResponse::BatchRename) => {
+                self.tool_bar.batch_rename();
+            }
+            Some(ToolBarResponse::SwitchToClass(cid)) => {
+                let state = &mut *self.state.borrow_mut();
+                *state.class_list.selected_mut() = Some(cid);
+                state.selection = None;
+                state.multi_selection.clear();
+            }
+            Some(ToolBarResponse::ViewHex(address)) => {
+                self.tool_bar.view_hex(address);
+            }
+            #[cfg(feature = "disasm")]
+            Some(ToolBarResponse::ViewCode(address)) => {
+                self.tool_bar.view_code(address);
+            }
+            Some(ToolBarResponse::Group) => {
+                let state = &mut *self.state.borrow_mut();
+                group_selected_fields(state);
+            }
+            Some(ToolBarResponse::Section) => {
+                let state = &mut *self.state.borrow_mut();
+                section_selected_fields(state);
+            }
+            Some(ToolBarResponse::Embed(cid)) => {
+                let state = &mut *self.state.borrow_mut();
 
-                    state.dummy = false;
+                if let Some(container_id) = state.selection.map(|s| s.container_id) {
+                    if state.class_list.depends_on(cid, container_id) {
+                        state.toasts.error("Class can't be embedded into itself");
+                    } else {
+                        let size = state.class_list.class_size(cid);
+                        replace_selected_fields(state, size, |name| {
+                            Box::new(EmbeddedClassField::new(
+                                name.unwrap_or_else(|| "embedded".into()),
+                                cid,
+                                size,
+                            ))
+                        });
+                    }
                 }
             }
             Some(ToolBarResponse::ProcessDetach) => {
@@ -177,6 +240,8 @@ impl YClassApp {
                     .try_write()
                 {
                     *process = None;
+                    state.frozen.clear();
+                    state.reader.clear();
                     ctx.send_viewport_cmd(ViewportCommand::Title("YClass".to_owned()));
                 } else {
                     state.toasts.warning("Process is currently in use");
@@ -195,7 +260,7 @@ impl YClassApp {
                             ctx.send_viewport_cmd(ViewportCommand::Title(format!(
                                 "YClass - Attached to {pid}"
                             )));
-                            if let Process::Internal((op, _)) = &proc {
+                            if let Process::Internal((op, ..)) = &proc {
                                 match op.name() {
                                     Ok(name) => {
                                         state.config.last_attached_process_name = Some(name);
@@ -210,6 +275,8 @@ impl YClassApp {
                             }
 
                             *process = Some(proc);
+                            state.frozen.clear();
+                            state.reader.clear();
                         }
                         Err(e) => {
                             state.toasts.error(format!(
@@ -221,6 +288,33 @@ impl YClassApp {
                     state.toasts.warning("Process is currently in use");
                 }
             }
+            Some(ToolBarResponse::RemoteConnect(address)) => {
+                let mut state = self.state.borrow_mut();
+
+                if let Some(mut process) = state.process.clone().try_write() {
+                    match Process::connect(&address) {
+                        Ok(proc) => {
+                            ctx.send_viewport_cmd(ViewportCommand::Title(format!(
+                                "YClass - Connected to {address}"
+                            )));
+
+                            state.config.last_remote_address = Some(address);
+                            state.config.save();
+
+                            *process = Some(proc);
+                            state.frozen.clear();
+                            state.reader.clear();
+                        }
+                        Err(e) => {
+                            state
+                                .toasts
+                                .error(format!("Failed to connect to {address}.\n{e}"));
+                        }
+                    }
+                } else {
+                    state.toasts.warning("Process is currently in use");
+                }
+            }
             Some(ToolBarResponse::MinidumpLoad(path)) => {
                 let mut state = self.state.borrow_mut();
 
@@ -248,6 +342,8 @@ impl YClassApp {
                             state.config.save();
 
                             *process = Some(proc);
+                            state.frozen.clear();
+                            state.reader.clear();
                         }
                         Err(e) => {
                             state
@@ -266,13 +362,35 @@ impl YClassApp {
 
 impl App for YClassApp {
     fn update(&mut self, ctx: &Context, frame: &mut Frame) {
-        ctx.request_repaint_after(Duration::from_millis(100));
+        if let Some(interval) = self.state.borrow().config.update_interval() {
+            ctx.request_repaint_after(interval);
+        }
 
         static DPI_INIT: Once = Once::new();
         DPI_INIT.call_once(|| {
             let dpi = self.state.borrow().config.dpi.unwrap_or(1.);
             ctx.set_pixels_per_point(dpi);
         });
+        self.state.borrow().config.theme().apply(ctx);
+
+        {
+            let state = &mut *self.state.borrow_mut();
+            // Opened projects may have been made for a process of another width.
+            if let Some(size) = state.process.read().as_ref().map(Process::pointer_size) {
+                state.class_list.set_pointer_size(size);
+            }
+            state.class_list.refresh_sizes();
+            state.write_frozen();
+            state.record_values();
+            self.plugin_watcher.update(state);
+
+            if let Some(secs) = state.config.auto_save_interval_secs.filter(|s| *s > 0) {
+                if self.last_auto_save.elapsed() >= Duration::from_secs(secs) {
+                    state.auto_save();
+                    self.last_auto_save = Instant::now();
+                }
+            }
+        }
 
         let res = self.tool_bar.show(ctx);
         self.handle_reponse(ctx, frame, res);
@@ -281,18 +399,358 @@ impl App for YClassApp {
 
         let res = self.inspector.show(ctx);
         self.handle_reponse(ctx, frame, res);
-
-        let mut style = (*ctx.style()).clone();
-        let saved = style.clone();
-        style.visuals.widgets.noninteractive.bg_fill = Color32::from_rgb(0x10, 0x10, 0x10);
-        style.visuals.widgets.noninteractive.fg_stroke.color = Color32::LIGHT_GRAY;
-        ctx.set_style(style);
-
-        self.state.borrow_mut().toasts.show(ctx);
-        ctx.set_style(saved);
+        // Dropped if nothing was there to rename.
+        self.state.borrow_mut().rename_requested = false;
+
+        let state = &mut *self.state.borrow_mut();
+        let saved = ctx.style().visuals.clone();
+        ctx.set_visuals(state.config.theme().toast_visuals());
+        state.toasts.show(ctx);
+        ctx.set_visuals(saved);
     }
 }
 
 pub fn is_valid_ident(name: &str) -> bool {
     !name.starts_with(char::is_numeric) && !name.contains(char::is_whitespace) && !name.is_empty()
 }
+
+/// Moves the field `from_id` to the position of `to_id`, both have to be in the same class and
+/// section. Fields in between shift by the size of the moved field, so the class size stays the same.
+fn reorder_field(state: &mut GlobalState, from_id: FieldId, to_id: FieldId) {
+    let Some(cid) = state
+        .class_list
+        .classes()
+        .iter()
+        .find(|c| c.fields_with(from_id).is_some())
+        .map(|c| c.id())
+    else {
+        return;
+    };
+
+    let fields = state
+        .class_list
+        .by_id(cid)
+        .unwrap()
+        .fields_with(from_id)
+        .unwrap();
+    let position = |id| fields.iter().position(|f| f.id() == id);
+    let (Some(from), Some(to)) = (position(from_id), position(to_id)) else {
+        state
+            .toasts
+            .error("Fields can only be moved within the class or section they're in");
+        return;
+    };
+    if !fits_over_padding(fields, from, to) {
+        state.toasts.error(
+            "The field doesn't fit there, it can only replace hex fields so others keep their offsets",
+        );
+        return;
+    }
+
+    state.record_edit(cid);
+    let class = state.class_list.by_id_mut(cid).unwrap();
+    move_over_padding(class.fields_with_mut(from_id).unwrap(), from, to);
+
+    // Selection holds the address of the field, which is no longer valid.
+    state.clear_selection();
+    state.dummy = false;
+}
+
+/// Offsets of the fields from the start of the list.
+fn field_offsets(fields: &[Box<dyn Field>]) -> Vec<usize> {
+    fields
+        .iter()
+        .scan(0, |offset, f| {
+            let start = *offset;
+            *offset += f.size();
+            Some(start)
+        })
+        .collect()
+}
+
+/// Returns `true` if the field at `from` can be moved to the offset of the field at `to` by
+/// replacing only hex fields, its own bytes are free once it's moved.
+fn fits_over_padding(fields: &[Box<dyn Field>], from: usize, to: usize) -> bool {
+    let offsets = field_offsets(fields);
+    let start = offsets[to];
+    let end = start + fields[from].size();
+    let total = offsets
+        .last()
+        .map_or(0, |o| o + fields.last().unwrap().size());
+
+    end <= total
+        && fields.iter().zip(&offsets).enumerate().all(|(i, (f, &o))| {
+            i == from
+                || o + f.size() <= start
+                || o >= end
+                || matches!(
+                    f.kind(),
+                    FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64
+                )
+        })
+}
+
+/// Moves the field at `from` to the offset of the field at `to`, other fields keep their
+/// offsets. Its old place is filled with padding and it replaces the hex fields it's moved over,
+/// which must be checked with [`fits_over_padding`].
+fn move_over_padding(fields: &mut Vec<Box<dyn Field>>, from: usize, to: usize) {
+    let start = field_offsets(fields)[to];
+    let field = fields.remove(from);
+    let size = field.size();
+    fields.splice(from..from, allocate_padding(size));
+
+    let offsets = field_offsets(fields);
+    let first = offsets.iter().rposition(|&o| o <= start).unwrap();
+    let last = offsets.iter().rposition(|&o| o < start + size).unwrap();
+    let head = start - offsets[first];
+    let tail = offsets[last] + fields[last].size() - (start + size);
+
+    let mut replacement = allocate_padding(head);
+    replacement.push(field);
+    replacement.extend(allocate_padding(tail));
+    fields.splice(first..=last, replacement);
+}
+
+/// Removes the selected field along with the multi-selection.
+fn remove_selected_fields(state: &mut GlobalState) {
+    let Some(container_id) = state.selection.map(|s| s.container_id) else {
+        return;
+    };
+
+    let ids = state.selected_fields();
+    state.record_edit(container_id);
+    let class = state.class_list.by_id_mut(container_id).unwrap();
+    if let Some(fields) = ids.first().and_then(|id| class.fields_with_mut(*id)) {
+        fields.retain(|f| !ids.contains(&f.id()));
+    }
+
+    state.clear_selection();
+    state.dummy = false;
+}
+
+/// Moves fields from the first to the last selected one into a new class and embeds it in their place.
+fn group_selected_fields(state: &mut GlobalState) {
+    let Some(container_id) = state.selection.map(|s| s.container_id) else {
+        return;
+    };
+
+    let ids = state.selected_fields();
+    let class = state.class_list.by_id(container_id).unwrap();
+    let Some((from, to)) = selected_range(class, &ids) else {
+        return;
+    };
+
+    let mut name = format!("{}Group", class.name);
+    for i in 1.. {
+        if state.class_list.by_name(&name).is_none() {
+            break;
+        }
+        name = format!("{}Group{i}", class.name);
+    }
+
+    let edit = Command::edit_class(&state.class_list, container_id);
+    let group_id = state.class_list.add_empty_class(name);
+
+    let class = state.class_list.by_id_mut(container_id).unwrap();
+    let container = class.fields_with_mut(ids[0]).unwrap();
+    let fields = container.drain(from..=to).collect::<Vec<_>>();
+    let size = fields.iter().map(|f| f.size()).sum();
+    container.insert(
+        from,
+        Box::new(EmbeddedClassField::new("group".into(), group_id, size)),
+    );
+    state.class_list.by_id_mut(group_id).unwrap().fields = fields;
+
+    state.history.push(Command::Batch(
+        edit.into_iter()
+            .chain([Command::AddClass(group_id)])
+            .collect(),
+    ));
+    state.clear_selection();
+    state.dummy = false;
+}
+
+/// Moves fields from the first to the last selected one into a new section in their place.
+fn section_selected_fields(state: &mut GlobalState) {
+    let Some(container_id) = state.selection.map(|s| s.container_id) else {
+        return;
+    };
+
+    let ids = state.selected_fields();
+    let class = state.class_list.by_id(container_id).unwrap();
+    let Some((from, to)) = selected_range(class, &ids) else {
+        return;
+    };
+
+    state.record_edit(container_id);
+    let class = state.class_list.by_id_mut(container_id).unwrap();
+    let container = class.fields_with_mut(ids[0]).unwrap();
+    let fields = container.drain(from..=to).collect::<Vec<_>>();
+    container.insert(from, Box::new(SectionField::new("section".into(), fields)));
+
+    state.clear_selection();
+    state.dummy = false;
+}
+
+/// Positions of the first and the last of the fields, they all share the class or section.
+fn selected_range(class: &Class, ids: &[FieldId]) -> Option<(usize, usize)> {
+    let fields = class.fields_with(*ids.first()?)?;
+    let positions = ids
+        .iter()
+        .filter_map(|id| fields.iter().position(|f| f.id() == *id))
+        .collect::<Vec<_>>();
+    Some((*positions.first()?, *positions.last()?))
+}
+
+/// Replaces the selected field and the multi-selection with `new_field`.
+fn replace_selected_fields(
+    state: &mut GlobalState,
+    new_size: usize,
+    new_field: impl Fn(Option<String>) -> Box<dyn Field>,
+) {
+    let Some(Selection {
+        container_id,
+        field_id,
+        ..
+    }) = state.selection
+    else {
+        return;
+    };
+
+    let ids = state.selected_fields();
+    let edit = Command::edit_class(&state.class_list, container_id);
+    let class = state.class_list.by_id_mut(container_id).unwrap();
+    let Some(fields) = class.fields_with_mut(field_id) else {
+        return;
+    };
+
+    let mut replaced = false;
+    // Fields are replaced in order, so a field may have been taken over by the previous one.
+    for id in ids {
+        let Some(pos) = fields.iter().position(|f| f.id() == id) else {
+            continue;
+        };
+
+        let Some(new_id) = replace_field(fields, pos, new_size, &new_field) else {
+            state.toasts.error("Not enough space for a new field");
+            continue;
+        };
+
+        replaced = true;
+        if id == field_id {
+            state.selection.as_mut().unwrap().field_id = new_id;
+        } else {
+            state.multi_selection.remove(&id);
+            state.multi_selection.insert(new_id);
+        }
+    }
+
+    state
+        .multi_selection
+        .retain(|id| fields.iter().any(|f| f.id() == *id));
+    let field_id = state.selection.unwrap().field_id;
+    if !fields.iter().any(|f| f.id() == field_id) {
+        state.clear_selection();
+    }
+
+    if replaced {
+        if let Some(edit) = edit {
+            state.history.push(edit);
+        }
+    }
+    state.dummy = false;
+}
+
+/// Replaces the field at `pos` with `new_field`, stealing space from the following fields if needed.
+/// Returns id of the new field, `None` if there is not enough space for it.
+fn replace_field(
+    fields: &mut Vec<Box<dyn Field>>,
+    pos: usize,
+    new_size: usize,
+    new_field: impl FnOnce(Option<String>) -> Box<dyn Field>,
+) -> Option<FieldId> {
+    let (old_size, old_name) = (fields[pos].size(), fields[pos].name());
+    if old_size > new_size {
+        let mut padding = allocate_padding(old_size - new_size);
+        fields[pos] = new_field(old_name);
+        while let Some(pad) = padding.pop() {
+            fields.insert(pos + 1, pad);
+        }
+    } else {
+        let (mut steal_size, mut steal_len) = (0, 0);
+        while steal_size < new_size {
+            let index = pos + steal_len;
+            if index >= fields.len() {
+                break;
+            }
+
+            steal_size += fields[index].size();
+            steal_len += 1;
+        }
+
+        if steal_size < new_size {
+            return None;
+        }
+
+        fields.drain(pos..pos + steal_len);
+        let mut padding = allocate_padding(steal_size - new_size);
+        fields.insert(pos, new_field(old_name));
+
+        while let Some(pad) = padding.pop() {
+            fields.insert(pos + 1, pad);
+        }
+    }
+
+    Some(fields[pos].id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(fields: &[Box<dyn Field>]) -> Vec<(usize, FieldKind)> {
+        field_offsets(fields)
+            .into_iter()
+            .zip(fields.iter().map(|f| f.kind()))
+            .filter(|(_, kind)| {
+                !matches!(
+                    kind,
+                    FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn moved_fields_keep_other_offsets() {
+        let mut fields = vec![FieldKind::U32.into_field(None, 8)];
+        fields.extend(allocate_padding(12));
+        fields.push(FieldKind::F64.into_field(None, 8));
+        fields.extend(allocate_padding(8));
+
+        // The u32 lands in the padding after the f64, which doesn't move.
+        let to = fields.len() - 1;
+        assert!(fits_over_padding(&fields, 0, to));
+        move_over_padding(&mut fields, 0, to);
+        assert_eq!(
+            layout(&fields),
+            [(0x10, FieldKind::F64), (0x18, FieldKind::U32)]
+        );
+        assert_eq!(fields.iter().map(|f| f.size()).sum::<usize>(), 0x20);
+
+        // The f64 can't replace the u32.
+        let (from, to) = (
+            fields
+                .iter()
+                .position(|f| f.kind() == FieldKind::F64)
+                .unwrap(),
+            fields
+                .iter()
+                .position(|f| f.kind() == FieldKind::U32)
+                .unwrap(),
+        );
+        assert!(!fits_over_padding(&fields, from, to));
+        // Nor run past the end.
+        assert!(!fits_over_padding(&fields, from, fields.len() - 1));
+    }
+}
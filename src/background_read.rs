@@ -0,0 +1,120 @@
+use crate::{field::FieldId, process::Process, state::ProcessSlot};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        mpsc::{channel, Sender},
+        Arc,
+    },
+    thread,
+};
+
+/// Field value read at an address, the same field is drawn at many addresses inside of arrays
+/// and embedded classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ReadKey {
+    field_id: FieldId,
+    address: usize,
+    len: usize,
+}
+
+#[derive(Default)]
+struct Shared {
+    /// `None` if the memory couldn't be read.
+    results: HashMap<ReadKey, Option<Vec<u8>>>,
+    /// Reads queued but not finished yet, they aren't queued again.
+    pending: HashSet<ReadKey>,
+}
+
+/// Reads values of fields on a background thread so slow processes don't stall frames.
+/// Fields are drawn with the value of the last finished read, i.e. one frame behind.
+pub struct BackgroundReader {
+    requests: Sender<ReadKey>,
+    shared: Arc<Mutex<Shared>>,
+    /// Keys read this frame, results of others are dropped.
+    requested: Mutex<HashSet<ReadKey>>,
+}
+
+impl BackgroundReader {
+    /// The thread exits once the reader is dropped.
+    pub fn new(process: ProcessSlot) -> Self {
+        let (requests, receiver) = channel::<ReadKey>();
+        let shared = Arc::new(Mutex::new(Shared::default()));
+
+        let thread_shared = shared.clone();
+        thread::spawn(move || {
+            while let Ok(key) = receiver.recv() {
+                let mut buf = vec![0; key.len];
+                let read = process
+                    .read()
+                    .as_ref()
+                    .map(|p| p.read_checked(key.address, &mut buf));
+
+                let shared = &mut *thread_shared.lock();
+                shared.pending.remove(&key);
+                if let Some(read) = read {
+                    shared.results.insert(key, read.then_some(buf));
+                }
+            }
+        });
+
+        Self {
+            requests,
+            shared,
+            requested: Mutex::default(),
+        }
+    }
+
+    /// Fills `buf` with the last read value of the field and queues a new read, returns `false`
+    /// if the memory couldn't be read. The first read is done right away so fields are never
+    /// drawn empty.
+    pub fn read(
+        &self,
+        process: &Process,
+        field_id: FieldId,
+        address: usize,
+        buf: &mut [u8],
+    ) -> bool {
+        let key = ReadKey {
+            field_id,
+            address,
+            len: buf.len(),
+        };
+        self.requested.lock().insert(key);
+
+        let shared = &mut *self.shared.lock();
+        let read = match shared.results.get(&key) {
+            Some(Some(value)) => {
+                buf.copy_from_slice(value);
+                true
+            }
+            Some(None) => false,
+            None => {
+                let read = process.read_checked(address, buf);
+                shared.results.insert(key, read.then(|| buf.to_vec()));
+                read
+            }
+        };
+
+        if shared.pending.insert(key) && self.requests.send(key).is_err() {
+            shared.pending.remove(&key);
+        }
+        read
+    }
+
+    /// Drops values of fields that weren't drawn since the last call.
+    pub fn finish_frame(&self) {
+        let mut requested = self.requested.lock();
+        self.shared
+            .lock()
+            .results
+            .retain(|key, _| requested.contains(key));
+        requested.clear();
+    }
+
+    /// Forgets all values, e.g. when they may belong to another process.
+    pub fn clear(&self) {
+        self.shared.lock().results.clear();
+        self.requested.lock().clear();
+    }
+}
@@ -0,0 +1,63 @@
+use crate::class::ClassId;
+
+/// Names of hotkeys that jump to the N-th bookmark.
+pub const BOOKMARK_HOTKEYS: [&str; 9] = [
+    "bookmark_1",
+    "bookmark_2",
+    "bookmark_3",
+    "bookmark_4",
+    "bookmark_5",
+    "bookmark_6",
+    "bookmark_7",
+    "bookmark_8",
+    "bookmark_9",
+];
+
+#[derive(Debug, Clone)]
+pub struct Bookmark {
+    pub name: String,
+    pub address: usize,
+    /// Class that is selected when jumping to the bookmark.
+    pub class_id: Option<ClassId>,
+}
+
+#[derive(Debug, Default)]
+pub struct Bookmarks {
+    entries: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    pub fn from_entries(entries: Vec<Bookmark>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[Bookmark] {
+        &self.entries
+    }
+
+    pub fn entries_mut(&mut self) -> &mut [Bookmark] {
+        &mut self.entries
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Bookmark> {
+        self.entries.get(index)
+    }
+
+    pub fn add(&mut self, name: String, address: usize, class_id: Option<ClassId>) {
+        self.entries.push(Bookmark {
+            name,
+            address,
+            class_id,
+        });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
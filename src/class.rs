@@ -1,13 +1,59 @@
-use crate::field::{Field, FieldKind, HexField};
-use std::{cell::Cell, iter::repeat_with};
+use crate::{
+    enums::EnumRegistry,
+    field::{
+        allocate_padding, walk_fields, EmbeddedClassField, Field, FieldId, FieldKind, HexField,
+    },
+    project::{ClassSnapshot, FieldClipboard},
+};
+use eframe::epaint::Color32;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    iter::repeat_with,
+    ops::Range,
+};
 
 pub type ClassId = usize;
 
+/// Part of the fields of a class merged with another one, see [`ClassList::plan_merge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePart {
+    /// Field of the class at the index.
+    Kept(usize),
+    /// Bytes left of padding the merged fields were placed over.
+    Padding(usize),
+    /// Fields of the merged class.
+    Merged,
+}
+
+/// Background color of the field rows of a class.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorOverride {
+    /// Fields of the same kind share a color.
+    ByKind(HashMap<FieldKind, Color32>),
+    PerField(HashMap<FieldId, Color32>),
+    Solid(Color32),
+}
+
+impl ColorOverride {
+    pub fn color_of(&self, field: &dyn Field) -> Option<Color32> {
+        match self {
+            Self::ByKind(colors) => colors.get(&field.kind()).copied(),
+            Self::PerField(colors) => colors.get(&field.id()).copied(),
+            Self::Solid(color) => Some(*color),
+        }
+    }
+}
+
 pub struct Class {
     id: ClassId,
     pub name: String,
     pub address: Cell<usize>,
+    /// Fields of the class, they start right after the base class.
     pub fields: Vec<Box<dyn Field>>,
+    /// Set from context menus of the class and its fields without a mutable class list.
+    pub colors: RefCell<Option<ColorOverride>>,
+    base: Option<ClassId>,
 }
 
 impl Class {
@@ -19,6 +65,8 @@ impl Class {
                 .take(10)
                 .collect(),
             address: 0.into(),
+            colors: None.into(),
+            base: None,
         }
     }
 
@@ -28,51 +76,147 @@ impl Class {
             name,
             fields: vec![],
             address: 0.into(),
+            colors: None.into(),
+            base: None,
         }
     }
 
     pub fn id(&self) -> usize {
         self.id
     }
+
+    pub fn base(&self) -> Option<ClassId> {
+        self.base
+    }
+
+    /// Fields of the class or of the section that directly contain the field.
+    pub fn fields_with(&self, id: FieldId) -> Option<&[Box<dyn Field>]> {
+        fn find(fields: &[Box<dyn Field>], id: FieldId) -> Option<&[Box<dyn Field>]> {
+            if fields.iter().any(|f| f.id() == id) {
+                return Some(fields);
+            }
+            fields.iter().find_map(|f| find(f.section_fields()?, id))
+        }
+
+        find(&self.fields, id)
+    }
+
+    pub fn fields_with_mut(&mut self, id: FieldId) -> Option<&mut Vec<Box<dyn Field>>> {
+        fn find(fields: &mut Vec<Box<dyn Field>>, id: FieldId) -> Option<&mut Vec<Box<dyn Field>>> {
+            if fields.iter().any(|f| f.id() == id) {
+                return Some(fields);
+            }
+            fields
+                .iter_mut()
+                .find_map(|f| find(f.section_fields_mut()?, id))
+        }
+
+        find(&mut self.fields, id)
+    }
+
+    /// Looks up a field of the class, including fields of sections.
+    pub fn field(&self, id: FieldId) -> Option<&dyn Field> {
+        self.fields_with(id)?
+            .iter()
+            .find(|f| f.id() == id)
+            .map(|f| f.as_ref())
+    }
+
+    /// Returns `true` if the class contains only hex fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.iter().all(|f| {
+            f.kind() == FieldKind::Unk8
+                || f.kind() == FieldKind::Unk16
+                || f.kind() == FieldKind::Unk32
+                || f.kind() == FieldKind::Unk64
+        })
+    }
 }
 
+/// Pointer width of projects that don't specify one, 64-bit processes are the most common.
+pub const DEFAULT_POINTER_SIZE: usize = 8;
+
 pub struct ClassList {
     classes: Vec<Class>,
+    enums: EnumRegistry,
     selected: Option<usize>,
+    /// Pointer width in bytes of the process the classes describe.
+    pointer_size: usize,
 }
 
 impl Default for ClassList {
     fn default() -> Self {
         Self {
             classes: vec![Class::new(0, "FirstClass".into())],
+            enums: EnumRegistry::default(),
             selected: Some(0),
+            pointer_size: DEFAULT_POINTER_SIZE,
         }
     }
 }
 
 impl ClassList {
-    pub const EMPTY: Self = Self {
-        selected: None,
-        classes: vec![],
-    };
+    pub fn empty() -> Self {
+        Self {
+            selected: None,
+            classes: vec![],
+            enums: EnumRegistry::default(),
+            pointer_size: DEFAULT_POINTER_SIZE,
+        }
+    }
+
+    /// Pointer width in bytes, `8` until a process is attached or a project sets it.
+    pub fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+
+    /// Changes the pointer width and updates sizes of the fields that depend on it.
+    pub fn set_pointer_size(&mut self, size: usize) {
+        if self.pointer_size != size {
+            self.pointer_size = size;
+            self.refresh_sizes();
+        }
+    }
 
     pub fn classes(&self) -> &[Class] {
         &self.classes[..]
     }
 
-    pub fn classes_mut(&mut self) -> &mut [Class] {
-        &mut self.classes[..]
+    /// Classes whose name contains `query` ignoring case, along with the matching part of the name.
+    /// Query starting with `#` matches hex ids of classes instead, empty query matches every class.
+    pub fn iter_filtered<'a>(
+        &'a self,
+        query: &str,
+    ) -> impl Iterator<Item = (&'a Class, Option<Range<usize>>)> + 'a {
+        let query = query.trim().to_ascii_lowercase();
+
+        self.classes.iter().filter_map(move |class| {
+            if query.is_empty() {
+                return Some((class, None));
+            }
+
+            if let Some(id) = query.strip_prefix('#') {
+                return format!("{:x}", class.id)
+                    .contains(id.trim_start_matches("0x"))
+                    .then_some((class, None));
+            }
+
+            // ASCII lowercase keeps byte offsets of the name intact.
+            let start = class.name.to_ascii_lowercase().find(&query)?;
+            Some((class, Some(start..start + query.len())))
+        })
+    }
+
+    pub fn enums(&self) -> &EnumRegistry {
+        &self.enums
+    }
+
+    pub fn set_enums(&mut self, enums: EnumRegistry) {
+        self.enums = enums;
     }
 
     pub fn remove_empty(&mut self) {
-        self.classes.retain(|c| {
-            !c.fields.iter().all(|f| {
-                f.kind() == FieldKind::Unk8
-                    || f.kind() == FieldKind::Unk16
-                    || f.kind() == FieldKind::Unk32
-                    || f.kind() == FieldKind::Unk64
-            })
-        });
+        self.classes.retain(|c| !c.is_empty());
     }
 
     pub fn add_empty_class(&mut self, name: String) -> usize {
@@ -88,6 +232,35 @@ impl ClassList {
         id
     }
 
+    pub fn insert_empty_class(&mut self, index: usize, name: String, id: ClassId) {
+        let index = index.min(self.classes.len());
+        self.classes.insert(index, Class::empty(id, name));
+    }
+
+    /// Adds a copy of the class right after it and returns its id, fields of the copy get new ids.
+    pub fn duplicate(&mut self, id: ClassId) -> Option<ClassId> {
+        let index = self.position(id)? + 1;
+        let original = &self.by_id(id)?.name;
+
+        let mut name = format!("{original}_copy");
+        for i in 2.. {
+            if self.by_name(&name).is_none() {
+                break;
+            }
+            name = format!("{original}_copy{i}");
+        }
+
+        let new_id = fastrand::usize(..);
+        ClassSnapshot::take(self, id)?
+            .renamed(new_id, name)
+            .restore(self, index);
+        Some(new_id)
+    }
+
+    pub fn position(&self, id: ClassId) -> Option<usize> {
+        self.classes.iter().position(|c| c.id == id)
+    }
+
     pub fn add_class_with_id(&mut self, name: String, id: usize) {
         self.classes.push(Class::new(id, name));
     }
@@ -120,4 +293,402 @@ impl ClassList {
         self.selected
             .and_then(|i| self.classes.iter().find(|c| c.id == i))
     }
+
+    /// Returns the base class of the class, if it still exists.
+    pub fn base_of(&self, id: ClassId) -> Option<ClassId> {
+        self.by_id(id)?
+            .base
+            .filter(|base| self.by_id(*base).is_some())
+    }
+
+    /// Returns all base classes of the class, starting from the root one.
+    pub fn bases_of(&self, id: ClassId) -> Vec<ClassId> {
+        let mut bases = vec![];
+        let mut current = id;
+        while let Some(base) = self.base_of(current) {
+            bases.push(base);
+            current = base;
+        }
+
+        bases.reverse();
+        bases
+    }
+
+    /// Offset at which fields of the class start.
+    pub fn base_size(&self, id: ClassId) -> usize {
+        self.base_of(id).map(|b| self.class_size(b)).unwrap_or(0)
+    }
+
+    /// Size of the class including its base classes.
+    pub fn class_size(&self, id: ClassId) -> usize {
+        self.base_size(id)
+            + self
+                .by_id(id)
+                .map(|c| c.fields.iter().map(|f| f.size()).sum::<usize>())
+                .unwrap_or(0)
+    }
+
+    /// Appends padding until the class including its base is `target` bytes, returns the number
+    /// of bytes added.
+    pub fn fill_to_size(&mut self, id: ClassId, target: usize) -> eyre::Result<usize> {
+        let size = self.class_size(id);
+        if size > target {
+            return Err(eyre::eyre!(
+                "Class is already 0x{size:X} bytes, larger than 0x{target:X}"
+            ));
+        }
+
+        self.by_id_mut(id)
+            .ok_or(eyre::eyre!("Class doesn't exist"))?
+            .fields
+            .extend(allocate_padding(target - size));
+        Ok(target - size)
+    }
+
+    /// Layout of `primary` after fields of `secondary` are placed at `offset`, counted from the
+    /// start of its base class. Only padding can be replaced by the merged fields.
+    pub fn plan_merge(
+        &self,
+        primary: ClassId,
+        secondary: ClassId,
+        offset: usize,
+    ) -> eyre::Result<Vec<MergePart>> {
+        if primary == secondary {
+            return Err(eyre::eyre!("Class can't be merged into itself"));
+        }
+
+        let class = self
+            .by_id(primary)
+            .ok_or(eyre::eyre!("Class doesn't exist"))?;
+        let merged = self
+            .by_id(secondary)
+            .ok_or(eyre::eyre!("Class doesn't exist"))?;
+        if merged
+            .fields
+            .iter()
+            .filter_map(|f| f.embedded_class())
+            .any(|cid| self.depends_on(cid, primary))
+        {
+            return Err(eyre::eyre!("Class can't be embedded into itself"));
+        }
+
+        let base = self.base_size(primary);
+        if offset < base {
+            return Err(eyre::eyre!(
+                "0x{offset:X} is inside of the base class, fields must start at 0x{base:X}"
+            ));
+        }
+        let size = merged.fields.iter().map(|f| f.size()).sum::<usize>();
+        if size == 0 {
+            return Err(eyre::eyre!("`{}` has no fields", merged.name));
+        }
+
+        let (start, end) = (offset - base, offset - base + size);
+        let (mut parts, mut position, mut placed) = (vec![], 0, false);
+        for (i, field) in class.fields.iter().enumerate() {
+            let (field_start, field_end) = (position, position + field.size());
+            position = field_end;
+
+            if field_end <= start || field_start >= end {
+                parts.push(MergePart::Kept(i));
+                continue;
+            }
+
+            if !matches!(
+                field.kind(),
+                FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64
+            ) {
+                return Err(eyre::eyre!(
+                    "Field `{}` at 0x{:X} overlaps fields of `{}`",
+                    field.name().unwrap_or_default(),
+                    base + field_start,
+                    merged.name
+                ));
+            }
+
+            if field_start < start {
+                parts.push(MergePart::Padding(start - field_start));
+            }
+            if !placed {
+                parts.push(MergePart::Merged);
+                placed = true;
+            }
+            if field_end > end {
+                parts.push(MergePart::Padding(field_end - end));
+            }
+        }
+
+        if !placed {
+            if position < start {
+                parts.push(MergePart::Padding(start - position));
+            }
+            parts.push(MergePart::Merged);
+        }
+        Ok(parts)
+    }
+
+    /// Places copies of fields of `secondary` into `primary` at `offset`, see [`Self::plan_merge`].
+    pub fn merge(
+        &mut self,
+        primary: ClassId,
+        secondary: ClassId,
+        offset: usize,
+    ) -> eyre::Result<()> {
+        let parts = self.plan_merge(primary, secondary, offset)?;
+
+        // Copied like the clipboard does, both classes keep fields of their own.
+        let merged = self.by_id(secondary).unwrap();
+        let merged = FieldClipboard::copy(
+            self,
+            &merged.fields.iter().map(|f| f.as_ref()).collect::<Vec<_>>(),
+        )
+        .paste(self);
+
+        let class = self.by_id_mut(primary).unwrap();
+        let mut old = std::mem::take(&mut class.fields)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut merged = Some(merged);
+        for part in parts {
+            match part {
+                MergePart::Kept(i) => class.fields.extend(old[i].take()),
+                MergePart::Padding(size) => class.fields.extend(allocate_padding(size)),
+                MergePart::Merged => class.fields.extend(merged.take().unwrap_or_default()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves fields of the class before and after `offset` into two new classes placed after it,
+    /// the class embeds both of them instead. Padding at `offset` is split, other fields can't be.
+    pub fn split(&mut self, id: ClassId, offset: usize) -> eyre::Result<(ClassId, ClassId)> {
+        let (base, size) = (self.base_size(id), self.class_size(id));
+        let class = self.by_id(id).ok_or(eyre::eyre!("Class doesn't exist"))?;
+        if offset <= base || offset >= size {
+            return Err(eyre::eyre!(
+                "Class can only be split between 0x{base:X} and 0x{size:X}"
+            ));
+        }
+
+        let (mut position, mut at) = (base, (0, 0));
+        for (i, field) in class.fields.iter().enumerate() {
+            let end = position + field.size();
+            if end > offset {
+                if position < offset
+                    && !matches!(
+                        field.kind(),
+                        FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64
+                    )
+                {
+                    return Err(eyre::eyre!(
+                        "Field `{}` at 0x{position:X} crosses 0x{offset:X}",
+                        field.name().unwrap_or_default()
+                    ));
+                }
+                at = (i, offset - position);
+                break;
+            }
+            position = end;
+        }
+
+        let name = class.name.clone();
+        let index = self.position(id).unwrap();
+        let mut fields = std::mem::take(&mut self.by_id_mut(id).unwrap().fields);
+        let (mut split_at, cut) = at;
+        if cut > 0 {
+            let padding = fields.remove(split_at).size();
+            let head = allocate_padding(cut);
+            let head_len = head.len();
+            fields.splice(
+                split_at..split_at,
+                head.into_iter().chain(allocate_padding(padding - cut)),
+            );
+            split_at += head_len;
+        }
+        let tail = fields.split_off(split_at);
+
+        let mut parts = vec![];
+        for (i, part) in [fields, tail].into_iter().enumerate() {
+            let mut part_name = format!("{name}_{}", i + 1);
+            for n in 2.. {
+                if self.by_name(&part_name).is_none() {
+                    break;
+                }
+                part_name = format!("{name}_{}_{n}", i + 1);
+            }
+
+            let part_id = fastrand::usize(..);
+            let part_size = part.iter().map(|f| f.size()).sum::<usize>();
+            self.insert_empty_class(index + 1 + i, part_name, part_id);
+            self.by_id_mut(part_id).unwrap().fields = part;
+            parts.push(Box::new(EmbeddedClassField::new(
+                format!("part{}", i + 1),
+                part_id,
+                part_size,
+            )) as Box<dyn Field>);
+        }
+
+        let class = self.by_id_mut(id).unwrap();
+        class.fields = parts;
+        Ok((
+            class.fields[0].embedded_class().unwrap(),
+            class.fields[1].embedded_class().unwrap(),
+        ))
+    }
+
+    /// Sets the base class without touching the fields, so they move by the size of the base class.
+    pub fn set_base(&mut self, derived: ClassId, base: Option<ClassId>) -> eyre::Result<()> {
+        if let Some(base) = base {
+            if self.depends_on(base, derived) {
+                return Err(eyre::eyre!("Circular inheritance is not allowed"));
+            }
+        }
+
+        self.by_id_mut(derived)
+            .ok_or(eyre::eyre!("Class doesn't exist"))?
+            .base = base;
+        Ok(())
+    }
+
+    /// Changes the base class keeping the offsets of the fields.
+    /// Only unknown bytes at the start of the class can be taken by the base class.
+    pub fn rebase(&mut self, derived: ClassId, base: Option<ClassId>) -> eyre::Result<()> {
+        let old_size = self.base_size(derived);
+        let new_size = base.map(|b| self.class_size(b)).unwrap_or(0);
+
+        let class = self
+            .by_id(derived)
+            .ok_or(eyre::eyre!("Class doesn't exist"))?;
+        let (mut offset, mut covered) = (old_size, 0);
+        for field in class.fields.iter() {
+            if offset >= new_size {
+                break;
+            }
+
+            if let Some(name) = field.name() {
+                return Err(eyre::eyre!(
+                    "Field `{name}` at 0x{offset:X} overlaps the base class, fields must start at 0x{new_size:X}"
+                ));
+            }
+
+            offset += field.size();
+            covered += 1;
+        }
+
+        self.set_base(derived, base)?;
+
+        let class = self.by_id_mut(derived).unwrap();
+        class.fields.drain(..covered);
+        class
+            .fields
+            .splice(0..0, allocate_padding(offset.saturating_sub(new_size)));
+
+        Ok(())
+    }
+
+    /// Classes that have to be defined before the class, its base and embedded classes.
+    fn dependencies(&self, id: ClassId) -> Vec<ClassId> {
+        let Some(class) = self.by_id(id) else {
+            return vec![];
+        };
+
+        self.base_of(id)
+            .into_iter()
+            .chain(
+                walk_fields(&class.fields, 0)
+                    .into_iter()
+                    .filter_map(|(_, f)| f.embedded_class()),
+            )
+            .filter(|dep| self.by_id(*dep).is_some())
+            .collect()
+    }
+
+    /// Returns `true` if layout of `class` includes `other`, directly or through other classes.
+    pub fn depends_on(&self, class: ClassId, other: ClassId) -> bool {
+        let mut visited = vec![];
+        let mut stack = vec![class];
+        while let Some(id) = stack.pop() {
+            if id == other {
+                return true;
+            }
+
+            if !visited.contains(&id) {
+                visited.push(id);
+                stack.extend(self.dependencies(id));
+            }
+        }
+
+        false
+    }
+
+    /// Returns classes ordered so that base and embedded classes come before classes using them.
+    pub fn in_dependency_order(&self) -> Vec<&Class> {
+        fn visit<'a>(list: &'a ClassList, id: ClassId, ordered: &mut Vec<&'a Class>) {
+            if ordered.iter().any(|c| c.id == id) {
+                return;
+            }
+
+            // Placeholder guards against cycles, it's replaced after the dependencies.
+            let index = ordered.len();
+            ordered.extend(list.by_id(id));
+            for dep in list.dependencies(id) {
+                visit(list, dep, ordered);
+            }
+
+            let class = ordered.remove(index);
+            ordered.push(class);
+        }
+
+        let mut ordered = Vec::with_capacity(self.classes.len());
+        for class in self.classes.iter() {
+            visit(self, class.id, &mut ordered);
+        }
+
+        ordered
+    }
+
+    /// Updates sizes of fields that depend on other classes or the pointer width, called every
+    /// frame.
+    pub fn refresh_sizes(&self) {
+        // Nested classes need a pass per level of nesting.
+        for _ in 0..self.classes.len() {
+            let mut changed = false;
+            for class in self.classes.iter() {
+                for field in class.fields.iter() {
+                    changed |= field.refresh_size(self);
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{FieldKind, StringAbi};
+
+    #[test]
+    fn pointer_size_resizes_fields() {
+        let mut list = ClassList::empty();
+        let id = list.add_empty_class("Class".into());
+        list.by_id_mut(id).unwrap().fields = [
+            FieldKind::Ptr,
+            FieldKind::StdVector(Box::new(FieldKind::U32)),
+            FieldKind::StdString(StringAbi::Msvc),
+            FieldKind::Array(Box::new(FieldKind::StrPtr), 2),
+            FieldKind::RelPtr32,
+        ]
+        .into_iter()
+        .map(|kind| kind.into_field(None, list.pointer_size()))
+        .collect();
+        assert_eq!(list.class_size(id), 8 + 24 + 32 + 16 + 4);
+
+        list.set_pointer_size(4);
+        assert_eq!(list.class_size(id), 4 + 12 + 24 + 8 + 4);
+    }
 }
@@ -1,15 +1,148 @@
+use crate::{
+    field::FieldKind,
+    generator::CppMathTypes,
+    theme::{Theme, DEFAULT_THEME},
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, fs, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+/// Recent projects that are kept in the config, older ones are forgotten.
+const MAX_RECENT_PROJECTS: usize = 10;
+
+pub const RECENT_PROJECT_HOTKEYS: [&str; 9] = [
+    "recent_project_1",
+    "recent_project_2",
+    "recent_project_3",
+    "recent_project_4",
+    "recent_project_5",
+    "recent_project_6",
+    "recent_project_7",
+    "recent_project_8",
+    "recent_project_9",
+];
+
+pub const CHANGE_KIND_HOTKEYS: [&str; 9] = [
+    "change_kind_1",
+    "change_kind_2",
+    "change_kind_3",
+    "change_kind_4",
+    "change_kind_5",
+    "change_kind_6",
+    "change_kind_7",
+    "change_kind_8",
+    "change_kind_9",
+];
+
+/// Kinds the change kind hotkeys switch selected fields to, bound to `1`..`9` by default.
+pub const DEFAULT_CHANGE_KINDS: [FieldKind; 9] = [
+    FieldKind::U8,
+    FieldKind::U16,
+    FieldKind::U32,
+    FieldKind::U64,
+    FieldKind::I32,
+    FieldKind::F32,
+    FieldKind::F64,
+    FieldKind::Ptr,
+    FieldKind::StrPtr,
+];
+
+/// Project listed in `File > Open recent...`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RecentProjectEntry")]
+pub struct RecentProject {
+    pub path: PathBuf,
+    /// Seconds since the Unix epoch when the project was last opened or saved.
+    pub modified: Option<u64>,
+    /// Number of classes when the project was last opened or saved.
+    pub classes: Option<usize>,
+}
+
+// Older configs only kept the paths.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RecentProjectEntry {
+    Path(PathBuf),
+    Project {
+        path: PathBuf,
+        modified: Option<u64>,
+        classes: Option<usize>,
+    },
+}
+
+impl From<RecentProjectEntry> for RecentProject {
+    fn from(entry: RecentProjectEntry) -> Self {
+        match entry {
+            RecentProjectEntry::Path(path) => Self {
+                path,
+                modified: None,
+                classes: None,
+            },
+            RecentProjectEntry::Project {
+                path,
+                modified,
+                classes,
+            } => Self {
+                path,
+                modified,
+                classes,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct YClassConfig {
     pub last_attached_process_name: Option<String>,
     pub last_minidump_path: Option<PathBuf>,
+    /// `IP:Port` of the last memory server yclass connected to.
+    pub last_remote_address: Option<String>,
 
     pub plugin_path: Option<PathBuf>,
-    pub recent_projects: Option<HashSet<PathBuf>>,
+    /// Reloads the plugin when its file changes, enabled by default.
+    pub plugin_hot_reload: Option<bool>,
+    /// Opens the first of `recent_projects` on startup, enabled by default.
+    pub reopen_last_project: Option<bool>,
     pub recent_minidumps: Option<HashSet<PathBuf>>,
     pub dpi: Option<f32>,
+    /// Size of the inspector font in points before scaling by `dpi`, 16 by default.
+    pub inspector_font_size: Option<f32>,
+    /// Font file used by the inspector instead of the bundled Roboto Mono.
+    pub inspector_font_family: Option<PathBuf>,
+    /// Name of one of the built-in themes or of `themes`, `Dark` by default.
+    pub theme: Option<String>,
+    /// Maximum number of undoable actions.
+    pub history_depth: Option<usize>,
+    pub clear_history_on_save: Option<bool>,
+    /// Milliseconds between repaints, `0` only repaints on input. 100 by default.
+    pub update_interval_ms: Option<u64>,
+    /// Seconds between automatic saves of the project, disabled if not set.
+    pub auto_save_interval_secs: Option<u64>,
+    /// Shortcuts that jump to the N-th bookmark, e.g. `"Ctrl+Shift+1"`. `Ctrl+1`..`Ctrl+9` by default.
+    pub bookmark_hotkeys: Option<Vec<String>>,
+    /// Kinds of the `change_kind_1`..`change_kind_9` hotkeys, e.g. `["U8", "F32"]`. Kinds without
+    /// parameters only, see [`DEFAULT_CHANGE_KINDS`].
+    pub change_kinds: Option<Vec<FieldKind>>,
+    /// Maximum number of displayed elements of dynamic arrays, 256 by default.
+    pub max_dynamic_array_elements: Option<usize>,
+    /// Maximum number of values kept for every recorded field, 10000 by default.
+    pub max_recorded_values: Option<usize>,
+    /// Reads frozen values back after writing them to catch writes that silently fail.
+    pub verify_writes: Option<bool>,
+
+    pub cpp_math_types: Option<CppMathTypes>,
+    /// PDB files by module name, imported in `Process > Import symbols`.
+    pub symbol_files: Option<BTreeMap<String, PathBuf>>,
+    /// Shortcuts changed in the hotkeys window by hotkey name, e.g. `undo = "Ctrl+Z"`.
+    pub hotkeys: Option<BTreeMap<String, String>>,
+    /// Custom themes, they take precedence over built-in themes of the same name.
+    pub themes: Option<Vec<Theme>>,
+    /// Most recently opened or saved first. Kept last, TOML needs tables after plain values.
+    pub recent_projects: Option<Vec<RecentProject>>,
 }
 
 impl YClassConfig {
@@ -19,6 +152,80 @@ impl YClassConfig {
             .unwrap_or_else(|| "./config.toml".into())
     }
 
+    /// How often values are reread from memory, `None` if only on input.
+    pub fn update_interval(&self) -> Option<Duration> {
+        match self.update_interval_ms.unwrap_or(100) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    pub fn plugin_hot_reload(&self) -> bool {
+        self.plugin_hot_reload.unwrap_or(true)
+    }
+
+    pub fn reopen_last_project(&self) -> bool {
+        self.reopen_last_project.unwrap_or(true)
+    }
+
+    /// Built-in and custom themes, built-in themes first.
+    pub fn themes(&self) -> impl Iterator<Item = &Theme> {
+        let custom = self.themes.as_deref().unwrap_or_default();
+        Theme::builtin()
+            .iter()
+            .filter(|t| !custom.iter().any(|c| c.name == t.name))
+            .chain(custom)
+    }
+
+    pub fn theme(&self) -> &Theme {
+        let name = self.theme.as_deref().unwrap_or(DEFAULT_THEME);
+        self.themes()
+            .find(|t| t.name == name)
+            .unwrap_or(&Theme::builtin()[0])
+    }
+
+    pub fn recent_projects(&self) -> &[RecentProject] {
+        self.recent_projects.as_deref().unwrap_or_default()
+    }
+
+    /// Moves the project to the top of the recent projects and updates its metadata.
+    pub fn add_recent_project(&mut self, path: &Path, classes: usize) {
+        let modified = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs());
+
+        let recent = self.recent_projects.get_or_insert_with(Vec::new);
+        recent.retain(|p| p.path != path);
+        recent.insert(
+            0,
+            RecentProject {
+                path: path.to_path_buf(),
+                modified,
+                classes: Some(classes),
+            },
+        );
+        recent.truncate(MAX_RECENT_PROJECTS);
+    }
+
+    pub fn remove_recent_project(&mut self, path: &Path) {
+        if let Some(recent) = self.recent_projects.as_mut() {
+            recent.retain(|p| p.path != path);
+        }
+    }
+
+    /// Kind of the N-th change kind hotkey, slots missing from the config use the defaults.
+    pub fn change_kind(&self, i: usize) -> Option<&FieldKind> {
+        self.change_kinds
+            .as_ref()
+            .and_then(|kinds| kinds.get(i))
+            .or_else(|| DEFAULT_CHANGE_KINDS.get(i))
+    }
+
+    pub fn verify_writes(&self) -> bool {
+        self.verify_writes.unwrap_or(false)
+    }
+
     pub fn load_or_default() -> Self {
         let path = Self::config_path();
 
@@ -1,17 +1,31 @@
 use crate::{
+    background_read::BackgroundReader,
     class::{ClassId, ClassList},
     field::FieldId,
+    named_addresses::NamedAddressRegistry,
     process::Process,
+    recording::Recording,
+    snapshot::Change,
+    theme::Theme,
 };
 use eframe::egui::Id;
 use egui_notify::Toasts;
 use fastrand::Rng;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+};
 
 pub struct InspectionContext<'a> {
     pub selection: Option<Selection>,
+    /// Fields selected with Shift or Ctrl in addition to `selection`, they belong to its container.
+    pub multi_selection: &'a mut HashSet<FieldId>,
+    /// Clicking a field adds it to `multi_selection` instead of replacing the selection.
+    pub extend_selection: bool,
     pub current_container: usize,
 
     pub current_id: Id,
+    #[allow(dead_code)]
     pub parent_id: Id,
     pub level_rng: &'a Rng,
 
@@ -19,8 +33,70 @@ pub struct InspectionContext<'a> {
     pub offset: usize,
 
     pub process: &'a Process,
+    /// Edits read the memory back and report values the process didn't keep.
+    pub verify_writes: bool,
+    pub reader: &'a BackgroundReader,
     pub class_list: &'a ClassList,
+    pub frozen: &'a mut HashMap<FieldId, FrozenValue>,
+    pub recordings: &'a mut HashMap<FieldId, Recording>,
     pub toasts: &'a mut Toasts,
+    pub named_addresses: &'a mut NamedAddressRegistry,
+    /// Fields whose names don't match the search are dimmed.
+    pub search: Option<&'a FieldSearch>,
+    /// Bytes of values read the previous frame, `None` if deltas aren't shown.
+    pub previous_values: Option<&'a mut HashMap<FieldId, Vec<u8>>>,
+    /// Fields of the inspected class that differ from the compared snapshot.
+    pub changes: Option<&'a HashMap<FieldId, Change>>,
+    /// Process whose memory at the same addresses is compared with fields of the inspected class.
+    pub secondary: Option<&'a Process>,
+    pub columns: Columns,
+    /// Field scrolled into view once it's drawn.
+    pub scroll_to: Option<FieldId>,
+    /// Primary selected field starts being renamed once it's drawn.
+    pub rename_selected: bool,
+    pub theme: &'a Theme,
+}
+
+/// Parts of field rows shown in the inspector, bytes of hex fields are always shown.
+#[derive(Debug, Clone, Copy)]
+pub struct Columns {
+    pub offset: bool,
+    pub address: bool,
+    pub ascii: bool,
+    pub int: bool,
+    pub float: bool,
+    pub pointer: bool,
+    pub string: bool,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            offset: true,
+            address: true,
+            ascii: true,
+            int: true,
+            float: false,
+            pointer: false,
+            string: true,
+        }
+    }
+}
+
+/// Search of fields by name in the inspector.
+#[derive(Default)]
+pub struct FieldSearch {
+    pub query: String,
+    /// Scrolls to the first matching field when set.
+    pub scroll: Cell<bool>,
+    /// Number of fields that matched while drawing the last frame.
+    pub matches: Cell<usize>,
+}
+
+impl FieldSearch {
+    pub fn matches(&self, name: &str) -> bool {
+        name.to_lowercase().contains(&self.query.to_lowercase())
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -30,9 +106,57 @@ pub struct Selection {
     pub field_id: FieldId,
 }
 
+/// Value that is written back to the process every frame.
+#[derive(Debug, Clone)]
+pub struct FrozenValue {
+    pub address: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl FrozenValue {
+    pub fn read(process: &Process, address: usize, size: usize) -> Self {
+        let mut bytes = vec![0; size];
+        process.read(address, &mut bytes);
+        Self { address, bytes }
+    }
+}
+
 impl InspectionContext<'_> {
+    /// Reads the value of the field from the last frame and queues a read for the next one,
+    /// returns `false` if the memory couldn't be read.
+    pub fn read(&self, field_id: FieldId, address: usize, buf: &mut [u8]) -> bool {
+        self.reader.read(self.process, field_id, address, buf)
+    }
+
+    /// Writes an edited value to the process, failures are shown as toasts.
+    pub fn write(&mut self, address: usize, buf: &[u8]) {
+        if let Err(e) = self.process.write(address, buf, self.verify_writes) {
+            self.toasts
+                .error(format!("Failed to write to {address:X}. {e}"));
+        }
+    }
+
+    /// Same as [`Process::read_pointer`] but through [`Self::read`].
+    pub fn read_pointer(&self, field_id: FieldId, address: usize) -> Option<usize> {
+        let mut buf = [0; 8];
+        self.read(field_id, address, &mut buf[..self.process.pointer_size()])
+            .then(|| usize::from_ne_bytes(buf))
+    }
+
     pub fn select(&mut self, field_id: FieldId) {
-        if self.is_selected(field_id) {
+        if self.extend_selection
+            && self
+                .selection
+                .is_some_and(|s| s.container_id == self.current_container)
+        {
+            if !self.is_primary_selected(field_id) && !self.multi_selection.remove(&field_id) {
+                self.multi_selection.insert(field_id);
+            }
+            return;
+        }
+
+        self.multi_selection.clear();
+        if self.is_primary_selected(field_id) {
             self.selection = None;
         } else {
             self.selection = Some(Selection {
@@ -43,7 +167,17 @@ impl InspectionContext<'_> {
         }
     }
 
+    /// Returns `true` if the field is either the selected field or a part of the multi-selection.
     pub fn is_selected(&self, field_id: FieldId) -> bool {
+        self.is_primary_selected(field_id)
+            || self.multi_selection.contains(&field_id)
+                && self
+                    .selection
+                    .is_some_and(|s| s.container_id == self.current_container)
+    }
+
+    /// Returns `true` if the field is `selection`, ignoring the multi-selection.
+    pub fn is_primary_selected(&self, field_id: FieldId) -> bool {
         self.selection
             .as_ref()
             .map(|s| s.address == self.address + self.offset)
@@ -54,4 +188,22 @@ impl InspectionContext<'_> {
                 .map(|s| s.field_id == field_id)
                 .unwrap_or(false)
     }
+
+    pub fn is_frozen(&self, field_id: FieldId) -> bool {
+        self.frozen
+            .get(&field_id)
+            .is_some_and(|f| f.address == self.address + self.offset)
+    }
+
+    pub fn toggle_freeze(&mut self, field_id: FieldId, size: usize) {
+        if self.is_frozen(field_id) {
+            self.frozen.remove(&field_id);
+        } else if self.process.is_read_only() {
+            self.toasts
+                .error("Can't freeze values of a read-only process");
+        } else {
+            let value = FrozenValue::read(self.process, self.address + self.offset, size);
+            self.frozen.insert(field_id, value);
+        }
+    }
 }
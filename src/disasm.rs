@@ -0,0 +1,887 @@
+//! Decoder of the commonly compiled subset of x86 and x86-64 instructions in Intel syntax.
+//! Bytes that don't start a known instruction are shown as `db`.
+
+const MAX_LENGTH: usize = 15;
+
+const R64: [&str; 16] = [
+    "rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15",
+];
+const R32: [&str; 16] = [
+    "eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "r8d", "r9d", "r10d", "r11d", "r12d",
+    "r13d", "r14d", "r15d",
+];
+const R16: [&str; 16] = [
+    "ax", "cx", "dx", "bx", "sp", "bp", "si", "di", "r8w", "r9w", "r10w", "r11w", "r12w", "r13w",
+    "r14w", "r15w",
+];
+const R8: [&str; 16] = [
+    "al", "cl", "dl", "bl", "spl", "bpl", "sil", "dil", "r8b", "r9b", "r10b", "r11b", "r12b",
+    "r13b", "r14b", "r15b",
+];
+/// Byte registers without a REX prefix.
+const R8_LEGACY: [&str; 8] = ["al", "cl", "dl", "bl", "ah", "ch", "dh", "bh"];
+
+const ALU: [&str; 8] = ["add", "or", "adc", "sbb", "and", "sub", "xor", "cmp"];
+const SHIFT: [&str; 8] = ["rol", "ror", "rcl", "rcr", "shl", "shr", "sal", "sar"];
+const CONDITIONS: [&str; 16] = [
+    "o", "no", "b", "ae", "e", "ne", "be", "a", "s", "ns", "p", "np", "l", "ge", "le", "g",
+];
+
+/// Part of an operand, registers and immediates are colored differently.
+#[derive(Debug, Clone)]
+pub enum Token {
+    Register(String),
+    Immediate(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub address: usize,
+    pub bytes: Vec<u8>,
+    pub mnemonic: String,
+    /// Operands separated by `Token::Text(", ")`.
+    pub operands: Vec<Token>,
+    /// Destination of relative jumps and calls.
+    pub target: Option<usize>,
+}
+
+impl Instruction {
+    pub fn is_branch(&self) -> bool {
+        self.mnemonic.starts_with('j') || self.mnemonic == "call" || self.mnemonic == "ret"
+    }
+}
+
+/// Decodes instructions of `bytes` that start at `address`, an instruction cut off at the end
+/// of `bytes` is left out.
+pub fn decode(bytes: &[u8], address: usize, is_64: bool) -> Vec<Instruction> {
+    let mut instructions = vec![];
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let mut decoder = Decoder::new(&bytes[offset..], address.wrapping_add(offset), is_64);
+        let instruction = match decoder.instruction() {
+            Some((mnemonic, operands, target)) => Instruction {
+                address: decoder.address,
+                bytes: bytes[offset..offset + decoder.pos].to_vec(),
+                mnemonic,
+                operands,
+                target,
+            },
+            // Unknown opcodes are skipped one byte at a time, truncated ones end the listing.
+            None if decoder.truncated => break,
+            None => Instruction {
+                address: decoder.address,
+                bytes: vec![bytes[offset]],
+                mnemonic: "db".to_owned(),
+                operands: vec![Token::Immediate(format!("0x{:02X}", bytes[offset]))],
+                target: None,
+            },
+        };
+
+        offset += instruction.bytes.len();
+        instructions.push(instruction);
+    }
+
+    instructions
+}
+
+#[derive(Clone, Copy)]
+struct ModRm {
+    md: u8,
+    /// Extended with `REX.R`.
+    reg: usize,
+    rm: u8,
+}
+
+type Operand = Vec<Token>;
+type Decoded = (String, Vec<Token>, Option<usize>);
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    address: usize,
+    is_64: bool,
+    truncated: bool,
+
+    rex: u8,
+    opsize16: bool,
+    addr32: bool,
+    /// `F2` or `F3` prefix.
+    rep: Option<u8>,
+    lock: bool,
+    segment: Option<&'static str>,
+    /// Displacement of a RIP relative operand, the address is only known once the whole
+    /// instruction is decoded.
+    rip_relative: Option<i64>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(bytes: &'a [u8], address: usize, is_64: bool) -> Self {
+        Self {
+            bytes: &bytes[..bytes.len().min(MAX_LENGTH)],
+            pos: 0,
+            address,
+            is_64,
+            truncated: false,
+            rex: 0,
+            opsize16: false,
+            addr32: false,
+            rep: None,
+            lock: false,
+            segment: None,
+            rip_relative: None,
+        }
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        let b = self.bytes.get(self.pos).copied();
+        if b.is_none() {
+            self.truncated = true;
+        }
+        self.pos += 1;
+        b
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let b = self.bytes.get(self.pos..self.pos + N);
+        if b.is_none() {
+            self.truncated = true;
+        }
+        self.pos += N;
+        b.map(|b| b.try_into().unwrap())
+    }
+
+    fn i8(&mut self) -> Option<i64> {
+        self.u8().map(|b| b as i8 as i64)
+    }
+
+    fn i16(&mut self) -> Option<i64> {
+        self.array().map(|b| i16::from_le_bytes(b) as i64)
+    }
+
+    fn i32(&mut self) -> Option<i64> {
+        self.array().map(|b| i32::from_le_bytes(b) as i64)
+    }
+
+    fn rex_w(&self) -> bool {
+        self.rex & 8 != 0
+    }
+
+    /// Operand size in bytes of instructions that default to 32 bits.
+    fn osize(&self) -> u8 {
+        if self.rex_w() {
+            8
+        } else if self.opsize16 {
+            2
+        } else {
+            4
+        }
+    }
+
+    /// Operand size of stack operations and indirect branches, they default to 64 bits in long mode.
+    fn stack_size(&self) -> u8 {
+        if self.opsize16 {
+            2
+        } else if self.is_64 {
+            8
+        } else {
+            4
+        }
+    }
+
+    fn register(&self, n: usize, size: u8) -> Token {
+        Token::Register(match size {
+            1 if self.rex == 0 && n < 8 => R8_LEGACY[n].to_owned(),
+            1 => R8[n].to_owned(),
+            2 => R16[n].to_owned(),
+            4 => R32[n].to_owned(),
+            8 => R64[n].to_owned(),
+            _ => format!("xmm{n}"),
+        })
+    }
+
+    fn modrm(&mut self) -> Option<ModRm> {
+        let b = self.u8()?;
+        Some(ModRm {
+            md: b >> 6,
+            reg: ((b >> 3) & 7) as usize | ((self.rex as usize & 4) << 1),
+            rm: b & 7,
+        })
+    }
+
+    /// Register operand of the `reg` field.
+    fn g(&self, m: ModRm, size: u8) -> Operand {
+        vec![self.register(m.reg, size)]
+    }
+
+    /// Register or memory operand of the `rm` field, `size` 0 omits the `ptr` prefix.
+    fn e(&mut self, m: ModRm, size: u8) -> Option<Operand> {
+        let rex_b = (self.rex as usize & 1) << 3;
+        if m.md == 3 {
+            return Some(vec![self.register(m.rm as usize | rex_b, size.max(1))]);
+        }
+
+        let names = if self.is_64 && !self.addr32 { R64 } else { R32 };
+        let (mut base, mut index, mut scale) = (None, None, 1);
+        let mut rip = false;
+
+        if m.rm == 4 {
+            let sib = self.u8()?;
+            scale = 1 << (sib >> 6);
+            let i = ((sib >> 3) & 7) as usize | ((self.rex as usize & 2) << 2);
+            if i != 4 {
+                index = Some(i);
+            }
+            if sib & 7 != 5 || m.md != 0 {
+                base = Some((sib & 7) as usize | rex_b);
+            }
+        } else if m.rm == 5 && m.md == 0 {
+            rip = self.is_64;
+        } else {
+            base = Some(m.rm as usize | rex_b);
+        }
+
+        let disp = match m.md {
+            1 => self.i8()?,
+            2 => self.i32()?,
+            _ if base.is_none() => self.i32()?,
+            _ => 0,
+        };
+
+        let mut operand = self.memory_prefix(size);
+
+        if rip {
+            // Replaced with the absolute address once the length is known.
+            self.rip_relative = Some(disp);
+            operand.push(Token::Immediate(String::new()));
+        } else {
+            if let Some(base) = base {
+                operand.push(Token::Register(names[base].to_owned()));
+            }
+            if let Some(index) = index {
+                if base.is_some() {
+                    operand.push(Token::Text("+".to_owned()));
+                }
+                operand.push(Token::Register(names[index].to_owned()));
+                if scale > 1 {
+                    operand.push(Token::Text(format!("*{scale}")));
+                }
+            }
+
+            if base.is_none() && index.is_none() {
+                operand.push(Token::Immediate(format!("0x{:X}", disp as u32)));
+            } else if disp != 0 {
+                let sign = if disp < 0 { "-" } else { "+" };
+                operand.push(Token::Text(sign.to_owned()));
+                operand.push(Token::Immediate(format!("0x{:X}", disp.unsigned_abs())));
+            }
+        }
+
+        operand.push(Token::Text("]".to_owned()));
+        Some(operand)
+    }
+
+    /// Size and segment of a memory operand up to the opening bracket.
+    fn memory_prefix(&self, size: u8) -> Operand {
+        let mut operand = vec![];
+        if let Some(name) = match size {
+            1 => Some("byte ptr "),
+            2 => Some("word ptr "),
+            4 => Some("dword ptr "),
+            8 => Some("qword ptr "),
+            16 => Some("xmmword ptr "),
+            _ => None,
+        } {
+            operand.push(Token::Text(name.to_owned()));
+        }
+        if let Some(segment) = self.segment {
+            operand.push(Token::Register(segment.to_owned()));
+            operand.push(Token::Text(":".to_owned()));
+        }
+        operand.push(Token::Text("[".to_owned()));
+        operand
+    }
+
+    /// Memory operand of `mov` with the absolute address in the instruction, the address has
+    /// the address size.
+    fn moffs(&mut self, size: u8) -> Option<Operand> {
+        let address = if self.is_64 && !self.addr32 {
+            u64::from_le_bytes(self.array()?)
+        } else {
+            u32::from_le_bytes(self.array()?) as u64
+        };
+
+        let mut operand = self.memory_prefix(size);
+        operand.push(Token::Immediate(format!("0x{address:X}")));
+        operand.push(Token::Text("]".to_owned()));
+        Some(operand)
+    }
+
+    /// Immediate of `size` bytes, 8 byte immediates are sign extended from 4 bytes.
+    fn imm(&mut self, size: u8) -> Option<Operand> {
+        let value = match size {
+            1 => self.i8()?,
+            2 => self.i16()?,
+            _ => self.i32()?,
+        };
+        Some(imm_operand(value, size))
+    }
+
+    /// Relative branch target of `rel`, read after the rest of the instruction.
+    fn relative(&self, rel: i64) -> usize {
+        let target = self
+            .address
+            .wrapping_add(self.pos)
+            .wrapping_add(rel as isize as usize);
+        if self.is_64 {
+            target
+        } else {
+            target & 0xFFFF_FFFF
+        }
+    }
+
+    fn instruction(&mut self) -> Option<Decoded> {
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(0x66) => self.opsize16 = true,
+                Some(0x67) => self.addr32 = true,
+                Some(b @ (0xF2 | 0xF3)) => self.rep = Some(*b),
+                Some(0xF0) => self.lock = true,
+                Some(0x26) => self.segment = Some("es"),
+                Some(0x2E) => self.segment = Some("cs"),
+                Some(0x36) => self.segment = Some("ss"),
+                Some(0x3E) => self.segment = Some("ds"),
+                Some(0x64) => self.segment = Some("fs"),
+                Some(0x65) => self.segment = Some("gs"),
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        // 16-bit addressing isn't supported.
+        if self.addr32 && !self.is_64 {
+            return None;
+        }
+        if self.is_64 && matches!(self.bytes.get(self.pos), Some(0x40..=0x4F)) {
+            self.rex = self.u8()?;
+        }
+
+        let (mnemonic, operands, target) = self.opcode()?;
+        let mut operands = operands.into_iter().fold(vec![], |mut acc, op| {
+            if !acc.is_empty() {
+                acc.push(Token::Text(", ".to_owned()));
+            }
+            acc.extend(op);
+            acc
+        });
+
+        // The offset of a RIP relative operand is counted from the next instruction.
+        if let Some(disp) = self.rip_relative {
+            let address = self.relative(disp);
+            if let Some(token) = operands
+                .iter_mut()
+                .find(|t| matches!(t, Token::Immediate(s) if s.is_empty()))
+            {
+                *token = Token::Immediate(format!("0x{address:X}"));
+            }
+        }
+
+        let mnemonic = if self.lock {
+            format!("lock {mnemonic}")
+        } else {
+            mnemonic
+        };
+        Some((mnemonic, operands, target))
+    }
+
+    fn opcode(&mut self) -> Option<(String, Vec<Operand>, Option<usize>)> {
+        let op = self.u8()?;
+        let (os, ss) = (self.osize(), self.stack_size());
+        let reg = |d: &Self, n: u8, size: u8| {
+            vec![d.register((n & 7) as usize | ((d.rex as usize & 1) << 3), size)]
+        };
+        let named = |s: &str| s.to_owned();
+
+        Some(match op {
+            0x00..=0x3F if op & 7 < 6 && op != 0x0F => {
+                let name = named(ALU[(op >> 3) as usize]);
+                match op & 7 {
+                    0 => {
+                        let m = self.modrm()?;
+                        (name, vec![self.e(m, 1)?, self.g(m, 1)], None)
+                    }
+                    1 => {
+                        let m = self.modrm()?;
+                        (name, vec![self.e(m, os)?, self.g(m, os)], None)
+                    }
+                    2 => {
+                        let m = self.modrm()?;
+                        (name, vec![self.g(m, 1), self.e(m, 1)?], None)
+                    }
+                    3 => {
+                        let m = self.modrm()?;
+                        (name, vec![self.g(m, os), self.e(m, os)?], None)
+                    }
+                    4 => (name, vec![vec![self.register(0, 1)], self.imm(1)?], None),
+                    _ => (name, vec![vec![self.register(0, os)], self.imm(os)?], None),
+                }
+            }
+            0x0F => return self.two_byte(),
+            0x40..=0x47 if !self.is_64 => (named("inc"), vec![reg(self, op, os)], None),
+            0x48..=0x4F if !self.is_64 => (named("dec"), vec![reg(self, op, os)], None),
+            0x50..=0x57 => (named("push"), vec![reg(self, op, ss)], None),
+            0x58..=0x5F => (named("pop"), vec![reg(self, op, ss)], None),
+            0x63 if self.is_64 => {
+                let m = self.modrm()?;
+                (named("movsxd"), vec![self.g(m, os), self.e(m, 4)?], None)
+            }
+            0x68 => (named("push"), vec![self.imm(ss)?], None),
+            // Byte immediates are sign extended to the operand size.
+            0x6A => (named("push"), vec![imm_operand(self.i8()?, ss)], None),
+            0x69 | 0x6B => {
+                let m = self.modrm()?;
+                let rm = self.e(m, os)?;
+                let imm = match op {
+                    0x69 => self.imm(os)?,
+                    _ => imm_operand(self.i8()?, os),
+                };
+                (named("imul"), vec![self.g(m, os), rm, imm], None)
+            }
+            0x70..=0x7F => {
+                let rel = self.i8()?;
+                let target = self.relative(rel);
+                let name = format!("j{}", CONDITIONS[(op & 0xF) as usize]);
+                (name, vec![branch_operand(target)], Some(target))
+            }
+            0x80 | 0x81 | 0x83 => {
+                let m = self.modrm()?;
+                let size = if op == 0x80 { 1 } else { os };
+                let rm = self.e(m, size)?;
+                let imm = match op {
+                    0x81 => self.imm(os)?,
+                    _ => imm_operand(self.i8()?, size),
+                };
+                (named(ALU[m.reg & 7]), vec![rm, imm], None)
+            }
+            0x84..=0x89 => {
+                let m = self.modrm()?;
+                let size = if op & 1 == 0 { 1 } else { os };
+                let name = match op {
+                    0x84 | 0x85 => "test",
+                    0x86 | 0x87 => "xchg",
+                    _ => "mov",
+                };
+                (named(name), vec![self.e(m, size)?, self.g(m, size)], None)
+            }
+            0x8A | 0x8B => {
+                let m = self.modrm()?;
+                let size = if op == 0x8A { 1 } else { os };
+                (named("mov"), vec![self.g(m, size), self.e(m, size)?], None)
+            }
+            0x8D => {
+                let m = self.modrm()?;
+                if m.md == 3 {
+                    return None;
+                }
+                (named("lea"), vec![self.g(m, os), self.e(m, 0)?], None)
+            }
+            0x90 if self.rex & 1 == 0 => {
+                let name = if self.rep == Some(0xF3) {
+                    "pause"
+                } else {
+                    "nop"
+                };
+                (named(name), vec![], None)
+            }
+            0x90..=0x97 => (
+                named("xchg"),
+                vec![reg(self, op, os), vec![self.register(0, os)]],
+                None,
+            ),
+            0x98 => (
+                named(match os {
+                    8 => "cdqe",
+                    4 => "cwde",
+                    _ => "cbw",
+                }),
+                vec![],
+                None,
+            ),
+            0x99 => (
+                named(match os {
+                    8 => "cqo",
+                    4 => "cdq",
+                    _ => "cwd",
+                }),
+                vec![],
+                None,
+            ),
+            0xA0..=0xA3 => {
+                let size = if op & 1 == 0 { 1 } else { os };
+                let (mem, acc) = (self.moffs(size)?, vec![self.register(0, size)]);
+                let operands = if op < 0xA2 {
+                    vec![acc, mem]
+                } else {
+                    vec![mem, acc]
+                };
+                (named("mov"), operands, None)
+            }
+            0xA4 | 0xA5 | 0xAA | 0xAB => {
+                let suffix = match (op & 1, os) {
+                    (0, _) => "b",
+                    (_, 2) => "w",
+                    (_, 4) => "d",
+                    _ => "q",
+                };
+                let name = if op < 0xA8 { "movs" } else { "stos" };
+                let prefix = if self.rep == Some(0xF3) { "rep " } else { "" };
+                (format!("{prefix}{name}{suffix}"), vec![], None)
+            }
+            0xA8 => (
+                named("test"),
+                vec![vec![self.register(0, 1)], self.imm(1)?],
+                None,
+            ),
+            0xA9 => (
+                named("test"),
+                vec![vec![self.register(0, os)], self.imm(os)?],
+                None,
+            ),
+            0xB0..=0xB7 => (named("mov"), vec![reg(self, op, 1), self.imm(1)?], None),
+            0xB8..=0xBF => {
+                let imm = if os == 8 {
+                    let value = u64::from_le_bytes(self.array()?);
+                    vec![Token::Immediate(format!("0x{value:X}"))]
+                } else {
+                    self.imm(os)?
+                };
+                (named("mov"), vec![reg(self, op, os), imm], None)
+            }
+            0xC0 | 0xC1 | 0xD0 | 0xD1 | 0xD2 | 0xD3 => {
+                let m = self.modrm()?;
+                let size = if op & 1 == 0 { 1 } else { os };
+                let rm = self.e(m, size)?;
+                let count = match op {
+                    0xC0 | 0xC1 => imm_operand(self.i8()?, 1),
+                    0xD0 | 0xD1 => vec![Token::Immediate("1".to_owned())],
+                    _ => vec![Token::Register("cl".to_owned())],
+                };
+                (named(SHIFT[m.reg & 7]), vec![rm, count], None)
+            }
+            0xC2 => (named("ret"), vec![imm_operand(self.i16()?, 2)], None),
+            0xC3 => (named("ret"), vec![], None),
+            0xC6 | 0xC7 => {
+                let m = self.modrm()?;
+                if m.reg & 7 != 0 {
+                    return None;
+                }
+                let size = if op == 0xC6 { 1 } else { os };
+                let rm = self.e(m, size)?;
+                (named("mov"), vec![rm, self.imm(size)?], None)
+            }
+            0xC9 => (named("leave"), vec![], None),
+            0xCC => (named("int3"), vec![], None),
+            0xCD => (named("int"), vec![imm_operand(self.u8()? as i64, 1)], None),
+            0xE8 | 0xE9 => {
+                let rel = self.i32()?;
+                let target = self.relative(rel);
+                let name = if op == 0xE8 { "call" } else { "jmp" };
+                (named(name), vec![branch_operand(target)], Some(target))
+            }
+            0xEB => {
+                let rel = self.i8()?;
+                let target = self.relative(rel);
+                (named("jmp"), vec![branch_operand(target)], Some(target))
+            }
+            0xF4 => (named("hlt"), vec![], None),
+            0xF6 | 0xF7 => {
+                let m = self.modrm()?;
+                let size = if op == 0xF6 { 1 } else { os };
+                let rm = self.e(m, size)?;
+                match m.reg & 7 {
+                    0 | 1 => (named("test"), vec![rm, self.imm(size)?], None),
+                    n => {
+                        let name = ["", "", "not", "neg", "mul", "imul", "div", "idiv"][n];
+                        (named(name), vec![rm], None)
+                    }
+                }
+            }
+            0xFE => {
+                let m = self.modrm()?;
+                let name = match m.reg & 7 {
+                    0 => "inc",
+                    1 => "dec",
+                    _ => return None,
+                };
+                (named(name), vec![self.e(m, 1)?], None)
+            }
+            0xFF => {
+                let m = self.modrm()?;
+                let (name, size) = match m.reg & 7 {
+                    0 => ("inc", os),
+                    1 => ("dec", os),
+                    2 => ("call", ss),
+                    4 => ("jmp", ss),
+                    6 => ("push", ss),
+                    _ => return None,
+                };
+                (named(name), vec![self.e(m, size)?], None)
+            }
+            _ => return None,
+        })
+    }
+
+    fn two_byte(&mut self) -> Option<(String, Vec<Operand>, Option<usize>)> {
+        let op = self.u8()?;
+        let os = self.osize();
+        let named = |s: &str| s.to_owned();
+        // Mandatory prefix of SSE instructions.
+        let sse = match (self.opsize16, self.rep) {
+            (_, Some(0xF3)) => "ss",
+            (_, Some(_)) => "sd",
+            (true, None) => "pd",
+            (false, None) => "ps",
+        };
+        let sse_size = match sse {
+            "ss" => 4,
+            "sd" => 8,
+            _ => 16,
+        };
+
+        Some(match op {
+            0x05 => (named("syscall"), vec![], None),
+            0x0B => (named("ud2"), vec![], None),
+            0x1E if self.rep == Some(0xF3) => match self.u8()? {
+                0xFA => (named("endbr64"), vec![], None),
+                0xFB => (named("endbr32"), vec![], None),
+                _ => return None,
+            },
+            0x1F => {
+                let m = self.modrm()?;
+                (named("nop"), vec![self.e(m, os)?], None)
+            }
+            0x10 | 0x11 => {
+                let m = self.modrm()?;
+                let name = match sse {
+                    "ss" | "sd" => format!("mov{sse}"),
+                    _ => format!("movu{sse}"),
+                };
+                let (x, rm) = (self.g(m, 16), self.e(m, sse_size)?);
+                let operands = if op == 0x10 { vec![x, rm] } else { vec![rm, x] };
+                (name, operands, None)
+            }
+            0x28 | 0x29 if self.rep.is_none() => {
+                let m = self.modrm()?;
+                let (x, rm) = (self.g(m, 16), self.e(m, 16)?);
+                let operands = if op == 0x28 { vec![x, rm] } else { vec![rm, x] };
+                (format!("mova{sse}"), operands, None)
+            }
+            0x2A if self.rep.is_some() => {
+                let m = self.modrm()?;
+                let size = if self.rex_w() { 8 } else { 4 };
+                let operands = vec![self.g(m, 16), self.e(m, size)?];
+                (format!("cvtsi2{sse}"), operands, None)
+            }
+            0x2C | 0x2D if self.rep.is_some() => {
+                let m = self.modrm()?;
+                let size = if self.rex_w() { 8 } else { 4 };
+                let name = if op == 0x2C { "cvtt" } else { "cvt" };
+                let operands = vec![self.g(m, size), self.e(m, sse_size)?];
+                (format!("{name}{sse}2si"), operands, None)
+            }
+            0x2E | 0x2F if self.rep.is_none() => {
+                let m = self.modrm()?;
+                let name = if op == 0x2E { "ucomis" } else { "comis" };
+                let size = if self.opsize16 { 8 } else { 4 };
+                let operands = vec![self.g(m, 16), self.e(m, size)?];
+                (
+                    format!("{name}{}", if self.opsize16 { "d" } else { "s" }),
+                    operands,
+                    None,
+                )
+            }
+            0x31 => (named("rdtsc"), vec![], None),
+            0x40..=0x4F => {
+                let m = self.modrm()?;
+                let name = format!("cmov{}", CONDITIONS[(op & 0xF) as usize]);
+                (name, vec![self.g(m, os), self.e(m, os)?], None)
+            }
+            0x51 | 0x58 | 0x59 | 0x5C | 0x5D | 0x5E | 0x5F => {
+                let m = self.modrm()?;
+                let name = match op {
+                    0x51 => "sqrt",
+                    0x58 => "add",
+                    0x59 => "mul",
+                    0x5C => "sub",
+                    0x5D => "min",
+                    0x5E => "div",
+                    _ => "max",
+                };
+                let operands = vec![self.g(m, 16), self.e(m, sse_size)?];
+                (format!("{name}{sse}"), operands, None)
+            }
+            0x54 | 0x57 if self.rep.is_none() => {
+                let m = self.modrm()?;
+                let name = if op == 0x54 { "and" } else { "xor" };
+                let operands = vec![self.g(m, 16), self.e(m, 16)?];
+                (format!("{name}{sse}"), operands, None)
+            }
+            0x5A => {
+                let m = self.modrm()?;
+                let name = match sse {
+                    "ss" => "cvtss2sd",
+                    "sd" => "cvtsd2ss",
+                    "ps" => "cvtps2pd",
+                    _ => "cvtpd2ps",
+                };
+                let operands = vec![self.g(m, 16), self.e(m, sse_size)?];
+                (named(name), operands, None)
+            }
+            0x6E if self.opsize16 => {
+                let m = self.modrm()?;
+                let size = if self.rex_w() { 8 } else { 4 };
+                let name = if size == 8 { "movq" } else { "movd" };
+                (named(name), vec![self.g(m, 16), self.e(m, size)?], None)
+            }
+            0x7E if self.opsize16 => {
+                let m = self.modrm()?;
+                let size = if self.rex_w() { 8 } else { 4 };
+                let name = if size == 8 { "movq" } else { "movd" };
+                (named(name), vec![self.e(m, size)?, self.g(m, 16)], None)
+            }
+            0x7E if self.rep == Some(0xF3) => {
+                let m = self.modrm()?;
+                (named("movq"), vec![self.g(m, 16), self.e(m, 8)?], None)
+            }
+            0xD6 if self.opsize16 => {
+                let m = self.modrm()?;
+                (named("movq"), vec![self.e(m, 8)?, self.g(m, 16)], None)
+            }
+            0x6F | 0x7F if self.opsize16 || self.rep == Some(0xF3) => {
+                let m = self.modrm()?;
+                let name = if self.opsize16 { "movdqa" } else { "movdqu" };
+                let (x, rm) = (self.g(m, 16), self.e(m, 16)?);
+                let operands = if op == 0x6F { vec![x, rm] } else { vec![rm, x] };
+                (named(name), operands, None)
+            }
+            0xEF if self.opsize16 => {
+                let m = self.modrm()?;
+                (named("pxor"), vec![self.g(m, 16), self.e(m, 16)?], None)
+            }
+            0x80..=0x8F => {
+                let rel = self.i32()?;
+                let target = self.relative(rel);
+                let name = format!("j{}", CONDITIONS[(op & 0xF) as usize]);
+                (name, vec![branch_operand(target)], Some(target))
+            }
+            0x90..=0x9F => {
+                let m = self.modrm()?;
+                let name = format!("set{}", CONDITIONS[(op & 0xF) as usize]);
+                (name, vec![self.e(m, 1)?], None)
+            }
+            0xA2 => (named("cpuid"), vec![], None),
+            0xA3 | 0xAB | 0xB3 | 0xBB => {
+                let m = self.modrm()?;
+                let name = ["bt", "bts", "btr", "btc"][((op >> 3) & 3) as usize];
+                (named(name), vec![self.e(m, os)?, self.g(m, os)], None)
+            }
+            0xAF => {
+                let m = self.modrm()?;
+                (named("imul"), vec![self.g(m, os), self.e(m, os)?], None)
+            }
+            0xB0 | 0xB1 | 0xC0 | 0xC1 => {
+                let m = self.modrm()?;
+                let size = if op & 1 == 0 { 1 } else { os };
+                let name = if op < 0xC0 { "cmpxchg" } else { "xadd" };
+                (named(name), vec![self.e(m, size)?, self.g(m, size)], None)
+            }
+            0xB6 | 0xB7 | 0xBE | 0xBF => {
+                let m = self.modrm()?;
+                let size = if op & 1 == 0 { 1 } else { 2 };
+                let name = if op < 0xBE { "movzx" } else { "movsx" };
+                (named(name), vec![self.g(m, os), self.e(m, size)?], None)
+            }
+            0xC8..=0xCF => {
+                let n = (op & 7) as usize | ((self.rex as usize & 1) << 3);
+                (named("bswap"), vec![vec![self.register(n, os)]], None)
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Immediate of `size` bytes, negative ones are shown as the unsigned value of the operand size.
+fn imm_operand(value: i64, size: u8) -> Operand {
+    let mask = match size {
+        1 => 0xFF,
+        2 => 0xFFFF,
+        4 => 0xFFFF_FFFF,
+        _ => u64::MAX,
+    };
+    vec![Token::Immediate(format!("0x{:X}", value as u64 & mask))]
+}
+
+fn branch_operand(target: usize) -> Operand {
+    vec![Token::Immediate(format!("0x{target:X}"))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(bytes: &[u8], is_64: bool) -> Vec<String> {
+        decode(bytes, 0x1000, is_64)
+            .into_iter()
+            .map(|i| {
+                let operands = i.operands.iter().fold(String::new(), |acc, t| match t {
+                    Token::Register(s) | Token::Immediate(s) | Token::Text(s) => acc + s,
+                });
+                if operands.is_empty() {
+                    i.mnemonic
+                } else {
+                    format!("{} {operands}", i.mnemonic)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn moffs() {
+        assert_eq!(
+            text(
+                &[0xA1, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x00],
+                true
+            ),
+            ["mov eax, dword ptr [0x70605040302010]"]
+        );
+        assert_eq!(
+            text(&[0x48, 0xA3, 0x10, 0x20, 0x30, 0x40, 0, 0, 0, 0], true),
+            ["mov qword ptr [0x40302010], rax"]
+        );
+        assert_eq!(
+            text(&[0x64, 0xA1, 0x30, 0x00, 0x00, 0x00], false),
+            ["mov eax, dword ptr fs:[0x30]"]
+        );
+        assert_eq!(
+            text(&[0xA2, 0x00, 0x10, 0x00, 0x00], false),
+            ["mov byte ptr [0x1000], al"]
+        );
+    }
+
+    #[test]
+    fn sign_extended_immediates() {
+        assert_eq!(text(&[0x6A, 0xFF], true), ["push 0xFFFFFFFFFFFFFFFF"]);
+        assert_eq!(text(&[0x6A, 0xFF], false), ["push 0xFFFFFFFF"]);
+        assert_eq!(text(&[0x6A, 0x10], true), ["push 0x10"]);
+        assert_eq!(
+            text(&[0x6B, 0xC0, 0xFE], false),
+            ["imul eax, eax, 0xFFFFFFFE"]
+        );
+    }
+
+    #[test]
+    fn no_operands() {
+        assert_eq!(text(&[0xC3, 0xCC], true), ["ret", "int3"]);
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    collections::BTreeMap,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumDefinition {
+    pub name: String,
+    /// Size of the underlying integer in bytes.
+    pub size: usize,
+    pub variants: BTreeMap<i64, String>,
+    /// `true` if variants are bit masks of flags that can be combined.
+    #[serde(default)]
+    pub flags: bool,
+}
+
+impl EnumDefinition {
+    pub fn variant(&self, value: i64) -> Option<&str> {
+        self.variants.get(&value).map(String::as_str)
+    }
+
+    /// Formats value of a variant for generated code, flags are written as unsigned hexadecimal.
+    pub fn format_value(&self, value: i64) -> String {
+        if self.flags {
+            format!("{:#X}", value as u64)
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// Enums shared between all classes of the project.
+#[derive(Default)]
+pub struct EnumRegistry {
+    enums: RefCell<Vec<EnumDefinition>>,
+}
+
+impl EnumRegistry {
+    pub fn from_definitions(enums: Vec<EnumDefinition>) -> Self {
+        Self {
+            enums: enums.into(),
+        }
+    }
+
+    pub fn definitions(&self) -> Ref<'_, [EnumDefinition]> {
+        Ref::map(self.enums.borrow(), |e| &e[..])
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<Ref<'_, EnumDefinition>> {
+        Ref::filter_map(self.enums.borrow(), |e| e.iter().find(|d| d.name == name)).ok()
+    }
+
+    pub fn by_name_mut(&self, name: &str) -> Option<RefMut<'_, EnumDefinition>> {
+        RefMut::filter_map(self.enums.borrow_mut(), |e| {
+            e.iter_mut().find(|d| d.name == name)
+        })
+        .ok()
+    }
+
+    /// Adds an empty enum with a unique name, returning the name.
+    pub fn add_empty(&self, size: usize) -> String {
+        self.add("Enum", size, false)
+    }
+
+    /// Adds an empty set of flags with a unique name, returning the name.
+    pub fn add_empty_flags(&self, size: usize) -> String {
+        self.add("Flags", size, true)
+    }
+
+    fn add(&self, prefix: &str, size: usize, flags: bool) -> String {
+        let enums = &mut *self.enums.borrow_mut();
+
+        let name = (0..)
+            .map(|i| format!("{prefix}{i}"))
+            .find(|n| !enums.iter().any(|d| d.name == *n))
+            .unwrap();
+        enums.push(EnumDefinition {
+            name: name.clone(),
+            variants: BTreeMap::new(),
+            size,
+            flags,
+        });
+
+        name
+    }
+}
@@ -0,0 +1,209 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{
+    class::ClassList,
+    context::{InspectionContext, Selection},
+    generator::Generator,
+};
+use eframe::{
+    egui::{collapsing_header::CollapsingState, Label, Sense, TextFormat, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use std::cell::Cell;
+
+/// Arrays creating more element fields than this, nested arrays included, are refused.
+pub const MAX_ARRAY_ELEMENTS: usize = 0x10000;
+
+/// Returns the number of element fields an array of `count` `inner`s creates, `None` if it's
+/// above [`MAX_ARRAY_ELEMENTS`].
+pub fn array_elements(inner: &FieldKind, count: usize) -> Option<usize> {
+    let per_element = match inner {
+        FieldKind::Array(inner, count) => array_elements(inner, *count)?,
+        _ => 1,
+    };
+    per_element
+        .checked_mul(count)
+        .filter(|&n| n <= MAX_ARRAY_ELEMENTS)
+}
+
+pub struct ArrayField {
+    id: FieldId,
+    state: NamedState,
+    inner: FieldKind,
+    count: usize,
+    elements: Vec<Box<dyn Field>>,
+    // New element count requested by user, applied through `FieldResponse::ChangeKind`.
+    new_count: Cell<Option<usize>>,
+}
+
+impl ArrayField {
+    pub fn new(name: String, inner: FieldKind, count: usize, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            elements: (0..count)
+                .map(|i| {
+                    inner
+                        .clone()
+                        .into_field(Some(format!("[{i}]")), pointer_size)
+                })
+                .collect(),
+            new_count: None.into(),
+            inner,
+            count,
+        }
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_YELLOW);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("{:?}", self.inner),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+        );
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_value(
+            self,
+            ui,
+            ctx,
+            &self.state,
+            Color32::GRAY,
+            |v| {
+                if v {
+                    self.count.to_string()
+                } else {
+                    format!("[{}]", self.count)
+                }
+            },
+            |new, ctx| match new.parse::<usize>() {
+                Ok(count) if count > 0 && array_elements(&self.inner, count).is_none() => {
+                    ctx.toasts.error(format!(
+                        "Arrays can have at most {MAX_ARRAY_ELEMENTS} elements"
+                    ));
+                    false
+                }
+                Ok(count) if count > 0 => {
+                    self.new_count.set(Some(count));
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let parent_id = ctx.current_id;
+
+        let response = self.elements.iter().enumerate().fold(None, |r, (i, f)| {
+            ctx.current_id = parent_id.with(i);
+            r.or(f.draw(ui, ctx))
+        });
+        ctx.current_id = parent_id;
+
+        response
+    }
+}
+
+impl Field for ArrayField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.elements.iter().map(|f| f.size()).sum()
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Array(Box::new(self.inner.clone()), self.count)
+    }
+
+    fn contains(&self, id: FieldId) -> bool {
+        self.id == id || self.elements.iter().any(|f| f.contains(id))
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.elements
+            .iter()
+            .fold(false, |changed, f| f.refresh_size(classes) | changed)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let start = ctx.offset;
+
+        let state = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false);
+        let mut response = state
+            .show_header(ui, |ui| self.show_header(ui, ctx))
+            .body(|ui| self.show_body(ui, ctx))
+            .2
+            .and_then(|inner| inner.inner);
+
+        // Elements aren't a part of the class, so selecting them selects the whole array.
+        if let Some(sel) = ctx.selection {
+            if sel.container_id == ctx.current_container
+                && self.elements.iter().any(|f| f.id() == sel.field_id)
+            {
+                ctx.selection = Some(Selection {
+                    address: ctx.address + start,
+                    field_id: self.id,
+                    ..sel
+                });
+            }
+        }
+
+        if let Some(count) = self.new_count.take() {
+            // Resizing goes through `ChangeKind` so neighbouring fields keep their offsets.
+            ctx.selection = Some(Selection {
+                address: ctx.address + start,
+                container_id: ctx.current_container,
+                field_id: self.id,
+            });
+            response = Some(FieldResponse::ChangeKind(FieldKind::Array(
+                Box::new(self.inner.clone()),
+                count,
+            )));
+        }
+
+        ctx.offset = start + self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_array_field(
+            self.state.name.borrow().as_str(),
+            self.inner.clone(),
+            self.count,
+            None,
+        );
+    }
+}
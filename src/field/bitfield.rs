@@ -0,0 +1,272 @@
+use super::{
+    claim_secondary_click, create_text_format, display_field_name, display_field_prelude,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, fonts::fid_m, generator::Generator};
+use eframe::{
+    egui::{
+        collapsing_header::CollapsingState, Button, DragValue, Label, Popup, PopupCloseBehavior,
+        Sense, TextEdit, TextFormat, Ui,
+    },
+    epaint::{text::LayoutJob, Color32},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BitSlice {
+    pub name: String,
+    pub bit_offset: u8,
+    pub bit_width: u8,
+}
+
+impl BitSlice {
+    pub fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bit_width as u32)
+    }
+
+    pub fn extract(&self, raw: u64) -> u64 {
+        (raw >> self.bit_offset) & self.mask()
+    }
+
+    pub fn insert(&self, raw: u64, value: u64) -> u64 {
+        (raw & !(self.mask() << self.bit_offset)) | ((value & self.mask()) << self.bit_offset)
+    }
+}
+
+pub struct BitfieldField {
+    id: FieldId,
+    size: usize,
+    state: NamedState,
+    slices: RefCell<Vec<BitSlice>>,
+    // Index of the slice which value is being edited, the edit buffer and whether it should be focused.
+    editing: RefCell<Option<(usize, String, bool)>>,
+}
+
+impl BitfieldField {
+    pub fn new(name: String, size: usize, slices: Vec<BitSlice>) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            slices: slices.into(),
+            editing: None.into(),
+            size,
+        }
+    }
+
+    fn read_raw(&self, ctx: &InspectionContext) -> Option<u64> {
+        let mut buf = [0; 8];
+        ctx.read(self.id, ctx.address + ctx.offset, &mut buf[..self.size])
+            .then(|| u64::from_ne_bytes(buf))
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext, raw: u64) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_BLUE);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("[Bitfield{}]", self.size * 8),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+        );
+        job.append(
+            &format!("{raw:#X}"),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.foreground),
+        );
+
+        let r = ui.add(Label::new(job).sense(Sense::click()));
+        if r.clicked() {
+            ctx.select(self.id);
+        }
+
+        Popup::context_menu(&r)
+            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+            .show(|ui| self.slices_menu(ui));
+    }
+
+    fn slices_menu(&self, ui: &mut Ui) {
+        let bits = (self.size * 8) as u8;
+        let slices = &mut *self.slices.borrow_mut();
+
+        let mut removed = None;
+        for (i, slice) in slices.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut slice.name).desired_width(100.));
+                ui.add(DragValue::new(&mut slice.bit_offset).range(0..=bits - 1))
+                    .on_hover_text("Bit offset");
+                ui.add(DragValue::new(&mut slice.bit_width).range(1..=bits - slice.bit_offset))
+                    .on_hover_text("Bit width");
+                if ui.small_button("x").clicked() {
+                    removed = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = removed {
+            slices.remove(i);
+        }
+
+        let next = slices
+            .iter()
+            .map(|s| s.bit_offset + s.bit_width)
+            .max()
+            .unwrap_or(0);
+        if ui
+            .add_enabled(next < bits, Button::new("Add slice"))
+            .clicked()
+        {
+            slices.push(BitSlice {
+                name: format!("bits{next}"),
+                bit_offset: next,
+                bit_width: 1,
+            });
+        }
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext, raw: u64) {
+        let address = ctx.address + ctx.offset;
+        let slices = self.slices.borrow();
+        let editing = &mut *self.editing.borrow_mut();
+
+        for (i, slice) in slices.iter().enumerate() {
+            let value = slice.extract(raw);
+
+            ui.horizontal(|ui| {
+                let mut job = LayoutJob::default();
+                job.append(
+                    &format!(
+                        "{:>2}:{:<2}",
+                        slice.bit_offset,
+                        slice.bit_offset + slice.bit_width - 1
+                    ),
+                    0.,
+                    create_text_format(ctx.theme, false, Color32::KHAKI),
+                );
+                job.append(
+                    &slice.name,
+                    8.,
+                    create_text_format(ctx.theme, false, Color32::LIGHT_BLUE),
+                );
+                ui.add(Label::new(job));
+
+                match editing {
+                    Some((index, buf, should_focus)) if *index == i => {
+                        let r = TextEdit::singleline(buf)
+                            .desired_width(80.)
+                            .font(fid_m())
+                            .show(ui)
+                            .response;
+                        if *should_focus {
+                            r.request_focus();
+                            *should_focus = false;
+                        }
+
+                        if r.clicked_elsewhere() {
+                            *editing = None;
+                        } else if r.lost_focus() {
+                            match parse_value(buf) {
+                                Some(new) if new <= slice.mask() => {
+                                    let raw = slice.insert(raw, new);
+                                    ctx.write(address, &raw.to_ne_bytes()[..self.size]);
+                                    *editing = None;
+                                }
+                                _ => {
+                                    ctx.toasts.error("Invalid value");
+                                    *should_focus = true;
+                                }
+                            }
+                        }
+                    }
+                    _ => {
+                        let mut job = LayoutJob::default();
+                        job.append(
+                            &format!(
+                                "{value} (0b{value:0width$b})",
+                                width = slice.bit_width as usize
+                            ),
+                            4.,
+                            create_text_format(ctx.theme, false, ctx.theme.foreground),
+                        );
+
+                        let r = ui.add(Label::new(job).sense(Sense::click()));
+                        if r.secondary_clicked() {
+                            claim_secondary_click(ui);
+                            *editing = Some((i, value.to_string(), true));
+                        } else if r.clicked() {
+                            ctx.select(self.id);
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl Field for BitfieldField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Bitfield(self.size, self.slices.borrow().clone())
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some(raw) = self.read_raw(ctx) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        let state = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false);
+        state
+            .show_header(ui, |ui| self.show_header(ui, ctx, raw))
+            .body(|ui| self.show_body(ui, ctx, raw));
+
+        ctx.offset += self.size;
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+/// Parses decimal, `0x` prefixed hexadecimal or `0b` prefixed binary value.
+fn parse_value(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+        u64::from_str_radix(bin, 2).ok()
+    } else {
+        s.parse().ok()
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
-    display_field_name, display_field_prelude, display_field_value, next_id, CodegenData, Field,
-    FieldId, FieldKind, FieldResponse, NamedState,
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
 };
 use crate::{context::InspectionContext, generator::Generator};
 use eframe::{
@@ -32,6 +32,18 @@ impl Field for BoolField {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn size(&self) -> usize {
         1
     }
@@ -43,38 +55,64 @@ impl Field for BoolField {
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
         let mut val = 0u8;
         let address = ctx.address + ctx.offset;
-        ctx.process.read(address, slice::from_mut(&mut val));
+        if !ctx.read(self.id, address, slice::from_mut(&mut val)) {
+            return display_read_error(ui, self, ctx);
+        }
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
 
             if ui.add(Label::new(job).sense(Sense::click())).clicked() {
                 ctx.select(self.id);
             }
 
             display_field_name(self, ui, ctx, &self.state, Color32::GOLD);
+
+            let (glyph, color) = match val {
+                0 => ("✖", Color32::RED),
+                1 => ("✔", Color32::GREEN),
+                _ => ("✔", Color32::ORANGE),
+            };
+            let mut job = LayoutJob::default();
+            job.append(
+                glyph,
+                4.,
+                create_text_format(ctx.theme, ctx.is_selected(self.id), color),
+            );
+
+            let mut r = ui
+                .add(Label::new(job).sense(Sense::click()))
+                .on_hover_text("Click to toggle");
+            if val > 1 {
+                r = r.on_hover_text("Value is neither 0 nor 1, the field is likely not a boolean");
+            }
+            if r.clicked() {
+                ctx.write(address, &[(val == 0) as u8]);
+                // Keep the new value instead of restoring the frozen one.
+                if let Some(frozen) = ctx.frozen.get_mut(&self.id) {
+                    ctx.process.read(frozen.address, &mut frozen.bytes);
+                }
+            }
+
             display_field_value(
                 self,
                 ui,
                 ctx,
                 &self.state,
-                Color32::WHITE,
-                |_| {
-                    match val {
-                        1 => "true",
-                        0 => "false",
-                        _ => "invalid",
-                    }
-                    .to_owned()
+                color,
+                |_| match val {
+                    0 => "false".to_owned(),
+                    1 => "true".to_owned(),
+                    _ => format!("true (0x{val:02X})"),
                 },
-                |new: &str| match new {
+                |new: &str, ctx| match new {
                     "1" | "true" | "yes" | "on" => {
-                        ctx.process.write(address, &[1]);
+                        ctx.write(address, &[1]);
                         true
                     }
                     "0" | "false" | "no" | "off" => {
-                        ctx.process.write(address, &[0]);
+                        ctx.write(address, &[0]);
                         true
                     }
                     _ => false,
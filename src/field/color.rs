@@ -0,0 +1,243 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{
+    context::{InspectionContext, Selection},
+    generator::Generator,
+};
+use eframe::{
+    egui::{
+        color_picker::{color_edit_button_hsva, Alpha},
+        Label, Popup, Sense, Ui,
+    },
+    epaint::{text::LayoutJob, Color32, Hsva},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Order and type of the color channels in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColorLayout {
+    RgbaU8,
+    /// `D3DCOLOR` on big endian machines.
+    ArgbU8,
+    /// `D3DCOLOR` on little endian machines and Unreal Engine `FColor`.
+    BgraU8,
+    AbgrU8,
+    /// Four `f32`s in linear space, e.g. Unreal Engine `FLinearColor`.
+    RgbaF32,
+}
+
+impl ColorLayout {
+    pub const ALL: [ColorLayout; 5] = [
+        Self::RgbaU8,
+        Self::ArgbU8,
+        Self::BgraU8,
+        Self::AbgrU8,
+        Self::RgbaF32,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RgbaU8 => "RGBA",
+            Self::ArgbU8 => "ARGB",
+            Self::BgraU8 => "BGRA",
+            Self::AbgrU8 => "ABGR",
+            Self::RgbaF32 => "RGBA f32",
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        self == Self::RgbaF32
+    }
+
+    pub fn size(self) -> usize {
+        if self.is_float() {
+            16
+        } else {
+            4
+        }
+    }
+
+    /// Kind of the value the color is generated as.
+    pub fn raw_kind(self) -> FieldKind {
+        if self.is_float() {
+            FieldKind::Array(Box::new(FieldKind::F32), 4)
+        } else {
+            FieldKind::U32
+        }
+    }
+
+    /// Index of the red, green, blue and alpha channel of every byte in memory.
+    fn channel_order(self) -> [usize; 4] {
+        match self {
+            Self::RgbaU8 | Self::RgbaF32 => [0, 1, 2, 3],
+            Self::ArgbU8 => [3, 0, 1, 2],
+            Self::BgraU8 => [2, 1, 0, 3],
+            Self::AbgrU8 => [3, 2, 1, 0],
+        }
+    }
+
+    /// Decodes `bytes`, which must be `self.size()` long.
+    pub fn decode(self, bytes: &[u8]) -> Hsva {
+        if self.is_float() {
+            let [r, g, b, a] = [0, 1, 2, 3]
+                .map(|i| f32::from_ne_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap()));
+            return Hsva::from_rgba_unmultiplied(r, g, b, a);
+        }
+
+        let mut rgba = [0; 4];
+        for (byte, channel) in bytes.iter().zip(self.channel_order()) {
+            rgba[channel] = *byte;
+        }
+        Hsva::from_srgba_unmultiplied(rgba)
+    }
+
+    pub fn encode(self, color: Hsva) -> Vec<u8> {
+        if self.is_float() {
+            return color
+                .to_rgba_unmultiplied()
+                .iter()
+                .flat_map(|c| c.to_ne_bytes())
+                .collect();
+        }
+
+        let rgba = color.to_srgba_unmultiplied();
+        self.channel_order().iter().map(|c| rgba[*c]).collect()
+    }
+}
+
+/// Formats the color as `#RRGGBBAA`.
+pub fn format_color(color: Hsva) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+}
+
+pub struct ColorField {
+    id: FieldId,
+    state: NamedState,
+    layout: ColorLayout,
+    // New layout requested by user, applied through `FieldResponse::ChangeKind`.
+    new_layout: Cell<Option<ColorLayout>>,
+}
+
+impl ColorField {
+    pub fn new(name: String, layout: ColorLayout) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            new_layout: None.into(),
+            layout,
+        }
+    }
+}
+
+impl Field for ColorField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.layout.size()
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Color(self.layout)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let address = ctx.address + ctx.offset;
+        let mut bytes = vec![0; self.size()];
+        if !ctx.read(self.id, address, &mut bytes) {
+            return display_read_error(ui, self, ctx);
+        }
+        let mut color = self.layout.decode(&bytes);
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_RED);
+
+            if color_edit_button_hsva(ui, &mut color, Alpha::OnlyBlend).changed() {
+                ctx.write(address, &self.layout.encode(color));
+                // Keep the new value instead of restoring the frozen one.
+                if let Some(frozen) = ctx.frozen.get_mut(&self.id) {
+                    ctx.process.read(frozen.address, &mut frozen.bytes);
+                }
+            }
+
+            let is_selected = ctx.is_selected(self.id);
+            let mut job = LayoutJob::default();
+            job.append(
+                &format_color(color),
+                4.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.foreground),
+            );
+            job.append(
+                &format!("[{}]", self.layout.label()),
+                4.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.dim),
+            );
+
+            let r = ui
+                .add(Label::new(job).sense(Sense::click()))
+                .on_hover_text("Right click to change the layout");
+            if r.clicked() {
+                ctx.select(self.id);
+            }
+
+            Popup::context_menu(&r).show(|ui| {
+                for layout in ColorLayout::ALL {
+                    if ui
+                        .selectable_label(layout == self.layout, layout.label())
+                        .clicked()
+                    {
+                        self.new_layout
+                            .set(Some(layout).filter(|layout| *layout != self.layout));
+                        ui.close();
+                    }
+                }
+            });
+        });
+
+        let mut response = None;
+        if let Some(layout) = self.new_layout.take() {
+            // Layouts differ in size, so it goes through `ChangeKind` like array resizing.
+            ctx.selection = Some(Selection {
+                container_id: ctx.current_container,
+                field_id: self.id,
+                address,
+            });
+            response = Some(FieldResponse::ChangeKind(FieldKind::Color(layout)));
+        }
+
+        ctx.offset += self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
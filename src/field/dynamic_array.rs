@@ -0,0 +1,354 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState, PointerWidth,
+};
+use crate::{
+    class::ClassList,
+    context::{InspectionContext, Selection},
+    fonts::fid_m,
+    generator::Generator,
+};
+use eframe::{
+    egui::{
+        collapsing_header::CollapsingState, Id, Label, RichText, ScrollArea, Sense, TextFormat, Ui,
+    },
+    epaint::{text::LayoutJob, Color32},
+};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Number of elements displayed by default, configured with `max_dynamic_array_elements`.
+pub const DEFAULT_MAX_DISPLAYED_ELEMENTS: usize = 256;
+
+static MAX_DISPLAYED_ELEMENTS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DISPLAYED_ELEMENTS);
+
+pub fn set_max_displayed_elements(count: usize) {
+    MAX_DISPLAYED_ELEMENTS.store(count, Ordering::Relaxed);
+}
+
+/// How the heap buffer of a dynamic array is described.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrayLayout {
+    /// Pointer to the data, then length and capacity in elements.
+    SizeCapacity,
+    /// Pointers to the first element, past the last element and past the allocation, as in
+    /// `std::vector`.
+    BeginEnd,
+}
+
+/// Array stored on the heap, the field itself only holds the pointer and the length.
+pub struct DynamicArrayField {
+    id: FieldId,
+    state: NamedState,
+    inner: FieldKind,
+    layout: ArrayLayout,
+    elements: RefCell<Vec<Box<dyn Field>>>,
+    width: PointerWidth,
+}
+
+impl DynamicArrayField {
+    pub fn new(name: String, inner: FieldKind, pointer_size: usize) -> Self {
+        Self::with_layout(name, inner, ArrayLayout::SizeCapacity, pointer_size)
+    }
+
+    pub fn with_layout(
+        name: String,
+        inner: FieldKind,
+        layout: ArrayLayout,
+        pointer_size: usize,
+    ) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            elements: vec![].into(),
+            width: PointerWidth::new(pointer_size),
+            inner,
+            layout,
+        }
+    }
+
+    /// Returns pointer to the data, length and capacity, `None` if the header couldn't be read.
+    fn read_header(&self, ctx: &InspectionContext) -> Option<(usize, usize, usize)> {
+        let size = self.width.get();
+        let mut bytes = [0; 24];
+        if !ctx.read(self.id, ctx.address + ctx.offset, &mut bytes[..size * 3]) {
+            return None;
+        }
+        let words = [0, 1, 2].map(|i| {
+            let mut word = [0; 8];
+            word[..size].copy_from_slice(&bytes[i * size..(i + 1) * size]);
+            usize::from_ne_bytes(word)
+        });
+
+        Some(match self.layout {
+            ArrayLayout::SizeCapacity => (words[0], words[1], words[2]),
+            ArrayLayout::BeginEnd => {
+                let element = self.inner.size(size);
+                (
+                    words[0],
+                    words[1].saturating_sub(words[0]) / element,
+                    words[2].saturating_sub(words[0]) / element,
+                )
+            }
+        })
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext, len: usize, cap: usize) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_YELLOW);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("{}<{:?}> [{len}/{cap}]", self.label(), self.inner),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+        );
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+    }
+
+    fn show_body(
+        &self,
+        ui: &mut Ui,
+        ctx: &mut InspectionContext,
+        data: usize,
+        len: usize,
+    ) -> Option<FieldResponse> {
+        if data == 0 || len == 0 {
+            ui.label(RichText::new("Empty").color(Color32::GRAY).font(fid_m()));
+            return None;
+        }
+
+        if !ctx.process.can_read(data) {
+            ui.label(
+                RichText::new(format!("Can't read memory at address {data:#X}"))
+                    .color(Color32::RED)
+                    .font(fid_m()),
+            );
+            return None;
+        }
+
+        let shown = len.min(MAX_DISPLAYED_ELEMENTS.load(Ordering::Relaxed));
+        let mut elements = self.elements.borrow_mut();
+        while elements.len() < shown {
+            let name = format!("[{}]", elements.len());
+            elements.push(self.inner.clone().into_field(Some(name), self.width.get()));
+        }
+
+        let mut inner_ctx = InspectionContext {
+            class_list: ctx.class_list,
+            selection: ctx.selection,
+            multi_selection: ctx.multi_selection,
+            extend_selection: ctx.extend_selection,
+            current_container: ctx.current_container,
+            current_id: Id::NULL,
+            parent_id: ctx.current_id,
+            level_rng: ctx.level_rng,
+            process: ctx.process,
+            verify_writes: ctx.verify_writes,
+            reader: ctx.reader,
+            frozen: ctx.frozen,
+            recordings: ctx.recordings,
+            toasts: ctx.toasts,
+            named_addresses: ctx.named_addresses,
+            search: ctx.search,
+            previous_values: ctx.previous_values.as_deref_mut(),
+            // Snapshots only cover the inspected class.
+            changes: None,
+            // Pointers of the processes point to different memory.
+            secondary: None,
+            columns: ctx.columns,
+            scroll_to: None,
+            rename_selected: ctx.rename_selected,
+            theme: ctx.theme,
+            offset: 0,
+            address: data,
+        };
+
+        let parent_id = ctx.current_id;
+        let response = ScrollArea::vertical()
+            .id_salt(parent_id)
+            .max_height(240.)
+            .show(ui, |ui| {
+                elements[..shown]
+                    .iter()
+                    .enumerate()
+                    .fold(None, |r, (i, f)| {
+                        inner_ctx.current_id = parent_id.with(i);
+                        r.or(f.draw(ui, &mut inner_ctx))
+                    })
+            })
+            .inner;
+        ctx.selection = inner_ctx.selection;
+
+        if shown < len {
+            ui.label(
+                RichText::new(format!("{} more elements aren't displayed", len - shown))
+                    .color(Color32::GRAY)
+                    .font(fid_m()),
+            );
+        }
+
+        response
+    }
+
+    fn label(&self) -> &'static str {
+        match self.layout {
+            ArrayLayout::SizeCapacity => "DynArray",
+            ArrayLayout::BeginEnd => "std::vector",
+        }
+    }
+}
+
+impl Field for DynamicArrayField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.width.get() * 3
+    }
+
+    fn kind(&self) -> FieldKind {
+        match self.layout {
+            ArrayLayout::SizeCapacity => FieldKind::DynamicArray(Box::new(self.inner.clone())),
+            ArrayLayout::BeginEnd => FieldKind::StdVector(Box::new(self.inner.clone())),
+        }
+    }
+
+    fn contains(&self, id: FieldId) -> bool {
+        self.id == id || self.elements.borrow().iter().any(|f| f.contains(id))
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        let changed = self.width.refresh(classes);
+        if changed {
+            // Elements are created again with the new width when they're displayed.
+            self.elements.borrow_mut().clear();
+        }
+        changed
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some((data, len, cap)) = self.read_header(ctx) else {
+            return display_read_error(ui, self, ctx);
+        };
+        let start = ctx.offset;
+
+        let response = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false)
+            .show_header(ui, |ui| self.show_header(ui, ctx, len, cap))
+            .body(|ui| self.show_body(ui, ctx, data, len))
+            .2
+            .and_then(|inner| inner.inner);
+
+        // Elements aren't a part of the class, so selecting them selects the whole array.
+        if let Some(sel) = ctx.selection {
+            if sel.container_id == ctx.current_container
+                && self
+                    .elements
+                    .borrow()
+                    .iter()
+                    .any(|f| f.id() == sel.field_id)
+            {
+                ctx.selection = Some(Selection {
+                    address: ctx.address + start,
+                    field_id: self.id,
+                    ..sel
+                });
+            }
+        }
+
+        ctx.offset = start + self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+/// `std::vector`, dynamic array described by begin, end and capacity pointers.
+pub struct StdVectorField(DynamicArrayField);
+
+impl StdVectorField {
+    pub fn new(name: String, inner: FieldKind, pointer_size: usize) -> Self {
+        Self(DynamicArrayField::with_layout(
+            name,
+            inner,
+            ArrayLayout::BeginEnd,
+            pointer_size,
+        ))
+    }
+}
+
+impl Field for StdVectorField {
+    fn id(&self) -> FieldId {
+        self.0.id()
+    }
+
+    fn name(&self) -> Option<String> {
+        self.0.name()
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.0.comment()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        self.0.set_comment(comment);
+    }
+
+    fn set_name(&self, name: String) {
+        self.0.set_name(name);
+    }
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn kind(&self) -> FieldKind {
+        self.0.kind()
+    }
+
+    fn contains(&self, id: FieldId) -> bool {
+        self.0.contains(id)
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.0.refresh_size(classes)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        self.0.draw(ui, ctx)
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, data: &CodegenData) {
+        self.0.codegen(generator, data);
+    }
+}
@@ -0,0 +1,154 @@
+use super::{
+    create_text_format, display_class_fields, display_field_name, display_field_prelude, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{
+    class::{ClassId, ClassList},
+    context::InspectionContext,
+    fonts::fid_m,
+    generator::Generator,
+};
+use eframe::{
+    egui::{collapsing_header::CollapsingState, Label, RichText, Sense, TextFormat, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use std::cell::Cell;
+
+/// Another class stored inline, its fields are displayed as a part of the field.
+pub struct EmbeddedClassField {
+    id: FieldId,
+    state: NamedState,
+    class_id: ClassId,
+    // Size of the class when it was last seen, kept up to date by `ClassList::refresh_sizes`.
+    size: Cell<usize>,
+}
+
+impl EmbeddedClassField {
+    pub fn new(name: String, class_id: ClassId, size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            class_id,
+            size: size.into(),
+        }
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::KHAKI);
+
+        let (text, color) = match ctx.class_list.by_id(self.class_id) {
+            Some(class) => (format!("[{}]", class.name), Color32::LIGHT_GRAY),
+            None => ("[missing class]".to_owned(), Color32::DARK_GRAY),
+        };
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &text,
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), color),
+        );
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        ctx.class_list.by_id(self.class_id)?;
+
+        // Hand edited projects may still contain cycles.
+        if ctx
+            .class_list
+            .depends_on(self.class_id, ctx.current_container)
+        {
+            ui.label(
+                RichText::new("Class can't be embedded into itself")
+                    .color(Color32::RED)
+                    .font(fid_m()),
+            );
+            return None;
+        }
+
+        let container = ctx.current_container;
+        ctx.current_container = self.class_id;
+        let response = display_class_fields(ui, ctx, self.class_id, ctx.current_id.value());
+        ctx.current_container = container;
+        response
+    }
+}
+
+impl Field for EmbeddedClassField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.size.get()
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Embedded(self.size.get())
+    }
+
+    fn embedded_class(&self) -> Option<ClassId> {
+        Some(self.class_id)
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        // Size of a deleted class is kept so the following fields don't move.
+        if classes.by_id(self.class_id).is_none() {
+            return false;
+        }
+
+        let size = classes.class_size(self.class_id);
+        self.size.replace(size) != size
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let start = ctx.offset;
+
+        let response = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false)
+            .show_header(ui, |ui| self.show_header(ui, ctx))
+            .body(|ui| self.show_body(ui, ctx))
+            .2
+            .and_then(|inner| inner.inner);
+
+        ctx.offset = start + self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, data: &CodegenData) {
+        // Generators always get the class name, fields of deleted classes become padding.
+        match data.classes.iter().find(|c| c.id() == self.class_id) {
+            Some(class) => generator.add_field(
+                self.state.name.borrow().as_str(),
+                self.kind(),
+                Some(&class.name),
+            ),
+            None => generator.add_offset(self.size()),
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, enums::EnumRegistry, generator::Generator};
+use eframe::{
+    egui::{Label, Popup, PopupCloseBehavior, Sense, TextEdit, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use std::{cell::RefCell, mem::take};
+
+pub struct EnumField {
+    id: FieldId,
+    size: usize,
+    state: NamedState,
+    // Name of the definition in the project's `EnumRegistry`.
+    enum_name: RefCell<Option<String>>,
+    // Name and value of the variant being added.
+    new_variant: RefCell<(String, String)>,
+}
+
+impl EnumField {
+    pub fn new(name: String, size: usize) -> Self {
+        Self::new_with_enum(name, size, None)
+    }
+
+    pub fn new_with_enum(name: String, size: usize, enum_name: Option<String>) -> Self {
+        Self {
+            id: next_id(),
+            size,
+            state: NamedState::new(name),
+            enum_name: enum_name.into(),
+            new_variant: RefCell::default(),
+        }
+    }
+
+    /// Returns raw value and the same value sign extended, `None` if it couldn't be read.
+    fn read_value(&self, ctx: &InspectionContext) -> Option<(u64, i64)> {
+        let mut buf = [0; 8];
+        if !ctx.read(self.id, ctx.address + ctx.offset, &mut buf[..self.size]) {
+            return None;
+        }
+
+        let raw = u64::from_ne_bytes(buf);
+        let shift = 64 - self.size as u32 * 8;
+        Some((raw, ((raw << shift) as i64) >> shift))
+    }
+
+    fn enum_menu(&self, ui: &mut Ui, enums: &EnumRegistry) {
+        ui.set_width(200.);
+
+        let names = enums
+            .definitions()
+            .iter()
+            .filter(|def| def.size == self.size && !def.flags)
+            .map(|def| def.name.clone())
+            .collect::<Vec<_>>();
+
+        ui.vertical_centered_justified(|ui| {
+            let current = &mut *self.enum_name.borrow_mut();
+            for name in names {
+                if ui
+                    .selectable_label(current.as_ref() == Some(&name), &name)
+                    .clicked()
+                {
+                    *current = Some(name);
+                }
+            }
+
+            if ui.button("New enum").clicked() {
+                *current = Some(enums.add_empty(self.size));
+            }
+        });
+
+        let Some(mut def) = self
+            .enum_name
+            .borrow()
+            .as_deref()
+            .and_then(|name| enums.by_name_mut(name))
+        else {
+            return;
+        };
+
+        ui.separator();
+
+        let mut removed = None;
+        for (value, name) in def.variants.iter_mut() {
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(name).desired_width(100.));
+                ui.label(format!("= {value}"));
+                if ui.small_button("x").clicked() {
+                    removed = Some(*value);
+                }
+            });
+        }
+
+        if let Some(value) = removed {
+            def.variants.remove(&value);
+        }
+
+        ui.horizontal(|ui| {
+            let (name, value) = &mut *self.new_variant.borrow_mut();
+            ui.add(
+                TextEdit::singleline(name)
+                    .desired_width(100.)
+                    .hint_text("Name"),
+            );
+            ui.add(
+                TextEdit::singleline(value)
+                    .desired_width(50.)
+                    .hint_text("Value"),
+            );
+
+            if ui.small_button("+").clicked() && !name.is_empty() {
+                let value = if value.is_empty() {
+                    def.variants
+                        .keys()
+                        .next_back()
+                        .map_or(Some(0), |v| v.checked_add(1))
+                } else {
+                    parse_value(value)
+                };
+
+                if let Some(value) = value {
+                    def.variants.insert(value, take(name));
+                }
+            }
+        });
+    }
+}
+
+impl Field for EnumField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Enum(self.size)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some((raw, value)) = self.read_value(ctx) else {
+            return display_read_error(ui, self, ctx);
+        };
+        let address = ctx.address + ctx.offset;
+        let enums = ctx.class_list.enums();
+
+        let (type_name, variant) = match self
+            .enum_name
+            .borrow()
+            .as_deref()
+            .and_then(|name| enums.by_name(name))
+        {
+            Some(def) => (
+                Some(def.name.clone()),
+                def.variant(value).map(str::to_owned),
+            ),
+            None => (None, None),
+        };
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::KHAKI);
+
+            let mut job = LayoutJob::default();
+            job.append(
+                &format!("[{}]", type_name.as_deref().unwrap_or("enum")),
+                0.,
+                create_text_format(
+                    ctx.theme,
+                    ctx.is_selected(self.id),
+                    if type_name.is_some() {
+                        Color32::LIGHT_GRAY
+                    } else {
+                        Color32::DARK_GRAY
+                    },
+                ),
+            );
+
+            let r = ui.add(Label::new(job).sense(Sense::click()));
+            if r.clicked() {
+                ctx.select(self.id);
+            }
+
+            Popup::context_menu(&r)
+                .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+                .show(|ui| self.enum_menu(ui, enums));
+
+            display_field_value(
+                self,
+                ui,
+                ctx,
+                &self.state,
+                ctx.theme.foreground,
+                |v| match &variant {
+                    Some(name) if v => name.clone(),
+                    Some(name) => format!("{name} ({raw:#X})"),
+                    None => format!("{raw:#X}"),
+                },
+                |new, ctx| {
+                    let new = new.trim();
+                    let value = type_name
+                        .as_deref()
+                        .and_then(|name| enums.by_name(name))
+                        .and_then(|def| {
+                            def.variants
+                                .iter()
+                                .find_map(|(v, name)| (name == new).then_some(*v))
+                        })
+                        .or_else(|| parse_value(new));
+
+                    if let Some(value) = value {
+                        ctx.write(address, &value.to_ne_bytes()[..self.size]);
+                        true
+                    } else {
+                        false
+                    }
+                },
+            );
+        });
+
+        ctx.offset += self.size;
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(
+            self.state.name.borrow().as_str(),
+            FieldKind::Enum(self.size),
+            self.enum_name.borrow().as_deref(),
+        );
+    }
+}
+
+/// Parses decimal or `0x` prefixed hexadecimal value.
+fn parse_value(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let value = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()? as i64,
+        None => s.parse::<i64>().ok()?,
+    };
+
+    Some(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
@@ -0,0 +1,305 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, enums::EnumRegistry, fonts::fid_m, generator::Generator};
+use eframe::{
+    egui::{
+        collapsing_header::CollapsingState, Grid, Label, Popup, PopupCloseBehavior, RichText,
+        ScrollArea, Sense, TextEdit, TextFormat, Ui,
+    },
+    epaint::{text::LayoutJob, Color32},
+};
+use std::cell::RefCell;
+
+/// Integer where every bit has its own meaning, bit names are shared through the `EnumRegistry`.
+pub struct FlagsField {
+    id: FieldId,
+    size: usize,
+    state: NamedState,
+    // Name of the flags definition in the project's `EnumRegistry`.
+    flags_name: RefCell<Option<String>>,
+}
+
+impl FlagsField {
+    pub fn new(name: String, size: usize) -> Self {
+        Self::new_with_flags(name, size, None)
+    }
+
+    pub fn new_with_flags(name: String, size: usize, flags_name: Option<String>) -> Self {
+        Self {
+            id: next_id(),
+            size,
+            state: NamedState::new(name),
+            flags_name: flags_name.into(),
+        }
+    }
+
+    fn read_raw(&self, ctx: &InspectionContext) -> Option<u64> {
+        let mut buf = [0; 8];
+        ctx.read(self.id, ctx.address + ctx.offset, &mut buf[..self.size])
+            .then(|| u64::from_ne_bytes(buf))
+    }
+
+    /// Returns name of every bit, `None` for unnamed ones.
+    fn bit_names(&self, enums: &EnumRegistry) -> Vec<Option<String>> {
+        let def = self
+            .flags_name
+            .borrow()
+            .as_deref()
+            .and_then(|name| enums.by_name(name));
+
+        (0..self.size * 8)
+            .map(|bit| {
+                def.as_ref()
+                    .and_then(|def| def.variant(mask(bit)))
+                    .map(str::to_owned)
+            })
+            .collect()
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext, raw: u64) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::KHAKI);
+
+        let enums = ctx.class_list.enums();
+        let type_name = self
+            .flags_name
+            .borrow()
+            .clone()
+            .filter(|name| enums.by_name(name).is_some());
+        let is_selected = ctx.is_selected(self.id);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("[{}]", type_name.as_deref().unwrap_or("flags")),
+            4.,
+            create_text_format(
+                ctx.theme,
+                is_selected,
+                if type_name.is_some() {
+                    Color32::LIGHT_GRAY
+                } else {
+                    Color32::DARK_GRAY
+                },
+            ),
+        );
+
+        let r = ui.add(Label::new(job).sense(Sense::click()));
+        if r.clicked() {
+            ctx.select(self.id);
+        }
+
+        Popup::context_menu(&r)
+            .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+            .show(|ui| self.flags_menu(ui, enums));
+
+        let set = self
+            .bit_names(enums)
+            .into_iter()
+            .enumerate()
+            .filter(|(bit, _)| raw >> bit & 1 == 1)
+            .map(|(bit, name)| name.unwrap_or_else(|| format!("bit{bit}")))
+            .collect::<Vec<_>>();
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("{raw:#X}"),
+            4.,
+            create_text_format(ctx.theme, is_selected, ctx.theme.foreground),
+        );
+        if !set.is_empty() {
+            job.append(
+                &set.join(" | "),
+                4.,
+                create_text_format(ctx.theme, is_selected, Color32::LIGHT_GRAY),
+            );
+        }
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+    }
+
+    fn flags_menu(&self, ui: &mut Ui, enums: &EnumRegistry) {
+        ui.set_width(200.);
+
+        let names = enums
+            .definitions()
+            .iter()
+            .filter(|def| def.size == self.size && def.flags)
+            .map(|def| def.name.clone())
+            .collect::<Vec<_>>();
+
+        ui.vertical_centered_justified(|ui| {
+            let current = &mut *self.flags_name.borrow_mut();
+            for name in names {
+                if ui
+                    .selectable_label(current.as_ref() == Some(&name), &name)
+                    .clicked()
+                {
+                    *current = Some(name);
+                }
+            }
+
+            if ui.button("New flags").clicked() {
+                *current = Some(enums.add_empty_flags(self.size));
+            }
+        });
+
+        let Some(mut def) = self
+            .flags_name
+            .borrow()
+            .as_deref()
+            .and_then(|name| enums.by_name_mut(name))
+        else {
+            return;
+        };
+
+        ui.separator();
+
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            Grid::new("_flags_names").show(ui, |ui| {
+                for bit in 0..self.size * 8 {
+                    let mut name = def.variants.get(&mask(bit)).cloned().unwrap_or_default();
+
+                    ui.label(RichText::new(bit.to_string()).font(fid_m()));
+                    if ui
+                        .add(
+                            TextEdit::singleline(&mut name)
+                                .desired_width(140.)
+                                .hint_text("Unnamed"),
+                        )
+                        .changed()
+                    {
+                        if name.is_empty() {
+                            def.variants.remove(&mask(bit));
+                        } else {
+                            def.variants.insert(mask(bit), name);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext, raw: u64) {
+        let address = ctx.address + ctx.offset;
+        let names = self.bit_names(ctx.class_list.enums());
+
+        // Unnamed bits are only interesting when they're set.
+        let shown = names
+            .iter()
+            .enumerate()
+            .filter(|(bit, name)| name.is_some() || raw >> bit & 1 == 1)
+            .collect::<Vec<_>>();
+        if shown.is_empty() {
+            ui.label(
+                RichText::new("No flags are set")
+                    .color(Color32::GRAY)
+                    .font(fid_m()),
+            );
+            return;
+        }
+
+        Grid::new(ctx.current_id.with("flags"))
+            .striped(true)
+            .show(ui, |ui| {
+                for (bit, name) in shown {
+                    let set = raw >> bit & 1 == 1;
+
+                    ui.label(
+                        RichText::new(format!("{bit:>2}"))
+                            .font(fid_m())
+                            .color(Color32::KHAKI),
+                    );
+                    ui.label(
+                        RichText::new(name.as_deref().unwrap_or("-"))
+                            .font(fid_m())
+                            .color(Color32::LIGHT_BLUE),
+                    );
+
+                    let value = RichText::new(if set { "1" } else { "0" })
+                        .font(fid_m())
+                        .color(if set { Color32::GREEN } else { Color32::GRAY });
+                    if ui
+                        .add(Label::new(value).sense(Sense::click()))
+                        .on_hover_text("Click to toggle")
+                        .clicked()
+                    {
+                        let raw = raw ^ (1 << bit);
+                        ctx.write(address, &raw.to_ne_bytes()[..self.size]);
+                        // Keep the new value instead of restoring the frozen one.
+                        if let Some(frozen) = ctx.frozen.get_mut(&self.id) {
+                            ctx.process.read(frozen.address, &mut frozen.bytes);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+impl Field for FlagsField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Flags(self.size)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some(raw) = self.read_raw(ctx) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        let state = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false);
+        state
+            .show_header(ui, |ui| self.show_header(ui, ctx, raw))
+            .body(|ui| self.show_body(ui, ctx, raw));
+
+        ctx.offset += self.size;
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(
+            self.state.name.borrow().as_str(),
+            FieldKind::Flags(self.size),
+            self.flags_name.borrow().as_deref(),
+        );
+    }
+}
+
+/// Value of the variant for `bit` in the flags definition.
+fn mask(bit: usize) -> i64 {
+    (1u64 << bit) as i64
+}
@@ -1,12 +1,13 @@
 use super::{
-    display_field_name, display_field_prelude, display_field_value, next_id, CodegenData, Field,
-    FieldId, FieldKind, FieldResponse, NamedState,
+    display_field_delta, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
 };
 use crate::{context::InspectionContext, generator::Generator};
 use eframe::{
     egui::{Label, Sense, Ui},
     epaint::{text::LayoutJob, Color32},
 };
+use std::cmp::Ordering;
 
 pub struct FloatField<const N: usize> {
     id: FieldId,
@@ -20,6 +21,14 @@ impl<const N: usize> FloatField<N> {
             state: NamedState::new(name),
         }
     }
+
+    fn value(buf: &[u8]) -> f64 {
+        match N {
+            4 => f32::from_ne_bytes(buf.try_into().unwrap()) as f64,
+            8 => f64::from_ne_bytes(buf.try_into().unwrap()),
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl<const N: usize> Field for FloatField<N> {
@@ -35,6 +44,18 @@ impl<const N: usize> Field for FloatField<N> {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn kind(&self) -> FieldKind {
         match N {
             4 => FieldKind::F32,
@@ -46,11 +67,13 @@ impl<const N: usize> Field for FloatField<N> {
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
         let mut buf = [0; N];
         let address = ctx.address + ctx.offset;
-        ctx.process.read(address, &mut buf);
+        if !ctx.read(self.id, address, &mut buf) {
+            return display_read_error(ui, self, ctx);
+        }
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
 
             if ui.add(Label::new(job).sense(Sense::click())).clicked() {
                 ctx.select(self.id);
@@ -62,16 +85,12 @@ impl<const N: usize> Field for FloatField<N> {
                 ui,
                 ctx,
                 &self.state,
-                Color32::WHITE,
-                |_| match N {
-                    4 => f32::from_ne_bytes(buf[..].try_into().unwrap()) as f64,
-                    8 => f64::from_ne_bytes(buf[..].try_into().unwrap()),
-                    _ => unreachable!(),
-                },
-                |new| match N {
+                ctx.theme.foreground,
+                |_| Self::value(&buf),
+                |new, ctx| match N {
                     4 => {
                         if let Ok(val) = new.parse::<f32>() {
-                            ctx.process.write(address, &val.to_ne_bytes());
+                            ctx.write(address, &val.to_ne_bytes());
                             true
                         } else {
                             false
@@ -79,7 +98,7 @@ impl<const N: usize> Field for FloatField<N> {
                     }
                     8 => {
                         if let Ok(val) = new.parse::<f64>() {
-                            ctx.process.write(address, &val.to_ne_bytes());
+                            ctx.write(address, &val.to_ne_bytes());
                             true
                         } else {
                             false
@@ -88,6 +107,18 @@ impl<const N: usize> Field for FloatField<N> {
                     _ => false,
                 },
             );
+
+            display_field_delta(ui, ctx, self.id, &buf, |previous| {
+                let delta = Self::value(&buf) - Self::value(previous);
+                if delta == 0. {
+                    (Ordering::Equal, "0".to_owned())
+                } else {
+                    (
+                        delta.partial_cmp(&0.).unwrap_or(Ordering::Equal),
+                        format!("{delta:.2e}"),
+                    )
+                }
+            });
         });
 
         ctx.offset += N;
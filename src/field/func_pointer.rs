@@ -0,0 +1,161 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+    PointerWidth,
+};
+use crate::{
+    address::parse_address, class::ClassList, context::InspectionContext, fonts::fid_m,
+    generator::Generator, process::Process,
+};
+use eframe::{
+    egui::{Label, RichText, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+
+/// Number of bytes at the start of the function shown on hover.
+const PREVIEW_LEN: usize = 16;
+
+/// Pointer to a function, hovering it shows the first bytes of the function.
+pub struct FuncPtrField {
+    id: FieldId,
+    state: NamedState,
+    width: PointerWidth,
+}
+
+impl FuncPtrField {
+    pub fn new(name: String, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            width: PointerWidth::new(pointer_size),
+        }
+    }
+}
+
+impl Field for FuncPtrField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.width.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::FuncPtr
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let paddr = ctx.address + ctx.offset;
+        let Some(function) = ctx.read_pointer(self.id, paddr) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::BROWN);
+            display_field_value(
+                self,
+                ui,
+                ctx,
+                &self.state,
+                Color32::YELLOW,
+                |v| {
+                    if v {
+                        format!("{function:X}")
+                    } else {
+                        format!("-> {function:X}")
+                    }
+                },
+                |new, ctx| {
+                    let Some(addr) = parse_address(new) else {
+                        return false;
+                    };
+
+                    let size = ctx.process.pointer_size();
+                    ctx.write(paddr, &addr.to_ne_bytes()[..size]);
+                    true
+                },
+            );
+
+            let is_selected = ctx.is_selected(self.id);
+            let symbol = ctx.process.resolve_symbol(function);
+            let (text, color) = match &symbol {
+                Some(symbol) => (format!("({symbol})"), Color32::LIGHT_GRAY),
+                None if ctx.process.can_read(function) => ("[fn]".to_owned(), Color32::GRAY),
+                None => ("[invalid]".to_owned(), Color32::RED),
+            };
+
+            let mut job = LayoutJob::default();
+            job.append(&text, 4., create_text_format(ctx.theme, is_selected, color));
+            let r = ui
+                .add(Label::new(job).sense(Sense::click()))
+                .on_hover_ui(|ui| {
+                    if let Some(symbol) = symbol.as_ref().filter(|s| s.symbol.is_some()) {
+                        ui.label(symbol.details());
+                    }
+                    show_preview(ui, ctx.process, function);
+                });
+            if r.clicked() {
+                ctx.select(self.id);
+            }
+        });
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+fn show_preview(ui: &mut Ui, process: &Process, function: usize) {
+    if !process.can_read(function) {
+        ui.label(
+            RichText::new(format!("Can't read memory at address {function:#X}"))
+                .color(Color32::RED)
+                .font(fid_m()),
+        );
+        return;
+    }
+
+    let mut buf = [0; PREVIEW_LEN];
+    process.read(function, &mut buf);
+    ui.label(RichText::new(format_bytes(&buf)).font(fid_m()));
+}
+
+/// Formats bytes as `XX XX XX ...`.
+fn format_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,198 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, generator::Generator};
+use eframe::{
+    egui::{Label, Popup, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// How the 16 bytes of a GUID are ordered in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GuidFormat {
+    /// Windows `GUID`, first three groups are little endian.
+    Windows,
+    /// RFC 4122 UUID, all bytes are big endian.
+    BigEndian,
+    /// Unreal Engine `FGuid`, four little endian 32-bit integers.
+    Unreal,
+}
+
+impl GuidFormat {
+    pub const ALL: [GuidFormat; 3] = [Self::Windows, Self::BigEndian, Self::Unreal];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Windows => "Windows",
+            Self::BigEndian => "Big endian",
+            Self::Unreal => "Unreal",
+        }
+    }
+
+    /// Reorders `bytes` as stored in memory into the big endian order of the textual form.
+    pub fn to_big_endian(self, mut bytes: [u8; 16]) -> [u8; 16] {
+        match self {
+            Self::Windows => {
+                bytes[0..4].reverse();
+                bytes[4..6].reverse();
+                bytes[6..8].reverse();
+            }
+            Self::BigEndian => {}
+            Self::Unreal => bytes.chunks_exact_mut(4).for_each(<[u8]>::reverse),
+        }
+
+        bytes
+    }
+}
+
+/// Formats big endian bytes as `{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}`.
+pub fn format_guid(bytes: &[u8; 16]) -> String {
+    let hex = |range: std::ops::Range<usize>| {
+        bytes[range]
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<String>()
+    };
+
+    format!(
+        "{{{}-{}-{}-{}-{}}}",
+        hex(0..4),
+        hex(4..6),
+        hex(6..8),
+        hex(8..10),
+        hex(10..16)
+    )
+}
+
+/// Formats big endian bytes as an initializer of the Windows `GUID` struct.
+pub fn format_guid_initializer(bytes: &[u8; 16]) -> String {
+    let tail = bytes[8..]
+        .iter()
+        .map(|b| format!("0x{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{{ 0x{:08X}, 0x{:04X}, 0x{:04X}, {{ {tail} }} }}",
+        u32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+        u16::from_be_bytes(bytes[4..6].try_into().unwrap()),
+        u16::from_be_bytes(bytes[6..8].try_into().unwrap()),
+    )
+}
+
+pub struct GuidField {
+    id: FieldId,
+    state: NamedState,
+    format: Cell<GuidFormat>,
+}
+
+impl GuidField {
+    pub fn new(name: String, format: GuidFormat) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            format: format.into(),
+        }
+    }
+}
+
+impl Field for GuidField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        16
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Guid(self.format.get())
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let mut bytes = [0; 16];
+        if !ctx.read(self.id, ctx.address + ctx.offset, &mut bytes) {
+            return display_read_error(ui, self, ctx);
+        }
+        let bytes = self.format.get().to_big_endian(bytes);
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_GRAY);
+
+            let is_selected = ctx.is_selected(self.id);
+            let mut job = LayoutJob::default();
+            job.append(
+                &format_guid(&bytes),
+                0.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.foreground),
+            );
+            job.append(
+                &format!("[{}]", self.format.get().label()),
+                4.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.dim),
+            );
+
+            let r = ui.add(Label::new(job).sense(Sense::click()));
+            if r.clicked() {
+                ctx.select(self.id);
+            }
+
+            Popup::context_menu(&r).show(|ui| {
+                if ui.button("Copy as GUID string").clicked() {
+                    ui.ctx().copy_text(format_guid(&bytes));
+                    ui.close();
+                }
+
+                if ui.button("Copy as C initializer").clicked() {
+                    ui.ctx().copy_text(format_guid_initializer(&bytes));
+                    ui.close();
+                }
+
+                ui.separator();
+
+                for format in GuidFormat::ALL {
+                    if ui
+                        .selectable_label(format == self.format.get(), format.label())
+                        .clicked()
+                    {
+                        self.format.set(format);
+                        ui.close();
+                    }
+                }
+            });
+        });
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
@@ -1,8 +1,8 @@
 use super::{
-    create_text_format, display_field_prelude, next_id, CodegenData, Field, FieldId, FieldKind,
-    FieldResponse,
+    create_text_format, display_field_prelude, display_read_error, next_id, CodegenData, Field,
+    FieldId, FieldKind, FieldResponse,
 };
-use crate::{context::InspectionContext, generator::Generator};
+use crate::{context::InspectionContext, generator::Generator, gui::HexEditor};
 use eframe::{
     egui::{Label, ScrollArea, Sense, Ui},
     epaint::{text::LayoutJob, Color32},
@@ -38,6 +38,8 @@ impl PreviewState {
 
 pub struct HexField<const N: usize> {
     preview_state: RefCell<Option<PreviewState>>,
+    /// Hex editor shown under the field after double clicking its bytes.
+    editor: RefCell<Option<HexEditor>>,
     id: FieldId,
 }
 
@@ -46,6 +48,7 @@ impl<const N: usize> HexField<N> {
         Self {
             id: next_id(),
             preview_state: None.into(),
+            editor: None.into(),
         }
     }
 
@@ -53,7 +56,7 @@ impl<const N: usize> HexField<N> {
         for (i, b) in buf.iter().enumerate() {
             let rng = fastrand::Rng::with_seed(*b as _);
             let color = if *b == 0 {
-                Color32::DARK_GRAY
+                ctx.theme.unknown
             } else {
                 const MIN: RangeFrom<u8> = 45..;
                 Color32::from_rgb(rng.u8(MIN), rng.u8(MIN), rng.u8(MIN))
@@ -62,7 +65,7 @@ impl<const N: usize> HexField<N> {
             job.append(
                 &format!("{b:02X}"),
                 4. + if i == 0 { 4. } else { 0. },
-                create_text_format(ctx.is_selected(self.id), color),
+                create_text_format(ctx.theme, ctx.is_selected(self.id), color),
             );
         }
     }
@@ -89,7 +92,7 @@ impl<const N: usize> HexField<N> {
         job.append(
             &format!("{}", displayed),
             4.,
-            create_text_format(ctx.is_selected(self.id), Color32::LIGHT_BLUE),
+            create_text_format(ctx.theme, ctx.is_selected(self.id), Color32::LIGHT_BLUE),
         );
 
         let r = ui.add(Label::new(job).sense(Sense::click()));
@@ -118,7 +121,7 @@ impl<const N: usize> HexField<N> {
         job.append(
             &format!("{:e}", displayed),
             4.,
-            create_text_format(ctx.is_selected(self.id), Color32::LIGHT_RED),
+            create_text_format(ctx.theme, ctx.is_selected(self.id), Color32::LIGHT_RED),
         );
 
         let r = ui.add(Label::new(job).sense(Sense::click()));
@@ -145,18 +148,25 @@ impl<const N: usize> HexField<N> {
         buf: &[u8; N],
         response: &mut Option<FieldResponse>,
     ) {
-        if N != 8 {
+        if N != ctx.process.pointer_size() {
             return;
         }
 
-        let address = usize::from_ne_bytes(buf[..].try_into().unwrap());
+        let address = pointer_from_bytes(buf);
         if ctx.process.can_read(address) {
             let mut job = LayoutJob::default();
             job.append(
                 &format!("-> {address:X}"),
                 4.,
-                create_text_format(ctx.is_selected(self.id), Color32::YELLOW),
+                create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.pointer),
             );
+            if let Some((module, rva)) = ctx.process.address_to_rva(address) {
+                job.append(
+                    &format!("({module}+{rva:X})"),
+                    4.,
+                    create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+                );
+            }
 
             let r = ui.add(Label::new(job).sense(Sense::click()));
 
@@ -164,6 +174,28 @@ impl<const N: usize> HexField<N> {
                 ctx.select(self.id);
             }
 
+            // Reverse of finding references, the pointer leads to the address of a class.
+            if let Some(class) = ctx
+                .class_list
+                .classes()
+                .iter()
+                .find(|c| c.address.get() == address)
+            {
+                let mut job = LayoutJob::default();
+                job.append(
+                    &format!("-> {}", class.name),
+                    4.,
+                    create_text_format(ctx.theme, ctx.is_selected(self.id), Color32::KHAKI),
+                );
+                if ui
+                    .add(Label::new(job).sense(Sense::click()))
+                    .on_hover_text("Inspect the class")
+                    .clicked()
+                {
+                    *response = Some(FieldResponse::SwitchToClass(class.id()));
+                }
+            }
+
             let preview_state = &mut *self.preview_state.borrow_mut();
             if r.hovered() {
                 if let Some(preview) = preview_state {
@@ -220,13 +252,13 @@ impl<const N: usize> HexField<N> {
             let (color, ch) = if byte.is_ascii_graphic() || byte == b' ' {
                 (Color32::LIGHT_GREEN, char::from(byte))
             } else {
-                (Color32::DARK_GRAY, '.')
+                (ctx.theme.unknown, '.')
             };
 
             job.append(
                 &ch.to_string(),
                 0.,
-                create_text_format(ctx.is_selected(self.id), color),
+                create_text_format(ctx.theme, ctx.is_selected(self.id), color),
             );
         }
 
@@ -237,11 +269,11 @@ impl<const N: usize> HexField<N> {
     }
 
     fn string_view(&self, ui: &mut Ui, ctx: &mut InspectionContext, buf: &[u8; N]) {
-        if N != 8 {
+        if N != ctx.process.pointer_size() {
             return;
         }
 
-        let address = usize::from_ne_bytes(buf[..].try_into().unwrap());
+        let address = pointer_from_bytes(buf);
         if ctx.process.can_read(address) {
             let mut str_buf = [0; 0x100];
             ctx.process.read(address, &mut str_buf);
@@ -289,7 +321,7 @@ impl<const N: usize> HexField<N> {
                         }
                     ),
                     4.,
-                    create_text_format(ctx.is_selected(self.id), Color32::RED),
+                    create_text_format(ctx.theme, ctx.is_selected(self.id), Color32::RED),
                 );
 
                 let r = ui.add(Label::new(job).sense(Sense::click()));
@@ -327,26 +359,53 @@ impl<const N: usize> Field for HexField<N> {
 
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
         let mut buf = [0; N];
-        ctx.process.read(ctx.address + ctx.offset, &mut buf);
+        if !ctx.read(self.id, ctx.address + ctx.offset, &mut buf) {
+            return display_read_error(ui, self, ctx);
+        }
 
         let mut response = None;
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
             self.byte_view(ctx, &mut job, &buf);
 
-            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            let r = ui.add(Label::new(job).sense(Sense::click()));
+            if r.clicked() {
                 ctx.select(self.id);
             }
+            if r.double_clicked() {
+                let editor = &mut *self.editor.borrow_mut();
+                *editor = match editor {
+                    Some(_) => None,
+                    None => Some(HexEditor::new(ctx.address + ctx.offset, 16, 4)),
+                };
+            }
 
-            self.ascii_view(ui, ctx, &buf);
-            self.int_view(ui, ctx, &buf);
-            self.float_view(ui, ctx, &buf);
-            self.pointer_view(ui, ctx, &buf, &mut response);
-            self.string_view(ui, ctx, &buf);
+            let columns = ctx.columns;
+            if columns.ascii {
+                self.ascii_view(ui, ctx, &buf);
+            }
+            if columns.int {
+                self.int_view(ui, ctx, &buf);
+            }
+            if columns.float {
+                self.float_view(ui, ctx, &buf);
+            }
+            if columns.pointer {
+                self.pointer_view(ui, ctx, &buf, &mut response);
+            }
+            if columns.string {
+                self.string_view(ui, ctx, &buf);
+            }
         });
 
+        if let Some(editor) = &mut *self.editor.borrow_mut() {
+            ui.indent(ctx.current_id.with("_hex_editor"), |ui| {
+                editor.show(ui, ctx.process, ctx.verify_writes, ctx.toasts)
+            });
+        }
+
         ctx.offset += N;
         response
     }
@@ -356,6 +415,13 @@ impl<const N: usize> Field for HexField<N> {
     }
 }
 
+/// Zero extends pointer sized `buf` to `usize`.
+fn pointer_from_bytes<const N: usize>(buf: &[u8; N]) -> usize {
+    let mut raw = [0; 8];
+    raw[..N].copy_from_slice(buf);
+    usize::from_ne_bytes(raw)
+}
+
 fn int_high_low_from_le<const N: usize>(high: &[u8], low: &[u8]) -> (i64, i64) {
     match N {
         8 => (
@@ -0,0 +1,148 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, generator::Generator};
+use eframe::{
+    egui::{Label, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+
+/// `char[N]`.
+pub type InlineStringField = InlineTextField<1>;
+/// `wchar_t[N]` of UTF-16 characters.
+pub type InlineWideStringField = InlineTextField<2>;
+
+/// Null terminated string stored in place, `W` is the size of a character in bytes.
+pub struct InlineTextField<const W: usize> {
+    id: FieldId,
+    state: NamedState,
+    len: usize,
+}
+
+impl<const W: usize> InlineTextField<W> {
+    /// `len` is the number of characters, including the null terminator.
+    pub fn new(name: String, len: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            len,
+        }
+    }
+
+    /// Returns the string up to the first null terminator and its length in characters.
+    fn decode(bytes: &[u8]) -> (String, usize) {
+        if W == 1 {
+            let end = bytes.iter().position(|c| *c == 0).unwrap_or(bytes.len());
+            (String::from_utf8_lossy(&bytes[..end]).into_owned(), end)
+        } else {
+            let chars = bytes
+                .chunks(2)
+                .map(|c| u16::from_le_bytes(c.try_into().unwrap()))
+                .take_while(|c| *c != 0)
+                .collect::<Vec<_>>();
+            (String::from_utf16_lossy(&chars), chars.len())
+        }
+    }
+
+    /// Encodes `s` padded with null characters, `None` if it doesn't fit with the terminator.
+    fn encode(&self, s: &str) -> Option<Vec<u8>> {
+        let mut bytes = if W == 1 {
+            s.as_bytes().to_vec()
+        } else {
+            s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+        };
+
+        if bytes.len() >= self.size() {
+            return None;
+        }
+        bytes.resize(self.size(), 0);
+        Some(bytes)
+    }
+}
+
+impl<const W: usize> Field for InlineTextField<W> {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.len * W
+    }
+
+    fn kind(&self) -> FieldKind {
+        match W {
+            1 => FieldKind::InlineStr(self.len),
+            2 => FieldKind::InlineWStr(self.len),
+            _ => unreachable!(),
+        }
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let address = ctx.address + ctx.offset;
+        let mut bytes = vec![0; self.size()];
+        if !ctx.read(self.id, address, &mut bytes) {
+            return display_read_error(ui, self, ctx);
+        }
+        let (text, count) = Self::decode(&bytes);
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_RED);
+            display_field_value(
+                self,
+                ui,
+                ctx,
+                &self.state,
+                Color32::LIGHT_BLUE,
+                |v| if v { text.clone() } else { format!("{text:?}") },
+                |new, ctx| match self.encode(new) {
+                    Some(buf) => {
+                        ctx.write(address, &buf);
+                        true
+                    }
+                    None => false,
+                },
+            );
+
+            let mut job = LayoutJob::default();
+            job.append(
+                &format!("[{count}/{}]", self.len),
+                4.,
+                create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+            );
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+        });
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
@@ -1,8 +1,8 @@
 use super::{
-    display_field_name, display_field_prelude, display_field_value, next_id, CodegenData, Field,
-    FieldId, FieldKind, FieldResponse, NamedState,
+    display_field_delta, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
 };
-use crate::{context::InspectionContext, generator::Generator, process::Process};
+use crate::{context::InspectionContext, generator::Generator};
 use eframe::{
     egui::{Label, Sense, Ui},
     epaint::{text::LayoutJob, Color32},
@@ -31,11 +31,24 @@ impl<const N: usize> IntField<N> {
         }
     }
 
-    fn write_value(&self, new: &str, address: usize, proc: &Process) -> bool {
+    /// Value widened to `i128` so the difference of any two values fits.
+    fn to_i128(&self, buf: &[u8]) -> i128 {
+        let mut bytes = [0; 8];
+        bytes[..N].copy_from_slice(buf);
+        let shift = 64 - N * 8;
+        let raw = u64::from_le_bytes(bytes) << shift;
+        if self.signed {
+            ((raw as i64) >> shift) as i128
+        } else {
+            (raw >> shift) as i128
+        }
+    }
+
+    fn write_value(&self, new: &str, address: usize, ctx: &mut InspectionContext) -> bool {
         macro_rules! do_arm {
-            ($buf:ident, $addr:ident, $proc:ident, $new:ident, $type:ty) => {
+            ($buf:ident, $addr:ident, $ctx:ident, $new:ident, $type:ty) => {
                 if let Ok(val) = $new.parse::<$type>() {
-                    $proc.write($addr, &val.to_ne_bytes());
+                    $ctx.write($addr, &val.to_ne_bytes());
                     true
                 } else {
                     false
@@ -44,14 +57,14 @@ impl<const N: usize> IntField<N> {
         }
 
         match N {
-            1 if self.signed => do_arm!(buf, address, proc, new, i8),
-            1 if !self.signed => do_arm!(buf, address, proc, new, u8),
-            2 if self.signed => do_arm!(buf, address, proc, new, i16),
-            2 if !self.signed => do_arm!(buf, address, proc, new, u16),
-            4 if self.signed => do_arm!(buf, address, proc, new, i32),
-            4 if !self.signed => do_arm!(buf, address, proc, new, u32),
-            8 if self.signed => do_arm!(buf, address, proc, new, i64),
-            8 if !self.signed => do_arm!(buf, address, proc, new, u64),
+            1 if self.signed => do_arm!(buf, address, ctx, new, i8),
+            1 if !self.signed => do_arm!(buf, address, ctx, new, u8),
+            2 if self.signed => do_arm!(buf, address, ctx, new, i16),
+            2 if !self.signed => do_arm!(buf, address, ctx, new, u16),
+            4 if self.signed => do_arm!(buf, address, ctx, new, i32),
+            4 if !self.signed => do_arm!(buf, address, ctx, new, u32),
+            8 if self.signed => do_arm!(buf, address, ctx, new, i64),
+            8 if !self.signed => do_arm!(buf, address, ctx, new, u64),
             _ => unreachable!(),
         }
     }
@@ -70,16 +83,28 @@ impl<const N: usize> Field for IntField<N> {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn kind(&self) -> FieldKind {
         match N {
-            1 if self.signed => FieldKind::U8,
-            1 if !self.signed => FieldKind::I8,
-            2 if self.signed => FieldKind::U16,
-            2 if !self.signed => FieldKind::I16,
-            4 if self.signed => FieldKind::U32,
-            4 if !self.signed => FieldKind::I32,
-            8 if self.signed => FieldKind::U64,
-            8 if !self.signed => FieldKind::I64,
+            1 if self.signed => FieldKind::I8,
+            1 if !self.signed => FieldKind::U8,
+            2 if self.signed => FieldKind::I16,
+            2 if !self.signed => FieldKind::U16,
+            4 if self.signed => FieldKind::I32,
+            4 if !self.signed => FieldKind::U32,
+            8 if self.signed => FieldKind::I64,
+            8 if !self.signed => FieldKind::U64,
             _ => unreachable!(),
         }
     }
@@ -87,11 +112,13 @@ impl<const N: usize> Field for IntField<N> {
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
         let mut buf = [0; N];
         let address = ctx.address + ctx.offset;
-        ctx.process.read(ctx.address + ctx.offset, &mut buf);
+        if !ctx.read(self.id, address, &mut buf) {
+            return display_read_error(ui, self, ctx);
+        }
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
 
             if ui.add(Label::new(job).sense(Sense::click())).clicked() {
                 ctx.select(self.id);
@@ -113,7 +140,7 @@ impl<const N: usize> Field for IntField<N> {
                 ui,
                 ctx,
                 &self.state,
-                Color32::WHITE,
+                ctx.theme.foreground,
                 |_| match N {
                     1 if self.signed => (buf[0] as i8).to_string(),
                     1 if !self.signed => buf[0].to_string(),
@@ -131,8 +158,13 @@ impl<const N: usize> Field for IntField<N> {
                     }
                     _ => unreachable!(),
                 },
-                |new| self.write_value(new, address, ctx.process),
+                |new, ctx| self.write_value(new, address, ctx),
             );
+
+            display_field_delta(ui, ctx, self.id, &buf, |previous| {
+                let delta = self.to_i128(&buf) - self.to_i128(previous);
+                (delta.cmp(&0), delta.to_string())
+            });
         });
 
         ctx.offset += N;
@@ -140,20 +172,6 @@ impl<const N: usize> Field for IntField<N> {
     }
 
     fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
-        generator.add_field(
-            self.state.name.borrow().as_str(),
-            match N {
-                1 if self.signed => FieldKind::I8,
-                1 if !self.signed => FieldKind::U8,
-                2 if self.signed => FieldKind::I16,
-                2 if !self.signed => FieldKind::U16,
-                4 if self.signed => FieldKind::I32,
-                4 if !self.signed => FieldKind::U32,
-                8 if self.signed => FieldKind::I64,
-                8 if !self.signed => FieldKind::U64,
-                _ => unreachable!(),
-            },
-            None,
-        );
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
     }
 }
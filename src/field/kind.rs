@@ -1,10 +1,14 @@
 use super::{
-    BoolField, Field, FloatField, HexField, IntField, PointerField, StringPointerField,
-    WideStringPointerField,
+    allocate_padding, ArrayField, BitSlice, BitfieldField, BoolField, ColorField, ColorLayout,
+    DynamicArrayField, EmbeddedClassField, EnumField, Field, FlagsField, FloatField, FuncPtrField,
+    GuidField, GuidFormat, HexField, InlineStringField, InlineWideStringField, IntField, Mat4Field,
+    PointerChainField, PointerField, SectionField, StdStringField, StdVectorField, StringAbi,
+    StringPointerField, TimestampField, TimestampFormat, Vec2Field, Vec3Field, Vec4Field,
+    VtableField, WideStringPointerField,
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[rustfmt::skip]
 pub enum FieldKind {
     Unk8, Unk16, Unk32, Unk64,
@@ -14,7 +18,43 @@ pub enum FieldKind {
     Ptr,
     StrPtr,
     WStrPtr,
+    /// Signed offset from the field to the target.
+    RelPtr32, RelPtr64,
+    FuncPtr,
     Bool,
+    Vec2, Vec3, Vec4, Mat4,
+    /// Element kind and element count.
+    Array(Box<FieldKind>, usize),
+    /// Size of the underlying integer in bytes.
+    Enum(usize),
+    /// Size of the container in bytes and its slices.
+    Bitfield(usize, Vec<BitSlice>),
+    /// Size of the underlying integer in bytes.
+    Flags(usize),
+    /// Number of displayed functions.
+    Vtable(usize),
+    /// Size of the embedded class in bytes, the class is passed as metadata.
+    Embedded(usize),
+    /// Offsets added to the pointer at each level of indirection.
+    PointerChain(Vec<isize>),
+    /// Element kind, stored as pointer, length and capacity.
+    DynamicArray(Box<FieldKind>),
+    /// Element kind, stored as begin, end and capacity pointers.
+    StdVector(Box<FieldKind>),
+    /// Layout of the string.
+    StdString(StringAbi),
+    /// Byte order of the GUID.
+    Guid(GuidFormat),
+    /// Encoding of the point in time.
+    Timestamp(TimestampFormat),
+    /// Number of characters, including the null terminator.
+    InlineStr(usize),
+    /// Number of UTF-16 characters, including the null terminator.
+    InlineWStr(usize),
+    /// Order and type of the channels.
+    Color(ColorLayout),
+    /// Size of the fields grouped in the section, they're stored as its children.
+    Section(usize),
 }
 
 impl FieldKind {
@@ -31,6 +71,32 @@ impl FieldKind {
         (Self::F64, "F64"),
     ];
 
+    /// Kinds without parameters, offered as array elements and in the field menu.
+    pub const SIMPLE: &'static [FieldKind] = &[
+        Self::Bool,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::U64,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::I64,
+        Self::F32,
+        Self::F64,
+        Self::Vec2,
+        Self::Vec3,
+        Self::Vec4,
+        Self::Mat4,
+        Self::Unk8,
+        Self::Unk16,
+        Self::Unk32,
+        Self::Unk64,
+        Self::Ptr,
+        Self::StrPtr,
+        Self::WStrPtr,
+    ];
+
     pub fn label(&self) -> Option<&'static str> {
         Self::NAMED_VARIANTS
             .iter()
@@ -39,24 +105,73 @@ impl FieldKind {
 }
 
 impl FieldKind {
-    /// Returns size in bytes.
-    pub fn size(&self) -> usize {
+    /// Returns size in bytes, pointers are `pointer_size` bytes wide.
+    pub fn size(&self, pointer_size: usize) -> usize {
         match self {
             Self::Unk8 | Self::I8 | Self::U8 | Self::Bool => 1,
             Self::Unk16 | Self::I16 | Self::U16 => 2,
-            Self::Unk32 | Self::I32 | Self::U32 | Self::F32 => 4,
-            // TODO(ItsEthra): Pointer size is... sigh, different for 32-bit processes
-            Self::Unk64
-            | Self::I64
-            | Self::U64
-            | Self::F64
-            | Self::Ptr
+            Self::Unk32 | Self::I32 | Self::U32 | Self::F32 | Self::RelPtr32 => 4,
+            Self::Unk64 | Self::I64 | Self::U64 | Self::F64 | Self::RelPtr64 => 8,
+            Self::Ptr
             | Self::StrPtr
-            | Self::WStrPtr => 8,
+            | Self::WStrPtr
+            | Self::FuncPtr
+            | Self::Vtable(_)
+            | Self::PointerChain(_) => pointer_size,
+            Self::DynamicArray(_) | Self::StdVector(_) => pointer_size * 3,
+            Self::StdString(abi) => abi.size(pointer_size),
+            Self::Timestamp(format) => format.size(),
+            Self::Color(layout) => layout.size(),
+            Self::InlineStr(len) => *len,
+            Self::InlineWStr(len) => len * 2,
+            Self::Vec2 => 8,
+            Self::Vec3 => 12,
+            Self::Vec4 | Self::Guid(_) => 16,
+            Self::Mat4 => 64,
+            Self::Array(inner, count) => inner.size(pointer_size) * count,
+            Self::Enum(size)
+            | Self::Flags(size)
+            | Self::Bitfield(size, _)
+            | Self::Embedded(size)
+            | Self::Section(size) => *size,
+        }
+    }
+
+    /// Splits dynamic arrays into the members they are generated as, along with element kind.
+    /// Pointer members should point to the element type.
+    pub fn dynamic_array_members(
+        &self,
+        name: &str,
+        pointer_size: usize,
+    ) -> Option<(&FieldKind, [(String, Self); 3])> {
+        let length = if pointer_size == 4 {
+            Self::U32
+        } else {
+            Self::U64
+        };
+
+        match self {
+            Self::DynamicArray(inner) => Some((
+                inner,
+                [
+                    (format!("{name}_ptr"), Self::Ptr),
+                    (format!("{name}_size"), length.clone()),
+                    (format!("{name}_cap"), length),
+                ],
+            )),
+            Self::StdVector(inner) => Some((
+                inner,
+                [
+                    (format!("{name}_begin"), Self::Ptr),
+                    (format!("{name}_end"), Self::Ptr),
+                    (format!("{name}_cap_end"), Self::Ptr),
+                ],
+            )),
+            _ => None,
         }
     }
 
-    pub fn into_field(self, name: Option<String>) -> Box<dyn Field> {
+    pub fn into_field(self, name: Option<String>, pointer_size: usize) -> Box<dyn Field> {
         match self {
             Self::Unk8 => Box::new(HexField::<1>::new()),
             Self::Unk16 => Box::new(HexField::<2>::new()),
@@ -89,12 +204,102 @@ impl FieldKind {
                 name.unwrap_or_else(|| "double".into()),
             )),
             Self::Bool => Box::new(BoolField::new(name.unwrap_or_else(|| "boolean".into()))),
-            Self::Ptr => Box::new(PointerField::new(name.unwrap_or_else(|| "pointer".into()))),
+            Self::Ptr => Box::new(PointerField::new(
+                name.unwrap_or_else(|| "pointer".into()),
+                pointer_size,
+            )),
+            Self::FuncPtr => Box::new(FuncPtrField::new(
+                name.unwrap_or_else(|| "func_ptr".into()),
+                pointer_size,
+            )),
+            Self::RelPtr32 | Self::RelPtr64 => Box::new(PointerField::new_relative(
+                name.unwrap_or_else(|| "rel_pointer".into()),
+                self.size(pointer_size),
+            )),
             Self::StrPtr => Box::new(StringPointerField::new(
                 name.unwrap_or_else(|| "str_ptr".into()),
+                pointer_size,
             )),
             Self::WStrPtr => Box::new(WideStringPointerField::new(
                 name.unwrap_or_else(|| "wstr_ptr".into()),
+                pointer_size,
+            )),
+            Self::Vec2 => Box::new(Vec2Field::new(name.unwrap_or_else(|| "vec2".into()))),
+            Self::Vec3 => Box::new(Vec3Field::new(name.unwrap_or_else(|| "vec3".into()))),
+            Self::Vec4 => Box::new(Vec4Field::new(name.unwrap_or_else(|| "vec4".into()))),
+            Self::Mat4 => Box::new(Mat4Field::new(name.unwrap_or_else(|| "mat4".into()))),
+            Self::Array(inner, count) => Box::new(ArrayField::new(
+                name.unwrap_or_else(|| "array".into()),
+                *inner,
+                count,
+                pointer_size,
+            )),
+            Self::Enum(size) => {
+                Box::new(EnumField::new(name.unwrap_or_else(|| "enum".into()), size))
+            }
+            Self::Flags(size) => Box::new(FlagsField::new(
+                name.unwrap_or_else(|| "flags".into()),
+                size,
+            )),
+            Self::Bitfield(size, slices) => Box::new(BitfieldField::new(
+                name.unwrap_or_else(|| "bitfield".into()),
+                size,
+                slices,
+            )),
+            Self::Vtable(count) => Box::new(VtableField::new(
+                name.unwrap_or_else(|| "vtable".into()),
+                count,
+                pointer_size,
+            )),
+            // Embedded classes are created with a class, without one it's displayed as missing.
+            Self::Embedded(size) => Box::new(EmbeddedClassField::new(
+                name.unwrap_or_else(|| "embedded".into()),
+                fastrand::usize(..),
+                size,
+            )),
+            Self::PointerChain(offsets) => Box::new(PointerChainField::new(
+                name.unwrap_or_else(|| "chain".into()),
+                offsets,
+                pointer_size,
+            )),
+            Self::DynamicArray(inner) => Box::new(DynamicArrayField::new(
+                name.unwrap_or_else(|| "dyn_array".into()),
+                *inner,
+                pointer_size,
+            )),
+            Self::StdVector(inner) => Box::new(StdVectorField::new(
+                name.unwrap_or_else(|| "vector".into()),
+                *inner,
+                pointer_size,
+            )),
+            Self::StdString(abi) => Box::new(StdStringField::new(
+                name.unwrap_or_else(|| "string".into()),
+                abi,
+                pointer_size,
+            )),
+            Self::Guid(format) => Box::new(GuidField::new(
+                name.unwrap_or_else(|| "guid".into()),
+                format,
+            )),
+            Self::Timestamp(format) => Box::new(TimestampField::new(
+                name.unwrap_or_else(|| "timestamp".into()),
+                format,
+            )),
+            Self::InlineStr(len) => Box::new(InlineStringField::new(
+                name.unwrap_or_else(|| "str".into()),
+                len,
+            )),
+            Self::InlineWStr(len) => Box::new(InlineWideStringField::new(
+                name.unwrap_or_else(|| "wstr".into()),
+                len,
+            )),
+            Self::Color(layout) => Box::new(ColorField::new(
+                name.unwrap_or_else(|| "color".into()),
+                layout,
+            )),
+            Self::Section(size) => Box::new(SectionField::new(
+                name.unwrap_or_else(|| "section".into()),
+                allocate_padding(size),
             )),
         }
     }
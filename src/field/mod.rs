@@ -14,8 +14,47 @@ mod string_pointer;
 pub use string_pointer::*;
 mod boolean;
 pub use boolean::*;
+mod array;
+pub use array::*;
+mod enumeration;
+pub use enumeration::*;
+mod bitfield;
+pub use bitfield::*;
+mod vector;
+pub use vector::*;
+mod vtable;
+pub use vtable::*;
+mod embedded;
+pub use embedded::*;
+mod pointer_chain;
+pub use pointer_chain::*;
+mod dynamic_array;
+pub use dynamic_array::*;
+mod std_string;
+pub use std_string::*;
+mod guid;
+pub use guid::*;
+mod timestamp;
+pub use timestamp::*;
+mod color;
+pub use color::*;
+mod func_pointer;
+pub use func_pointer::*;
+mod inline_string;
+pub use inline_string::*;
+mod flags;
+pub use flags::*;
+mod section;
+pub use section::*;
 
-use crate::{class::Class, context::InspectionContext, generator::Generator, FID_M};
+use crate::{
+    class::{Class, ClassId, ClassList},
+    context::InspectionContext,
+    enums::EnumRegistry,
+    fonts::fid_m,
+    generator::Generator,
+    theme::Theme,
+};
 use eframe::{
     egui::{Id, TextFormat, Ui},
     epaint::{Color32, Stroke},
@@ -28,6 +67,24 @@ pub enum FieldResponse {
     NewClass(String, usize),
     LockScroll,
     UnlockScroll,
+    /// Replaces the selected field with a field of a new kind.
+    ChangeKind(FieldKind),
+    /// Moves the field `from_id` to the position of `to_id` in the same class.
+    Reorder {
+        from_id: FieldId,
+        to_id: FieldId,
+    },
+    /// Searches pointer fields of all classes for the address.
+    FindReferences(usize),
+    /// Opens the hex editor at the address.
+    ViewHex(usize),
+    /// Opens the batch rename window for the selected fields.
+    BatchRename,
+    /// Inspects the class instead of the current one.
+    SwitchToClass(ClassId),
+    /// Opens the disassembly window at the address.
+    #[cfg(feature = "disasm")]
+    ViewCode(usize),
 }
 
 pub trait Field {
@@ -36,12 +93,48 @@ pub trait Field {
     fn size(&self) -> usize;
     fn kind(&self) -> FieldKind;
 
+    /// User comment attached to the field.
+    fn comment(&self) -> Option<String> {
+        None
+    }
+    fn set_comment(&self, _comment: Option<String>) {}
+    /// Does nothing for fields without a name.
+    fn set_name(&self, _name: String) {}
+
+    /// Returns `true` if `id` belongs to this field or any of its nested fields.
+    fn contains(&self, id: FieldId) -> bool {
+        self.id() == id
+    }
+
+    /// Fields grouped under this field, they belong to the same class.
+    fn section_fields(&self) -> Option<&[Box<dyn Field>]> {
+        None
+    }
+    fn section_fields_mut(&mut self) -> Option<&mut Vec<Box<dyn Field>>> {
+        None
+    }
+
+    /// Class whose fields are a part of this field.
+    fn embedded_class(&self) -> Option<ClassId> {
+        None
+    }
+    /// Class the field points to, it may not exist anymore.
+    fn pointed_class(&self) -> Option<ClassId> {
+        None
+    }
+    /// Recomputes size of the field if it depends on other classes or the pointer width, returns
+    /// `true` if it changed.
+    fn refresh_size(&self, _classes: &ClassList) -> bool {
+        false
+    }
+
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse>;
     fn codegen(&self, generator: &mut dyn Generator, data: &CodegenData);
 }
 
 pub struct CodegenData<'a> {
     pub classes: &'a [Class],
+    pub enums: &'a EnumRegistry,
 }
 
 pub struct EditingState {
@@ -66,6 +159,8 @@ pub struct NamedState {
     focused_id: Cell<Option<Id>>,
     name: RefCell<String>,
     saved_name: RefCell<String>,
+    comment: RefCell<Option<String>>,
+    comment_buf: RefCell<String>,
     editing_state: RefCell<Option<EditingState>>,
 }
 
@@ -78,6 +173,24 @@ impl NamedState {
     }
 }
 
+/// Pointer width of a field, kept up to date by `ClassList::refresh_sizes`.
+pub struct PointerWidth(Cell<usize>);
+
+impl PointerWidth {
+    pub fn new(size: usize) -> Self {
+        Self(size.into())
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.get()
+    }
+
+    /// Takes the pointer width of the classes, returns `true` if it changed.
+    pub fn refresh(&self, classes: &ClassList) -> bool {
+        self.0.replace(classes.pointer_size()) != classes.pointer_size()
+    }
+}
+
 mod private {
     use super::FieldId;
     use std::sync::atomic::{AtomicU64, Ordering};
@@ -91,13 +204,13 @@ mod private {
 
 pub use private::next_id;
 
-fn create_text_format(selected: bool, col: Color32) -> TextFormat {
+fn create_text_format(theme: &Theme, selected: bool, col: Color32) -> TextFormat {
     if selected {
         TextFormat {
-            underline: Stroke::new(1., Color32::LIGHT_GRAY),
-            ..TextFormat::simple(FID_M, col)
+            underline: Stroke::new(1., theme.selection),
+            ..TextFormat::simple(fid_m(), col)
         }
     } else {
-        TextFormat::simple(FID_M, col)
+        TextFormat::simple(fid_m(), col)
     }
 }
@@ -1,8 +1,17 @@
 use super::{
-    create_text_format, display_field_name, display_field_prelude, display_field_value, next_id,
-    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+    create_text_format, display_class_fields, display_field_delta, display_field_name,
+    display_field_prelude, display_field_value, display_read_error, next_id, CodegenData, Field,
+    FieldId, FieldKind, FieldResponse, NamedState, PointerWidth,
+};
+use crate::{
+    address::parse_address,
+    app::is_valid_ident,
+    class::ClassList,
+    context::InspectionContext,
+    fonts::fid_m,
+    generator::Generator,
+    rtti::{class_name_from_rtti, detect_rtti_name},
 };
-use crate::{address::parse_address, context::InspectionContext, generator::Generator, FID_M};
 use eframe::{
     egui::{collapsing_header::CollapsingState, Id, Label, Popup, RichText, Sense, TextFormat, Ui},
     epaint::{text::LayoutJob, Color32},
@@ -14,36 +23,101 @@ pub struct PointerField {
     id: FieldId,
     state: NamedState,
     class_id: Cell<Option<usize>>,
+    // Width of the stored offset if the pointer is relative to the address of the field.
+    relative: Option<usize>,
+    // Pointer width of the process, or the width of the offset of relative pointers.
+    width: PointerWidth,
 }
 
 impl PointerField {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            class_id: None.into(),
+            relative: None,
+            width: PointerWidth::new(pointer_size),
+        }
+    }
+
+    pub fn new_with_class_id(name: String, class_id: usize, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            class_id: Some(class_id).into(),
+            relative: None,
+            width: PointerWidth::new(pointer_size),
+        }
+    }
+
+    /// Self-relative pointer, stores a signed offset of `width` bytes from the field to the target.
+    pub fn new_relative(name: String, width: usize) -> Self {
         Self {
             id: next_id(),
             state: NamedState::new(name),
             class_id: None.into(),
+            relative: Some(width),
+            width: PointerWidth::new(width),
         }
     }
 
-    pub fn new_with_class_id(name: String, class_id: usize) -> Self {
+    pub fn new_relative_with_class_id(name: String, width: usize, class_id: usize) -> Self {
         Self {
             id: next_id(),
             state: NamedState::new(name),
             class_id: Some(class_id).into(),
+            relative: Some(width),
+            width: PointerWidth::new(width),
+        }
+    }
+
+    /// Returns address the pointer at `paddr` points to, `None` if it couldn't be read.
+    fn read_target(&self, ctx: &InspectionContext, paddr: usize) -> Option<usize> {
+        let Some(width) = self.relative else {
+            return ctx.read_pointer(self.id, paddr);
+        };
+
+        let mut buf = [0; 8];
+        if !ctx.read(self.id, paddr, &mut buf[..width]) {
+            return None;
+        }
+        let offset = if width == 4 {
+            i32::from_ne_bytes(buf[..4].try_into().unwrap()) as isize
+        } else {
+            i64::from_ne_bytes(buf) as isize
+        };
+
+        Some(paddr.wrapping_add_signed(offset))
+    }
+
+    /// Encodes `address` as the value of the pointer at `paddr`.
+    fn encode_target(&self, address: usize, paddr: usize) -> Option<Vec<u8>> {
+        let Some(width) = self.relative else {
+            return Some(address.to_ne_bytes()[..self.width.get()].to_vec());
+        };
+
+        let offset = address.wrapping_sub(paddr) as isize as i64;
+        if width == 4 {
+            i32::try_from(offset).ok().map(|o| o.to_ne_bytes().to_vec())
+        } else {
+            Some(offset.to_ne_bytes().to_vec())
         }
     }
 
     fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext, address: usize) {
         let class = self.class_id.get().and_then(|id| ctx.class_list.by_id(id));
 
-        let (text, exists) = if let Some(cl) = class {
+        let (mut text, exists) = if let Some(cl) = class {
             (format!("[{}]", cl.name), true)
         } else {
             (format!("[C{:X}]", address), false)
         };
+        if let Some(width) = self.relative {
+            text = format!("rel{} {text}", width * 8);
+        }
 
         let mut job = LayoutJob::default();
-        display_field_prelude(ui.ctx(), self, ctx, &mut job, false);
+        display_field_prelude(ui, self, ctx, &mut job, false);
         job.append(" ", 0., TextFormat::default());
 
         if ui.add(Label::new(job).sense(Sense::click())).clicked() {
@@ -57,34 +131,46 @@ impl PointerField {
 
         ui.add_space(4.);
 
+        let target_name = ctx
+            .named_addresses
+            .name(address)
+            .map(|name| format!(" ({name})"))
+            .unwrap_or_default();
         display_field_value(
             self,
             ui,
             ctx,
             &self.state,
-            Color32::YELLOW,
+            ctx.theme.pointer,
             |v| {
                 if v {
                     format!("{address:X}")
                 } else {
-                    format!("-> {address:X}")
+                    format!("-> {address:X}{target_name}")
                 }
             },
-            |new| {
-                if let Some(addr) = parse_address(new) {
-                    ctx.process.write(paddr, &addr.to_ne_bytes());
+            |new, ctx| match parse_address(new).and_then(|addr| self.encode_target(addr, paddr)) {
+                Some(buf) => {
+                    ctx.write(paddr, &buf);
                     true
-                } else {
-                    false
                 }
+                None => false,
             },
         );
 
+        display_field_delta(ui, ctx, self.id, &address.to_ne_bytes(), |previous| {
+            let previous = usize::from_ne_bytes(previous.try_into().unwrap());
+            let delta = address as i128 - previous as i128;
+            let sign = if delta < 0 { "-" } else { "" };
+            (delta.cmp(&0), format!("{sign}0x{:X}", delta.unsigned_abs()))
+        });
+
         let mut job = LayoutJob::default();
         job.append(
             &text,
             4.,
             create_text_format(
+                ctx.theme,
                 is_selected,
                 if exists {
                     Color32::LIGHT_GRAY
@@ -121,46 +207,52 @@ impl PointerField {
             ui.heading(
                 RichText::new(format!("Can't read memory at address {address:#X}"))
                     .color(Color32::RED)
-                    .font(FID_M),
+                    .font(fid_m()),
             );
             return None;
         }
 
-        let mut response = None;
-
         let cid = self.class_id.get()?;
-        if let Some(class) = ctx.class_list.by_id(cid) {
+        if ctx.class_list.by_id(cid).is_some() {
             let rng = Rng::with_seed(ctx.current_id.value());
 
             let mut inner_ctx = InspectionContext {
                 class_list: ctx.class_list,
                 parent_id: ctx.current_id,
                 selection: ctx.selection,
+                multi_selection: ctx.multi_selection,
+                extend_selection: ctx.extend_selection,
                 current_container: cid,
                 // Will be immideately reassigned.
                 current_id: Id::NULL,
                 process: ctx.process,
+                verify_writes: ctx.verify_writes,
+                reader: ctx.reader,
+                frozen: ctx.frozen,
+                recordings: ctx.recordings,
                 toasts: ctx.toasts,
+                named_addresses: ctx.named_addresses,
+                search: ctx.search,
+                previous_values: ctx.previous_values.as_deref_mut(),
+                // Snapshots only cover the inspected class.
+                changes: None,
+                // Pointers of the processes point to different memory.
+                secondary: None,
+                columns: ctx.columns,
+                scroll_to: None,
+                rename_selected: ctx.rename_selected,
+                theme: ctx.theme,
                 level_rng: &rng,
                 offset: 0,
                 address,
             };
 
-            #[allow(clippy::single_match)]
-            match class.fields.iter().fold(None, |r, f| {
-                inner_ctx.current_id = Id::new(rng.u64(..));
-                r.or(f.draw(ui, &mut inner_ctx))
-            }) {
-                Some(other) => response = Some(other),
-                None => {}
-            }
-
+            let response = display_class_fields(ui, &mut inner_ctx, cid, ctx.current_id.value());
             ctx.selection = inner_ctx.selection;
+            response
         } else {
-            response = Some(FieldResponse::NewClass(format!("C{:X}", address), cid));
+            Some(FieldResponse::NewClass(new_class_name(ctx, address), cid))
         }
-
-        response
     }
 }
 
@@ -170,27 +262,47 @@ impl Field for PointerField {
     }
 
     fn size(&self) -> usize {
-        // TODO(ItsEthra): When inspecting 32-bit processes
-        // size of the pointer would be `4`. But I am not sure
-        // if the rest of this app isn't break in this case lol.
-        8
+        self.width.get()
+    }
+
+    fn pointed_class(&self) -> Option<usize> {
+        self.class_id.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.relative.is_none() && self.width.refresh(classes)
     }
 
     fn name(&self) -> Option<String> {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn kind(&self) -> FieldKind {
-        FieldKind::Ptr
+        match self.relative {
+            None => FieldKind::Ptr,
+            Some(4) => FieldKind::RelPtr32,
+            Some(_) => FieldKind::RelPtr64,
+        }
     }
 
     fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
         let mut response = None;
 
-        // TODO(ItsEthra): Again, pointer size differs in 32-bit processes.
-        let mut buf = [0; 8];
-        ctx.process.read(ctx.address + ctx.offset, &mut buf);
-        let address = usize::from_ne_bytes(buf);
+        let Some(address) = self.read_target(ctx, ctx.address + ctx.offset) else {
+            return display_read_error(ui, self, ctx);
+        };
 
         if self.class_id.get().is_none() {
             self.class_id.set(Some(fastrand::usize(..)));
@@ -214,7 +326,7 @@ impl Field for PointerField {
     fn codegen(&self, generator: &mut dyn Generator, data: &CodegenData) {
         generator.add_field(
             self.state.name.borrow().as_str(),
-            FieldKind::Ptr,
+            self.kind(),
             data.classes
                 .iter()
                 .find(|c| c.id() == self.class_id.get().unwrap())
@@ -222,3 +334,33 @@ impl Field for PointerField {
         );
     }
 }
+
+/// Comment of generated relative pointers, `class` is the name of the pointed to class.
+pub fn format_relative_pointer(class: Option<&str>) -> String {
+    match class {
+        Some(class) => format!("relative pointer to {class}"),
+        None => "relative pointer".to_owned(),
+    }
+}
+
+/// Name of the class created for the object at `address`, taken from its RTTI if it has any.
+fn new_class_name(ctx: &mut InspectionContext, address: usize) -> String {
+    let Some(rtti) = detect_rtti_name(ctx.process, address) else {
+        return format!("C{address:X}");
+    };
+    let base = class_name_from_rtti(&rtti);
+    if !is_valid_ident(&base) {
+        return format!("C{address:X}");
+    }
+
+    let mut name = base.clone();
+    for i in 2.. {
+        if ctx.class_list.by_name(&name).is_none() {
+            break;
+        }
+        name = format!("{base}_{i}");
+    }
+    ctx.toasts
+        .info(format!("Named the new class after its RTTI type `{rtti}`"));
+    name
+}
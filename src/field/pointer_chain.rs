@@ -0,0 +1,201 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+    PointerWidth,
+};
+use crate::{class::ClassList, context::InspectionContext, generator::Generator, process::Process};
+use eframe::{
+    egui::{Label, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use std::cell::RefCell;
+
+/// Pointer that is followed through several levels of indirection.
+pub struct PointerChainField {
+    id: FieldId,
+    state: NamedState,
+    offsets: RefCell<Vec<isize>>,
+    width: PointerWidth,
+}
+
+impl PointerChainField {
+    pub fn new(name: String, offsets: Vec<isize>, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            offsets: offsets.into(),
+            width: PointerWidth::new(pointer_size),
+        }
+    }
+
+    /// Returns address at the end of the chain or the step at which it breaks.
+    fn resolve(&self, process: &Process, address: usize) -> Result<usize, usize> {
+        let mut address = process.read_pointer(address);
+        for (step, offset) in self.offsets.borrow().iter().enumerate() {
+            if step > 0 {
+                address = process.read_pointer(address);
+            }
+
+            address = address.wrapping_add_signed(*offset);
+            if !process.can_read(address) {
+                return Err(step + 1);
+            }
+        }
+
+        Ok(address)
+    }
+}
+
+impl Field for PointerChainField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.width.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::PointerChain(self.offsets.borrow().clone())
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        if ctx
+            .read_pointer(self.id, ctx.address + ctx.offset)
+            .is_none()
+        {
+            return display_read_error(ui, self, ctx);
+        }
+        let resolved = self.resolve(ctx.process, ctx.address + ctx.offset);
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::BROWN);
+            display_field_value(
+                self,
+                ui,
+                ctx,
+                &self.state,
+                Color32::LIGHT_GRAY,
+                |v| {
+                    let offsets = self.offsets.borrow();
+                    if v {
+                        offsets
+                            .iter()
+                            .map(|o| format_offset(*o))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    } else {
+                        format_pointer_chain(&offsets)
+                    }
+                },
+                |new, _| match parse_offsets(new) {
+                    Some(offsets) => {
+                        *self.offsets.borrow_mut() = offsets;
+                        true
+                    }
+                    None => false,
+                },
+            );
+
+            let (text, color) = match resolved {
+                Ok(address) => {
+                    let mut buf = [0; 8];
+                    ctx.process
+                        .read(address, &mut buf[..ctx.process.pointer_size()]);
+                    (
+                        format!("-> {address:X} = {:X}", u64::from_ne_bytes(buf)),
+                        Color32::YELLOW,
+                    )
+                }
+                Err(step) => (format!("broken chain at step {step}"), Color32::RED),
+            };
+
+            let mut job = LayoutJob::default();
+            job.append(
+                &text,
+                4.,
+                create_text_format(ctx.theme, ctx.is_selected(self.id), color),
+            );
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+        });
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+/// Formats offsets of the chain as `[[this] + 0x10] + 0x28`.
+pub fn format_pointer_chain(offsets: &[isize]) -> String {
+    let mut chain = "[this]".to_owned();
+    for (i, offset) in offsets.iter().enumerate() {
+        let sign = if *offset < 0 { '-' } else { '+' };
+        chain = format!("{chain} {sign} 0x{:X}", offset.unsigned_abs());
+
+        if i != offsets.len() - 1 {
+            chain = format!("[{chain}]");
+        }
+    }
+
+    chain
+}
+
+fn format_offset(offset: isize) -> String {
+    if offset < 0 {
+        format!("-0x{:X}", offset.unsigned_abs())
+    } else {
+        format!("0x{offset:X}")
+    }
+}
+
+/// Parses comma or space separated hex offsets, e.g. `0x10, -0x8`.
+fn parse_offsets(s: &str) -> Option<Vec<isize>> {
+    let offsets = s
+        .split([',', ' '])
+        .filter(|o| !o.is_empty())
+        .map(|o| {
+            let (negative, o) = match o.strip_prefix('-') {
+                Some(o) => (true, o),
+                None => (false, o),
+            };
+            let o = o.strip_prefix("0x").unwrap_or(o);
+            let value = isize::from_str_radix(o, 16).ok()?;
+
+            Some(if negative { -value } else { value })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!offsets.is_empty()).then_some(offsets)
+}
@@ -0,0 +1,151 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, draw_draggable_field, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{class::ClassList, context::InspectionContext, generator::Generator};
+use eframe::{
+    egui::{collapsing_header::CollapsingState, Label, Sense, TextFormat, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+
+/// Collapsible group of fields, they're still a part of the class and are edited like its other
+/// fields.
+pub struct SectionField {
+    id: FieldId,
+    state: NamedState,
+    fields: Vec<Box<dyn Field>>,
+}
+
+impl SectionField {
+    pub fn new(name: String, fields: Vec<Box<dyn Field>>) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            fields,
+        }
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_BLUE);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("[{} fields]", self.fields.len()),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+        );
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let parent_id = ctx.current_id;
+
+        let mut response = None;
+        for i in 0..self.fields.len() {
+            ctx.current_id = parent_id.with(i);
+            response = response
+                .take()
+                .or(draw_draggable_field(ui, ctx, &self.fields, i));
+        }
+        ctx.current_id = parent_id;
+
+        response
+    }
+}
+
+impl Field for SectionField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.fields.iter().map(|f| f.size()).sum()
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Section(self.size())
+    }
+
+    fn contains(&self, id: FieldId) -> bool {
+        self.id == id || self.fields.iter().any(|f| f.contains(id))
+    }
+
+    fn section_fields(&self) -> Option<&[Box<dyn Field>]> {
+        Some(&self.fields)
+    }
+
+    fn section_fields_mut(&mut self) -> Option<&mut Vec<Box<dyn Field>>> {
+        Some(&mut self.fields)
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.fields
+            .iter()
+            .fold(false, |changed, f| f.refresh_size(classes) | changed)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let start = ctx.offset;
+
+        let response = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, true)
+            .show_header(ui, |ui| self.show_header(ui, ctx))
+            .body(|ui| self.show_body(ui, ctx))
+            .2
+            .and_then(|inner| inner.inner);
+
+        // Collapsed sections don't advance the offset.
+        ctx.offset = start + self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, data: &CodegenData) {
+        generator.begin_section(self.state.name.borrow().as_str());
+        for field in self.fields.iter() {
+            if let Some(comment) = field.comment() {
+                generator.add_comment(&comment);
+            }
+            field.codegen(generator, data);
+        }
+        generator.end_section();
+    }
+}
+
+/// Fields with their offsets from `start`, fields of a section follow the section itself.
+pub fn walk_fields(fields: &[Box<dyn Field>], start: usize) -> Vec<(usize, &dyn Field)> {
+    let mut walked = vec![];
+    let mut offset = start;
+    for field in fields {
+        walked.push((offset, field.as_ref()));
+        if let Some(children) = field.section_fields() {
+            walked.extend(walk_fields(children, offset));
+        }
+        offset += field.size();
+    }
+    walked
+}
@@ -0,0 +1,365 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState, PointerWidth,
+};
+use crate::{
+    class::ClassList,
+    context::{InspectionContext, Selection},
+    generator::Generator,
+};
+use eframe::{
+    egui::{Label, Popup, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Longest displayed string, longer strings are cut off.
+const MAX_DISPLAYED_LEN: usize = 256;
+
+/// Standard library implementation that decides the layout of `std::string`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StringAbi {
+    /// Buffer or pointer, size and capacity.
+    Msvc,
+    /// Pointer, size and buffer or capacity, used by GCC.
+    Libstdcxx,
+    /// Tagged union of short and long strings, used by Clang on macOS.
+    Libcxx,
+}
+
+impl StringAbi {
+    pub const ALL: [StringAbi; 3] = [Self::Msvc, Self::Libstdcxx, Self::Libcxx];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Msvc => "MSVC",
+            Self::Libstdcxx => "libstdc++",
+            Self::Libcxx => "libc++",
+        }
+    }
+
+    /// Size of `std::string` in bytes.
+    pub fn size(self, pointer_size: usize) -> usize {
+        match self {
+            Self::Msvc | Self::Libstdcxx => 16 + pointer_size * 2,
+            Self::Libcxx => pointer_size * 3,
+        }
+    }
+}
+
+/// Where the characters of a string are stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StringHeader {
+    /// `true` if characters are stored inline because of the small string optimisation.
+    pub sso: bool,
+    pub data: usize,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl StringHeader {
+    /// Decodes `bytes` of a string at `address`, `bytes` must be `abi.size(pointer_size)` long.
+    pub fn parse(abi: StringAbi, bytes: &[u8], address: usize, pointer_size: usize) -> Self {
+        let word = |offset: usize| {
+            let mut buf = [0; 8];
+            buf[..pointer_size].copy_from_slice(&bytes[offset..offset + pointer_size]);
+            usize::from_ne_bytes(buf)
+        };
+
+        match abi {
+            StringAbi::Msvc => {
+                let (len, capacity) = (word(16), word(16 + pointer_size));
+                // Buffer holds 15 characters and the terminator.
+                let sso = capacity < 16;
+                Self {
+                    data: if sso { address } else { word(0) },
+                    sso,
+                    len,
+                    capacity,
+                }
+            }
+            StringAbi::Libstdcxx => {
+                let (data, len) = (word(0), word(pointer_size));
+                // Short strings point to the buffer after the size.
+                let sso = data == address + pointer_size * 2;
+                Self {
+                    capacity: if sso { 15 } else { word(pointer_size * 2) },
+                    sso,
+                    data,
+                    len,
+                }
+            }
+            StringAbi::Libcxx => {
+                // Lowest bit of the first byte marks long strings.
+                if bytes[0] & 1 == 0 {
+                    Self {
+                        sso: true,
+                        data: address + 1,
+                        len: (bytes[0] >> 1) as usize,
+                        capacity: pointer_size * 3 - 2,
+                    }
+                } else {
+                    Self {
+                        sso: false,
+                        data: word(pointer_size * 2),
+                        len: word(pointer_size),
+                        capacity: (word(0) & !1).saturating_sub(1),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `std::string` of one of the standard library implementations.
+pub struct StdStringField {
+    id: FieldId,
+    state: NamedState,
+    abi: StringAbi,
+    // New ABI requested by user, applied through `FieldResponse::ChangeKind`.
+    new_abi: Cell<Option<StringAbi>>,
+    width: PointerWidth,
+}
+
+impl StdStringField {
+    pub fn new(name: String, abi: StringAbi, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            new_abi: None.into(),
+            width: PointerWidth::new(pointer_size),
+            abi,
+        }
+    }
+
+    fn read_text(&self, ctx: &InspectionContext, header: &StringHeader) -> Option<String> {
+        if !ctx.process.can_read(header.data) {
+            return None;
+        }
+
+        let mut buf = vec![0; header.len.min(MAX_DISPLAYED_LEN)];
+        ctx.process.read(header.data, &mut buf);
+
+        let mut text = String::from_utf8_lossy(&buf).into_owned();
+        if header.len > MAX_DISPLAYED_LEN {
+            text += "...";
+        }
+        Some(text)
+    }
+}
+
+impl Field for StdStringField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.abi.size(self.width.get())
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::StdString(self.abi)
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let address = ctx.address + ctx.offset;
+        let mut bytes = vec![0; self.size()];
+        if !ctx.read(self.id, address, &mut bytes) {
+            return display_read_error(ui, self, ctx);
+        }
+        let header = StringHeader::parse(self.abi, &bytes, address, self.width.get());
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_RED);
+
+            let is_selected = ctx.is_selected(self.id);
+            let mut job = LayoutJob::default();
+            job.append(
+                &format!("[{}]", self.abi.label()),
+                4.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.dim),
+            );
+
+            let r = ui
+                .add(Label::new(job).sense(Sense::click()))
+                .on_hover_text("Right click to change the layout");
+            if r.clicked() {
+                ctx.select(self.id);
+            }
+
+            Popup::context_menu(&r).show(|ui| {
+                for abi in StringAbi::ALL {
+                    if ui.selectable_label(abi == self.abi, abi.label()).clicked() {
+                        self.new_abi.set(Some(abi).filter(|abi| *abi != self.abi));
+                        ui.close();
+                    }
+                }
+            });
+
+            let (text, color) = match self.read_text(ctx, &header) {
+                Some(text) if header.sso => (format!("[SSO] {text:?}"), Color32::LIGHT_BLUE),
+                Some(text) => (
+                    format!("[heap] {text:?} (cap={})", header.capacity),
+                    Color32::LIGHT_BLUE,
+                ),
+                None => (
+                    format!("Can't read memory at address {:#X}", header.data),
+                    Color32::RED,
+                ),
+            };
+
+            let mut job = LayoutJob::default();
+            job.append(&text, 4., create_text_format(ctx.theme, is_selected, color));
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+        });
+
+        let mut response = None;
+        if let Some(abi) = self.new_abi.take() {
+            // Layouts differ in size, so it goes through `ChangeKind` like array resizing.
+            ctx.selection = Some(Selection {
+                container_id: ctx.current_container,
+                field_id: self.id,
+                address,
+            });
+            response = Some(FieldResponse::ChangeKind(FieldKind::StdString(abi)));
+        }
+
+        ctx.offset += self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: usize = 0x1000;
+    const HEAP: usize = 0x7FF0_1234;
+
+    fn words(words: &[usize], pointer_size: usize) -> Vec<u8> {
+        words
+            .iter()
+            .flat_map(|w| w.to_ne_bytes()[..pointer_size].to_vec())
+            .collect()
+    }
+
+    fn header(sso: bool, data: usize, len: usize, capacity: usize) -> StringHeader {
+        StringHeader {
+            sso,
+            data,
+            len,
+            capacity,
+        }
+    }
+
+    #[test]
+    fn msvc() {
+        for pointer_size in [4, 8] {
+            // "hello" in the 16 byte buffer.
+            let mut bytes = b"hello\0\0\0\0\0\0\0\0\0\0\0".to_vec();
+            bytes.extend(words(&[5, 15], pointer_size));
+            assert_eq!(
+                StringHeader::parse(StringAbi::Msvc, &bytes, ADDRESS, pointer_size),
+                header(true, ADDRESS, 5, 15)
+            );
+
+            let mut bytes = words(&[HEAP], pointer_size);
+            bytes.resize(16, 0);
+            bytes.extend(words(&[40, 47], pointer_size));
+            assert_eq!(
+                StringHeader::parse(StringAbi::Msvc, &bytes, ADDRESS, pointer_size),
+                header(false, HEAP, 40, 47)
+            );
+        }
+    }
+
+    #[test]
+    fn libstdcxx() {
+        for pointer_size in [4, 8] {
+            let buffer = ADDRESS + pointer_size * 2;
+            let mut bytes = words(&[buffer, 5], pointer_size);
+            bytes.extend(b"hello\0\0\0\0\0\0\0\0\0\0\0");
+            assert_eq!(
+                StringHeader::parse(StringAbi::Libstdcxx, &bytes, ADDRESS, pointer_size),
+                header(true, buffer, 5, 15)
+            );
+
+            let mut bytes = words(&[HEAP, 40, 47], pointer_size);
+            bytes.resize(16 + pointer_size * 2, 0);
+            assert_eq!(
+                StringHeader::parse(StringAbi::Libstdcxx, &bytes, ADDRESS, pointer_size),
+                header(false, HEAP, 40, 47)
+            );
+        }
+    }
+
+    #[test]
+    fn libcxx() {
+        for pointer_size in [4, 8] {
+            // Size shifted left by one, then the characters.
+            let mut bytes = vec![5 << 1];
+            bytes.extend(b"hello");
+            bytes.resize(pointer_size * 3, 0);
+            assert_eq!(
+                StringHeader::parse(StringAbi::Libcxx, &bytes, ADDRESS, pointer_size),
+                header(true, ADDRESS + 1, 5, pointer_size * 3 - 2)
+            );
+
+            // Allocation size with the long bit, size and pointer.
+            let bytes = words(&[48 | 1, 40, HEAP], pointer_size);
+            assert_eq!(
+                StringHeader::parse(StringAbi::Libcxx, &bytes, ADDRESS, pointer_size),
+                header(false, HEAP, 40, 47)
+            );
+        }
+    }
+
+    #[test]
+    fn kind_round_trip() {
+        for (abi, pointer_size) in StringAbi::ALL
+            .into_iter()
+            .flat_map(|abi| [(abi, 4), (abi, 8)])
+        {
+            let field = FieldKind::StdString(abi).into_field(Some("name".into()), pointer_size);
+            assert_eq!(field.kind(), FieldKind::StdString(abi));
+            assert_eq!(field.size(), abi.size(pointer_size));
+
+            let kind = ron::from_str::<FieldKind>(&ron::to_string(&field.kind()).unwrap());
+            assert_eq!(kind.unwrap(), FieldKind::StdString(abi));
+        }
+    }
+}
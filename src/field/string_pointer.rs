@@ -3,23 +3,25 @@ use eframe::{
     epaint::{text::LayoutJob, Color32},
 };
 
-use crate::FID_M;
+use crate::{class::ClassList, fonts::fid_m};
 
 use super::{
-    display_field_name, display_field_prelude, display_field_value, next_id, Field, FieldId,
-    FieldKind, NamedState,
+    display_field_name, display_field_prelude, display_field_value, display_read_error, next_id,
+    Field, FieldId, FieldKind, NamedState, PointerWidth,
 };
 
 pub struct StringPointerField {
     id: FieldId,
     state: NamedState,
+    width: PointerWidth,
 }
 
 impl StringPointerField {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, pointer_size: usize) -> Self {
         Self {
             id: next_id(),
             state: NamedState::new(name),
+            width: PointerWidth::new(pointer_size),
         }
     }
 }
@@ -33,9 +35,24 @@ impl Field for StringPointerField {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn size(&self) -> usize {
-        // TODO: The size of the pointer would be 4 bytes on x86
-        8
+        self.width.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
     }
 
     fn kind(&self) -> super::FieldKind {
@@ -47,17 +64,16 @@ impl Field for StringPointerField {
         ui: &mut eframe::egui::Ui,
         ctx: &mut crate::context::InspectionContext,
     ) -> Option<super::FieldResponse> {
-        // TODO: The size of the pointer would be 4 bytes on x86
-        let mut buf = [0; 8];
-        ctx.process.read(ctx.address + ctx.offset, &mut buf);
-        let address = usize::from_ne_bytes(buf);
+        let Some(address) = ctx.read_pointer(self.id, ctx.address + ctx.offset) else {
+            return display_read_error(ui, self, ctx);
+        };
 
         let mut str_buf = [0; 64];
         ctx.process.read(address, &mut str_buf);
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
             if ui.add(Label::new(job).sense(Sense::click())).clicked() {
                 ctx.select(self.id);
             }
@@ -82,14 +98,14 @@ impl Field for StringPointerField {
                             format!("-> {str:?}")
                         }
                     },
-                    |_| false,
+                    |_, _| false,
                 )
             } else {
                 ui.add_space(2.);
                 ui.heading(
                     RichText::new("Invalid Address")
                         .color(Color32::RED)
-                        .font(FID_M),
+                        .font(fid_m()),
                 );
             }
         });
@@ -105,13 +121,15 @@ impl Field for StringPointerField {
 pub struct WideStringPointerField {
     id: FieldId,
     state: NamedState,
+    width: PointerWidth,
 }
 
 impl WideStringPointerField {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, pointer_size: usize) -> Self {
         Self {
             id: next_id(),
             state: NamedState::new(name),
+            width: PointerWidth::new(pointer_size),
         }
     }
 }
@@ -125,9 +143,24 @@ impl Field for WideStringPointerField {
         Some(self.state.name.borrow().clone())
     }
 
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
     fn size(&self) -> usize {
-        // TODO: The size of the pointer would be 4 bytes on x86
-        8
+        self.width.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
     }
 
     fn kind(&self) -> super::FieldKind {
@@ -139,17 +172,16 @@ impl Field for WideStringPointerField {
         ui: &mut eframe::egui::Ui,
         ctx: &mut crate::context::InspectionContext,
     ) -> Option<super::FieldResponse> {
-        // TODO: The size of the pointer would be 4 bytes on x86
-        let mut buf = [0; 8];
-        ctx.process.read(ctx.address + ctx.offset, &mut buf);
-        let address = usize::from_ne_bytes(buf);
+        let Some(address) = ctx.read_pointer(self.id, ctx.address + ctx.offset) else {
+            return display_read_error(ui, self, ctx);
+        };
 
         let mut str_buf = [0; 64];
         ctx.process.read(address, &mut str_buf);
 
         ui.horizontal(|ui| {
             let mut job = LayoutJob::default();
-            display_field_prelude(ui.ctx(), self, ctx, &mut job, true);
+            display_field_prelude(ui, self, ctx, &mut job, true);
             if ui.add(Label::new(job).sense(Sense::click())).clicked() {
                 ctx.select(self.id);
             }
@@ -175,14 +207,14 @@ impl Field for WideStringPointerField {
                             format!("-> {str:?}")
                         }
                     },
-                    |_| false,
+                    |_, _| false,
                 )
             } else {
                 ui.add_space(2.);
                 ui.heading(
                     RichText::new("Invalid Address")
                         .color(Color32::RED)
-                        .font(FID_M),
+                        .font(fid_m()),
                 );
             }
         });
@@ -0,0 +1,246 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_read_error, next_id,
+    CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{
+    context::{InspectionContext, Selection},
+    generator::Generator,
+};
+use eframe::{
+    egui::{Label, Popup, Sense, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Seconds between 1601-01-01, the FILETIME epoch, and 1970-01-01.
+const FILETIME_EPOCH_OFFSET: i64 = 11_644_473_600;
+/// Seconds from the Unix epoch to 0001-01-01 00:00:00 and to 9999-12-31 23:59:59.
+const VALID_SECONDS: std::ops::RangeInclusive<i64> = -62_135_596_800..=253_402_300_799;
+
+/// How the point in time is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// Windows FILETIME, 100 nanosecond intervals since 1601-01-01.
+    FiletimeU64,
+    UnixU32,
+    UnixU64,
+    /// Seconds since 1970-01-01 with a fractional part.
+    UnixF64,
+}
+
+impl TimestampFormat {
+    pub const ALL: [TimestampFormat; 4] = [
+        Self::FiletimeU64,
+        Self::UnixU32,
+        Self::UnixU64,
+        Self::UnixF64,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FiletimeU64 => "FILETIME",
+            Self::UnixU32 => "Unix u32",
+            Self::UnixU64 => "Unix u64",
+            Self::UnixF64 => "Unix f64",
+        }
+    }
+
+    /// Size of the stored value in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Self::FiletimeU64 | Self::UnixU64 | Self::UnixF64 => 8,
+            Self::UnixU32 => 4,
+        }
+    }
+
+    /// Kind of the value the timestamp is stored as.
+    pub fn raw_kind(self) -> FieldKind {
+        match self {
+            Self::FiletimeU64 | Self::UnixU64 => FieldKind::U64,
+            Self::UnixU32 => FieldKind::U32,
+            Self::UnixF64 => FieldKind::F64,
+        }
+    }
+
+    /// Converts raw bytes into seconds since the Unix epoch, `None` if it's not a valid date.
+    pub fn to_unix_seconds(self, bytes: [u8; 8]) -> Option<i64> {
+        let seconds = match self {
+            Self::FiletimeU64 => {
+                (u64::from_ne_bytes(bytes) / 10_000_000) as i64 - FILETIME_EPOCH_OFFSET
+            }
+            Self::UnixU32 => u32::from_ne_bytes(bytes[..4].try_into().unwrap()) as i64,
+            Self::UnixU64 => i64::try_from(u64::from_ne_bytes(bytes)).ok()?,
+            Self::UnixF64 => {
+                let seconds = f64::from_ne_bytes(bytes);
+                if !seconds.is_finite() {
+                    return None;
+                }
+                seconds.floor() as i64
+            }
+        };
+
+        VALID_SECONDS.contains(&seconds).then_some(seconds)
+    }
+}
+
+/// Formats seconds since the Unix epoch as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_unix_seconds(seconds: i64) -> String {
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+
+    // Converts days to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+pub struct TimestampField {
+    id: FieldId,
+    state: NamedState,
+    format: TimestampFormat,
+    // Shows the date instead of the raw value.
+    formatted: Cell<bool>,
+    // New format requested by user, applied through `FieldResponse::ChangeKind`.
+    new_format: Cell<Option<TimestampFormat>>,
+}
+
+impl TimestampField {
+    pub fn new(name: String, format: TimestampFormat) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            formatted: false.into(),
+            new_format: None.into(),
+            format,
+        }
+    }
+}
+
+impl Field for TimestampField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.format.size()
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Timestamp(self.format)
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let address = ctx.address + ctx.offset;
+        let mut bytes = [0; 8];
+        if !ctx.read(self.id, address, &mut bytes[..self.size()]) {
+            return display_read_error(ui, self, ctx);
+        }
+
+        let date = self
+            .format
+            .to_unix_seconds(bytes)
+            .map(format_unix_seconds)
+            .unwrap_or_else(|| "invalid timestamp".to_owned());
+        let raw = match self.format {
+            TimestampFormat::UnixF64 => f64::from_ne_bytes(bytes).to_string(),
+            _ => format!("0x{:X}", u64::from_ne_bytes(bytes)),
+        };
+
+        ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_BLUE);
+
+            let is_selected = ctx.is_selected(self.id);
+            let (text, hover) = if self.formatted.get() {
+                (&date, &raw)
+            } else {
+                (&raw, &date)
+            };
+
+            let mut job = LayoutJob::default();
+            job.append(
+                text,
+                0.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.foreground),
+            );
+            job.append(
+                &format!("[{}]", self.format.label()),
+                4.,
+                create_text_format(ctx.theme, is_selected, ctx.theme.dim),
+            );
+
+            let r = ui
+                .add(Label::new(job).sense(Sense::click()))
+                .on_hover_text(hover);
+            if r.clicked() {
+                self.formatted.set(!self.formatted.get());
+            }
+
+            Popup::context_menu(&r).show(|ui| {
+                for format in TimestampFormat::ALL {
+                    if ui
+                        .selectable_label(format == self.format, format.label())
+                        .clicked()
+                    {
+                        self.new_format
+                            .set(Some(format).filter(|format| *format != self.format));
+                        ui.close();
+                    }
+                }
+            });
+        });
+
+        let mut response = None;
+        if let Some(format) = self.new_format.take() {
+            // Formats differ in size, so it goes through `ChangeKind` like array resizing.
+            ctx.selection = Some(Selection {
+                container_id: ctx.current_container,
+                field_id: self.id,
+                address,
+            });
+            response = Some(FieldResponse::ChangeKind(FieldKind::Timestamp(format)));
+        }
+
+        ctx.offset += self.size();
+        response
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
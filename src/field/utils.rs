@@ -1,57 +1,669 @@
-use super::{create_text_format, EditingState, Field, HexField, NamedState};
-use crate::{app::is_valid_ident, context::InspectionContext, FID_M};
+use super::{
+    create_text_format, EditingState, Field, FieldId, FieldKind, FieldResponse, HexField,
+    NamedState,
+};
+use crate::{
+    app::is_valid_ident,
+    class::{ClassId, ColorOverride},
+    context::{InspectionContext, Selection},
+    fonts::fid_m,
+    named_addresses::NamedAddressRegistry,
+    recording::Recording,
+    snapshot::Change,
+    theme::Theme,
+};
 use eframe::{
-    egui::{Context, FontSelection, Key, Label, Modifiers, Sense, TextEdit, Ui},
-    epaint::{text::LayoutJob, Color32, Stroke},
+    egui::{
+        collapsing_header::CollapsingState,
+        color_picker::{color_picker_color32, Alpha},
+        Align, Button, Event, FontSelection, Frame, Id, Key, Label, Modifiers, Popup,
+        PopupCloseBehavior, Response, RichText, Sense, SetOpenCommand, TextEdit, Ui,
+    },
+    epaint::{text::LayoutJob, Color32, FontId, RectShape, Shape, Stroke},
 };
-use std::fmt::Display;
+use fastrand::Rng;
+use std::{cmp::Ordering, fmt::Display, fs};
+
+const FROZEN_BACKGROUND: Color32 = Color32::from_rgb(0x1A, 0x2A, 0x44);
+const BASE_CLASS_BACKGROUND: Color32 = Color32::from_rgb(0x22, 0x2A, 0x22);
+/// Row colors are blended with the panel so the values stay readable.
+const ROW_COLOR_OPACITY: f32 = 0.3;
+
+/// Draws fields of the class preceded by collapsible sections with fields of its base classes.
+pub fn display_class_fields(
+    ui: &mut Ui,
+    ctx: &mut InspectionContext,
+    cid: ClassId,
+    seed: u64,
+) -> Option<FieldResponse> {
+    let (class_list, container) = (ctx.class_list, ctx.current_container);
+    let mut response = None;
+
+    for base in class_list.bases_of(cid) {
+        let class = class_list.by_id(base)?;
+        let (start, rng) = (ctx.offset, Rng::with_seed(seed ^ base as u64));
+
+        Frame::default().fill(BASE_CLASS_BACKGROUND).show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+
+            CollapsingState::load_with_default_open(
+                ui.ctx(),
+                Id::new(("_base_class", seed, base)),
+                false,
+            )
+            .show_header(ui, |ui| {
+                ui.label(
+                    RichText::new(format!("{} (base class)", class.name))
+                        .font(fid_m())
+                        .color(Color32::GRAY),
+                );
+            })
+            .body(|ui| {
+                ctx.current_container = base;
+                for i in 0..class.fields.len() {
+                    ctx.current_id = Id::new(rng.u64(..));
+                    response = response
+                        .take()
+                        .or(draw_draggable_field(ui, ctx, &class.fields, i));
+                }
+            });
+        });
+
+        // Collapsed sections don't advance the offset.
+        ctx.offset = start + class.fields.iter().map(|f| f.size()).sum::<usize>();
+    }
+
+    ctx.current_container = container;
+    let rng = Rng::with_seed(seed);
+    let fields = &class_list.by_id(cid)?.fields;
+    for i in 0..fields.len() {
+        ctx.current_id = Id::new(rng.u64(..));
+        response = response.take().or(draw_draggable_field(ui, ctx, fields, i));
+    }
+
+    response
+}
+
+/// Field that is being dragged by its handle.
+#[derive(Clone, Copy)]
+struct FieldDrag {
+    container: ClassId,
+    field_id: FieldId,
+}
+
+/// Draws the field with a drag handle on the left, fields of the same class can be dropped on it.
+pub fn draw_draggable_field(
+    ui: &mut Ui,
+    ctx: &mut InspectionContext,
+    fields: &[Box<dyn Field>],
+    index: usize,
+) -> Option<FieldResponse> {
+    let field = &fields[index];
+    let container = ctx.current_container;
+    let address = ctx.address + ctx.offset;
+    let matches_before = ctx.search.map(|s| s.matches.get()).unwrap_or_default();
+
+    // Filled in after the field is drawn and its size is known.
+    let background = ui.painter().add(Shape::Noop);
+    let r = ui.horizontal_top(|ui| {
+        let payload = FieldDrag {
+            container,
+            field_id: field.id(),
+        };
+        let recording = ctx.recordings.get(&field.id()).is_some_and(|r| r.active);
+        let handle = ui
+            .dnd_drag_source(ctx.current_id.with("_drag_handle"), payload, |ui| {
+                ui.label(RichText::new("⋮").font(fid_m()).color(if recording {
+                    Color32::RED
+                } else {
+                    Color32::from_gray(90)
+                }))
+            })
+            .response;
+
+        // Handle only senses drags so clicks go to this one.
+        let menu = ui
+            .interact(
+                handle.rect,
+                ctx.current_id.with("_field_menu"),
+                Sense::click(),
+            )
+            .on_hover_text("Drag to move the field, right click for more");
+        let (start, row_id) = (ctx.offset, ctx.current_id);
+        let response = ui.vertical(|ui| field.draw(ui, ctx)).inner;
+        // The menu acts on the field, drawing it moved the offset past it.
+        let (end, nested_id) = (ctx.offset, ctx.current_id);
+        (ctx.offset, ctx.current_id) = (start, row_id);
+
+        // Right clicks anywhere on the row open the menu of the handle, unless a part of the row
+        // or a nested row has handled them already.
+        let mut popup = Popup::context_menu(&menu);
+        if ui.input(|i| i.pointer.secondary_clicked())
+            && ui.rect_contains_pointer(ui.min_rect())
+            && !Popup::is_any_open(ui.ctx())
+            && !secondary_click_claimed(ui)
+        {
+            popup = popup.open_memory(SetOpenCommand::Bool(true));
+        }
+        let menu_response = popup
+            .show(|ui| field_menu(ui, ctx, container, field.as_ref(), address))
+            .and_then(|r| r.inner);
+        (ctx.offset, ctx.current_id) = (end, nested_id);
+
+        response.or(menu_response)
+    });
+
+    let change = ctx
+        .changes
+        .and_then(|c| c.get(&field.id()).copied())
+        .or_else(|| {
+            let secondary = ctx.secondary?;
+            let (mut primary_bytes, mut secondary_bytes) =
+                (vec![0; field.size()], vec![0; field.size()]);
+            ctx.process.read(address, &mut primary_bytes);
+            secondary.read(address, &mut secondary_bytes);
+            Change::of(&secondary_bytes, &primary_bytes)
+        });
+    let fill = change.map(|c| c.color()).or_else(|| {
+        let class = ctx.class_list.by_id(container)?;
+        let colors = class.colors.borrow();
+        colors
+            .as_ref()?
+            .color_of(field.as_ref())
+            .map(|c| c.gamma_multiply(ROW_COLOR_OPACITY))
+    });
+    if let Some(fill) = fill {
+        ui.painter()
+            .set(background, RectShape::filled(r.response.rect, 0., fill));
+    }
+
+    if ctx.scroll_to == Some(field.id()) {
+        ctx.scroll_to = None;
+        r.response.scroll_to_me(Some(Align::Center));
+    }
+
+    if let Some(search) = ctx.search {
+        if field.name().is_some_and(|name| search.matches(&name)) {
+            search.matches.set(search.matches.get() + 1);
+            if search.scroll.take() {
+                r.response.scroll_to_me(Some(Align::Center));
+            }
+        } else if search.matches.get() == matches_before {
+            // Rows with matching fields inside, i.e. expanded pointers, aren't dimmed.
+            ui.painter().rect_filled(
+                r.response.rect,
+                0.,
+                ui.visuals().panel_fill.gamma_multiply(0.7),
+            );
+        }
+    }
+
+    let accepts = |drag: &FieldDrag| drag.container == container && drag.field_id != field.id();
+    if let Some(drag) = r.response.dnd_hover_payload::<FieldDrag>() {
+        // Dragged field is moved to the offset of this one, the line shows where it starts.
+        if fields.iter().any(|f| f.id() == drag.field_id) && accepts(&drag) {
+            let rect = r.response.rect;
+            ui.painter().hline(
+                rect.x_range(),
+                rect.top(),
+                Stroke::new(2., Color32::LIGHT_BLUE),
+            );
+        }
+    }
+
+    if let Some(drag) = r.response.dnd_release_payload::<FieldDrag>() {
+        if accepts(&drag) {
+            return Some(FieldResponse::Reorder {
+                from_id: drag.field_id,
+                to_id: field.id(),
+            });
+        }
+    }
+
+    r.inner
+}
+
+fn field_menu(
+    ui: &mut Ui,
+    ctx: &mut InspectionContext,
+    container: ClassId,
+    field: &dyn Field,
+    address: usize,
+) -> Option<FieldResponse> {
+    let id = field.id();
+    let mut response = None;
+
+    if ui.button("Copy address").clicked() {
+        ui.ctx().copy_text(format!("{address:X}"));
+        ui.close();
+    }
+
+    let mut bytes = vec![0; field.size()];
+    ctx.process.read(address, &mut bytes);
+    if ui.button("Copy value as hex").clicked() {
+        ui.ctx().copy_text(match integer_value(&bytes, false) {
+            Some(value) => format!("{value:X}"),
+            None => bytes
+                .iter()
+                .map(|b| format!("{b:02X}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        });
+        ui.close();
+    }
+    let signed = matches!(
+        field.kind(),
+        FieldKind::I8 | FieldKind::I16 | FieldKind::I32 | FieldKind::I64
+    );
+    if ui
+        .add_enabled(bytes.len() <= 8, Button::new("Copy value as decimal"))
+        .clicked()
+    {
+        if let Some(value) = integer_value(&bytes, signed) {
+            ui.ctx().copy_text(if signed {
+                (value as i64).to_string()
+            } else {
+                value.to_string()
+            });
+        }
+        ui.close();
+    }
+    ui.separator();
+
+    // Picked up by `display_field_name` of the field the next frame.
+    let named = field.name().is_some();
+    if ui.add_enabled(named, Button::new("Rename field")).clicked() {
+        ui.data_mut(|d| d.insert_temp(ctx.current_id.with(RENAME_REQUEST), true));
+        ui.close();
+    }
+    let comment = if field.comment().is_some() {
+        "Edit comment"
+    } else {
+        "Add comment"
+    };
+    if ui.add_enabled(named, Button::new(comment)).clicked() {
+        ui.data_mut(|d| d.insert_temp(ctx.current_id.with(COMMENT_REQUEST), true));
+        ui.close();
+    }
+    if !ctx.multi_selection.is_empty()
+        && ctx.is_selected(id)
+        && ui.button("Batch rename...").clicked()
+    {
+        response = Some(FieldResponse::BatchRename);
+        ui.close();
+    }
+
+    ui.menu_button("Change type", |ui| {
+        for kind in FieldKind::SIMPLE {
+            if ui
+                .selectable_label(field.kind() == *kind, format!("{kind:?}"))
+                .clicked()
+            {
+                // The kind of the selected field is changed.
+                ctx.selection = Some(Selection {
+                    address,
+                    container_id: container,
+                    field_id: id,
+                });
+                ctx.multi_selection.clear();
+                response = Some(FieldResponse::ChangeKind(kind.clone()));
+                ui.close();
+            }
+        }
+    });
+
+    let freeze = if ctx.is_frozen(id) {
+        "Unfreeze value"
+    } else {
+        "Freeze value"
+    };
+    if ui.button(freeze).clicked() {
+        ctx.toggle_freeze(id, field.size());
+        ui.close();
+    }
+    ui.separator();
+
+    name_address_menu(ui, ctx.named_addresses, address);
+    if ui
+        .button("Find references")
+        .on_hover_text("Searches pointer fields of all classes for the address of the field")
+        .clicked()
+    {
+        response = Some(FieldResponse::FindReferences(address));
+        ui.close();
+    }
+    if ui.button("View in hex editor").clicked() {
+        response = Some(FieldResponse::ViewHex(address));
+        ui.close();
+    }
+    response = response.or(view_code_menu(ui, ctx, field, address));
+    // Colors are saved by offset, which sections share with their first field.
+    if let Some(class) = ctx
+        .class_list
+        .by_id(container)
+        .filter(|_| field.section_fields().is_none())
+    {
+        ui.menu_button("Row color", |ui| row_color_menu(ui, &class.colors, field));
+    }
+    ui.separator();
+
+    if ctx.recordings.get(&id).is_some_and(|r| r.active) {
+        if ui.button("Stop recording").clicked() {
+            ctx.recordings.get_mut(&id).unwrap().active = false;
+            ui.close();
+        }
+    } else if ui
+        .button("Start recording")
+        .on_hover_text("Reads the value every frame, previously recorded values are discarded")
+        .clicked()
+    {
+        ctx.recordings.insert(id, Recording::new(field, address));
+        ui.close();
+    }
+
+    let Some(recording) = ctx.recordings.get_mut(&id) else {
+        return response;
+    };
+
+    ui.separator();
+    ui.label(
+        RichText::new(format!("{} values recorded", recording.samples.len())).color(Color32::GRAY),
+    );
+
+    if ui.button("Export as CSV").clicked() {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title("Export recording")
+            .set_file_name(&format!("{}.csv", recording.name))
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        {
+            if let Err(e) = fs::write(&path, recording.to_csv()) {
+                ctx.toasts
+                    .error(format!("Failed to export the recording. {e}"));
+            }
+        }
+        ui.close();
+    }
+
+    if ui
+        .add_enabled(recording.is_numeric(), Button::new("Plot"))
+        .clicked()
+    {
+        recording.plotted = true;
+        ui.close();
+    }
+
+    if ui.button("Discard recording").clicked() {
+        ctx.recordings.remove(&id);
+        ui.close();
+    }
+
+    response
+}
+
+/// Zero or sign extends a value of up to 8 bytes.
+fn integer_value(bytes: &[u8], signed: bool) -> Option<u64> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return None;
+    }
+
+    let mut raw = [0; 8];
+    raw[..bytes.len()].copy_from_slice(bytes);
+    let shift = 64 - bytes.len() as u32 * 8;
+    let value = u64::from_ne_bytes(raw);
+    Some(if signed {
+        (((value << shift) as i64) >> shift) as u64
+    } else {
+        value
+    })
+}
+
+/// Memory keys of requests from the field menu, set for the id of the field.
+const RENAME_REQUEST: &str = "_rename_request";
+const COMMENT_REQUEST: &str = "_comment_request";
+
+/// Marks the secondary click of this frame as handled, so it doesn't open the menu of the row.
+pub fn claim_secondary_click(ui: &Ui) {
+    let frame = ui.ctx().cumulative_frame_nr();
+    ui.data_mut(|d| d.insert_temp(Id::new("_secondary_click_claimed"), frame));
+}
+
+fn secondary_click_claimed(ui: &Ui) -> bool {
+    let frame = ui.ctx().cumulative_frame_nr();
+    ui.data(|d| d.get_temp::<u64>(Id::new("_secondary_click_claimed"))) == Some(frame)
+}
+
+/// Colors the single field, other colors of the class are replaced with per field colors.
+fn row_color_menu(
+    ui: &mut Ui,
+    colors: &std::cell::RefCell<Option<ColorOverride>>,
+    field: &dyn Field,
+) {
+    let mut colors = colors.borrow_mut();
+    let mut color = colors
+        .as_ref()
+        .and_then(|c| c.color_of(field))
+        .unwrap_or(Color32::from_rgb(0x60, 0x40, 0xA0));
+
+    if color_picker_color32(ui, &mut color, Alpha::Opaque) {
+        if !matches!(*colors, Some(ColorOverride::PerField(_))) {
+            *colors = Some(ColorOverride::PerField(Default::default()));
+        }
+        if let Some(ColorOverride::PerField(colors)) = &mut *colors {
+            colors.insert(field.id(), color);
+        }
+    }
+
+    if let Some(ColorOverride::PerField(per_field)) = &mut *colors {
+        if ui
+            .add_enabled(per_field.contains_key(&field.id()), Button::new("Clear"))
+            .clicked()
+        {
+            per_field.remove(&field.id());
+            ui.close();
+        }
+    }
+}
+
+/// Disassembles the value of pointers and hex fields of pointer size.
+#[cfg(feature = "disasm")]
+fn view_code_menu(
+    ui: &mut Ui,
+    ctx: &InspectionContext,
+    field: &dyn Field,
+    address: usize,
+) -> Option<FieldResponse> {
+    use super::FieldKind;
+
+    let pointer_valued = match field.kind() {
+        FieldKind::Ptr | FieldKind::FuncPtr => true,
+        FieldKind::Unk32 | FieldKind::Unk64 => field.size() == ctx.process.pointer_size(),
+        _ => false,
+    };
+    if pointer_valued && ui.button("View as code").clicked() {
+        ui.close();
+        return Some(FieldResponse::ViewCode(ctx.process.read_pointer(address)));
+    }
+    None
+}
+
+#[cfg(not(feature = "disasm"))]
+fn view_code_menu(
+    _: &mut Ui,
+    _: &InspectionContext,
+    _: &dyn Field,
+    _: usize,
+) -> Option<FieldResponse> {
+    None
+}
+
+/// Draws the field with `[read error]` in place of its value, zeroed bytes would look valid.
+pub fn display_read_error(
+    ui: &mut Ui,
+    field: &dyn Field,
+    ctx: &mut InspectionContext,
+) -> Option<FieldResponse> {
+    ui.horizontal(|ui| {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, field, ctx, &mut job, true);
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(field.id());
+        }
+
+        if let Some(name) = field.name() {
+            ui.label(RichText::new(name).font(fid_m()).color(Color32::GRAY));
+        }
+        ui.label(
+            RichText::new("[read error]")
+                .font(fid_m())
+                .color(Color32::RED),
+        );
+    });
+
+    ctx.offset += field.size();
+    None
+}
 
 pub fn display_field_prelude(
-    egui_ctx: &Context,
+    ui: &mut Ui,
     field: &dyn Field,
     ctx: &mut InspectionContext,
     job: &mut LayoutJob,
     indent: bool,
 ) {
+    let egui_ctx = &ui.ctx().clone();
+
+    let frozen = ctx.is_frozen(field.id());
+    let (icon, color, hint) = if frozen {
+        ("🔒", Color32::LIGHT_BLUE, "Unfreeze value")
+    } else {
+        ("🔓", Color32::from_gray(60), "Freeze value")
+    };
+    if ui
+        .add(Label::new(RichText::new(icon).color(color)).sense(Sense::click()))
+        .on_hover_text(hint)
+        .clicked()
+    {
+        ctx.toggle_freeze(field.id(), field.size());
+    }
+    let background = if frozen {
+        FROZEN_BACKGROUND
+    } else {
+        Color32::TRANSPARENT
+    };
+
     // Add indent to match collapsing header arrow width if requested
     if indent {
         job.append("", egui_ctx.style().spacing.indent, {
-            create_text_format(false, Color32::TRANSPARENT)
+            create_text_format(ctx.theme, false, Color32::TRANSPARENT)
         });
     }
-    job.append(&format!("{:04X}", ctx.offset), 0., {
-        let mut tf = create_text_format(ctx.is_selected(field.id()), Color32::KHAKI);
-        // Highlight unaligned fields
-        if !ctx.offset.is_multiple_of(8) {
-            tf.underline = Stroke::new(1., Color32::RED);
-        }
+    // egui-winit turns Ctrl-C into a copy event instead of a key press. Text edits copy their
+    // own selection.
+    let copy = !egui_ctx.wants_keyboard_input()
+        && egui_ctx.input(|i| i.key_pressed(Key::C) || i.events.contains(&Event::Copy));
+    if copy
+        && egui_ctx.input(|i| i.modifiers.matches_exact(Modifiers::CTRL))
+        && ctx.is_primary_selected(field.id())
+    {
+        egui_ctx.copy_text(format!("{:X}", ctx.address + ctx.offset));
+    }
 
-        if egui_ctx.input(|i| i.key_pressed(Key::C))
-            && egui_ctx.input(|i| i.modifiers.matches_exact(Modifiers::CTRL))
-            && ctx.is_selected(field.id())
-        {
-            egui_ctx.copy_text(format!("{:X}", ctx.address + ctx.offset));
-        }
+    if copy
+        && egui_ctx.input(|i| {
+            i.modifiers
+                .matches_exact(Modifiers::CTRL | Modifiers::SHIFT)
+        })
+        && ctx.is_primary_selected(field.id())
+    {
+        let mut buf = [0; 8];
+        ctx.process.read(ctx.address + ctx.offset, &mut buf[..]);
+        egui_ctx.copy_text(format!("{:X}", usize::from_ne_bytes(buf)));
+    }
 
-        if egui_ctx.input(|i| i.key_pressed(Key::C))
-            && egui_ctx.input(|i| {
-                i.modifiers
-                    .matches_exact(Modifiers::CTRL | Modifiers::SHIFT)
-            })
-            && ctx.is_selected(field.id())
-        {
-            let mut buf = [0; 8];
-            ctx.process.read(ctx.address + ctx.offset, &mut buf[..]);
-            egui_ctx.copy_text(format!("{:X}", usize::from_ne_bytes(buf)));
-        }
+    if ctx.columns.offset {
+        job.append(&format!("{:04X}", ctx.offset), 0., {
+            let mut tf = create_text_format(
+                ctx.theme,
+                ctx.is_selected(field.id()),
+                alignment_color(ctx.theme, ctx.offset, field.size()),
+            );
+            tf.background = background;
+            tf
+        });
+    }
+    if ctx.columns.address {
+        let leading_space = if ctx.columns.offset { 8. } else { 0. };
+        job.append(
+            &format!("{:012X}", ctx.address + ctx.offset),
+            leading_space,
+            {
+                let mut tf =
+                    create_text_format(ctx.theme, ctx.is_selected(field.id()), ctx.theme.address);
+                tf.background = background;
+                tf
+            },
+        );
+    }
+    if let Some(name) = ctx.named_addresses.name(ctx.address + ctx.offset) {
+        job.append(&format!("({name})"), 4., {
+            let mut tf = create_text_format(
+                ctx.theme,
+                ctx.is_selected(field.id()),
+                ctx.theme.named_address,
+            );
+            tf.background = background;
+            tf
+        });
+    }
+}
+
+/// Text edit that names `address`, shown in context menus.
+/// Alignment the field would get in a C struct, that of its largest primitive up to 8 bytes.
+fn natural_alignment(size: usize) -> usize {
+    1 << size.trailing_zeros().min(3)
+}
 
-        tf
+pub fn is_naturally_aligned(offset: usize, size: usize) -> bool {
+    offset.is_multiple_of(natural_alignment(size))
+}
+
+/// Theme's offset color for aligned fields, yellow for fields at least 2 byte aligned and red for
+/// the rest.
+fn alignment_color(theme: &Theme, offset: usize, size: usize) -> Color32 {
+    if is_naturally_aligned(offset, size) {
+        theme.offset
+    } else if offset.is_multiple_of(2) {
+        Color32::YELLOW
+    } else {
+        Color32::RED
+    }
+}
+
+pub fn name_address_menu(ui: &mut Ui, names: &mut NamedAddressRegistry, address: usize) {
+    ui.menu_button("Name this address", |ui| {
+        let id = Id::new(("_address_name", address));
+        let mut name = ui.data_mut(|d| {
+            d.get_temp::<String>(id)
+                .unwrap_or_else(|| names.name(address).unwrap_or_default().to_owned())
+        });
+
+        let r = ui.add(
+            TextEdit::singleline(&mut name)
+                .hint_text("i.e. Player::vtable")
+                .desired_width(200.),
+        );
+        r.request_focus();
+        ui.label(RichText::new("Enter to save, empty name removes it").color(Color32::GRAY));
+
+        if r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+            names.set(address, &name);
+            ui.data_mut(|d| d.remove::<String>(id));
+            ui.close();
+        } else {
+            ui.data_mut(|d| d.insert_temp(id, name));
+        }
     });
-    job.append(
-        &format!("{:012X}", ctx.address + ctx.offset),
-        8.,
-        create_text_format(ctx.is_selected(field.id()), Color32::LIGHT_GREEN),
-    );
 }
 
 pub fn display_field_value<T: Display>(
@@ -64,7 +676,7 @@ pub fn display_field_value<T: Display>(
     // the value returned would be used as initial value for
     // text edit box.
     mut displayed_value: impl FnMut(bool) -> T,
-    write_new_value: impl FnOnce(&str) -> bool,
+    write_new_value: impl FnOnce(&str, &mut InspectionContext) -> bool,
 ) {
     let editing_value = &mut *state.editing_state.borrow_mut();
     if let Some(EditingState {
@@ -76,7 +688,7 @@ pub fn display_field_value<T: Display>(
         if *address == ctx.address + ctx.offset {
             let mut w = buf
                 .chars()
-                .map(|c| ui.fonts(|f| f.glyph_width(&FID_M, c)))
+                .map(|c| ui.fonts(|f| f.glyph_width(&fid_m(), c)))
                 .sum::<f32>();
             if w > 80. {
                 w += 10.
@@ -93,10 +705,14 @@ pub fn display_field_value<T: Display>(
             if r.clicked_elsewhere() {
                 *editing_value = None;
             } else if r.lost_focus() {
-                if !write_new_value(buf) {
+                if !write_new_value(buf, ctx) {
                     ctx.toasts.error("Invalid value");
                     *should_focus = true;
                 } else {
+                    // Keep the new value instead of restoring the frozen one.
+                    if let Some(frozen) = ctx.frozen.get_mut(&field.id()) {
+                        ctx.process.read(frozen.address, &mut frozen.bytes);
+                    }
                     *editing_value = None;
                 }
             }
@@ -109,11 +725,12 @@ pub fn display_field_value<T: Display>(
     job.append(
         &displayed_value(false).to_string(),
         0.,
-        create_text_format(ctx.is_selected(field.id()), color),
+        create_text_format(ctx.theme, ctx.is_selected(field.id()), color),
     );
 
     let r = ui.add(Label::new(job).sense(Sense::click()));
     if r.secondary_clicked() {
+        claim_secondary_click(ui);
         *editing_value = Some(EditingState::new(
             ctx.address + ctx.offset,
             displayed_value(true).to_string(),
@@ -123,6 +740,44 @@ pub fn display_field_value<T: Display>(
     }
 }
 
+/// Shows how much the value changed since the last frame if deltas are turned on.
+/// `delta` is called with bytes of the previous value and returns the direction and size of the change.
+pub fn display_field_delta(
+    ui: &mut Ui,
+    ctx: &mut InspectionContext,
+    field_id: FieldId,
+    bytes: &[u8],
+    delta: impl FnOnce(&[u8]) -> (Ordering, String),
+) {
+    let Some(previous_values) = ctx.previous_values.as_deref_mut() else {
+        return;
+    };
+    let Some(previous) = previous_values.insert(field_id, bytes.to_vec()) else {
+        return;
+    };
+    // Kind of the field has changed.
+    if previous.len() != bytes.len() {
+        return;
+    }
+
+    let (direction, text) = delta(&previous);
+    let color = match direction {
+        Ordering::Greater => Color32::GREEN,
+        Ordering::Less => Color32::RED,
+        Ordering::Equal => Color32::GRAY,
+    };
+
+    ui.add_space(4.);
+    ui.add(
+        Label::new(
+            RichText::new(format!("Δ{text}"))
+                .font(FontId::monospace(11.))
+                .color(color),
+        )
+        .selectable(false),
+    );
+}
+
 pub fn display_field_name(
     field: &dyn Field,
     ui: &mut Ui,
@@ -139,14 +794,14 @@ pub fn display_field_name(
         let name = &mut *state.name.borrow_mut();
         let w = name
             .chars()
-            .map(|c| ui.fonts(|f| f.glyph_width(&FID_M, c)))
+            .map(|c| ui.fonts(|f| f.glyph_width(&fid_m(), c)))
             .sum::<f32>()
             .max(80.)
             + 32.;
 
         let r = TextEdit::singleline(name)
             .desired_width(w)
-            .font(FontSelection::FontId(FID_M))
+            .font(FontSelection::FontId(fid_m()))
             .show(ui)
             .response;
 
@@ -176,18 +831,77 @@ pub fn display_field_name(
         job.append(
             state.name.borrow().as_ref(),
             0.,
-            create_text_format(ctx.is_selected(field.id()), color),
+            create_text_format(ctx.theme, ctx.is_selected(field.id()), color),
         );
 
         let r = ui.add(Label::new(job).sense(Sense::click()));
-        if r.secondary_clicked() {
+        let take_request = |key| {
+            ui.data_mut(|d| d.remove_temp::<bool>(ctx.current_id.with(key)))
+                .unwrap_or_default()
+        };
+        let mut edit_comment = r.double_clicked() || take_request(COMMENT_REQUEST);
+        let hotkey = ctx.rename_selected && ctx.is_primary_selected(field.id());
+        if r.secondary_clicked() || take_request(RENAME_REQUEST) || hotkey {
+            claim_secondary_click(ui);
             *state.saved_name.borrow_mut() = state.name.borrow().clone();
             state.renaming_id.set(Some(ctx.current_id));
             state.focused_id.set(Some(ctx.current_id));
-        } else if r.clicked() {
+        } else if r.clicked() && !edit_comment {
             ctx.select(field.id());
         }
+
+        if let Some(comment) = state.comment.borrow().as_deref() {
+            edit_comment |= ui
+                .add(Label::new(RichText::new("💬").color(Color32::GRAY)).sense(Sense::click()))
+                .on_hover_text(comment)
+                .double_clicked();
+        }
+
+        display_comment_popup(ui, ctx, state, &r, edit_comment);
+    }
+}
+
+fn display_comment_popup(
+    ui: &mut Ui,
+    ctx: &InspectionContext,
+    state: &NamedState,
+    anchor: &Response,
+    open: bool,
+) {
+    let popup_id = ctx.current_id.with("_comment_popup");
+    if open {
+        *state.comment_buf.borrow_mut() = state.comment.borrow().clone().unwrap_or_default();
+        Popup::open_id(ui.ctx(), popup_id);
     }
+
+    Popup::from_response(anchor)
+        .id(popup_id)
+        .open_memory(None)
+        .close_behavior(PopupCloseBehavior::CloseOnClickOutside)
+        .show(|ui| {
+            let buf = &mut *state.comment_buf.borrow_mut();
+            let r = ui.add(
+                TextEdit::multiline(buf)
+                    .hint_text("Comment")
+                    .desired_rows(3)
+                    .desired_width(240.),
+            );
+            if open {
+                r.request_focus();
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    let comment = buf.trim();
+                    *state.comment.borrow_mut() = (!comment.is_empty()).then(|| comment.to_owned());
+                    Popup::close_id(ui.ctx(), popup_id);
+                }
+
+                if ui.button("Cancel").clicked() {
+                    Popup::close_id(ui.ctx(), popup_id);
+                }
+            });
+        });
 }
 
 pub fn allocate_padding(mut n: usize) -> Vec<Box<dyn Field>> {
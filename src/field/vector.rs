@@ -0,0 +1,248 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+};
+use crate::{context::InspectionContext, generator::Generator};
+use eframe::{
+    egui::{collapsing_header::CollapsingState, Grid, Label, Sense, TextFormat, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+
+pub type Vec2Field = VectorField<2>;
+pub type Vec3Field = VectorField<3>;
+pub type Vec4Field = VectorField<4>;
+
+/// `N` consecutive `f32`s.
+pub struct VectorField<const N: usize> {
+    id: FieldId,
+    state: NamedState,
+}
+
+impl<const N: usize> VectorField<N> {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+        }
+    }
+}
+
+impl<const N: usize> Field for VectorField<N> {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        N * 4
+    }
+
+    fn kind(&self) -> FieldKind {
+        match N {
+            2 => FieldKind::Vec2,
+            3 => FieldKind::Vec3,
+            4 => FieldKind::Vec4,
+            _ => unreachable!(),
+        }
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let address = ctx.address + ctx.offset;
+        let Some(values) = read_floats::<N>(ctx, self.id, address) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        let r = ui.horizontal(|ui| {
+            let mut job = LayoutJob::default();
+            display_field_prelude(ui, self, ctx, &mut job, true);
+
+            if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                ctx.select(self.id);
+            }
+
+            display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_RED);
+            display_field_value(
+                self,
+                ui,
+                ctx,
+                &self.state,
+                ctx.theme.foreground,
+                |v| {
+                    let values = values.map(|f| f.to_string()).join(", ");
+                    if v {
+                        values
+                    } else {
+                        format!("({values})")
+                    }
+                },
+                |new, ctx| {
+                    let Some(values) = parse_floats::<N>(new) else {
+                        return false;
+                    };
+
+                    let buf = values
+                        .iter()
+                        .flat_map(|f| f.to_ne_bytes())
+                        .collect::<Vec<_>>();
+                    ctx.write(address, &buf);
+                    true
+                },
+            );
+        });
+
+        if N >= 3 {
+            let magnitude = values.iter().map(|f| f * f).sum::<f32>().sqrt();
+            r.response.on_hover_text(format!("Magnitude: {magnitude}"));
+        }
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
+
+/// 4x4 matrix of `f32`s.
+pub struct Mat4Field {
+    id: FieldId,
+    state: NamedState,
+}
+
+impl Mat4Field {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+        }
+    }
+}
+
+impl Field for Mat4Field {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        64
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Mat4
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some(values) = read_floats::<16>(ctx, self.id, ctx.address + ctx.offset) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        let state = CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false);
+        state
+            .show_header(ui, |ui| {
+                let mut job = LayoutJob::default();
+                display_field_prelude(ui, self, ctx, &mut job, false);
+                job.append(" ", 0., TextFormat::default());
+
+                if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                    ctx.select(self.id);
+                }
+
+                display_field_name(self, ui, ctx, &self.state, Color32::LIGHT_RED);
+
+                let mut job = LayoutJob::default();
+                job.append(
+                    "[Mat4]",
+                    4.,
+                    create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+                );
+                if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+                    ctx.select(self.id);
+                }
+            })
+            .body(|ui| {
+                Grid::new(ctx.current_id.with("mat4"))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for row in values.chunks(4) {
+                            for value in row {
+                                ui.monospace(format!("{value:.4}"));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), FieldKind::Mat4, None);
+    }
+}
+
+fn read_floats<const N: usize>(
+    ctx: &InspectionContext,
+    id: FieldId,
+    address: usize,
+) -> Option<[f32; N]> {
+    let mut buf = vec![0; N * 4];
+    if !ctx.read(id, address, &mut buf) {
+        return None;
+    }
+
+    let mut values = [0.; N];
+    for (value, bytes) in values.iter_mut().zip(buf.chunks_exact(4)) {
+        *value = f32::from_ne_bytes(bytes.try_into().unwrap());
+    }
+    Some(values)
+}
+
+/// Parses comma separated floats, optionally wrapped in parentheses.
+fn parse_floats<const N: usize>(s: &str) -> Option<[f32; N]> {
+    let s = s.trim();
+    let s = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or(s);
+
+    let mut values = [0.; N];
+    let mut parts = s.split(',');
+    for value in values.iter_mut() {
+        *value = parts.next()?.trim().parse().ok()?;
+    }
+
+    parts.next().is_none().then_some(values)
+}
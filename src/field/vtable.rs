@@ -0,0 +1,172 @@
+use super::{
+    create_text_format, display_field_name, display_field_prelude, display_field_value,
+    display_read_error, next_id, CodegenData, Field, FieldId, FieldKind, FieldResponse, NamedState,
+    PointerWidth,
+};
+use crate::{class::ClassList, context::InspectionContext, fonts::fid_m, generator::Generator};
+use eframe::{
+    egui::{collapsing_header::CollapsingState, Label, RichText, Sense, TextFormat, Ui},
+    epaint::{text::LayoutJob, Color32},
+};
+use std::cell::Cell;
+
+/// Number of functions displayed for new vtable fields.
+pub const DEFAULT_VTABLE_COUNT: usize = 16;
+
+/// Pointer to a table of virtual functions.
+pub struct VtableField {
+    id: FieldId,
+    state: NamedState,
+    count: Cell<usize>,
+    width: PointerWidth,
+}
+
+impl VtableField {
+    pub fn new(name: String, count: usize, pointer_size: usize) -> Self {
+        Self {
+            id: next_id(),
+            state: NamedState::new(name),
+            count: count.into(),
+            width: PointerWidth::new(pointer_size),
+        }
+    }
+
+    fn show_header(&self, ui: &mut Ui, ctx: &mut InspectionContext, table: usize) {
+        let mut job = LayoutJob::default();
+        display_field_prelude(ui, self, ctx, &mut job, false);
+        job.append(" ", 0., TextFormat::default());
+
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        display_field_name(self, ui, ctx, &self.state, Color32::BROWN);
+
+        let mut job = LayoutJob::default();
+        job.append(
+            &format!("-> {table:X}"),
+            4.,
+            create_text_format(ctx.theme, ctx.is_selected(self.id), Color32::YELLOW),
+        );
+        if let Some((module, rva)) = ctx.process.address_to_rva(table) {
+            job.append(
+                &format!("({module}+{rva:X})"),
+                4.,
+                create_text_format(ctx.theme, ctx.is_selected(self.id), ctx.theme.dim),
+            );
+        }
+        if ui.add(Label::new(job).sense(Sense::click())).clicked() {
+            ctx.select(self.id);
+        }
+
+        ui.add_space(4.);
+
+        display_field_value(
+            self,
+            ui,
+            ctx,
+            &self.state,
+            Color32::GRAY,
+            |v| {
+                if v {
+                    self.count.get().to_string()
+                } else {
+                    format!("[{}]", self.count.get())
+                }
+            },
+            |new, _| match new.parse::<usize>() {
+                Ok(count) if count > 0 => {
+                    self.count.set(count);
+                    true
+                }
+                _ => false,
+            },
+        );
+    }
+
+    fn show_body(&self, ui: &mut Ui, ctx: &mut InspectionContext, table: usize) {
+        if !ctx.process.can_read(table) {
+            ui.label(
+                RichText::new(format!("Can't read memory at address {table:#X}"))
+                    .color(Color32::RED)
+                    .font(fid_m()),
+            );
+            return;
+        }
+
+        let size = ctx.process.pointer_size();
+        for i in 0..self.count.get() {
+            let function = ctx.process.read_pointer(table + i * size);
+
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("vfunc_{i}: 0x{function:X}"))
+                        .font(fid_m())
+                        .color(Color32::LIGHT_GRAY),
+                );
+
+                if let Some(symbol) = ctx.process.resolve_symbol(function) {
+                    let r = ui.label(
+                        RichText::new(format!("({symbol})"))
+                            .font(fid_m())
+                            .color(Color32::GRAY),
+                    );
+                    if symbol.symbol.is_some() {
+                        r.on_hover_text(symbol.details());
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl Field for VtableField {
+    fn id(&self) -> FieldId {
+        self.id
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.state.name.borrow().clone())
+    }
+
+    fn comment(&self) -> Option<String> {
+        self.state.comment.borrow().clone()
+    }
+
+    fn set_comment(&self, comment: Option<String>) {
+        *self.state.comment.borrow_mut() = comment;
+    }
+
+    fn set_name(&self, name: String) {
+        *self.state.name.borrow_mut() = name;
+    }
+
+    fn size(&self) -> usize {
+        self.width.get()
+    }
+
+    fn refresh_size(&self, classes: &ClassList) -> bool {
+        self.width.refresh(classes)
+    }
+
+    fn kind(&self) -> FieldKind {
+        FieldKind::Vtable(self.count.get())
+    }
+
+    fn draw(&self, ui: &mut Ui, ctx: &mut InspectionContext) -> Option<FieldResponse> {
+        let Some(table) = ctx.read_pointer(self.id, ctx.address + ctx.offset) else {
+            return display_read_error(ui, self, ctx);
+        };
+
+        CollapsingState::load_with_default_open(ui.ctx(), ctx.current_id, false)
+            .show_header(ui, |ui| self.show_header(ui, ctx, table))
+            .body(|ui| self.show_body(ui, ctx, table));
+
+        ctx.offset += self.size();
+        None
+    }
+
+    fn codegen(&self, generator: &mut dyn Generator, _: &CodegenData) {
+        generator.add_field(self.state.name.borrow().as_str(), self.kind(), None);
+    }
+}
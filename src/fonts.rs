@@ -0,0 +1,85 @@
+use crate::config::YClassConfig;
+use eframe::{
+    egui::{Context, FontData, FontDefinitions},
+    epaint::{FontFamily, FontId},
+};
+use eyre::{bail, eyre, Result};
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, LazyLock,
+    },
+};
+
+pub const DEFAULT_INSPECTOR_FONT_SIZE: f32 = 16.;
+
+/// Monospaced font of the inspector, a custom font file is put before the bundled fonts.
+static INSPECTOR_FAMILY: LazyLock<FontFamily> =
+    LazyLock::new(|| FontFamily::Name("inspector".into()));
+// Bits of the `f32` size in logical points, `egui` scales it by the DPI.
+static INSPECTOR_FONT_SIZE: AtomicU32 = AtomicU32::new(DEFAULT_INSPECTOR_FONT_SIZE.to_bits());
+
+/// Monospaced font id of the inspector.
+pub fn fid_m() -> FontId {
+    inspector_font(f32::from_bits(INSPECTOR_FONT_SIZE.load(Ordering::Relaxed)))
+}
+
+pub fn inspector_font(size: f32) -> FontId {
+    FontId::new(size, INSPECTOR_FAMILY.clone())
+}
+
+pub fn set_inspector_font_size(size: f32) {
+    INSPECTOR_FONT_SIZE.store(size.to_bits(), Ordering::Relaxed);
+}
+
+/// Registers the bundled fonts and the inspector font from the config.
+pub fn load_fonts(ctx: &Context, config: &YClassConfig) -> Result<()> {
+    set_inspector_font_size(
+        config
+            .inspector_font_size
+            .unwrap_or(DEFAULT_INSPECTOR_FONT_SIZE),
+    );
+
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.insert(
+        "roboto-mono".into(),
+        FontData::from_static(include_bytes!("../fonts/RobotoMono-Regular.ttf")).into(),
+    );
+    let monospace = fonts.families.get_mut(&FontFamily::Monospace).unwrap();
+    monospace.insert(0, "roboto-mono".into());
+    let mut inspector = monospace.clone();
+
+    // The family is registered anyway, the inspector falls back to the bundled font.
+    let mut result = Ok(());
+    if let Some(path) = config.inspector_font_family.as_deref() {
+        match read_font(path) {
+            Ok(data) => {
+                let name = path.display().to_string();
+                fonts
+                    .font_data
+                    .insert(name.clone(), Arc::new(FontData::from_owned(data)));
+                inspector.insert(0, name);
+            }
+            Err(e) => result = Err(e),
+        }
+    }
+    fonts.families.insert(INSPECTOR_FAMILY.clone(), inspector);
+    ctx.set_fonts(fonts);
+
+    result
+}
+
+fn read_font(path: &Path) -> Result<Vec<u8>> {
+    let data =
+        fs::read(path).map_err(|e| eyre!("Failed to read the font `{}`. {e}", path.display()))?;
+    // `egui` panics on invalid font data, this at least skips files that aren't fonts.
+    if !matches!(
+        data.get(..4),
+        Some([0, 1, 0, 0] | b"OTTO" | b"true" | b"ttcf")
+    ) {
+        bail!("`{}` isn't a TrueType or OpenType font", path.display());
+    }
+    Ok(data)
+}
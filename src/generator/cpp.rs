@@ -1,51 +1,240 @@
-use super::Generator;
-use crate::field::FieldKind;
+use super::{is_32bit_pointer, Generator};
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, BitSlice, FieldKind},
+};
+use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, mem::take};
 
+/// Type names used for vector and matrix fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CppMathTypes {
+    pub vec2: String,
+    pub vec3: String,
+    pub vec4: String,
+    pub mat4: String,
+}
+
+impl Default for CppMathTypes {
+    fn default() -> Self {
+        Self::glm()
+    }
+}
+
+impl CppMathTypes {
+    pub fn glm() -> Self {
+        Self {
+            vec2: "glm::vec2".into(),
+            vec3: "glm::vec3".into(),
+            vec4: "glm::vec4".into(),
+            mat4: "glm::mat4".into(),
+        }
+    }
+
+    pub fn directx() -> Self {
+        Self {
+            vec2: "DirectX::XMFLOAT2".into(),
+            vec3: "DirectX::XMFLOAT3".into(),
+            vec4: "DirectX::XMFLOAT4".into(),
+            mat4: "DirectX::XMFLOAT4X4".into(),
+        }
+    }
+}
+
 pub struct CppGenerator {
     predecls: String,
     main: String,
+    math_types: CppMathTypes,
+    comment: Option<String>,
     offset: usize,
     last_offset: usize,
+    pointer_size: usize,
 }
 
-impl Default for CppGenerator {
-    fn default() -> Self {
+impl CppGenerator {
+    pub fn new(pointer_size: usize) -> Self {
         Self {
             predecls: format!(
                 "// Generated by YClass {}\n// Made by @ItsEthra\n\n#include <cstdint>\n\n",
                 env!("YCLASS_VERSION")
             ),
             main: "".to_owned(),
+            math_types: CppMathTypes::default(),
+            comment: None,
             offset: 0,
             last_offset: 0,
+            pointer_size,
+        }
+    }
+
+    pub fn with_math_types(math_types: CppMathTypes, pointer_size: usize) -> Self {
+        Self {
+            math_types,
+            ..Self::new(pointer_size)
+        }
+    }
+
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            self.main += &format!(
+                "    char _pad0x{:x}[0x{:x}];\n",
+                self.offset,
+                self.offset - self.last_offset
+            );
+        }
+    }
+
+    fn add_pending_comment(&mut self) {
+        if let Some(comment) = self.comment.take() {
+            for line in comment.lines() {
+                self.main += &format!("    // {line}\n");
+            }
+        }
+    }
+
+    fn add_bitfield(&mut self, name: &str, size: usize, mut slices: Vec<BitSlice>) {
+        let ty = unsigned_type(size);
+        slices.sort_by_key(|s| s.bit_offset);
+
+        self.main += &format!("    // {name}\n");
+        let mut bit = 0;
+        for BitSlice {
+            name,
+            bit_offset,
+            bit_width,
+        } in slices
+        {
+            if bit_offset < bit {
+                self.main += &format!("    // {name} : {bit_width} overlaps previous bits\n");
+                continue;
+            }
+
+            if bit_offset > bit {
+                self.main += &format!("    {ty} : {};\n", bit_offset - bit);
+            }
+            self.main += &format!("    {ty} {name} : {bit_width};\n");
+            bit = bit_offset + bit_width;
+        }
+
+        if (bit as usize) < size * 8 {
+            self.main += &format!("    {ty} : {};\n", size * 8 - bit as usize);
         }
     }
 }
 
 impl Generator for CppGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        self.main += &format!(
+            "enum class {} : {} {{\n",
+            def.name,
+            if def.flags {
+                unsigned_type(def.size)
+            } else {
+                enum_underlying_type(def.size)
+            }
+        );
+        for (value, name) in def.variants.iter() {
+            self.main += &format!("    {name} = {},\n", def.format_value(*value));
+        }
+        self.main += "};\n\n";
+    }
+
     fn begin_class(&mut self, name: &str) {
         self.predecls += &format!("class {name};\n");
         self.main += &format!("class {name} {{\npublic:\n");
     }
 
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        // Replaces the opening of the class written by `begin_class`.
+        self.main.truncate(self.main.len() - " {\npublic:\n".len());
+        self.main += &format!(" : public {name} {{\npublic:\n");
+
+        self.offset = size;
+        self.last_offset = size;
+    }
+
     fn end_class(&mut self) {
         self.main += "};\n\n";
         self.offset = 0;
         self.last_offset = 0;
     }
 
+    fn add_comment(&mut self, comment: &str) {
+        self.comment = Some(comment.to_owned());
+    }
+
     fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
-        let size = kind.size();
-        if self.offset != self.last_offset {
-            self.main += &format!(
-                "    char _pad0x{:x}[0x{:x}];\n",
-                self.offset,
-                self.offset - self.last_offset
-            );
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = kind_to_type64(inner.clone(), None, &self.math_types);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(&element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+        self.add_pending_comment();
+
+        match &kind {
+            FieldKind::PointerChain(offsets) => {
+                self.main += &format!("    // {}\n", format_pointer_chain(offsets))
+            }
+            FieldKind::FuncPtr => self.main += "    // function pointer\n",
+            FieldKind::Flags(_) => {
+                if let Some(flags) = metadata {
+                    self.main += &format!("    // {flags} flags\n")
+                }
+            }
+            FieldKind::RelPtr32 | FieldKind::RelPtr64 => {
+                self.main += &format!("    // {}\n", format_relative_pointer(metadata))
+            }
+            FieldKind::StdString(abi) => self.main += &format!("    // {} layout\n", abi.label()),
+            FieldKind::Timestamp(format) => {
+                self.main += &format!("    // {} timestamp\n", format.label())
+            }
+            FieldKind::Color(layout) => {
+                self.main += &format!("    // {} color\n", layout.label());
+                if layout.is_float() {
+                    return self.add_array_field(name, FieldKind::F32, 4, None);
+                }
+            }
+            _ => {}
+        }
+
+        match kind {
+            FieldKind::Bitfield(size, slices) if !slices.is_empty() => {
+                self.add_bitfield(name, size, slices)
+            }
+            FieldKind::InlineStr(len) => self.main += &format!("    char {name}[{len}];\n"),
+            FieldKind::InlineWStr(len) => self.main += &format!("    wchar_t {name}[{len}];\n"),
+            kind => {
+                self.main += &format!(
+                    "    {} {name};\n",
+                    kind_to_type(kind, metadata, &self.math_types, self.pointer_size)
+                )
+            }
         }
 
-        self.main += &format!("    {} {name};\n", kind_to_type(kind, metadata));
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        let size = kind.size(self.pointer_size) * count;
+        self.add_padding();
+        self.add_pending_comment();
+
+        self.main += &format!(
+            "    {} {name}[{count}];\n",
+            kind_to_type(kind, metadata, &self.math_types, self.pointer_size)
+        );
 
         self.offset += size;
         self.last_offset = self.offset;
@@ -55,12 +244,40 @@ impl Generator for CppGenerator {
         self.offset += offset;
     }
 
+    fn begin_section(&mut self, name: &str) {
+        self.add_padding();
+        self.last_offset = self.offset;
+        self.add_pending_comment();
+        self.main += &format!("    // section: {name}\n");
+    }
+
     fn finilize(&mut self) -> String {
         take(&mut self.predecls) + "\n" + &take(&mut self.main)
     }
 }
 
-fn kind_to_type(kind: FieldKind, metadata: Option<&str>) -> Cow<'static, str> {
+fn kind_to_type(
+    kind: FieldKind,
+    metadata: Option<&str>,
+    math_types: &CppMathTypes,
+    pointer_size: usize,
+) -> Cow<'static, str> {
+    if is_32bit_pointer(&kind, pointer_size) {
+        return format!(
+            "uint32_t /* {} */",
+            kind_to_type64(kind, metadata, math_types)
+        )
+        .into();
+    }
+
+    kind_to_type64(kind, metadata, math_types)
+}
+
+fn kind_to_type64(
+    kind: FieldKind,
+    metadata: Option<&str>,
+    math_types: &CppMathTypes,
+) -> Cow<'static, str> {
     match kind {
         FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => unreachable!(),
         FieldKind::I8 => "int8_t".into(),
@@ -74,8 +291,52 @@ fn kind_to_type(kind: FieldKind, metadata: Option<&str>) -> Cow<'static, str> {
         FieldKind::F32 => "float".into(),
         FieldKind::F64 => "double".into(),
         FieldKind::Ptr => format!("{}*", metadata.unwrap_or("void")).into(),
+        FieldKind::Vtable(_) => "void*".into(),
+        FieldKind::Embedded(_) => metadata.unwrap_or_default().to_owned().into(),
+        FieldKind::PointerChain(_) | FieldKind::FuncPtr => "void*".into(),
+        FieldKind::StdString(_) => "std::string".into(),
+        FieldKind::Guid(_) => "GUID".into(),
+        FieldKind::Timestamp(format) => kind_to_type64(format.raw_kind(), None, math_types),
+        FieldKind::Color(_) => "uint32_t".into(),
+        // Declared with the name by `add_field`.
+        FieldKind::InlineStr(_) | FieldKind::InlineWStr(_) => unreachable!(),
+        // Split into members by `add_field`.
+        FieldKind::DynamicArray(_) | FieldKind::StdVector(_) => unreachable!(),
+        // Fields of sections are added one by one.
+        FieldKind::Section(_) => unreachable!(),
+        FieldKind::RelPtr32 => "int32_t".into(),
+        FieldKind::RelPtr64 => "int64_t".into(),
         FieldKind::StrPtr => "const char*".into(),
         FieldKind::WStrPtr => "const wchar*".into(),
         FieldKind::Bool => "bool".into(),
+        FieldKind::Vec2 => math_types.vec2.clone().into(),
+        FieldKind::Vec3 => math_types.vec3.clone().into(),
+        FieldKind::Vec4 => math_types.vec4.clone().into(),
+        FieldKind::Mat4 => math_types.mat4.clone().into(),
+        FieldKind::Enum(size) => match metadata {
+            Some(name) => name.to_owned().into(),
+            None => enum_underlying_type(size).into(),
+        },
+        FieldKind::Bitfield(size, _) | FieldKind::Flags(size) => unsigned_type(size).into(),
+        // Arrays are generated by `add_array_field`.
+        FieldKind::Array(..) => unreachable!(),
+    }
+}
+
+fn enum_underlying_type(size: usize) -> &'static str {
+    match size {
+        1 => "int8_t",
+        2 => "int16_t",
+        4 => "int32_t",
+        _ => "int64_t",
+    }
+}
+
+fn unsigned_type(size: usize) -> &'static str {
+    match size {
+        1 => "uint8_t",
+        2 => "uint16_t",
+        4 => "uint32_t",
+        _ => "uint64_t",
     }
 }
@@ -0,0 +1,296 @@
+use super::{is_32bit_pointer, Generator};
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, BitSlice, FieldKind},
+};
+use std::{borrow::Cow, mem::take};
+
+pub struct CSharpGenerator {
+    text: String,
+    offset: usize,
+    last_offset: usize,
+    pointer_size: usize,
+}
+
+impl CSharpGenerator {
+    pub fn new(pointer_size: usize) -> Self {
+        Self {
+            text: format!(
+                "// Generated by YClass {}\n// Made by @ItsEthra\n\nusing System.Runtime.InteropServices;\n\n",
+                env!("YCLASS_VERSION")
+            ),
+            offset: 0,
+            last_offset: 0,
+            pointer_size,
+        }
+    }
+
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            self.text += &format!(
+                "    [FieldOffset(0x{:x})] private fixed byte _pad0x{:x}[0x{:x}];\n",
+                self.last_offset,
+                self.last_offset,
+                self.offset - self.last_offset
+            );
+        }
+    }
+
+    fn add_bitfield(&mut self, name: &str, size: usize, slices: &[BitSlice]) {
+        let ty = unsigned_type(size);
+        let suffix = if size == 8 { "ul" } else { "u" };
+        self.text += &format!(
+            "    [FieldOffset(0x{:x})] public {ty} {name};\n",
+            self.offset
+        );
+
+        for slice in slices {
+            let (shift, mask) = (slice.bit_offset, slice.mask());
+            self.text += &format!(
+                "    public {ty} {name}_{} {{ get => ({ty})(({name} >> {shift}) & {mask:#x}{suffix}); set => {name} = ({ty})(({name} & ~({mask:#x}{suffix} << {shift})) | ((value & {mask:#x}{suffix}) << {shift})); }}\n",
+                slice.name
+            );
+        }
+    }
+}
+
+impl Generator for CSharpGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        self.text += &format!(
+            "public enum {} : {}\n{{\n",
+            def.name,
+            if def.flags {
+                unsigned_type(def.size)
+            } else {
+                enum_underlying_type(def.size)
+            }
+        );
+        for (value, name) in def.variants.iter() {
+            self.text += &format!("    {name} = {},\n", def.format_value(*value));
+        }
+        self.text += "}\n\n";
+    }
+
+    fn begin_class(&mut self, name: &str) {
+        self.text +=
+            &format!("[StructLayout(LayoutKind.Explicit)]\npublic unsafe struct {name}\n{{\n");
+    }
+
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        self.text += &format!("    [FieldOffset(0x0)] public {name} Base;\n");
+        self.offset = size;
+        self.last_offset = size;
+    }
+
+    fn end_class(&mut self) {
+        // Explicit layout doesn't need trailing padding but this way struct has the right size.
+        self.add_padding();
+        self.text += "}\n\n";
+        self.offset = 0;
+        self.last_offset = 0;
+    }
+
+    fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = kind_to_type64(inner.clone(), None);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(&element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+
+        match &kind {
+            FieldKind::PointerChain(offsets) => {
+                self.text += &format!("    // {}\n", format_pointer_chain(offsets))
+            }
+            FieldKind::FuncPtr => self.text += "    // function pointer\n",
+            FieldKind::Flags(_) => {
+                if let Some(flags) = metadata {
+                    self.text += &format!("    // {flags} flags\n")
+                }
+            }
+            FieldKind::RelPtr32 | FieldKind::RelPtr64 => {
+                self.text += &format!("    // {}\n", format_relative_pointer(metadata))
+            }
+            FieldKind::StdString(abi) => {
+                self.text += &format!("    // std::string, {} layout\n", abi.label());
+                return self.add_array_field(
+                    name,
+                    FieldKind::U8,
+                    abi.size(self.pointer_size),
+                    None,
+                );
+            }
+            FieldKind::Timestamp(format) => {
+                self.text += &format!("    // {} timestamp\n", format.label())
+            }
+            FieldKind::InlineStr(len) => {
+                return self.add_array_field(name, FieldKind::U8, *len, None);
+            }
+            FieldKind::InlineWStr(len) => {
+                return self.add_array_field(name, FieldKind::U16, *len, None);
+            }
+            FieldKind::Color(layout) => {
+                self.text += &format!("    // {} color\n", layout.label());
+                if layout.is_float() {
+                    return self.add_array_field(name, FieldKind::F32, 4, None);
+                }
+            }
+            _ => {}
+        }
+
+        match kind {
+            FieldKind::Bitfield(size, slices) => self.add_bitfield(name, size, &slices),
+            FieldKind::Bool => {
+                self.text += &format!(
+                    "    [FieldOffset(0x{:x})] [MarshalAs(UnmanagedType.U1)] public bool {name};\n",
+                    self.offset
+                )
+            }
+            kind => {
+                self.text += &format!(
+                    "    [FieldOffset(0x{:x})] public {} {name};\n",
+                    self.offset,
+                    kind_to_type(kind, metadata, self.pointer_size)
+                )
+            }
+        }
+
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        // Fixed size buffers only support primitive types.
+        let primitive = match kind {
+            FieldKind::Bool
+            | FieldKind::I8
+            | FieldKind::U8
+            | FieldKind::I16
+            | FieldKind::U16
+            | FieldKind::I32
+            | FieldKind::U32
+            | FieldKind::I64
+            | FieldKind::U64
+            | FieldKind::F32
+            | FieldKind::F64 => kind_to_type(kind.clone(), metadata, self.pointer_size),
+            _ => {
+                for i in 0..count {
+                    self.add_field(&format!("{name}_{i}"), kind.clone(), metadata);
+                }
+                return;
+            }
+        };
+
+        let size = kind.size(self.pointer_size) * count;
+        self.add_padding();
+
+        self.text += &format!(
+            "    [FieldOffset(0x{:x})] public fixed {primitive} {name}[{count}];\n",
+            self.offset
+        );
+
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_offset(&mut self, offset: usize) {
+        self.offset += offset;
+    }
+
+    fn begin_section(&mut self, name: &str) {
+        self.add_padding();
+        self.last_offset = self.offset;
+        self.text += &format!("    // section: {name}\n");
+    }
+
+    fn finilize(&mut self) -> String {
+        take(&mut self.text)
+    }
+}
+
+fn kind_to_type(kind: FieldKind, metadata: Option<&str>, pointer_size: usize) -> Cow<'static, str> {
+    if is_32bit_pointer(&kind, pointer_size) {
+        // `nint` is sized for the process running generated code, not the inspected one.
+        return format!("uint /* {} */", kind_to_type64(kind, metadata)).into();
+    }
+
+    kind_to_type64(kind, metadata)
+}
+
+fn kind_to_type64(kind: FieldKind, metadata: Option<&str>) -> Cow<'static, str> {
+    match kind {
+        FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => unreachable!(),
+        FieldKind::I8 => "sbyte".into(),
+        FieldKind::U8 => "byte".into(),
+        FieldKind::I16 => "short".into(),
+        FieldKind::U16 => "ushort".into(),
+        FieldKind::I32 => "int".into(),
+        FieldKind::U32 => "uint".into(),
+        FieldKind::I64 => "long".into(),
+        FieldKind::U64 => "ulong".into(),
+        FieldKind::F32 => "float".into(),
+        FieldKind::F64 => "double".into(),
+        FieldKind::Ptr => match metadata {
+            Some(class) => format!("nint /* {class}* */").into(),
+            None => "nint".into(),
+        },
+        FieldKind::Vtable(_) => "nint /* void** */".into(),
+        FieldKind::PointerChain(_) | FieldKind::FuncPtr => "nint".into(),
+        // Generated as a byte buffer by `add_field`.
+        FieldKind::StdString(_) => unreachable!(),
+        FieldKind::Guid(_) => "Guid".into(),
+        FieldKind::Timestamp(format) => kind_to_type64(format.raw_kind(), None),
+        FieldKind::Color(_) => "uint".into(),
+        // Generated as arrays by `add_field`.
+        FieldKind::InlineStr(_) | FieldKind::InlineWStr(_) => unreachable!(),
+        // Split into members by `add_field`.
+        FieldKind::DynamicArray(_) | FieldKind::StdVector(_) => unreachable!(),
+        // Fields of sections are added one by one.
+        FieldKind::Section(_) => unreachable!(),
+        FieldKind::Embedded(_) => metadata.unwrap_or_default().to_owned().into(),
+        FieldKind::RelPtr32 => "int".into(),
+        FieldKind::RelPtr64 => "long".into(),
+        FieldKind::StrPtr => "nint /* char* */".into(),
+        FieldKind::WStrPtr => "nint /* wchar_t* */".into(),
+        FieldKind::Bool => "bool".into(),
+        FieldKind::Vec2 => "System.Numerics.Vector2".into(),
+        FieldKind::Vec3 => "System.Numerics.Vector3".into(),
+        FieldKind::Vec4 => "System.Numerics.Vector4".into(),
+        FieldKind::Mat4 => "System.Numerics.Matrix4x4".into(),
+        FieldKind::Enum(size) => match metadata {
+            Some(name) => name.to_owned().into(),
+            None => enum_underlying_type(size).into(),
+        },
+        FieldKind::Bitfield(size, _) | FieldKind::Flags(size) => unsigned_type(size).into(),
+        // Arrays are generated by `add_array_field`.
+        FieldKind::Array(..) => unreachable!(),
+    }
+}
+
+fn enum_underlying_type(size: usize) -> &'static str {
+    match size {
+        1 => "sbyte",
+        2 => "short",
+        4 => "int",
+        _ => "long",
+    }
+}
+
+fn unsigned_type(size: usize) -> &'static str {
+    match size {
+        1 => "byte",
+        2 => "ushort",
+        4 => "uint",
+        _ => "ulong",
+    }
+}
@@ -0,0 +1,381 @@
+use super::Generator;
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, FieldKind},
+};
+use std::{borrow::Cow, mem::take};
+
+/// Generates XML that can be imported with Ghidra's XML loader.
+pub struct GhidraXmlGenerator {
+    text: String,
+    members: String,
+    class: String,
+    offset: usize,
+    last_offset: usize,
+    pointer_size: usize,
+}
+
+impl GhidraXmlGenerator {
+    pub fn new(pointer_size: usize) -> Self {
+        Self {
+            text: format!(
+                concat!(
+                    "<?xml version=\"1.0\" standalone=\"yes\"?>\n",
+                    "<?program_dtd version=\"1\"?>\n",
+                    "<PROGRAM NAME=\"yclass\" EXE_FORMAT=\"\" IMAGE_BASE=\"0x0\">\n",
+                    "    <INFO_SOURCE TOOL=\"YClass {}\" />\n",
+                    "    <DATATYPES>\n"
+                ),
+                env!("YCLASS_VERSION")
+            ),
+            members: String::new(),
+            class: String::new(),
+            offset: 0,
+            last_offset: 0,
+            pointer_size,
+        }
+    }
+
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            let size = self.offset - self.last_offset;
+            let ty = match size {
+                1 | 2 | 4 | 8 => format!("undefined{size}"),
+                _ => format!("undefined1[0x{size:x}]"),
+            };
+
+            self.add_member(
+                &format!("_pad0x{:x}", self.last_offset),
+                self.last_offset,
+                &ty,
+                size,
+                Some("Padding"),
+            );
+        }
+    }
+
+    fn add_member(
+        &mut self,
+        name: &str,
+        offset: usize,
+        ty: &str,
+        size: usize,
+        comment: Option<&str>,
+    ) {
+        self.members += &format!(
+            "            <MEMBER OFFSET=\"0x{offset:x}\" DATATYPE=\"{}\" DATATYPE_NAMESPACE=\"/\" NAME=\"{}\" SIZE=\"0x{size:x}\"",
+            escape(ty),
+            escape(name)
+        );
+
+        if let Some(comment) = comment {
+            self.members += &format!(
+                ">\n                <REGULAR_CMT>{}</REGULAR_CMT>\n            </MEMBER>\n",
+                escape(comment)
+            );
+        } else {
+            self.members += " />\n";
+        }
+    }
+}
+
+impl Generator for GhidraXmlGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        self.text += &format!(
+            "        <ENUM NAME=\"{}\" NAMESPACE=\"/\" SIZE=\"0x{:x}\">\n",
+            escape(&def.name),
+            def.size
+        );
+        for (value, name) in def.variants.iter() {
+            self.text += &format!(
+                "            <ENUM_ENTRY NAME=\"{}\" VALUE=\"{value}\" />\n",
+                escape(name)
+            );
+        }
+        self.text += "        </ENUM>\n";
+    }
+
+    fn begin_class(&mut self, name: &str) {
+        self.class = name.to_owned();
+    }
+
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        self.add_member("base", 0, name, size, Some("Base class"));
+        self.offset = size;
+        self.last_offset = size;
+    }
+
+    fn end_class(&mut self) {
+        self.add_padding();
+
+        // Size is only known once all fields are added.
+        self.text += &format!(
+            "        <STRUCTURE NAME=\"{}\" NAMESPACE=\"/\" SIZE=\"0x{:x}\">\n",
+            escape(&self.class),
+            self.offset
+        );
+        self.text += &take(&mut self.members);
+        self.text += "        </STRUCTURE>\n";
+
+        self.offset = 0;
+        self.last_offset = 0;
+    }
+
+    fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = kind_to_type(inner, None, self.pointer_size);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(&element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+
+        let comment = match &kind {
+            FieldKind::Bitfield(_, slices) if !slices.is_empty() => Some(
+                slices
+                    .iter()
+                    .map(|s| format!("{}:{}:{}", s.name, s.bit_offset, s.bit_width))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            FieldKind::PointerChain(offsets) => Some(format_pointer_chain(offsets)),
+            FieldKind::FuncPtr => Some("function pointer".into()),
+            FieldKind::Flags(_) => metadata.map(|flags| format!("{flags} flags")),
+            FieldKind::RelPtr32 | FieldKind::RelPtr64 => Some(format_relative_pointer(metadata)),
+            FieldKind::StdString(abi) => Some(format!("std::string, {} layout", abi.label())),
+            FieldKind::Guid(format) => Some(format!("GUID, {} byte order", format.label())),
+            FieldKind::Timestamp(format) => Some(format!("{} timestamp", format.label())),
+            FieldKind::Color(layout) => Some(format!("{} color", layout.label())),
+            _ => None,
+        };
+
+        self.add_member(
+            name,
+            self.offset,
+            &kind_to_type(&kind, metadata, self.pointer_size),
+            size,
+            comment.as_deref(),
+        );
+
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        self.add_field(name, FieldKind::Array(Box::new(kind), count), metadata);
+    }
+
+    fn add_offset(&mut self, offset: usize) {
+        self.offset += offset;
+    }
+
+    fn finilize(&mut self) -> String {
+        take(&mut self.text) + "    </DATATYPES>\n</PROGRAM>\n"
+    }
+}
+
+fn kind_to_type(
+    kind: &FieldKind,
+    metadata: Option<&str>,
+    pointer_size: usize,
+) -> Cow<'static, str> {
+    match kind {
+        FieldKind::Unk8 => "undefined1".into(),
+        FieldKind::Unk16 => "undefined2".into(),
+        FieldKind::Unk32 => "undefined4".into(),
+        FieldKind::Unk64 => "undefined8".into(),
+        FieldKind::I8 => "sbyte".into(),
+        FieldKind::U8 => "byte".into(),
+        FieldKind::I16 => "short".into(),
+        FieldKind::U16 => "ushort".into(),
+        FieldKind::I32 => "int".into(),
+        FieldKind::U32 => "uint".into(),
+        FieldKind::I64 => "longlong".into(),
+        FieldKind::U64 => "ulonglong".into(),
+        FieldKind::F32 => "float".into(),
+        FieldKind::F64 => "double".into(),
+        FieldKind::Ptr => match metadata {
+            Some(class) => format!("{class} *").into(),
+            None => format!("pointer{}", pointer_size * 8).into(),
+        },
+        FieldKind::Vtable(_) | FieldKind::PointerChain(_) | FieldKind::FuncPtr => {
+            format!("pointer{}", pointer_size * 8).into()
+        }
+        FieldKind::StdString(abi) => format!("undefined1[{}]", abi.size(pointer_size)).into(),
+        FieldKind::Guid(_) => "undefined1[16]".into(),
+        FieldKind::Timestamp(format) => kind_to_type(&format.raw_kind(), None, pointer_size),
+        FieldKind::Color(layout) => kind_to_type(&layout.raw_kind(), None, pointer_size),
+        FieldKind::InlineStr(len) => format!("char[{len}]").into(),
+        FieldKind::InlineWStr(len) => format!("wchar_t[{len}]").into(),
+        // Split into members by `add_field`.
+        FieldKind::DynamicArray(_) | FieldKind::StdVector(_) => unreachable!(),
+        // Fields of sections are added one by one.
+        FieldKind::Section(_) => unreachable!(),
+        FieldKind::Embedded(_) => metadata.unwrap_or_default().to_owned().into(),
+        FieldKind::RelPtr32 => kind_to_type(&FieldKind::I32, None, pointer_size),
+        FieldKind::RelPtr64 => kind_to_type(&FieldKind::I64, None, pointer_size),
+        FieldKind::StrPtr => "char *".into(),
+        FieldKind::WStrPtr => "wchar_t *".into(),
+        FieldKind::Bool => "bool".into(),
+        FieldKind::Vec2 => "float[2]".into(),
+        FieldKind::Vec3 => "float[3]".into(),
+        FieldKind::Vec4 => "float[4]".into(),
+        FieldKind::Mat4 => "float[4][4]".into(),
+        FieldKind::Enum(size) => match metadata {
+            Some(name) => name.to_owned().into(),
+            None => format!("undefined{size}").into(),
+        },
+        FieldKind::Bitfield(size, _) | FieldKind::Flags(size) => match size {
+            1 => "byte".into(),
+            2 => "ushort".into(),
+            4 => "uint".into(),
+            _ => "ulonglong".into(),
+        },
+        FieldKind::Array(inner, count) => {
+            format!("{}[{count}]", kind_to_type(inner, metadata, pointer_size)).into()
+        }
+    }
+}
+
+fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'']) {
+        return s.into();
+    }
+
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::{ClassList, DEFAULT_POINTER_SIZE},
+        enums::EnumRegistry,
+        field::{allocate_padding, BitSlice, EmbeddedClassField, PointerField},
+        generator::generate,
+    };
+    use roxmltree::{Document, Node};
+
+    fn hex(node: Node, attribute: &str) -> usize {
+        let value = node.attribute(attribute).unwrap();
+        usize::from_str_radix(value.trim_start_matches("0x"), 16).unwrap()
+    }
+
+    #[test]
+    fn structures_match_classes() {
+        let mut list = ClassList::empty();
+        list.set_enums(EnumRegistry::from_definitions(vec![EnumDefinition {
+            name: "State".into(),
+            size: 4,
+            variants: [(0, "<Idle>".into()), (-1, "\"Dead\" & gone".into())].into(),
+            flags: false,
+        }]));
+        let inner = list.add_empty_class("Inner".into());
+        list.by_id_mut(inner).unwrap().fields = vec![
+            FieldKind::U32.into_field(Some("a".into()), DEFAULT_POINTER_SIZE),
+            FieldKind::F32.into_field(Some("b".into()), DEFAULT_POINTER_SIZE),
+        ];
+        let outer = list.add_empty_class("Outer".into());
+        let mut fields = allocate_padding(4);
+        fields.extend([
+            FieldKind::Bitfield(
+                4,
+                vec![BitSlice {
+                    name: "a<b".into(),
+                    bit_offset: 0,
+                    bit_width: 4,
+                }],
+            )
+            .into_field(Some("bits".into()), DEFAULT_POINTER_SIZE),
+            Box::new(PointerField::new_with_class_id(
+                "p".into(),
+                inner,
+                DEFAULT_POINTER_SIZE,
+            )) as _,
+            FieldKind::Vtable(4).into_field(Some("vtable".into()), DEFAULT_POINTER_SIZE),
+            Box::new(EmbeddedClassField::new("inner".into(), inner, 8)) as _,
+            FieldKind::Array(Box::new(FieldKind::U16), 3)
+                .into_field(Some("arr".into()), DEFAULT_POINTER_SIZE),
+        ]);
+        fields.extend(allocate_padding(2));
+        list.by_id_mut(outer).unwrap().fields = fields;
+
+        let xml = generate(&mut GhidraXmlGenerator::new(DEFAULT_POINTER_SIZE), &list);
+        let doc = Document::parse(&xml).unwrap();
+        let root = doc.root_element();
+        assert!(root.has_tag_name("PROGRAM"));
+        let datatypes = root
+            .children()
+            .find(|n| n.has_tag_name("DATATYPES"))
+            .unwrap();
+
+        let entries = datatypes
+            .descendants()
+            .filter(|n| n.has_tag_name("ENUM_ENTRY"))
+            .map(|n| (n.attribute("NAME").unwrap(), n.attribute("VALUE").unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(entries, [("\"Dead\" & gone", "-1"), ("<Idle>", "0")]);
+
+        let structures = datatypes
+            .children()
+            .filter(|n| n.has_tag_name("STRUCTURE"))
+            .collect::<Vec<_>>();
+        assert_eq!(structures.len(), 2);
+        for structure in &structures {
+            let class = list.by_name(structure.attribute("NAME").unwrap()).unwrap();
+            assert_eq!(hex(*structure, "SIZE"), list.class_size(class.id()));
+
+            // Members cover the structure without gaps.
+            let end = structure
+                .children()
+                .filter(|n| n.has_tag_name("MEMBER"))
+                .fold(0, |end, member| {
+                    assert_eq!(hex(member, "OFFSET"), end);
+                    end + hex(member, "SIZE")
+                });
+            assert_eq!(end, hex(*structure, "SIZE"));
+        }
+
+        let members = structures[1]
+            .children()
+            .filter(|n| n.has_tag_name("MEMBER"))
+            .map(|n| {
+                let comment = n
+                    .children()
+                    .find(|c| c.has_tag_name("REGULAR_CMT"))
+                    .and_then(|c| c.text());
+                (
+                    n.attribute("NAME").unwrap(),
+                    n.attribute("DATATYPE").unwrap(),
+                    comment,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            members,
+            [
+                ("_pad0x0", "undefined4", Some("Padding")),
+                ("bits", "uint", Some("a<b:0:4")),
+                ("p", "Inner *", None),
+                ("vtable", "pointer64", None),
+                ("inner", "Inner", None),
+                ("arr", "ushort[3]", None),
+                ("_pad0x26", "undefined2", Some("Padding")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use super::Generator;
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, FieldKind},
+};
+use std::mem::take;
+
+/// Generates IDAPython script that creates structures in the database.
+pub struct IdaPythonGenerator {
+    header: String,
+    // All structures are created before adding members
+    // so members can reference structures defined later.
+    decls: String,
+    members: String,
+    class: String,
+    offset: usize,
+    last_offset: usize,
+    pointer_size: usize,
+}
+
+impl IdaPythonGenerator {
+    pub fn new(pointer_size: usize) -> Self {
+        Self {
+            header: format!(
+                r#"# Generated by YClass {}
+# Made by @ItsEthra
+
+import idc
+
+def create_struc(name):
+    sid = idc.get_struc_id(name)
+    if sid != idc.BADADDR:
+        idc.del_struc(sid)
+    return idc.add_struc(-1, name, 0)
+
+def create_enum(name):
+    eid = idc.get_enum(name)
+    if eid != idc.BADADDR:
+        idc.del_enum(eid)
+    return idc.add_enum(-1, name, 0)
+
+"#,
+                env!("YCLASS_VERSION")
+            ),
+            decls: String::new(),
+            members: String::new(),
+            class: String::new(),
+            offset: 0,
+            last_offset: 0,
+            pointer_size,
+        }
+    }
+
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            self.add_member(
+                &format!("_pad0x{:x}", self.last_offset),
+                self.last_offset,
+                "idc.FF_BYTE",
+                "-1",
+                self.offset - self.last_offset,
+            );
+        }
+    }
+
+    fn add_member(&mut self, name: &str, offset: usize, flags: &str, typeid: &str, size: usize) {
+        self.members += &format!(
+            "idc.add_struc_member(sid_{}, \"{name}\", 0x{offset:x}, {flags} | idc.FF_DATA, {typeid}, 0x{size:x})\n",
+            self.class
+        );
+    }
+
+    fn set_member_type(&mut self, offset: usize, ty: &str) {
+        self.members += &format!(
+            "idc.SetType(idc.get_member_id(sid_{}, 0x{offset:x}), \"{ty}\")\n",
+            self.class
+        );
+    }
+}
+
+impl Generator for IdaPythonGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        self.decls += &format!("eid_{0} = create_enum(\"{0}\")\n", def.name);
+        for (value, name) in def.variants.iter() {
+            self.decls += &format!(
+                "idc.add_enum_member(eid_{}, \"{name}\", {}, -1)\n",
+                def.name,
+                def.format_value(*value)
+            );
+        }
+    }
+
+    fn begin_class(&mut self, name: &str) {
+        self.decls += &format!("sid_{name} = create_struc(\"{name}\")\n");
+        self.members += "\n";
+        self.class = name.to_owned();
+    }
+
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        self.add_member("base", 0, "idc.FF_STRUCT", &format!("sid_{name}"), size);
+        self.offset = size;
+        self.last_offset = size;
+    }
+
+    fn end_class(&mut self) {
+        self.add_padding();
+        self.offset = 0;
+        self.last_offset = 0;
+    }
+
+    fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = element_type(inner);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+
+        // Types without an IDA counterpart are generated as bytes with a comment.
+        let type_comment = match &kind {
+            FieldKind::FuncPtr => Some("function pointer".into()),
+            FieldKind::Flags(_) => metadata.map(|flags| format!("{flags} flags")),
+            FieldKind::RelPtr32 | FieldKind::RelPtr64 => Some(format_relative_pointer(metadata)),
+            FieldKind::StdString(abi) => Some(format!("std::string, {} layout", abi.label())),
+            FieldKind::Guid(format) => Some(format!("GUID, {} byte order", format.label())),
+            FieldKind::Timestamp(format) => Some(format!("{} timestamp", format.label())),
+            FieldKind::Color(layout) => Some(format!("{} color", layout.label())),
+            _ => None,
+        };
+        let member_type = match &kind {
+            FieldKind::InlineStr(len) => Some(format!("char[{len}]")),
+            FieldKind::InlineWStr(len) => Some(format!("wchar_t[{len}]")),
+            _ => None,
+        };
+        let (elem, count) = match kind {
+            FieldKind::Array(inner, count) => (*inner, count),
+            FieldKind::StdString(_) | FieldKind::Guid(_) => (FieldKind::U8, size),
+            FieldKind::Timestamp(format) => (format.raw_kind(), 1),
+            FieldKind::InlineStr(len) => (FieldKind::U8, len),
+            FieldKind::InlineWStr(len) => (FieldKind::U16, len),
+            FieldKind::RelPtr32 => (FieldKind::I32, 1),
+            FieldKind::RelPtr64 => (FieldKind::I64, 1),
+            FieldKind::Color(layout) if layout.is_float() => (FieldKind::F32, 4),
+            FieldKind::Color(_) => (FieldKind::U32, 1),
+            kind => (kind, 1),
+        };
+
+        let (flags, typeid) = match (&elem, metadata) {
+            (FieldKind::Enum(_), Some(name)) => (
+                format!("{} | idc.FF_0ENUM", kind_to_flags(&elem, self.pointer_size)),
+                format!("eid_{name}"),
+            ),
+            (FieldKind::Embedded(_), Some(name)) => {
+                ("idc.FF_STRUCT".to_owned(), format!("sid_{name}"))
+            }
+            _ => (
+                kind_to_flags(&elem, self.pointer_size).to_owned(),
+                "-1".to_owned(),
+            ),
+        };
+
+        self.add_member(name, self.offset, &flags, &typeid, size);
+
+        match elem {
+            FieldKind::Ptr if count == 1 => {
+                if let Some(class) = metadata {
+                    self.set_member_type(self.offset, &format!("{class} *"));
+                }
+            }
+            FieldKind::Vtable(_) if count == 1 => self.set_member_type(self.offset, "void **"),
+            FieldKind::FuncPtr if count == 1 => self.set_member_type(self.offset, "void *"),
+            FieldKind::PointerChain(offsets) if count == 1 => {
+                self.set_member_type(self.offset, "void *");
+                self.members += &format!(
+                    "idc.set_member_cmt(sid_{}, 0x{:x}, \"{}\", 0)\n",
+                    self.class,
+                    self.offset,
+                    format_pointer_chain(&offsets)
+                );
+            }
+            FieldKind::StrPtr if count == 1 => self.set_member_type(self.offset, "char *"),
+            FieldKind::WStrPtr if count == 1 => self.set_member_type(self.offset, "wchar_t *"),
+            FieldKind::Bitfield(_, slices) if !slices.is_empty() => {
+                let comment = slices
+                    .iter()
+                    .map(|s| format!("{}:{}:{}", s.name, s.bit_offset, s.bit_width))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.members += &format!(
+                    "idc.set_member_cmt(sid_{}, 0x{:x}, \"{comment}\", 0)\n",
+                    self.class, self.offset
+                );
+            }
+            _ => {}
+        }
+
+        if let Some(ty) = member_type {
+            self.set_member_type(self.offset, &ty);
+        }
+
+        if let Some(comment) = type_comment {
+            self.members += &format!(
+                "idc.set_member_cmt(sid_{}, 0x{:x}, \"{comment}\", 0)\n",
+                self.class, self.offset
+            );
+        }
+
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        self.add_field(name, FieldKind::Array(Box::new(kind), count), metadata);
+    }
+
+    fn add_offset(&mut self, offset: usize) {
+        self.offset += offset;
+    }
+
+    fn finilize(&mut self) -> String {
+        take(&mut self.header) + &take(&mut self.decls) + &take(&mut self.members)
+    }
+}
+
+/// C type that IDA understands, used for pointers to array elements.
+fn element_type(kind: &FieldKind) -> &'static str {
+    match kind {
+        FieldKind::I8 => "__int8",
+        FieldKind::U8 | FieldKind::Unk8 => "unsigned __int8",
+        FieldKind::I16 => "__int16",
+        FieldKind::U16 | FieldKind::Unk16 => "unsigned __int16",
+        FieldKind::I32 => "__int32",
+        FieldKind::U32 | FieldKind::Unk32 => "unsigned __int32",
+        FieldKind::I64 => "__int64",
+        FieldKind::U64 | FieldKind::Unk64 => "unsigned __int64",
+        FieldKind::F32 | FieldKind::Vec2 | FieldKind::Vec3 | FieldKind::Vec4 | FieldKind::Mat4 => {
+            "float"
+        }
+        FieldKind::F64 => "double",
+        FieldKind::Bool => "bool",
+        FieldKind::StrPtr => "char *",
+        FieldKind::WStrPtr => "wchar_t *",
+        _ => "void *",
+    }
+}
+
+fn kind_to_flags(kind: &FieldKind, pointer_size: usize) -> &'static str {
+    match kind {
+        FieldKind::Vec2 | FieldKind::Vec3 | FieldKind::Vec4 | FieldKind::Mat4 => "idc.FF_FLOAT",
+        FieldKind::F32 => "idc.FF_FLOAT",
+        FieldKind::F64 => "idc.FF_DOUBLE",
+        FieldKind::Array(inner, _) => kind_to_flags(inner, pointer_size),
+        kind => match kind.size(pointer_size) {
+            1 => "idc.FF_BYTE",
+            2 => "idc.FF_WORD",
+            4 => "idc.FF_DWORD",
+            _ => "idc.FF_QWORD",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        class::{ClassList, DEFAULT_POINTER_SIZE},
+        field::{allocate_padding, EmbeddedClassField, PointerField},
+        generator::generate,
+    };
+    use regex::Regex;
+
+    #[test]
+    fn member_offsets_round_trip() {
+        let mut list = ClassList::empty();
+        let inner = list.add_empty_class("Inner".into());
+        list.by_id_mut(inner).unwrap().fields = vec![
+            FieldKind::U32.into_field(Some("a".into()), DEFAULT_POINTER_SIZE),
+            FieldKind::F32.into_field(Some("b".into()), DEFAULT_POINTER_SIZE),
+        ];
+        let outer = list.add_empty_class("Outer".into());
+        let mut fields = allocate_padding(4);
+        fields.extend([
+            FieldKind::U32.into_field(Some("x".into()), DEFAULT_POINTER_SIZE),
+            Box::new(PointerField::new_with_class_id(
+                "p".into(),
+                inner,
+                DEFAULT_POINTER_SIZE,
+            )) as _,
+            Box::new(EmbeddedClassField::new("inner".into(), inner, 8)) as _,
+            FieldKind::Array(Box::new(FieldKind::U16), 3)
+                .into_field(Some("arr".into()), DEFAULT_POINTER_SIZE),
+        ]);
+        fields.extend(allocate_padding(2));
+        list.by_id_mut(outer).unwrap().fields = fields;
+
+        let script = generate(&mut IdaPythonGenerator::new(DEFAULT_POINTER_SIZE), &list);
+        let member = Regex::new(
+            r#"idc\.add_struc_member\(sid_(\w+), "(\w+)", 0x([0-9a-f]+), ([^,]+), ([^,]+), 0x([0-9a-f]+)\)"#,
+        )
+        .unwrap();
+        let members = member
+            .captures_iter(&script)
+            .map(|c| {
+                let hex = |i| usize::from_str_radix(&c[i], 16).unwrap();
+                (
+                    c[1].to_owned(),
+                    c[2].to_owned(),
+                    hex(3),
+                    c[4].to_owned(),
+                    c[5].to_owned(),
+                    hex(6),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let expected = [
+            ("Inner", "a", 0, "idc.FF_DWORD | idc.FF_DATA", "-1", 4),
+            ("Inner", "b", 4, "idc.FF_FLOAT | idc.FF_DATA", "-1", 4),
+            ("Outer", "_pad0x0", 0, "idc.FF_BYTE | idc.FF_DATA", "-1", 4),
+            ("Outer", "x", 4, "idc.FF_DWORD | idc.FF_DATA", "-1", 4),
+            ("Outer", "p", 8, "idc.FF_QWORD | idc.FF_DATA", "-1", 8),
+            (
+                "Outer",
+                "inner",
+                0x10,
+                "idc.FF_STRUCT | idc.FF_DATA",
+                "sid_Inner",
+                8,
+            ),
+            ("Outer", "arr", 0x18, "idc.FF_WORD | idc.FF_DATA", "-1", 6),
+            (
+                "Outer",
+                "_pad0x1e",
+                0x1e,
+                "idc.FF_BYTE | idc.FF_DATA",
+                "-1",
+                2,
+            ),
+        ];
+        let expected = expected
+            .map(|(class, name, offset, flags, typeid, size)| {
+                let owned = |s: &str| s.to_owned();
+                (
+                    owned(class),
+                    owned(name),
+                    offset,
+                    owned(flags),
+                    owned(typeid),
+                    size,
+                )
+            })
+            .to_vec();
+        assert_eq!(members, expected, "{script}");
+
+        // Members cover the classes without gaps.
+        for class in [inner, outer] {
+            let name = &list.by_id(class).unwrap().name;
+            let end = members.iter().filter(|m| &m.0 == name).fold(
+                0,
+                |end, (_, _, offset, _, _, size)| {
+                    assert_eq!(*offset, end);
+                    end + size
+                },
+            );
+            assert_eq!(end, list.class_size(class));
+        }
+    }
+}
@@ -1,42 +1,139 @@
-use crate::field::FieldKind;
+use crate::{config::YClassConfig, enums::EnumDefinition, field::FieldKind};
 
 mod rust;
 pub use rust::*;
 mod cpp;
 pub use cpp::*;
+mod csharp;
+pub use csharp::*;
+mod python;
+pub use python::*;
+mod ida;
+pub use ida::*;
+mod ghidra;
+pub use ghidra::*;
 
 pub trait Generator {
+    /// Called for every enum of the project before any class is generated.
+    fn add_enum(&mut self, _def: &EnumDefinition) {}
+
     fn begin_class(&mut self, name: &str);
+    /// Called right after `begin_class` if the class is derived from `name`,
+    /// offsets of the following fields start at `size`.
+    fn add_base_class(&mut self, _name: &str, size: usize) {
+        self.add_offset(size);
+    }
     fn end_class(&mut self);
 
+    /// Called before the field the comment belongs to is added.
+    fn add_comment(&mut self, _comment: &str) {}
     fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>);
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        for i in 0..count {
+            self.add_field(&format!("{name}_{i}"), kind.clone(), metadata);
+        }
+    }
     fn add_offset(&mut self, offset: usize);
 
+    /// Called before the fields of a section, they're added as fields of the class.
+    fn begin_section(&mut self, _name: &str) {}
+    fn end_section(&mut self) {}
+
     fn finilize(&mut self) -> String;
 }
 
+/// Returns `true` for pointers of a 32-bit process. Generated code is not necessarily compiled
+/// for the inspected process so generators spell out their width as `u32`.
+fn is_32bit_pointer(kind: &FieldKind, pointer_size: usize) -> bool {
+    pointer_size == 4
+        && matches!(
+            kind,
+            FieldKind::Ptr
+                | FieldKind::StrPtr
+                | FieldKind::WStrPtr
+                | FieldKind::Vtable(_)
+                | FieldKind::FuncPtr
+                | FieldKind::PointerChain(_)
+        )
+}
+
+/// Generates every class of the list the way the generator window does.
+#[cfg(test)]
+pub(crate) fn generate(gen: &mut dyn Generator, list: &crate::class::ClassList) -> String {
+    let data = crate::field::CodegenData {
+        classes: list.classes(),
+        enums: list.enums(),
+    };
+    for def in data.enums.definitions().iter() {
+        gen.add_enum(def);
+    }
+
+    for class in list.in_dependency_order() {
+        gen.begin_class(&class.name);
+        if let Some(base) = list.base_of(class.id()) {
+            gen.add_base_class(&list.by_id(base).unwrap().name, list.class_size(base));
+        }
+        for field in class.fields.iter() {
+            if let Some(comment) = field.comment() {
+                gen.add_comment(&comment);
+            }
+            field.codegen(gen, &data);
+        }
+        gen.end_class();
+    }
+    gen.finilize()
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum AvailableGenerator {
     #[default]
     Rust,
     Cpp,
+    CSharp,
+    PythonCtypes,
+    IdaPython,
+    GhidraXml,
 }
 
 impl AvailableGenerator {
-    pub const ALL: &'static [AvailableGenerator] =
-        &[AvailableGenerator::Rust, AvailableGenerator::Cpp];
+    pub const ALL: &'static [AvailableGenerator] = &[
+        AvailableGenerator::Rust,
+        AvailableGenerator::Cpp,
+        AvailableGenerator::CSharp,
+        AvailableGenerator::PythonCtypes,
+        AvailableGenerator::IdaPython,
+        AvailableGenerator::GhidraXml,
+    ];
 
     pub fn label(&self) -> &'static str {
         match self {
             Self::Rust => "Rust",
             Self::Cpp => "C++",
+            Self::CSharp => "C#",
+            Self::PythonCtypes => "Python ctypes",
+            Self::IdaPython => "IDAPython script",
+            Self::GhidraXml => "Ghidra XML",
         }
     }
 
-    pub fn generator(&self) -> Box<dyn Generator> {
+    /// Creates the generator for classes of a process with `pointer_size` byte pointers.
+    pub fn generator(&self, config: &YClassConfig, pointer_size: usize) -> Box<dyn Generator> {
         match self {
-            Self::Rust => Box::<RustGenerator>::default(),
-            Self::Cpp => Box::<CppGenerator>::default(),
+            Self::Rust => Box::new(RustGenerator::new(pointer_size)),
+            Self::Cpp => Box::new(CppGenerator::with_math_types(
+                config.cpp_math_types.clone().unwrap_or_default(),
+                pointer_size,
+            )),
+            Self::CSharp => Box::new(CSharpGenerator::new(pointer_size)),
+            Self::PythonCtypes => Box::new(PythonCtypesGenerator::new(pointer_size)),
+            Self::IdaPython => Box::new(IdaPythonGenerator::new(pointer_size)),
+            Self::GhidraXml => Box::new(GhidraXmlGenerator::new(pointer_size)),
         }
     }
 }
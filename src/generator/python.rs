@@ -0,0 +1,269 @@
+use super::{is_32bit_pointer, Generator};
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, BitSlice, FieldKind},
+};
+use std::{borrow::Cow, mem::take};
+
+pub struct PythonCtypesGenerator {
+    header: String,
+    // Classes are declared first and get their fields assigned afterwards
+    // so pointers can reference classes defined later.
+    decls: String,
+    fields: String,
+    offset: usize,
+    last_offset: usize,
+    pointer_size: usize,
+}
+
+impl PythonCtypesGenerator {
+    pub fn new(pointer_size: usize) -> Self {
+        Self {
+            header: format!(
+                "# Generated by YClass {}\n# Made by @ItsEthra\n\nimport ctypes\nimport enum\n\n",
+                env!("YCLASS_VERSION")
+            ),
+            decls: String::new(),
+            fields: String::new(),
+            offset: 0,
+            last_offset: 0,
+            pointer_size,
+        }
+    }
+
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            self.fields += &format!(
+                "    (\"_pad0x{:x}\", ctypes.c_char * 0x{:x}),\n",
+                self.last_offset,
+                self.offset - self.last_offset
+            );
+        }
+    }
+
+    fn add_bitfield(&mut self, size: usize, mut slices: Vec<BitSlice>) {
+        let ty = unsigned_type(size);
+        slices.sort_by_key(|s| s.bit_offset);
+
+        let mut bit = 0;
+        for BitSlice {
+            name,
+            bit_offset,
+            bit_width,
+        } in slices
+        {
+            if bit_offset < bit {
+                self.fields += &format!("    # {name} : {bit_width} overlaps previous bits\n");
+                continue;
+            }
+
+            if bit_offset > bit {
+                self.fields +=
+                    &format!("    (\"_bits{bit}\", ctypes.{ty}, {}),\n", bit_offset - bit);
+            }
+            self.fields += &format!("    (\"{name}\", ctypes.{ty}, {bit_width}),\n");
+            bit = bit_offset + bit_width;
+        }
+
+        if (bit as usize) < size * 8 {
+            self.fields += &format!(
+                "    (\"_bits{bit}\", ctypes.{ty}, {}),\n",
+                size * 8 - bit as usize
+            );
+        }
+    }
+}
+
+impl Generator for PythonCtypesGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        let base = if def.flags { "IntFlag" } else { "IntEnum" };
+        self.decls += &format!("class {}(enum.{base}):\n", def.name);
+        if def.variants.is_empty() {
+            self.decls += "    pass\n";
+        }
+        for (value, name) in def.variants.iter() {
+            self.decls += &format!("    {name} = {}\n", def.format_value(*value));
+        }
+        self.decls += "\n";
+    }
+
+    fn begin_class(&mut self, name: &str) {
+        self.decls += &format!("class {name}(ctypes.Structure):\n    _pack_ = 1\n\n");
+        self.fields += &format!("{name}._fields_ = [\n");
+    }
+
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        self.fields += &format!("    (\"base\", {name}),\n");
+        self.offset = size;
+        self.last_offset = size;
+    }
+
+    fn end_class(&mut self) {
+        self.add_padding();
+        self.fields += "]\n\n";
+        self.offset = 0;
+        self.last_offset = 0;
+    }
+
+    fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = kind_to_type(inner, None, self.pointer_size);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(&element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+
+        match kind {
+            FieldKind::Bitfield(size, slices) if !slices.is_empty() => {
+                self.add_bitfield(size, slices)
+            }
+            kind => {
+                self.fields += &format!(
+                    "    (\"{name}\", {}),",
+                    kind_to_type(&kind, metadata, self.pointer_size)
+                );
+                if let Some(comment) = type_comment(&kind, metadata, self.pointer_size) {
+                    self.fields += &format!("  # {comment}");
+                }
+                self.fields += "\n";
+            }
+        }
+
+        self.offset += size;
+        self.last_offset = self.offset;
+    }
+
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        self.add_field(name, FieldKind::Array(Box::new(kind), count), metadata);
+    }
+
+    fn add_offset(&mut self, offset: usize) {
+        self.offset += offset;
+    }
+
+    fn begin_section(&mut self, name: &str) {
+        self.add_padding();
+        self.last_offset = self.offset;
+        self.fields += &format!("    # section: {name}\n");
+    }
+
+    fn finilize(&mut self) -> String {
+        take(&mut self.header) + &take(&mut self.decls) + &take(&mut self.fields)
+    }
+}
+
+fn type_comment(kind: &FieldKind, metadata: Option<&str>, pointer_size: usize) -> Option<String> {
+    match kind {
+        FieldKind::Ptr | FieldKind::StrPtr | FieldKind::WStrPtr | FieldKind::Vtable(_)
+            if pointer_size == 4 =>
+        {
+            Some(match (kind, metadata) {
+                (FieldKind::Ptr, Some(class)) => format!("{class}*"),
+                (FieldKind::Ptr, None) => "void*".into(),
+                (FieldKind::Vtable(_), _) => "void**".into(),
+                (FieldKind::StrPtr, _) => "char*".into(),
+                _ => "wchar_t*".into(),
+            })
+        }
+        FieldKind::Enum(_) => metadata.map(str::to_owned),
+        FieldKind::Flags(_) => metadata.map(|flags| format!("{flags} flags")),
+        FieldKind::FuncPtr => Some("function pointer".into()),
+        FieldKind::RelPtr32 | FieldKind::RelPtr64 => Some(format_relative_pointer(metadata)),
+        FieldKind::PointerChain(offsets) => Some(format_pointer_chain(offsets)),
+        FieldKind::StdString(abi) => Some(format!("std::string, {} layout", abi.label())),
+        FieldKind::Guid(format) => Some(format!("GUID, {} byte order", format.label())),
+        FieldKind::Timestamp(format) => Some(format!("{} timestamp", format.label())),
+        FieldKind::Color(layout) => Some(format!("{} color", layout.label())),
+        FieldKind::Array(inner, _) => type_comment(inner, metadata, pointer_size),
+        _ => None,
+    }
+}
+
+fn kind_to_type(
+    kind: &FieldKind,
+    metadata: Option<&str>,
+    pointer_size: usize,
+) -> Cow<'static, str> {
+    // ctypes pointers are sized for the python process, not the inspected one.
+    if is_32bit_pointer(kind, pointer_size) {
+        return "ctypes.c_uint32".into();
+    }
+
+    match kind {
+        FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => unreachable!(),
+        FieldKind::I8 => "ctypes.c_int8".into(),
+        FieldKind::U8 => "ctypes.c_uint8".into(),
+        FieldKind::I16 => "ctypes.c_int16".into(),
+        FieldKind::U16 => "ctypes.c_uint16".into(),
+        FieldKind::I32 => "ctypes.c_int32".into(),
+        FieldKind::U32 => "ctypes.c_uint32".into(),
+        FieldKind::I64 => "ctypes.c_int64".into(),
+        FieldKind::U64 => "ctypes.c_uint64".into(),
+        FieldKind::F32 => "ctypes.c_float".into(),
+        FieldKind::F64 => "ctypes.c_double".into(),
+        FieldKind::Ptr => match metadata {
+            Some(class) => format!("ctypes.POINTER({class})").into(),
+            None => "ctypes.c_void_p".into(),
+        },
+        FieldKind::Vtable(_) | FieldKind::PointerChain(_) | FieldKind::FuncPtr => {
+            "ctypes.c_void_p".into()
+        }
+        FieldKind::StdString(abi) => format!("ctypes.c_uint8 * {}", abi.size(pointer_size)).into(),
+        FieldKind::Guid(_) => "ctypes.c_uint8 * 16".into(),
+        FieldKind::Timestamp(format) => kind_to_type(&format.raw_kind(), None, pointer_size),
+        FieldKind::Color(layout) => kind_to_type(&layout.raw_kind(), None, pointer_size),
+        FieldKind::InlineStr(len) => format!("ctypes.c_char * {len}").into(),
+        FieldKind::InlineWStr(len) => format!("ctypes.c_wchar * {len}").into(),
+        // Split into members by `add_field`.
+        FieldKind::DynamicArray(_) | FieldKind::StdVector(_) => unreachable!(),
+        // Fields of sections are added one by one.
+        FieldKind::Section(_) => unreachable!(),
+        FieldKind::Embedded(_) => metadata.unwrap_or_default().to_owned().into(),
+        FieldKind::RelPtr32 => "ctypes.c_int32".into(),
+        FieldKind::RelPtr64 => "ctypes.c_int64".into(),
+        FieldKind::StrPtr => "ctypes.c_char_p".into(),
+        FieldKind::WStrPtr => "ctypes.c_wchar_p".into(),
+        FieldKind::Bool => "ctypes.c_bool".into(),
+        FieldKind::Vec2 => "ctypes.c_float * 2".into(),
+        FieldKind::Vec3 => "ctypes.c_float * 3".into(),
+        FieldKind::Vec4 => "ctypes.c_float * 4".into(),
+        FieldKind::Mat4 => "(ctypes.c_float * 4) * 4".into(),
+        FieldKind::Enum(size) => format!("ctypes.{}", signed_type(*size)).into(),
+        FieldKind::Bitfield(size, _) | FieldKind::Flags(size) => {
+            format!("ctypes.{}", unsigned_type(*size)).into()
+        }
+        FieldKind::Array(inner, count) => format!(
+            "({}) * {count}",
+            kind_to_type(inner, metadata, pointer_size)
+        )
+        .into(),
+    }
+}
+
+fn signed_type(size: usize) -> &'static str {
+    match size {
+        1 => "c_int8",
+        2 => "c_int16",
+        4 => "c_int32",
+        _ => "c_int64",
+    }
+}
+
+fn unsigned_type(size: usize) -> &'static str {
+    match size {
+        1 => "c_uint8",
+        2 => "c_uint16",
+        4 => "c_uint32",
+        _ => "c_uint64",
+    }
+}
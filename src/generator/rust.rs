@@ -1,63 +1,229 @@
-use super::Generator;
-use crate::field::FieldKind;
+use super::{is_32bit_pointer, Generator};
+use crate::{
+    enums::EnumDefinition,
+    field::{format_pointer_chain, format_relative_pointer, BitSlice, FieldKind},
+};
 use std::borrow::Cow;
 
 pub struct RustGenerator {
     text: String,
+    class_name: String,
+    // Helper types emitted after the current class.
+    deferred: String,
+    comment: Option<String>,
     last_offset: usize,
     offset: usize,
+    // Number of padding fields emitted in the current class.
+    padding: usize,
+    pointer_size: usize,
 }
 
-impl Default for RustGenerator {
-    fn default() -> Self {
+impl RustGenerator {
+    pub fn new(pointer_size: usize) -> Self {
         Self {
             text: format!(
-                "// Generated by YClass {}\n// Made by @ItsEthra\n\n",
+                "// Generated by YClass {}\n// Made by @ItsEthra\n\nuse std::ffi::c_void;\n\n",
                 env!("YCLASS_VERSION")
             ),
+            class_name: String::new(),
+            deferred: String::new(),
+            comment: None,
             last_offset: 0,
             offset: 0,
+            padding: 0,
+            pointer_size,
         }
     }
 }
 
+impl RustGenerator {
+    fn add_padding(&mut self) {
+        if self.offset != self.last_offset {
+            self.text += &format!(
+                "    _pad{}: [u8; 0x{:x}],\n",
+                self.padding,
+                self.offset - self.last_offset
+            );
+            self.padding += 1;
+            self.last_offset = self.offset;
+        }
+    }
+
+    /// Emits a wrapper type with accessors for every slice and returns its name.
+    fn add_bitfield_type(&mut self, name: &str, size: usize, slices: &[BitSlice]) -> String {
+        let ty = unsigned_type(size);
+        let type_name = format!("{}_{name}", self.class_name);
+
+        self.deferred += &format!(
+            "#[allow(non_camel_case_types)]\n#[repr(transparent)]\n#[derive(Debug, Clone, Copy)]\npub struct {type_name}(pub {ty});\n\nimpl {type_name} {{\n"
+        );
+        for slice in slices {
+            let (name, shift, mask) = (&slice.name, slice.bit_offset, slice.mask());
+            self.deferred += &format!(
+                "    pub fn {name}(&self) -> {ty} {{\n        (self.0 >> {shift}) & {mask:#x}\n    }}\n\n"
+            );
+            self.deferred += &format!(
+                "    pub fn set_{name}(&mut self, value: {ty}) {{\n        self.0 = (self.0 & !({mask:#x} << {shift})) | ((value & {mask:#x}) << {shift});\n    }}\n\n"
+            );
+        }
+        self.deferred.truncate(self.deferred.trim_end().len());
+        self.deferred += "\n}\n\n";
+
+        type_name
+    }
+}
+
 impl Generator for RustGenerator {
+    fn add_enum(&mut self, def: &EnumDefinition) {
+        let repr = if def.flags {
+            unsigned_type(def.size)
+        } else {
+            enum_underlying_type(def.size)
+        };
+
+        // Zero-variant enums can't have a `repr`.
+        if def.variants.is_empty() {
+            self.text += &format!(
+                "#[repr(transparent)]\npub struct {}(pub {repr});\n\n",
+                def.name
+            );
+            return;
+        }
+
+        self.text += &format!(
+            "#[repr({repr})]\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum {} {{\n",
+            def.name
+        );
+        for (value, name) in def.variants.iter() {
+            self.text += &format!("    {name} = {},\n", def.format_value(*value));
+        }
+        self.text += "}\n\n";
+    }
+
     fn begin_class(&mut self, name: &str) {
-        self.text += &format!("#[repr(C)]\npub struct {name} {{\n");
+        self.text +=
+            &format!("#[repr(C)]\n#[allow(dead_code, non_snake_case)]\npub struct {name} {{\n");
+        self.class_name = name.to_owned();
+    }
+
+    fn add_base_class(&mut self, name: &str, size: usize) {
+        self.text += &format!("    pub base: {name},\n");
+        self.offset = size;
+        self.last_offset = size;
     }
 
     fn end_class(&mut self) {
         self.text += "}\n\n";
+        self.text += &std::mem::take(&mut self.deferred);
         self.offset = 0;
         self.last_offset = 0;
+        self.padding = 0;
+    }
+
+    fn add_comment(&mut self, comment: &str) {
+        self.comment = Some(comment.to_owned());
     }
 
     fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
-        let size = kind.size();
-        if self.offset != self.last_offset {
-            self.text += &format!(
-                "    _pad_0x{:x}: [u8; 0x{:x}],\n",
-                self.offset,
-                self.offset - self.last_offset
-            );
+        if let Some((inner, members)) = kind.dynamic_array_members(name, self.pointer_size) {
+            let element = kind_to_type64(inner.clone(), None, self.pointer_size);
+            for (name, kind) in members {
+                self.add_field(&name, kind, Some(&element));
+            }
+            return;
+        }
+
+        let size = kind.size(self.pointer_size);
+        self.add_padding();
+
+        if let Some(comment) = self.comment.take() {
+            for line in comment.lines() {
+                self.text += &format!("    /// {line}\n");
+            }
+        }
+
+        match &kind {
+            FieldKind::Ptr => {
+                if let Some(class) = metadata {
+                    self.text += &format!("    // {class}\n")
+                }
+            }
+            FieldKind::PointerChain(offsets) => {
+                self.text += &format!("    // {}\n", format_pointer_chain(offsets))
+            }
+            FieldKind::FuncPtr => self.text += "    // function pointer\n",
+            FieldKind::Flags(_) => {
+                if let Some(flags) = metadata {
+                    self.text += &format!("    // {flags} flags\n")
+                }
+            }
+            FieldKind::RelPtr32 | FieldKind::RelPtr64 => {
+                self.text += &format!("    // {}\n", format_relative_pointer(metadata))
+            }
+            FieldKind::StdString(abi) => {
+                self.text += &format!("    // std::string, {} layout\n", abi.label())
+            }
+            FieldKind::Timestamp(format) => {
+                self.text += &format!("    // {} timestamp\n", format.label())
+            }
+            FieldKind::Color(layout) => self.text += &format!("    // {} color\n", layout.label()),
+            _ => {}
         }
 
-        self.text += &format!("    pub {name}: {},\n", kind_to_type(kind, metadata));
+        let ty = match kind {
+            FieldKind::Bitfield(size, slices) if !slices.is_empty() => {
+                self.add_bitfield_type(name, size, &slices).into()
+            }
+            kind => kind_to_type(kind, metadata, self.pointer_size),
+        };
+        self.text += &format!("    pub {name}: {ty},\n");
 
         self.offset += size;
         self.last_offset = self.offset;
     }
 
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        self.add_field(name, FieldKind::Array(Box::new(kind), count), metadata);
+    }
+
     fn add_offset(&mut self, offset: usize) {
         self.offset += offset;
     }
 
+    fn begin_section(&mut self, name: &str) {
+        self.add_padding();
+        if let Some(comment) = self.comment.take() {
+            for line in comment.lines() {
+                self.text += &format!("    // {line}\n");
+            }
+        }
+        self.text += &format!("    // section: {name}\n");
+    }
+
     fn finilize(&mut self) -> String {
         std::mem::take(&mut self.text)
     }
 }
 
-fn kind_to_type(kind: FieldKind, metadata: Option<&str>) -> Cow<'static, str> {
+fn kind_to_type(kind: FieldKind, metadata: Option<&str>, pointer_size: usize) -> Cow<'static, str> {
+    if is_32bit_pointer(&kind, pointer_size) {
+        return format!("u32 /* {} */", kind_to_type64(kind, metadata, pointer_size)).into();
+    }
+
+    kind_to_type64(kind, metadata, pointer_size)
+}
+
+fn kind_to_type64(
+    kind: FieldKind,
+    metadata: Option<&str>,
+    pointer_size: usize,
+) -> Cow<'static, str> {
     match kind {
         FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => unreachable!(),
         FieldKind::I8 => "i8".into(),
@@ -70,9 +236,90 @@ fn kind_to_type(kind: FieldKind, metadata: Option<&str>) -> Cow<'static, str> {
         FieldKind::U64 => "u64".into(),
         FieldKind::F32 => "f32".into(),
         FieldKind::F64 => "f64".into(),
-        FieldKind::Ptr => format!("Option<&'static {}>", metadata.unwrap_or("()")).into(),
+        FieldKind::Ptr | FieldKind::PointerChain(_) | FieldKind::FuncPtr => "*mut c_void".into(),
+        FieldKind::Vtable(_) => "*const *const c_void".into(),
+        FieldKind::StdString(abi) => format!("[u8; 0x{:x}]", abi.size(pointer_size)).into(),
+        FieldKind::Guid(_) => "[u8; 16]".into(),
+        FieldKind::Timestamp(format) => kind_to_type64(format.raw_kind(), None, pointer_size),
+        FieldKind::Color(layout) => kind_to_type64(layout.raw_kind(), None, pointer_size),
+        FieldKind::InlineStr(len) => format!("[u8; {len}]").into(),
+        FieldKind::InlineWStr(len) => format!("[u16; {len}]").into(),
+        // Split into members by `add_field`.
+        FieldKind::DynamicArray(_) | FieldKind::StdVector(_) => unreachable!(),
+        // Fields of sections are added one by one.
+        FieldKind::Section(_) => unreachable!(),
+        FieldKind::Embedded(_) => metadata.unwrap_or_default().to_owned().into(),
+        FieldKind::RelPtr32 => "i32".into(),
+        FieldKind::RelPtr64 => "i64".into(),
         FieldKind::StrPtr => "*const u8".into(),
         FieldKind::WStrPtr => "*const u16".into(),
         FieldKind::Bool => "bool".into(),
+        FieldKind::Vec2 => "[f32; 2]".into(),
+        FieldKind::Vec3 => "[f32; 3]".into(),
+        FieldKind::Vec4 => "[f32; 4]".into(),
+        FieldKind::Mat4 => "[[f32; 4]; 4]".into(),
+        FieldKind::Enum(size) => match metadata {
+            Some(name) => name.to_owned().into(),
+            None => enum_underlying_type(size).into(),
+        },
+        FieldKind::Bitfield(size, _) | FieldKind::Flags(size) => unsigned_type(size).into(),
+        FieldKind::Array(inner, count) => format!(
+            "[{}; {count}]",
+            kind_to_type(*inner, metadata, pointer_size)
+        )
+        .into(),
+    }
+}
+
+fn enum_underlying_type(size: usize) -> &'static str {
+    match size {
+        1 => "i8",
+        2 => "i16",
+        4 => "i32",
+        _ => "i64",
+    }
+}
+
+fn unsigned_type(size: usize) -> &'static str {
+    match size {
+        1 => "u8",
+        2 => "u16",
+        4 => "u32",
+        _ => "u64",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generator::generate, project::ProjectData};
+    use std::{fs, process::Command};
+
+    #[test]
+    fn output_compiles() {
+        let (mut list, ..) = ProjectData::sample_idents().load();
+        for pointer_size in [4, 8] {
+            list.set_pointer_size(pointer_size);
+            let source = generate(&mut RustGenerator::new(pointer_size), &list);
+
+            let dir = std::env::temp_dir()
+                .join(format!("yclass_rust_{}_{pointer_size}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("generated.rs");
+            fs::write(&path, &source).unwrap();
+            let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+                .args(["--edition", "2021", "--crate-type", "lib", "--out-dir"])
+                .arg(&dir)
+                .arg(&path)
+                .output();
+            _ = fs::remove_dir_all(&dir);
+
+            let output = output.expect("Failed to run rustc");
+            assert!(
+                output.status.success(),
+                "{}\n{source}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
     }
 }
@@ -0,0 +1,279 @@
+use crate::{
+    process::{Module, Process},
+    state::StateRef,
+};
+use eframe::{
+    egui::{Button, Context, TextEdit, Ui, Window},
+    epaint::{Color32, FontId},
+};
+use egui_extras::{Column, TableBuilder};
+use std::{borrow::Cow, thread::JoinHandle, time::Instant};
+
+/// Number of bytes displayed before and after the match.
+const CONTEXT_SIZE: usize = 8;
+
+type ScanOutput = (Vec<usize>, Vec<Module>);
+
+pub struct AobScanWindow {
+    state: StateRef,
+    shown: bool,
+
+    pattern_buf: String,
+    pattern_len: usize,
+
+    scanner_status: Option<Cow<'static, str>>,
+    results: Vec<usize>,
+    modules: Vec<Module>,
+
+    scan: Option<(Instant, JoinHandle<ScanOutput>)>,
+}
+
+impl AobScanWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            pattern_buf: String::new(),
+            pattern_len: 0,
+            scanner_status: None,
+            results: vec![],
+            modules: vec![],
+            scan: None,
+            shown: false,
+            state,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> eyre::Result<Option<()>> {
+        let mut shown = self.shown;
+
+        match self.scan.take() {
+            Some((start, handle)) if handle.is_finished() => {
+                (self.results, self.modules) = handle.join().unwrap_or_default();
+                self.scanner_status = Some(
+                    format!(
+                        "Found {} in: {:.2}",
+                        self.results.len(),
+                        start.elapsed().as_secs_f32()
+                    )
+                    .into(),
+                );
+            }
+            Some(scan) => {
+                self.scan = Some(scan);
+                self.scanner_status = Some("In progress".into());
+            }
+            None => {}
+        }
+
+        let result = Window::new("AOB scan")
+            .open(&mut shown)
+            .show(ctx, |ui| {
+                let state = &mut *self.state.borrow_mut();
+
+                let process_lock = state.process.read();
+                let Some(process) = process_lock.as_ref() else {
+                    ui.centered_and_justified(|ui| {
+                        ui.heading("Attach to a process first");
+                    });
+
+                    return Ok(());
+                };
+
+                let inner: eyre::Result<()> = ui
+                    .horizontal(|ui| {
+                        ui.add_enabled(
+                            self.scan.is_none(),
+                            TextEdit::singleline(&mut self.pattern_buf)
+                                .hint_text("48 8B 05 ? ? ? ? 48 8B 48 08")
+                                .font(FontId::monospace(14.))
+                                .desired_width(ui.available_width() / 2.),
+                        );
+
+                        if ui
+                            .add_enabled(self.scan.is_none(), Button::new("Scan"))
+                            .clicked()
+                        {
+                            let pattern = parse_pattern(&self.pattern_buf)?;
+                            self.pattern_len = pattern.len();
+
+                            let process = state.process.clone();
+                            let handle = std::thread::spawn(move || {
+                                let process = process.read();
+                                process
+                                    .as_ref()
+                                    .map(|p| (p.scan_pattern(&pattern), p.modules()))
+                                    .unwrap_or_default()
+                            });
+                            self.scan = Some((Instant::now(), handle));
+                        }
+
+                        if let Some(status) = self.scanner_status.as_deref() {
+                            ui.separator();
+                            ui.label(status);
+                        }
+
+                        Ok(())
+                    })
+                    .inner;
+                inner?;
+
+                ui.separator();
+
+                if self.scan.is_none() {
+                    self.display_results(process, ui);
+                }
+
+                Ok(())
+            })
+            .and_then(|v| v.inner)
+            .transpose();
+        self.shown = shown;
+
+        result
+    }
+
+    fn display_results(&mut self, process: &Process, ui: &mut Ui) {
+        const DATA_HEIGHT: f32 = 14.;
+        ui.style_mut().override_font_id = Some(FontId::monospace(DATA_HEIGHT));
+
+        let w = ui.available_width() / 4.;
+        let mut buf = vec![0; self.pattern_len + CONTEXT_SIZE * 2];
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .columns(Column::initial(w).resizable(true), 2)
+            .column(Column::remainder())
+            .header(16., |mut row| {
+                row.col(|ui| _ = ui.label("Address"));
+                row.col(|ui| _ = ui.label("Module"));
+                row.col(|ui| _ = ui.label("Bytes"));
+            })
+            .body(|body| {
+                body.rows(DATA_HEIGHT, self.results.len(), |mut row| {
+                    let address = self.results[row.index()];
+                    let module = self
+                        .modules
+                        .iter()
+                        .find(|m| (m.base..m.base + m.size).contains(&address));
+
+                    let (_, r) = row.col(|ui| _ = ui.label(format!("{address:X}")));
+                    r.context_menu(|ui| {
+                        if ui.button("Copy address").clicked() {
+                            ui.ctx().copy_text(format!("{address:X}"));
+                            ui.close();
+                        }
+
+                        if ui
+                            .add_enabled(module.is_some(), Button::new("Copy RVA"))
+                            .clicked()
+                        {
+                            let rva = address - module.unwrap().base;
+                            ui.ctx().copy_text(format!("{rva:X}"));
+                            ui.close();
+                        }
+                    });
+
+                    row.col(|ui| {
+                        if let Some(module) = module {
+                            ui.label(format!(
+                                "{}+{:X}",
+                                module.file_name(),
+                                address - module.base
+                            ))
+                            .on_hover_text(&module.name);
+                        }
+                    });
+
+                    let start = address.saturating_sub(CONTEXT_SIZE);
+                    let before = address - start;
+                    let buf = &mut buf[..before + self.pattern_len + CONTEXT_SIZE];
+                    buf.fill(0);
+                    process.read(start, buf);
+
+                    row.col(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.;
+                        ui.colored_label(Color32::GRAY, format_bytes(&buf[..before]));
+                        ui.label(format_bytes(&buf[before..before + self.pattern_len]));
+                        ui.colored_label(
+                            Color32::GRAY,
+                            format_bytes(&buf[before + self.pattern_len..]),
+                        );
+                    });
+                })
+            });
+    }
+}
+
+fn format_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02X} ")).collect()
+}
+
+/// Parses space separated hex bytes, `?` and `??` are wildcards.
+fn parse_pattern(s: &str) -> eyre::Result<Vec<Option<u8>>> {
+    let pattern = s
+        .split_whitespace()
+        .map(|byte| match byte {
+            "?" | "??" => Ok(None),
+            byte => u8::from_str_radix(byte, 16)
+                .map(Some)
+                .map_err(|_| eyre::eyre!("Invalid byte in pattern: {byte}")),
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    if pattern.iter().all(Option::is_none) {
+        return Err(eyre::eyre!("Pattern must contain at least one byte"));
+    }
+
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert_eq!(
+            parse_pattern("48 ?? 8B ? 05").unwrap(),
+            [Some(0x48), None, Some(0x8B), None, Some(0x05)]
+        );
+        assert_eq!(parse_pattern("  ff\t00 ").unwrap(), [Some(0xFF), Some(0)]);
+    }
+
+    #[test]
+    fn nibble_wildcards_are_invalid() {
+        // Only whole bytes can be wildcards.
+        for pattern in ["4?", "?8", "48 ?F", "???"] {
+            let e = parse_pattern(pattern).unwrap_err().to_string();
+            assert!(e.starts_with("Invalid byte in pattern"), "{pattern}: {e}");
+        }
+    }
+
+    #[test]
+    fn invalid_tokens() {
+        for (pattern, token) in [
+            ("48 GG", "GG"),
+            ("100", "100"),
+            ("0x48", "0x48"),
+            ("48,8B", "48,8B"),
+        ] {
+            assert_eq!(
+                parse_pattern(pattern).unwrap_err().to_string(),
+                format!("Invalid byte in pattern: {token}")
+            );
+        }
+    }
+
+    #[test]
+    fn empty_pattern() {
+        for pattern in ["", "   ", "?? ?"] {
+            assert_eq!(
+                parse_pattern(pattern).unwrap_err().to_string(),
+                "Pattern must contain at least one byte"
+            );
+        }
+    }
+}
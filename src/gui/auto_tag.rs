@@ -0,0 +1,181 @@
+use crate::{
+    class::ClassId,
+    field::{walk_fields, FieldId, FieldKind},
+    history::Command,
+    state::{GlobalState, StateRef},
+    suggest::suggest_field_type,
+};
+use eframe::{
+    egui::{Button, Context, Grid, ScrollArea, Ui, Window},
+    epaint::Color32,
+};
+
+struct Suggestion {
+    field_id: FieldId,
+    offset: usize,
+    old: FieldKind,
+    new: FieldKind,
+    value: String,
+    apply: bool,
+}
+
+/// Replaces unknown fields of the inspected class with kinds their values suggest, once the
+/// suggestions are confirmed.
+pub struct AutoTagWindow {
+    state: StateRef,
+    shown: bool,
+    class: Option<ClassId>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl AutoTagWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            class: None,
+            suggestions: vec![],
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+        if self.shown {
+            let state = self.state;
+            self.scan(&mut state.borrow_mut());
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Auto-tag unknowns")
+            .open(&mut shown)
+            .default_width(420.)
+            .show(ctx, |ui| self.suggestions(ui));
+        self.shown = shown;
+    }
+
+    fn scan(&mut self, state: &mut GlobalState) {
+        self.suggestions.clear();
+        self.class = state.class_list.selected();
+
+        let process = state.process.read();
+        let (Some(process), Some(class)) = (process.as_ref(), state.class_list.selected_class())
+        else {
+            return;
+        };
+
+        let address = class.address.get();
+        let start = state.class_list.base_size(class.id());
+        for (offset, field) in walk_fields(&class.fields, start) {
+            let old = field.kind();
+            if !matches!(
+                old,
+                FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64
+            ) {
+                continue;
+            }
+
+            let mut bytes = vec![0; field.size()];
+            if !process.read_checked(address + offset, &mut bytes) {
+                continue;
+            }
+            let Some(new) = suggest_field_type(&bytes, address + offset, process) else {
+                continue;
+            };
+
+            let mut raw = [0; 8];
+            raw[..bytes.len()].copy_from_slice(&bytes);
+            let value = match new {
+                FieldKind::F32 => f32::from_ne_bytes(raw[..4].try_into().unwrap()).to_string(),
+                FieldKind::F64 => f64::from_ne_bytes(raw).to_string(),
+                _ => format!("-> {:X}", u64::from_ne_bytes(raw)),
+            };
+            self.suggestions.push(Suggestion {
+                field_id: field.id(),
+                offset,
+                old,
+                new,
+                value,
+                apply: true,
+            });
+        }
+    }
+
+    fn suggestions(&mut self, ui: &mut Ui) {
+        let state = &mut *self.state.borrow_mut();
+
+        ui.horizontal(|ui| {
+            let name = self
+                .class
+                .and_then(|cid| state.class_list.by_id(cid))
+                .map(|c| c.name.as_str())
+                .unwrap_or("No class");
+            ui.label(format!("{name}: {} suggestions", self.suggestions.len()));
+            if ui.button("Rescan").clicked() {
+                self.scan(state);
+            }
+        });
+        if state.process.read().is_none() {
+            ui.colored_label(Color32::RED, "Attach to a process first");
+            return;
+        }
+        ui.separator();
+
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            Grid::new("_auto_tag_suggestions")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for s in &mut self.suggestions {
+                        ui.checkbox(&mut s.apply, format!("{:04X}", s.offset));
+                        ui.label(format!("{:?} → {:?}", s.old, s.new));
+                        ui.monospace(&s.value);
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.separator();
+
+        let count = self.suggestions.iter().filter(|s| s.apply).count();
+        if !ui
+            .add_enabled(count > 0, Button::new(format!("Apply {count} suggestions")))
+            .clicked()
+        {
+            return;
+        }
+        let Some(cid) = self.class else {
+            return;
+        };
+
+        let edit = Command::edit_class(&state.class_list, cid);
+        let pointer_size = state.class_list.pointer_size();
+        let Some(class) = state.class_list.by_id_mut(cid) else {
+            return;
+        };
+        let mut applied = 0;
+        for s in self.suggestions.iter().filter(|s| s.apply) {
+            let Some(fields) = class.fields_with_mut(s.field_id) else {
+                continue;
+            };
+            let Some(pos) = fields.iter().position(|f| f.id() == s.field_id) else {
+                continue;
+            };
+            // Sizes match, the field is replaced in place.
+            if fields[pos].kind() == s.old && s.old.size(pointer_size) == s.new.size(pointer_size) {
+                fields[pos] = s.new.clone().into_field(fields[pos].name(), pointer_size);
+                applied += 1;
+            }
+        }
+
+        if applied > 0 {
+            if let Some(edit) = edit {
+                state.history.push(edit);
+            }
+            state.dummy = false;
+            state.toasts.info(format!("Tagged {applied} fields"));
+        }
+        self.suggestions.clear();
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{app::is_valid_ident, field::FieldId, state::StateRef};
+use eframe::{
+    egui::{Button, Context, Grid, RichText, ScrollArea, TextEdit, Ui, Window},
+    epaint::Color32,
+};
+use regex::Regex;
+
+/// Renames the selected fields by replacing matches of a regex in their names.
+pub struct BatchRenameWindow {
+    state: StateRef,
+    shown: bool,
+    find: String,
+    replace: String,
+    // Compiled `find`, recompiled once it changes.
+    regex: Option<(String, Result<Regex, regex::Error>)>,
+}
+
+impl BatchRenameWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            find: String::new(),
+            replace: String::new(),
+            regex: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn open(&mut self) {
+        self.shown = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Batch rename")
+            .open(&mut shown)
+            .default_width(360.)
+            .show(ctx, |ui| self.rename(ui));
+        self.shown = shown;
+    }
+
+    fn rename(&mut self, ui: &mut Ui) {
+        Grid::new("_batch_rename_patterns")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Find");
+                ui.add(TextEdit::singleline(&mut self.find).hint_text("field_(\\d+)"));
+                ui.end_row();
+
+                ui.label("Replace");
+                ui.add(TextEdit::singleline(&mut self.replace).hint_text("value_$1"));
+                ui.end_row();
+            });
+
+        if self
+            .regex
+            .as_ref()
+            .is_none_or(|(find, _)| *find != self.find)
+        {
+            self.regex = Some((self.find.clone(), Regex::new(&self.find)));
+        }
+        let regex = match &self.regex {
+            Some((_, Ok(regex))) if !self.find.is_empty() => regex,
+            Some((_, Err(e))) => {
+                ui.colored_label(Color32::RED, e.to_string());
+                return;
+            }
+            _ => {
+                ui.label("Matches in names of the selected fields are replaced, `$1` refers to the first group");
+                return;
+            }
+        };
+
+        let state = &mut *self.state.borrow_mut();
+        let Some(container_id) = state.selection.map(|s| s.container_id) else {
+            ui.label("Select fields with Shift or Ctrl click first");
+            return;
+        };
+        let Some(class) = state.class_list.by_id(container_id) else {
+            return;
+        };
+
+        let renames = state
+            .selected_fields()
+            .into_iter()
+            .filter_map(|id| {
+                let name = class.field(id)?.name()?;
+                let new_name = regex.replace_all(&name, self.replace.as_str()).into_owned();
+                Some((id, name, new_name))
+            })
+            .collect::<Vec<(FieldId, String, String)>>();
+
+        ui.separator();
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            Grid::new("_batch_rename_preview")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (_, name, new_name) in &renames {
+                        ui.label(name);
+                        ui.label("→");
+                        let color = if !is_valid_ident(new_name) {
+                            Color32::RED
+                        } else if new_name == name {
+                            Color32::GRAY
+                        } else {
+                            Color32::LIGHT_GREEN
+                        };
+                        ui.label(RichText::new(new_name).color(color));
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.separator();
+
+        let changed = renames.iter().any(|(_, name, new_name)| name != new_name);
+        if !ui.add_enabled(changed, Button::new("Rename")).clicked() {
+            return;
+        }
+
+        let (valid, invalid): (Vec<_>, Vec<_>) = renames
+            .into_iter()
+            .filter(|(_, name, new_name)| name != new_name)
+            .partition(|(_, _, new_name)| is_valid_ident(new_name));
+
+        if !valid.is_empty() {
+            // The whole batch is undone at once.
+            state.record_edit(container_id);
+            let class = state.class_list.by_id(container_id).unwrap();
+            for (id, _, new_name) in valid {
+                if let Some(field) = class.field(id) {
+                    field.set_name(new_name);
+                }
+            }
+            state.dummy = false;
+        }
+
+        if !invalid.is_empty() {
+            let names = invalid
+                .iter()
+                .map(|(_, name, new_name)| format!("{name} → {new_name:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            state
+                .toasts
+                .error(format!("Skipped fields with invalid names: {names}"));
+        }
+    }
+}
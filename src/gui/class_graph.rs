@@ -0,0 +1,305 @@
+use crate::{
+    class::{ClassId, ClassList},
+    field::walk_fields,
+    state::StateRef,
+};
+use eframe::{
+    egui::{Align2, Context, Id, Rect, Sense, Ui, Window},
+    epaint::{vec2, Color32, FontId, Pos2, Stroke, StrokeKind, Vec2},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Preferred distance between connected classes.
+const SPRING_LENGTH: f32 = 120.;
+/// Pulls all classes to the center so unconnected ones don't drift away.
+const GRAVITY: f32 = 0.02;
+const STEP: f32 = 0.05;
+const MAX_STEP: f32 = 10.;
+/// The layout stops once the classes in total move less than this per step.
+const SETTLED: f32 = 0.5;
+const STEPS_PER_FRAME: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EdgeKind {
+    Pointer,
+    Embed,
+    Base,
+}
+
+impl EdgeKind {
+    fn color(self) -> Color32 {
+        match self {
+            Self::Pointer => Color32::YELLOW,
+            Self::Embed => Color32::LIGHT_BLUE,
+            Self::Base => Color32::LIGHT_GREEN,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Pointer => "Pointer",
+            Self::Embed => "Embedded",
+            Self::Base => "Base class",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Edge {
+    from: ClassId,
+    to: ClassId,
+    kind: EdgeKind,
+}
+
+/// Classes drawn as nodes and their pointers, embedded classes and base classes as edges,
+/// placed with a force-directed layout.
+pub struct ClassGraphWindow {
+    state: StateRef,
+    shown: bool,
+
+    positions: HashMap<ClassId, Pos2>,
+    pan: Vec2,
+    settled: bool,
+    /// Edges of the last frame, the layout restarts when they change.
+    edges: HashSet<Edge>,
+}
+
+impl ClassGraphWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            positions: HashMap::new(),
+            pan: Vec2::ZERO,
+            settled: false,
+            edges: HashSet::new(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Class graph")
+            .open(&mut shown)
+            .default_size([600., 400.])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for kind in [EdgeKind::Pointer, EdgeKind::Embed, EdgeKind::Base] {
+                        ui.colored_label(kind.color(), "⏵");
+                        ui.label(kind.label());
+                    }
+                    ui.separator();
+                    if ui.button("Relayout").clicked() {
+                        self.positions.clear();
+                        self.pan = Vec2::ZERO;
+                        self.settled = false;
+                    }
+                });
+                ui.separator();
+
+                self.graph(ui);
+            });
+        self.shown = shown;
+    }
+
+    fn graph(&mut self, ui: &mut Ui) {
+        let state = &mut *self.state.borrow_mut();
+        let list = &state.class_list;
+
+        let ids = list.classes().iter().map(|c| c.id()).collect::<Vec<_>>();
+        let edges = edges(list);
+        if edges != self.edges || ids.len() != self.positions.len() {
+            self.positions.retain(|id, _| ids.contains(id));
+            for (i, id) in ids.iter().enumerate() {
+                self.positions
+                    .entry(*id)
+                    .or_insert_with(|| initial_position(i));
+            }
+            self.edges = edges;
+            self.settled = false;
+        }
+
+        let (canvas, painter) = ui.allocate_painter(ui.available_size(), Sense::drag());
+        if canvas.dragged() {
+            self.pan += canvas.drag_delta();
+        }
+        let origin = canvas.rect.center() + self.pan;
+        painter.rect_filled(canvas.rect, 2., ui.visuals().extreme_bg_color);
+
+        let font = FontId::monospace(13.);
+        let rects = ids
+            .iter()
+            .filter_map(|id| {
+                let class = list.by_id(*id)?;
+                let galley = painter.layout_no_wrap(
+                    class.name.clone(),
+                    font.clone(),
+                    ui.visuals().text_color(),
+                );
+                let center = origin + self.positions[id].to_vec2();
+                let rect = Rect::from_center_size(center, galley.size() + vec2(12., 8.));
+                Some((*id, (rect, galley)))
+            })
+            .collect::<HashMap<_, _>>();
+
+        for edge in &self.edges {
+            let (Some((from, _)), Some((to, _))) = (rects.get(&edge.from), rects.get(&edge.to))
+            else {
+                continue;
+            };
+            let stroke = Stroke::new(1.5, edge.kind.color());
+            if edge.from == edge.to {
+                painter.circle_stroke(from.right_top(), 8., stroke);
+                continue;
+            }
+
+            let start = boundary_point(*from, to.center());
+            let end = boundary_point(*to, from.center());
+            painter.arrow(start, end - start, stroke);
+        }
+
+        let selected = list.selected();
+        let (mut clicked, mut dragged) = (None, None);
+        for (id, (rect, galley)) in rects {
+            let r = ui.interact(
+                rect,
+                Id::new(("_class_graph_node", id)),
+                Sense::click_and_drag(),
+            );
+            if r.dragged() {
+                *self.positions.get_mut(&id).unwrap() += r.drag_delta();
+                dragged = Some(id);
+                self.settled = false;
+            }
+            if r.clicked() {
+                clicked = Some(id);
+            }
+
+            let stroke = if selected == Some(id) {
+                Stroke::new(2., Color32::WHITE)
+            } else if r.hovered() {
+                Stroke::new(1., Color32::LIGHT_GRAY)
+            } else {
+                Stroke::new(1., Color32::DARK_GRAY)
+            };
+            painter.rect_filled(rect, 4., ui.visuals().window_fill());
+            painter.rect_stroke(rect, 4., stroke, StrokeKind::Inside);
+            painter.galley(
+                Align2::CENTER_CENTER
+                    .align_size_within_rect(galley.size(), rect)
+                    .min,
+                galley,
+                Color32::WHITE,
+            );
+        }
+
+        if let Some(id) = clicked {
+            *state.class_list.selected_mut() = Some(id);
+            state.clear_selection();
+        }
+
+        if !self.settled {
+            for _ in 0..STEPS_PER_FRAME {
+                self.settled = self.step(&ids, dragged);
+            }
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Moves classes by the forces acting on them, except for the `pinned` one that is being
+    /// dragged. Returns `true` once the layout is settled.
+    fn step(&mut self, ids: &[ClassId], pinned: Option<ClassId>) -> bool {
+        let mut forces = ids
+            .iter()
+            .map(|id| (*id, -self.positions[id].to_vec2() * GRAVITY))
+            .collect::<HashMap<_, _>>();
+
+        // Every class pushes others away.
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                let delta = self.positions[a] - self.positions[b];
+                let distance = delta.length().max(1.);
+                let force = delta / distance * (SPRING_LENGTH * SPRING_LENGTH / distance);
+                *forces.get_mut(a).unwrap() += force;
+                *forces.get_mut(b).unwrap() -= force;
+            }
+        }
+
+        // Edges pull connected classes together.
+        for edge in self.edges.iter().filter(|e| e.from != e.to) {
+            let (Some(from), Some(to)) =
+                (self.positions.get(&edge.from), self.positions.get(&edge.to))
+            else {
+                continue;
+            };
+            let delta = *to - *from;
+            let distance = delta.length().max(1.);
+            let force = delta / distance * (distance * distance / SPRING_LENGTH);
+            *forces.get_mut(&edge.from).unwrap() += force;
+            *forces.get_mut(&edge.to).unwrap() -= force;
+        }
+
+        let mut moved = 0.;
+        for (id, force) in forces.into_iter().filter(|(id, _)| Some(*id) != pinned) {
+            let mut step = force * STEP;
+            if step.length() > MAX_STEP {
+                step = step.normalized() * MAX_STEP;
+            }
+            moved += step.length();
+            *self.positions.get_mut(&id).unwrap() += step;
+        }
+
+        moved < SETTLED
+    }
+}
+
+fn edges(list: &ClassList) -> HashSet<Edge> {
+    let mut edges = HashSet::new();
+    for class in list.classes() {
+        let from = class.id();
+        if let Some(base) = list.base_of(from) {
+            edges.insert(Edge {
+                from,
+                to: base,
+                kind: EdgeKind::Base,
+            });
+        }
+
+        for (_, field) in walk_fields(&class.fields, 0) {
+            let targets = [
+                (field.pointed_class(), EdgeKind::Pointer),
+                (field.embedded_class(), EdgeKind::Embed),
+            ];
+            for (to, kind) in targets {
+                // Pointers get a random class id until a class is created for them.
+                if let Some(to) = to.filter(|id| list.by_id(*id).is_some()) {
+                    edges.insert(Edge { from, to, kind });
+                }
+            }
+        }
+    }
+    edges
+}
+
+/// Spreads new classes on a spiral so no two start at the same position.
+fn initial_position(index: usize) -> Pos2 {
+    let angle = index as f32 * 2.4;
+    let radius = 40. * (index as f32).sqrt();
+    Pos2::new(angle.cos() * radius, angle.sin() * radius)
+}
+
+/// Point where the line from the center of `rect` to `target` leaves the rect.
+fn boundary_point(rect: Rect, target: Pos2) -> Pos2 {
+    let direction = target - rect.center();
+    let half = rect.size() / 2.;
+    let scale = (half.x / direction.x.abs()).min(half.y / direction.y.abs());
+    if scale.is_finite() {
+        rect.center() + direction * scale.min(1.)
+    } else {
+        rect.center()
+    }
+}
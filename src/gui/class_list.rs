@@ -1,16 +1,39 @@
 use crate::{
+    address::parse_address,
     app::is_valid_ident,
-    class::{Class, ClassId},
+    class::{Class, ClassId, ColorOverride},
+    field::walk_fields,
+    gui::size_warning,
+    history::Command,
     state::StateRef,
 };
 use eframe::{
-    egui::{Button, Context, Key, ScrollArea, SidePanel, TextEdit},
-    epaint::vec2,
+    egui::{
+        color_picker::{color_picker_color32, Alpha},
+        Button, Context, Key, ScrollArea, SidePanel, TextEdit, TextFormat, Ui, WidgetText,
+    },
+    epaint::{text::LayoutJob, vec2, Color32},
 };
-use std::mem::take;
+use std::{collections::HashMap, mem::take, ops::Range};
+
+/// Initial colors of the kinds when a class gets colored by kind.
+const KIND_PALETTE: [Color32; 6] = [
+    Color32::from_rgb(0xC0, 0x50, 0x50),
+    Color32::from_rgb(0x50, 0xA0, 0x50),
+    Color32::from_rgb(0x50, 0x70, 0xC0),
+    Color32::from_rgb(0xC0, 0xA0, 0x40),
+    Color32::from_rgb(0x90, 0x50, 0xB0),
+    Color32::from_rgb(0x40, 0xA0, 0xA0),
+];
 
 enum RequestedAction {
     Delete(ClassId),
+    Duplicate(ClassId),
+    /// Splits the class at the offset, deletes the class afterwards if `true`.
+    Split(ClassId, usize, bool),
+    Rename(ClassId, String),
+    SetBase(ClassId, Option<ClassId>),
+    SetColors(ClassId, Option<ColorOverride>),
     ToggleSelection(ClassId),
 }
 
@@ -22,8 +45,12 @@ struct ClassEditState {
 
 pub struct ClassListPanel {
     new_class_buf: String,
+    filter: String,
     edit_state: Option<ClassEditState>,
     should_focus_edit: bool,
+    // Hex offset typed in the split menu.
+    split_offset: String,
+    split_delete: bool,
     state: StateRef,
 }
 
@@ -33,7 +60,10 @@ impl ClassListPanel {
             state,
             edit_state: None,
             should_focus_edit: false,
+            split_offset: String::new(),
+            split_delete: false,
             new_class_buf: "".to_owned(),
+            filter: String::new(),
         }
     }
 
@@ -62,6 +92,17 @@ impl ClassListPanel {
             ui.horizontal_top(|ui| {
                 let size = vec2(ui.available_width(), 18.);
                 if ui.add_sized(size, Button::new("Remove empty")).clicked() {
+                    let removed = state
+                        .class_list
+                        .classes()
+                        .iter()
+                        .filter(|c| c.is_empty())
+                        .filter_map(|c| Command::remove_class(&state.class_list, c.id()))
+                        .collect::<Vec<_>>();
+                    if !removed.is_empty() {
+                        state.history.push(Command::Batch(removed));
+                    }
+
                     state.class_list.remove_empty();
                 }
             });
@@ -70,6 +111,12 @@ impl ClassListPanel {
             ui.separator();
             ui.add_space(4.);
 
+            TextEdit::singleline(&mut self.filter)
+                .desired_width(f32::INFINITY)
+                .hint_text("Search, # for ids")
+                .show(ui);
+            ui.add_space(4.);
+
             if r.clicked_elsewhere() || (ui.input(|i| i.key_pressed(Key::Escape)) && r.lost_focus())
             {
                 self.new_class_buf.clear();
@@ -87,7 +134,8 @@ impl ClassListPanel {
                     state.toasts.error("Not a valid class name");
                     self.should_focus_edit = true;
                 } else {
-                    state.class_list.add_class(take(&mut self.new_class_buf));
+                    let id = state.class_list.add_class(take(&mut self.new_class_buf));
+                    state.history.push(Command::AddClass(id));
                     state.dummy = false;
                 }
             }
@@ -96,6 +144,12 @@ impl ClassListPanel {
                 ScrollArea::vertical().show(ui, |ui| {
                     let selected = state.class_list.selected();
                     let mut action = None;
+                    let class_names = state
+                        .class_list
+                        .classes()
+                        .iter()
+                        .map(|c| (c.id(), c.name.clone()))
+                        .collect::<Vec<_>>();
 
                     fn map_state<'s>(
                         class: &Class,
@@ -113,7 +167,20 @@ impl ClassListPanel {
                         }
                     }
 
-                    for class in state.class_list.classes_mut() {
+                    // Fields take the rename hotkey while one is selected.
+                    if state.selection.is_none() && take(&mut state.rename_requested) {
+                        if let Some(class) = state.class_list.selected_class() {
+                            self.edit_state = Some(ClassEditState {
+                                new_name: class.name.clone(),
+                                request_focus: true,
+                                id: class.id(),
+                            });
+                        }
+                    }
+
+                    let mut any_shown = false;
+                    for (class, matched) in state.class_list.iter_filtered(&self.filter) {
+                        any_shown = true;
                         if let Some((edit_buf, request_focus)) =
                             self.edit_state.as_mut().and_then(|s| map_state(class, s))
                         {
@@ -138,18 +205,26 @@ impl ClassListPanel {
                                     state.toasts.error("Not a valid class name");
                                     *request_focus = true;
                                 } else {
-                                    class.name = take(edit_buf);
+                                    action =
+                                        Some(RequestedAction::Rename(class.id(), take(edit_buf)));
                                     self.edit_state = None;
-                                    state.dummy = false;
                                 }
                             }
                         } else {
-                            let r = ui.add_sized(
-                                vec2(ui.available_width(), 24.),
-                                Button::new(&class.name).selected(
-                                    selected.map(|j| class.id() == j).unwrap_or_default(),
-                                ),
-                            );
+                            let size = state.class_list.class_size(class.id());
+                            let warning = size_warning(size, state.class_list.pointer_size());
+                            let r = ui
+                                .add_sized(
+                                    vec2(ui.available_width(), 24.),
+                                    Button::new(class_label(&class.name, matched, size, warning))
+                                        .selected(
+                                            selected.map(|j| class.id() == j).unwrap_or_default(),
+                                        ),
+                                )
+                                .on_hover_text(match warning {
+                                    Some((_, reason)) => format!("#{:x}, {reason}", class.id()),
+                                    None => format!("#{:x}", class.id()),
+                                });
 
                             if r.clicked() {
                                 action = Some(RequestedAction::ToggleSelection(class.id()));
@@ -169,18 +244,127 @@ impl ClassListPanel {
                                         });
                                     }
 
+                                    if ui.button("Duplicate").clicked() {
+                                        ui.close();
+
+                                        action = Some(RequestedAction::Duplicate(class.id()));
+                                    }
+
                                     if ui.button("Delete").clicked() {
                                         ui.close();
 
                                         action = Some(RequestedAction::Delete(class.id()));
                                     }
+
+                                    ui.menu_button("Split at", |ui| {
+                                        if let Some(offset) = self.split_menu(ui) {
+                                            action = Some(RequestedAction::Split(
+                                                class.id(),
+                                                offset,
+                                                self.split_delete,
+                                            ));
+                                            ui.close();
+                                        }
+                                    });
+
+                                    ui.menu_button("Row colors", |ui| {
+                                        if let Some(colors) = row_colors_menu(ui, class) {
+                                            action = Some(RequestedAction::SetColors(
+                                                class.id(),
+                                                colors,
+                                            ));
+                                        }
+                                    });
+
+                                    ui.menu_button("Base class", |ui| {
+                                        if ui
+                                            .selectable_label(class.base().is_none(), "None")
+                                            .clicked()
+                                        {
+                                            action =
+                                                Some(RequestedAction::SetBase(class.id(), None));
+                                            ui.close();
+                                        }
+
+                                        for (id, name) in class_names.iter() {
+                                            if *id == class.id() {
+                                                continue;
+                                            }
+
+                                            if ui
+                                                .selectable_label(class.base() == Some(*id), name)
+                                                .clicked()
+                                            {
+                                                action = Some(RequestedAction::SetBase(
+                                                    class.id(),
+                                                    Some(*id),
+                                                ));
+                                                ui.close();
+                                            }
+                                        }
+                                    });
                                 });
                             });
                         }
                     }
 
+                    if !any_shown && !self.filter.trim().is_empty() {
+                        ui.label("No classes match the search");
+                    }
+
                     match action.take()? {
-                        RequestedAction::Delete(cid) => state.class_list.delete_by_id(cid),
+                        RequestedAction::Delete(cid) => {
+                            if let Some(command) = Command::remove_class(&state.class_list, cid) {
+                                state.history.push(command);
+                            }
+                            state.class_list.delete_by_id(cid);
+                        }
+                        RequestedAction::Duplicate(cid) => {
+                            let id = state.class_list.duplicate(cid)?;
+                            state.history.push(Command::AddClass(id));
+                            *state.class_list.selected_mut() = Some(id);
+                            state.dummy = false;
+                        }
+                        RequestedAction::Split(cid, offset, delete) => {
+                            let edit = Command::edit_class(&state.class_list, cid);
+                            match state.class_list.split(cid, offset) {
+                                Ok((head, tail)) => {
+                                    let mut commands = edit.into_iter().collect::<Vec<_>>();
+                                    commands
+                                        .extend([Command::AddClass(head), Command::AddClass(tail)]);
+                                    if delete {
+                                        commands
+                                            .extend(Command::remove_class(&state.class_list, cid));
+                                        state.class_list.delete_by_id(cid);
+                                    }
+                                    state.history.push(Command::Batch(commands));
+                                    state.dummy = false;
+                                }
+                                Err(e) => _ = state.toasts.error(e.to_string()),
+                            }
+                        }
+                        RequestedAction::Rename(cid, name) => {
+                            state.record_edit(cid);
+                            state.class_list.by_id_mut(cid)?.name = name;
+                            state.dummy = false;
+                        }
+                        RequestedAction::SetBase(cid, base) => {
+                            let edit = Command::edit_class(&state.class_list, cid);
+                            match state.class_list.rebase(cid, base) {
+                                Ok(()) => {
+                                    if let Some(edit) = edit {
+                                        state.history.push(edit);
+                                    }
+                                    state.dummy = false;
+                                }
+                                Err(e) => _ = state.toasts.error(e.to_string()),
+                            }
+                        }
+                        RequestedAction::SetColors(cid, colors) => {
+                            state.record_edit(cid);
+                            *state.class_list.by_id_mut(cid)?.colors.get_mut() = colors;
+                            state.dummy = false;
+                        }
                         RequestedAction::ToggleSelection(cid) => {
                             let selected = state.class_list.selected_mut();
                             if *selected == Some(cid) {
@@ -197,3 +381,120 @@ impl ClassListPanel {
         });
     }
 }
+
+impl ClassListPanel {
+    /// Returns the offset to split the class at once it's submitted.
+    fn split_menu(&mut self, ui: &mut Ui) -> Option<usize> {
+        let offset = parse_address(&self.split_offset);
+        let edit = ui.add(
+            TextEdit::singleline(&mut self.split_offset)
+                .hint_text("Hex offset")
+                .desired_width(100.),
+        );
+        ui.checkbox(&mut self.split_delete, "Delete the class")
+            .on_hover_text("Otherwise the class embeds both parts");
+
+        let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        let clicked = ui
+            .add_enabled(offset.is_some(), Button::new("Split"))
+            .clicked();
+        (submitted || clicked).then_some(offset).flatten()
+    }
+}
+
+/// Returns new row colors of the class if they were changed.
+fn row_colors_menu(ui: &mut Ui, class: &Class) -> Option<Option<ColorOverride>> {
+    let current = class.colors.borrow().clone();
+    let mut new = None;
+
+    if ui.selectable_label(current.is_none(), "None").clicked() {
+        new = Some(None);
+        ui.close();
+    }
+
+    let kinds = walk_fields(&class.fields, 0)
+        .into_iter()
+        .fold(vec![], |mut kinds, (_, f)| {
+            if !kinds.contains(&f.kind()) {
+                kinds.push(f.kind());
+            }
+            kinds
+        });
+    if ui
+        .selectable_label(matches!(current, Some(ColorOverride::ByKind(_))), "By kind")
+        .clicked()
+        && !matches!(current, Some(ColorOverride::ByKind(_)))
+    {
+        let colors = kinds
+            .iter()
+            .cloned()
+            .zip(KIND_PALETTE.iter().cycle().copied());
+        new = Some(Some(ColorOverride::ByKind(colors.collect())));
+    }
+
+    if ui
+        .selectable_label(
+            matches!(current, Some(ColorOverride::PerField(_))),
+            "Per field",
+        )
+        .on_hover_text("Colors are picked in the context menus of the fields")
+        .clicked()
+        && !matches!(current, Some(ColorOverride::PerField(_)))
+    {
+        new = Some(Some(ColorOverride::PerField(HashMap::new())));
+    }
+
+    ui.menu_button("Solid", |ui| {
+        let mut color = match current {
+            Some(ColorOverride::Solid(color)) => color,
+            _ => KIND_PALETTE[2],
+        };
+        if color_picker_color32(ui, &mut color, Alpha::Opaque) {
+            new = Some(Some(ColorOverride::Solid(color)));
+        }
+    });
+
+    if let Some(ColorOverride::ByKind(colors)) = &current {
+        ui.separator();
+        for kind in kinds {
+            ui.menu_button(format!("{kind:?}"), |ui| {
+                let mut color = colors.get(&kind).copied().unwrap_or(KIND_PALETTE[0]);
+                if color_picker_color32(ui, &mut color, Alpha::Opaque) {
+                    let mut colors = colors.clone();
+                    colors.insert(kind.clone(), color);
+                    new = Some(Some(ColorOverride::ByKind(colors)));
+                }
+            });
+        }
+    }
+
+    new
+}
+
+/// Name with the `matched` part colored, followed by the size of the class.
+fn class_label(
+    name: &str,
+    matched: Option<Range<usize>>,
+    size: usize,
+    warning: Option<(Color32, &str)>,
+) -> WidgetText {
+    let color = |color| TextFormat {
+        color,
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    match matched {
+        Some(matched) => {
+            job.append(&name[..matched.start], 0., color(Color32::LIGHT_GRAY));
+            job.append(&name[matched.clone()], 0., color(Color32::YELLOW));
+            job.append(&name[matched.end..], 0., color(Color32::LIGHT_GRAY));
+        }
+        None => job.append(name, 0., color(Color32::LIGHT_GRAY)),
+    }
+    job.append(&format!("[0x{size:X}]"), 6., color(Color32::GRAY));
+    if let Some((warning, _)) = warning {
+        job.append("⚠", 4., color(warning));
+    }
+    job.into()
+}
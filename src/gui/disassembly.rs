@@ -0,0 +1,256 @@
+use crate::{
+    address::evaluate_address,
+    disasm::{self, Instruction, Token},
+    process::Process,
+    state::StateRef,
+};
+use eframe::{
+    egui::{Align, Context, Key, Label, RichText, ScrollArea, Sense, TextEdit, Ui, Window},
+    epaint::{Color32, RectShape, Shape},
+};
+
+/// Bytes decoded at once.
+const PAGE_SIZE: usize = 256;
+/// Number of instruction bytes shown before the byte column is cut off.
+const SHOWN_BYTES: usize = 8;
+
+const MNEMONIC_COLOR: Color32 = Color32::LIGHT_BLUE;
+const BRANCH_COLOR: Color32 = Color32::GOLD;
+const REGISTER_COLOR: Color32 = Color32::KHAKI;
+const IMMEDIATE_COLOR: Color32 = Color32::LIGHT_RED;
+const HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(0x1A, 0x2A, 0x44);
+
+/// Decoded instructions of `PAGE_SIZE` bytes starting at an address.
+pub struct DisassemblyWindow {
+    state: StateRef,
+    shown: bool,
+
+    address: usize,
+    address_buffer: String,
+    address_error: Option<String>,
+    /// Instruction that was jumped to with an arrow, it's highlighted and scrolled to once.
+    highlighted: Option<usize>,
+    scroll: bool,
+}
+
+impl DisassemblyWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            address: 0,
+            address_buffer: String::new(),
+            address_error: None,
+            highlighted: None,
+            scroll: false,
+        }
+    }
+
+    /// Shows instructions at `address`, warns if the memory isn't executable.
+    pub fn open(&mut self, address: usize) {
+        self.shown = true;
+        self.go_to(address);
+
+        let state = &mut *self.state.borrow_mut();
+        let process = state.process.read();
+        let Some(process) = process.as_ref() else {
+            return;
+        };
+
+        // Minidumps and some plugins don't know protection of regions.
+        let Ok(regions) = process.regions() else {
+            return;
+        };
+        let executable = match regions.iter().find(|r| r.range.contains(&address)) {
+            Some(region) => region.prot.is_none_or(|p| p.execute()),
+            None => false,
+        };
+        if !executable {
+            state
+                .toasts
+                .warning(format!("Memory at {address:X} is not executable"));
+        }
+    }
+
+    fn go_to(&mut self, address: usize) {
+        self.address = address;
+        self.address_buffer = format!("{address:X}");
+        self.address_error = None;
+        self.highlighted = None;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Disassembly")
+            .open(&mut shown)
+            .default_height(400.)
+            .show(ctx, |ui| {
+                let state = self.state;
+                let state = &*state.borrow();
+                let process_lock = state.process.read();
+                let Some(process) = process_lock.as_ref() else {
+                    ui.centered_and_justified(|ui| {
+                        ui.heading("Attach to a process first");
+                    });
+                    return;
+                };
+
+                let mut buf = vec![0; PAGE_SIZE];
+                process.read(self.address, &mut buf);
+                let instructions = disasm::decode(&buf, self.address, process.pointer_size() == 8);
+                let next = instructions
+                    .last()
+                    .map(|i| i.address.wrapping_add(i.bytes.len()))
+                    .unwrap_or(self.address.wrapping_add(PAGE_SIZE));
+
+                ui.horizontal(|ui| {
+                    let r = ui.add(
+                        TextEdit::singleline(&mut self.address_buffer)
+                            .hint_text("Address")
+                            .desired_width(160.),
+                    );
+                    let entered = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    if ui.button("Go to address").clicked() || entered {
+                        match evaluate_address(
+                            &self.address_buffer,
+                            process,
+                            &state.named_addresses,
+                        ) {
+                            Ok(address) => self.go_to(address),
+                            Err(e) => self.address_error = Some(e.to_string()),
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("⏴ Previous 256 bytes").clicked() {
+                        self.go_to(self.address.saturating_sub(PAGE_SIZE));
+                    }
+                    if ui.button("Next 256 bytes ⏵").clicked() {
+                        self.go_to(next);
+                    }
+                });
+
+                if let Some(error) = &self.address_error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| self.listing(ui, process, &instructions));
+            });
+        self.shown = shown;
+    }
+
+    fn listing(&mut self, ui: &mut Ui, process: &Process, instructions: &[Instruction]) {
+        ui.spacing_mut().item_spacing.x = 4.;
+        let range = self.address..self.address.saturating_add(PAGE_SIZE);
+        let mut go_to = None;
+
+        for instruction in instructions {
+            // Filled in after the row is drawn and its size is known.
+            let background = ui.painter().add(Shape::Noop);
+            let r = ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("{:012X}", instruction.address))
+                        .monospace()
+                        .color(Color32::LIGHT_GREEN),
+                );
+
+                let mut bytes = instruction
+                    .bytes
+                    .iter()
+                    .take(SHOWN_BYTES)
+                    .map(|b| format!("{b:02X}"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if instruction.bytes.len() > SHOWN_BYTES {
+                    bytes.push('…');
+                }
+                ui.label(
+                    RichText::new(format!("{bytes:<w$}", w = SHOWN_BYTES * 3))
+                        .monospace()
+                        .color(Color32::DARK_GRAY),
+                );
+
+                // Arrows of jumps within the listing scroll to the target, others open it.
+                match instruction.target {
+                    Some(target) if instructions.iter().any(|i| i.address == target) => {
+                        let arrow = if target < instruction.address {
+                            "⏶"
+                        } else {
+                            "⏷"
+                        };
+                        let r = ui
+                            .add(Label::new(RichText::new(arrow).monospace()).sense(Sense::click()))
+                            .on_hover_text(format!("Scroll to {target:X}"));
+                        if r.clicked() {
+                            self.highlighted = Some(target);
+                            self.scroll = true;
+                        }
+                    }
+                    Some(target) => {
+                        let hint = if range.contains(&target) {
+                            "Target is in the middle of an instruction, go to"
+                        } else {
+                            "Go to"
+                        };
+                        let r = ui
+                            .add(Label::new(RichText::new("⏵").monospace()).sense(Sense::click()))
+                            .on_hover_text(format!("{hint} {target:X}"));
+                        if r.clicked() {
+                            go_to = Some(target);
+                        }
+                    }
+                    None => _ = ui.label(RichText::new(" ").monospace()),
+                }
+
+                let color = match instruction.mnemonic.as_str() {
+                    "db" => Color32::DARK_GRAY,
+                    _ if instruction.is_branch() => BRANCH_COLOR,
+                    _ => MNEMONIC_COLOR,
+                };
+                // Operands are aligned unless the mnemonic is longer than usual.
+                let mnemonic = if instruction.operands.is_empty() {
+                    instruction.mnemonic.clone()
+                } else {
+                    format!("{:<6} ", instruction.mnemonic)
+                };
+                ui.label(RichText::new(mnemonic).monospace().color(color));
+
+                ui.spacing_mut().item_spacing.x = 0.;
+                for token in &instruction.operands {
+                    let (text, color) = match token {
+                        Token::Register(s) => (s, REGISTER_COLOR),
+                        Token::Immediate(s) => (s, IMMEDIATE_COLOR),
+                        Token::Text(s) => (s, Color32::GRAY),
+                    };
+                    ui.label(RichText::new(text).monospace().color(color));
+                }
+
+                if let Some(symbol) = instruction.target.and_then(|t| process.resolve_symbol(t)) {
+                    ui.label(
+                        RichText::new(format!("  ; {symbol}"))
+                            .monospace()
+                            .color(Color32::GRAY),
+                    );
+                }
+            });
+
+            if self.highlighted == Some(instruction.address) {
+                ui.painter().set(
+                    background,
+                    RectShape::filled(r.response.rect, 0., HIGHLIGHT_COLOR),
+                );
+                if std::mem::take(&mut self.scroll) {
+                    r.response.scroll_to_me(Some(Align::Center));
+                }
+            }
+        }
+
+        if let Some(target) = go_to {
+            self.go_to(target);
+        }
+    }
+}
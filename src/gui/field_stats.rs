@@ -0,0 +1,240 @@
+use crate::{
+    field::{walk_fields, FieldKind},
+    state::StateRef,
+};
+use eframe::{
+    egui::{Context, Grid, RichText, ScrollArea, Sense, Ui, Window},
+    epaint::{pos2, vec2, Color32, Shape, Stroke},
+};
+use std::{collections::BTreeMap, f32::consts::TAU};
+
+const PIE_RADIUS: f32 = 70.;
+/// Pie slices are drawn as polygons with a point per this many radians of the arc.
+const ARC_STEP: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    Unknown,
+    Pointer,
+    Integer,
+    Float,
+    String,
+    Other,
+}
+
+impl Category {
+    const ALL: [Category; 6] = [
+        Self::Unknown,
+        Self::Pointer,
+        Self::Integer,
+        Self::Float,
+        Self::String,
+        Self::Other,
+    ];
+
+    fn of(kind: &FieldKind) -> Self {
+        match kind {
+            FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => {
+                Self::Unknown
+            }
+            FieldKind::Ptr
+            | FieldKind::StrPtr
+            | FieldKind::WStrPtr
+            | FieldKind::RelPtr32
+            | FieldKind::RelPtr64
+            | FieldKind::FuncPtr
+            | FieldKind::Vtable(_)
+            | FieldKind::PointerChain(_) => Self::Pointer,
+            FieldKind::I8
+            | FieldKind::I16
+            | FieldKind::I32
+            | FieldKind::I64
+            | FieldKind::U8
+            | FieldKind::U16
+            | FieldKind::U32
+            | FieldKind::U64
+            | FieldKind::Bool
+            | FieldKind::Enum(_)
+            | FieldKind::Bitfield(..)
+            | FieldKind::Flags(_) => Self::Integer,
+            FieldKind::F32
+            | FieldKind::F64
+            | FieldKind::Vec2
+            | FieldKind::Vec3
+            | FieldKind::Vec4
+            | FieldKind::Mat4 => Self::Float,
+            FieldKind::InlineStr(_) | FieldKind::InlineWStr(_) | FieldKind::StdString(_) => {
+                Self::String
+            }
+            FieldKind::Array(inner, _) => Self::of(inner),
+            _ => Self::Other,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown bytes",
+            Self::Pointer => "Pointers",
+            Self::Integer => "Integers",
+            Self::Float => "Floats",
+            Self::String => "Strings",
+            Self::Other => "Other",
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Self::Unknown => Color32::DARK_GRAY,
+            Self::Pointer => Color32::YELLOW,
+            Self::Integer => Color32::LIGHT_GREEN,
+            Self::Float => Color32::LIGHT_RED,
+            Self::String => Color32::LIGHT_BLUE,
+            Self::Other => Color32::from_rgb(0xC0, 0x90, 0xE0),
+        }
+    }
+}
+
+/// Bytes and fields of the selected class by kind, shows how much of it is still unexplored.
+pub struct FieldStatsWindow {
+    state: StateRef,
+    shown: bool,
+}
+
+impl FieldStatsWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Field statistics")
+            .open(&mut shown)
+            .default_size([360., 420.])
+            .show(ctx, |ui| self.stats(ui));
+        self.shown = shown;
+    }
+
+    fn stats(&mut self, ui: &mut Ui) {
+        let state = self.state.borrow();
+        let Some(class) = state.class_list.selected_class() else {
+            ui.label("Select a class first");
+            return;
+        };
+
+        // Sections only group other fields, their bytes are counted by the fields themselves.
+        let fields = walk_fields(&class.fields, 0)
+            .into_iter()
+            .filter(|(_, f)| f.section_fields().is_none())
+            .collect::<Vec<_>>();
+
+        let mut bytes = BTreeMap::<Category, usize>::new();
+        let mut counts = BTreeMap::<String, usize>::new();
+        for (_, field) in &fields {
+            let kind = field.kind();
+            *bytes.entry(Category::of(&kind)).or_default() += field.size();
+            *counts.entry(format!("{kind:?}")).or_default() += 1;
+        }
+
+        let total = bytes.values().sum::<usize>();
+        let unknown = bytes.get(&Category::Unknown).copied().unwrap_or_default();
+        let known = if total == 0 {
+            0.
+        } else {
+            (total - unknown) as f32 / total as f32 * 100.
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&class.name).strong());
+            ui.label(format!("0x{total:X} bytes, {} fields", fields.len()));
+        });
+        ui.label(format!("{known:.1}% of the class is known"));
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            pie_chart(ui, &bytes, total);
+
+            Grid::new("_field_stats_categories")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for category in Category::ALL {
+                        let size = bytes.get(&category).copied().unwrap_or_default();
+                        ui.colored_label(category.color(), "■");
+                        ui.label(category.label());
+                        ui.label(format!("{:.1}%", percent(size, total)));
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.separator();
+
+        ui.label("Fields by kind");
+        ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("_field_stats_kinds")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    let mut counts = counts.into_iter().collect::<Vec<_>>();
+                    counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+                    for (kind, count) in counts {
+                        ui.label(kind);
+                        ui.label(count.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+fn percent(size: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.
+    } else {
+        size as f32 / total as f32 * 100.
+    }
+}
+
+fn pie_chart(ui: &mut Ui, bytes: &BTreeMap<Category, usize>, total: usize) {
+    let (rect, _) = ui.allocate_exact_size(vec2(PIE_RADIUS * 2., PIE_RADIUS * 2.), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let center = rect.center();
+
+    if total == 0 {
+        painter.circle_stroke(center, PIE_RADIUS, Stroke::new(1., Color32::DARK_GRAY));
+        return;
+    }
+
+    let point = |angle: f32| pos2(angle.cos(), angle.sin()) * PIE_RADIUS + center.to_vec2();
+    let mut start = -TAU / 4.;
+    for (category, size) in bytes {
+        let sweep = *size as f32 / total as f32 * TAU;
+        // Convex polygons can't cover more than half of the circle.
+        for half in 0..2 {
+            let (from, to) = (
+                start + sweep / 2. * half as f32,
+                start + sweep / 2. * (half + 1) as f32,
+            );
+            let mut points = vec![center];
+            let steps = ((to - from) / ARC_STEP).ceil().max(1.) as usize;
+            points.extend((0..=steps).map(|i| point(from + (to - from) * i as f32 / steps as f32)));
+            painter.add(Shape::convex_polygon(
+                points,
+                category.color(),
+                Stroke::NONE,
+            ));
+        }
+        start += sweep;
+    }
+    painter.circle_stroke(
+        center,
+        PIE_RADIUS,
+        Stroke::new(1., ui.visuals().window_fill()),
+    );
+}
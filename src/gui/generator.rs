@@ -1,6 +1,12 @@
-use crate::{field::CodegenData, generator::AvailableGenerator, state::StateRef};
+use crate::{
+    field::CodegenData,
+    generator::{AvailableGenerator, CppMathTypes},
+    state::StateRef,
+};
 use eframe::{
-    egui::{ComboBox, Context, FontSelection, TextEdit, Window},
+    egui::{
+        Checkbox, CollapsingHeader, ComboBox, Context, FontSelection, Grid, TextEdit, Ui, Window,
+    },
     epaint::FontId,
 };
 
@@ -9,6 +15,8 @@ pub struct GeneratorWindow {
     state: StateRef,
     shown: bool,
     output: Option<String>,
+    // Generates only the selected fields and classes they depend on.
+    selected_only: bool,
 }
 
 impl GeneratorWindow {
@@ -17,6 +25,7 @@ impl GeneratorWindow {
             state,
             shown: false,
             output: None,
+            selected_only: false,
             current_generator: AvailableGenerator::default(),
         }
     }
@@ -46,17 +55,61 @@ impl GeneratorWindow {
                         }
                     });
 
+                if self.current_generator == AvailableGenerator::Cpp {
+                    Self::math_types_ui(self.state, ui);
+                }
+
+                ui.add_enabled(
+                    self.state.borrow().selection.is_some(),
+                    Checkbox::new(&mut self.selected_only, "Only selected fields"),
+                )
+                .on_hover_text("Other fields of the class are generated as padding");
+
                 ui.horizontal(|ui| {
                     if ui.button("Generate").clicked() {
-                        let mut gen = self.current_generator.generator();
                         let state = self.state.borrow();
+                        let container = state
+                            .selection
+                            .filter(|_| self.selected_only)
+                            .map(|s| s.container_id);
+                        let selected = state.selected_fields();
+                        let mut gen = self
+                            .current_generator
+                            .generator(&state.config, state.class_list.pointer_size());
                         let data = CodegenData {
                             classes: state.class_list.classes(),
+                            enums: state.class_list.enums(),
                         };
 
-                        for class in state.class_list.classes() {
+                        for def in data.enums.definitions().iter() {
+                            gen.add_enum(def);
+                        }
+
+                        // Base and embedded classes have to be defined first.
+                        for class in state.class_list.in_dependency_order() {
+                            if container
+                                .is_some_and(|id| !state.class_list.depends_on(id, class.id()))
+                            {
+                                continue;
+                            }
+
                             gen.begin_class(&class.name);
+                            if let Some(base) = state.class_list.base_of(class.id()) {
+                                gen.add_base_class(
+                                    &state.class_list.by_id(base).unwrap().name,
+                                    state.class_list.class_size(base),
+                                );
+                            }
                             for field in class.fields.iter() {
+                                if container == Some(class.id()) && !selected.contains(&field.id())
+                                {
+                                    gen.add_offset(field.size());
+                                    continue;
+                                }
+
+                                if let Some(comment) = field.comment() {
+                                    gen.add_comment(&comment);
+                                }
                                 field.codegen(&mut *gen, &data);
                             }
                             gen.end_class();
@@ -82,4 +135,41 @@ impl GeneratorWindow {
                 }
             });
     }
+
+    fn math_types_ui(state: StateRef, ui: &mut Ui) {
+        let config = &mut state.borrow_mut().config;
+        let mut types = config.cpp_math_types.clone().unwrap_or_default();
+
+        CollapsingHeader::new("Math types").show(ui, |ui| math_types_editor(ui, &mut types));
+
+        if config.cpp_math_types.as_ref() != Some(&types) {
+            config.cpp_math_types = Some(types);
+            config.save();
+        }
+    }
+}
+
+/// Presets and type names that C++ math fields are generated with, shared with the settings.
+pub(super) fn math_types_editor(ui: &mut Ui, types: &mut CppMathTypes) {
+    ui.horizontal(|ui| {
+        if ui.button("glm").clicked() {
+            *types = CppMathTypes::glm();
+        }
+        if ui.button("DirectXMath").clicked() {
+            *types = CppMathTypes::directx();
+        }
+    });
+
+    Grid::new(ui.id().with("_cpp_math_types")).show(ui, |ui| {
+        for (label, name) in [
+            ("Vec2", &mut types.vec2),
+            ("Vec3", &mut types.vec3),
+            ("Vec4", &mut types.vec4),
+            ("Mat4", &mut types.mat4),
+        ] {
+            ui.label(label);
+            ui.text_edit_singleline(name);
+            ui.end_row();
+        }
+    });
 }
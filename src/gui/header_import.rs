@@ -0,0 +1,115 @@
+use crate::{header_import::import_header, history::Command, state::StateRef};
+use eframe::{
+    egui::{Button, Context, RichText, ScrollArea, TextEdit, Window},
+    epaint::Color32,
+};
+use std::fs;
+
+const HINT: &str = "struct Player {\n    uint32_t health; // Current health\n    float position[3];\n    char* name;\n    Weapon* weapon;\n};";
+
+/// Creates classes from structs pasted or loaded from a C header.
+pub struct HeaderImportWindow {
+    state: StateRef,
+    shown: bool,
+
+    source: String,
+    error: Option<String>,
+}
+
+impl HeaderImportWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            source: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Import from C header")
+            .open(&mut shown)
+            .default_size([420., 360.])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Open header").clicked() {
+                        self.open_file();
+                    }
+
+                    if ui
+                        .add_enabled(!self.source.trim().is_empty(), Button::new("Import"))
+                        .clicked()
+                    {
+                        self.import();
+                    }
+                });
+                ui.label(
+                    RichText::new(
+                        "Fixed width integers, float, double, bool, char*, wchar_t*, pointers, \
+                         arrays and embedded structs are supported",
+                    )
+                    .color(Color32::GRAY),
+                );
+
+                if let Some(error) = &self.error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.add(
+                        TextEdit::multiline(&mut self.source)
+                            .code_editor()
+                            .hint_text(HINT)
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(16),
+                    );
+                });
+            });
+        self.shown = shown;
+    }
+
+    fn open_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("C header", &["h", "hpp", "hxx"])
+            .add_filter("Any file", &["*"])
+            .set_title("Import from C header")
+            .pick_file()
+        else {
+            return;
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(source) => {
+                self.source = source;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    fn import(&mut self) {
+        let state = &mut *self.state.borrow_mut();
+        match import_header(&self.source, &mut state.class_list) {
+            Ok(ids) => {
+                self.error = None;
+                *state.class_list.selected_mut() = ids.first().copied();
+                state.clear_selection();
+                state
+                    .toasts
+                    .success(format!("Imported {} classes", ids.len()));
+                state.history.push(Command::Batch(
+                    ids.into_iter().map(Command::AddClass).collect(),
+                ));
+                state.dummy = false;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{address::evaluate_address, process::Process, state::StateRef};
+use eframe::{
+    egui::{
+        Context, Event, Id, Key, Label, Modifiers, Response, RichText, Sense, TextEdit, Ui, Window,
+    },
+    epaint::Color32,
+};
+use egui_notify::Toasts;
+
+/// Stores the frame in which a hex editor last took typed digits.
+const TYPING_ID: &str = "_hex_editor_typing";
+
+/// Returns `true` if a hex editor took typed digits this or the previous frame, digit hotkeys
+/// are ignored while it does.
+pub fn is_typing_hex(ctx: &Context) -> bool {
+    ctx.data(|d| d.get_temp::<u64>(Id::new(TYPING_ID)))
+        .is_some_and(|frame| frame + 1 >= ctx.cumulative_frame_nr())
+}
+
+/// Hex and ASCII view of memory where bytes can be selected and overwritten by typing hex digits.
+/// Used by the hex editor window and expanded hex fields.
+pub struct HexEditor {
+    pub address: usize,
+    pub bytes_per_row: usize,
+    pub rows: usize,
+    selected: Option<usize>,
+    /// High half of the selected byte that has been typed already.
+    nibble: Option<u8>,
+    /// Typed digits go to the selected byte, clicking outside of the bytes stops editing.
+    editing: bool,
+}
+
+impl HexEditor {
+    pub fn new(address: usize, bytes_per_row: usize, rows: usize) -> Self {
+        Self {
+            address,
+            bytes_per_row,
+            rows,
+            selected: None,
+            nibble: None,
+            editing: false,
+        }
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.bytes_per_row * self.rows
+    }
+
+    pub fn page_up(&mut self) {
+        self.address = self.address.saturating_sub(self.page_size());
+    }
+
+    pub fn page_down(&mut self) {
+        self.go_to(self.address.saturating_add(self.page_size()));
+    }
+
+    /// Moves the page to the address, clamped so the addresses of its bytes don't overflow.
+    pub fn go_to(&mut self, address: usize) {
+        self.address = address.min(usize::MAX - self.page_size());
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, process: &Process, verify: bool, toasts: &mut Toasts) {
+        // The row width may have changed since the page was moved.
+        self.go_to(self.address);
+        let read_only = process.is_read_only();
+        let mut buf = vec![0; self.page_size()];
+        process.read(self.address, &mut buf);
+
+        let mut clicked = None;
+        let r = ui.vertical(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.;
+
+            for (row, bytes) in buf.chunks(self.bytes_per_row).enumerate() {
+                let row_address = self.address + row * self.bytes_per_row;
+                let readable = process.can_read(row_address);
+
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{row_address:012X}"))
+                            .monospace()
+                            .color(Color32::LIGHT_GREEN),
+                    );
+                    ui.add_space(4.);
+
+                    for (i, b) in bytes.iter().enumerate() {
+                        let address = row_address + i;
+                        let text = match (readable, self.nibble) {
+                            (false, _) => "??".to_owned(),
+                            (true, Some(high)) if self.selected == Some(address) => {
+                                format!("{high:X}_")
+                            }
+                            (true, _) => format!("{b:02X}"),
+                        };
+                        let color = if *b == 0 || !readable {
+                            Color32::DARK_GRAY
+                        } else {
+                            Color32::LIGHT_GRAY
+                        };
+
+                        if self.byte(ui, address, text, color).clicked() {
+                            clicked = Some(address);
+                        }
+                    }
+                    ui.add_space(8.);
+
+                    for (i, b) in bytes.iter().enumerate() {
+                        let (text, color) = if !readable {
+                            ("?".to_owned(), Color32::DARK_GRAY)
+                        } else if b.is_ascii_graphic() || *b == b' ' {
+                            (char::from(*b).to_string(), Color32::LIGHT_GREEN)
+                        } else {
+                            (".".to_owned(), Color32::DARK_GRAY)
+                        };
+
+                        if self.byte(ui, row_address + i, text, color).clicked() {
+                            clicked = Some(row_address + i);
+                        }
+                    }
+                });
+            }
+        });
+
+        if let Some(address) = clicked {
+            self.selected = Some(address);
+            self.nibble = None;
+            self.editing = true;
+        } else if ui.input(|i| i.pointer.any_click()) || ui.input(|i| i.key_pressed(Key::Escape)) {
+            self.editing = false;
+            self.nibble = None;
+        }
+
+        if self.editing || r.response.hovered() {
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::PageUp)) {
+                self.page_up();
+            }
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::PageDown)) {
+                self.page_down();
+            }
+        }
+
+        if self.editing && !read_only {
+            let frame = ui.ctx().cumulative_frame_nr();
+            ui.ctx()
+                .data_mut(|d| d.insert_temp(Id::new(TYPING_ID), frame));
+            self.type_digits(ui, process, verify, toasts);
+        }
+
+        match self.selected {
+            Some(address) => {
+                let text = format!(
+                    "Selected {address:X}, offset {}{:X}",
+                    if address < self.address { "-" } else { "+" },
+                    address.abs_diff(self.address)
+                );
+                ui.label(RichText::new(text).monospace().color(Color32::GRAY));
+            }
+            None if read_only => {
+                ui.label(RichText::new("Process is read only").color(Color32::GRAY));
+            }
+            None => {
+                ui.label(RichText::new("Click a byte to edit it").color(Color32::GRAY));
+            }
+        }
+    }
+
+    fn byte(&self, ui: &mut Ui, address: usize, text: String, color: Color32) -> Response {
+        let mut text = RichText::new(text).monospace().color(color);
+        if self.selected == Some(address) {
+            text = text
+                .color(Color32::BLACK)
+                .background_color(if self.editing {
+                    Color32::LIGHT_BLUE
+                } else {
+                    Color32::GRAY
+                });
+        }
+        ui.add(Label::new(text).sense(Sense::click()))
+    }
+
+    /// Writes typed hex digits to the selected byte, the selection moves on once both halves are
+    /// typed. Failed writes are shown as toasts.
+    fn type_digits(&mut self, ui: &mut Ui, process: &Process, verify: bool, toasts: &mut Toasts) {
+        let Some(address) = self.selected else {
+            return;
+        };
+
+        let digits = ui.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|e| match e {
+                    Event::Text(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .collect::<String>()
+        });
+
+        let mut address = address;
+        for digit in digits.chars().filter_map(|c| c.to_digit(16)) {
+            match self.nibble.take() {
+                None => self.nibble = Some(digit as u8),
+                Some(high) => {
+                    if let Err(e) = process.write(address, &[high << 4 | digit as u8], verify) {
+                        toasts.error(format!("Failed to write to {address:X}. {e}"));
+                    }
+                    address = address.saturating_add(1);
+                }
+            }
+        }
+
+        // Keep the selection on the screen.
+        if address >= self.address + self.page_size() {
+            self.go_to(self.address + self.bytes_per_row);
+        }
+        self.selected = Some(address);
+    }
+}
+
+pub struct HexEditorWindow {
+    state: StateRef,
+    shown: bool,
+
+    editor: HexEditor,
+    address_buffer: String,
+    address_error: Option<String>,
+}
+
+impl HexEditorWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            editor: HexEditor::new(0, 16, 16),
+            address_buffer: String::new(),
+            address_error: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+
+        if self.shown && self.address_buffer.is_empty() {
+            let state = self.state.borrow();
+            let address = state
+                .selection
+                .map(|s| s.address)
+                .or_else(|| state.class_list.selected_class().map(|c| c.address.get()))
+                .unwrap_or(0);
+            self.editor.go_to(address);
+            self.address_buffer = format!("{:X}", self.editor.address);
+        }
+    }
+
+    /// Shows the window at the address.
+    pub fn open(&mut self, address: usize) {
+        self.shown = true;
+        self.editor.go_to(address);
+        self.address_buffer = format!("{address:X}");
+        self.address_error = None;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Hex editor")
+            .open(&mut shown)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let state = &mut *self.state.borrow_mut();
+                let process_lock = state.process.read();
+                let Some(process) = process_lock.as_ref() else {
+                    ui.centered_and_justified(|ui| {
+                        ui.heading("Attach to a process first");
+                    });
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    let r = ui.add(
+                        TextEdit::singleline(&mut self.address_buffer)
+                            .hint_text("Address")
+                            .desired_width(160.),
+                    );
+                    let entered = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                    if ui.button("Go to address").clicked() || entered {
+                        match evaluate_address(
+                            &self.address_buffer,
+                            process,
+                            &state.named_addresses,
+                        ) {
+                            Ok(address) => {
+                                self.editor.go_to(address);
+                                self.address_error = None;
+                            }
+                            Err(e) => self.address_error = Some(e.to_string()),
+                        }
+                    }
+
+                    ui.separator();
+                    for width in [16, 32] {
+                        if ui
+                            .selectable_label(
+                                self.editor.bytes_per_row == width,
+                                format!("{width} bytes"),
+                            )
+                            .clicked()
+                        {
+                            self.editor.bytes_per_row = width;
+                        }
+                    }
+
+                    ui.separator();
+                    if ui.button("⏶").on_hover_text("Page Up").clicked() {
+                        self.editor.page_up();
+                    }
+                    if ui.button("⏷").on_hover_text("Page Down").clicked() {
+                        self.editor.page_down();
+                    }
+                });
+
+                if let Some(error) = &self.address_error {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                }
+                ui.separator();
+
+                self.editor
+                    .show(ui, process, state.config.verify_writes(), &mut state.toasts);
+            });
+        self.shown = shown;
+    }
+}
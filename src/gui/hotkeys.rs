@@ -0,0 +1,160 @@
+use crate::{
+    config::{CHANGE_KIND_HOTKEYS, DEFAULT_CHANGE_KINDS},
+    field::FieldKind,
+    state::StateRef,
+};
+use eframe::{
+    egui::{Button, CollapsingHeader, ComboBox, Context, Grid, Key, Modifiers, RichText, Window},
+    epaint::Color32,
+};
+
+pub struct HotkeysWindow {
+    state: StateRef,
+    shown: bool,
+    // Hotkey that is waiting for a new key combination.
+    capturing: Option<&'static str>,
+}
+
+impl HotkeysWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            capturing: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+        self.capturing = None;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Hotkeys")
+            .open(&mut shown)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let state = &mut *self.state.borrow_mut();
+
+                if let Some(name) = self.capturing {
+                    let (key, modifiers) =
+                        ui.input(|i| (i.keys_down.iter().next().copied(), i.modifiers));
+
+                    match key {
+                        Some(Key::Escape) => self.capturing = None,
+                        Some(key) => {
+                            // Only modifiers that can be written to the config.
+                            let modifiers = Modifiers {
+                                alt: modifiers.alt,
+                                ctrl: modifiers.ctrl,
+                                shift: modifiers.shift,
+                                ..Default::default()
+                            };
+                            // Don't trigger whatever is bound to the combination already.
+                            ui.input_mut(|i| i.consume_key(modifiers, key));
+
+                            state.hotkeys.rebind(name, key, modifiers);
+                            state.config.hotkeys = Some(state.hotkeys.overrides());
+                            state.config.save();
+                            self.capturing = None;
+                        }
+                        None => {}
+                    }
+                }
+
+                let entries = state.hotkeys.entries();
+                Grid::new("_hotkeys")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, shortcut) in &entries {
+                            let conflict = entries
+                                .iter()
+                                .any(|(other, s)| other != name && s == shortcut);
+
+                            ui.label(name.replace('_', " "));
+
+                            let text = if self.capturing == Some(*name) {
+                                RichText::new("Press a key...").italics()
+                            } else {
+                                let text = RichText::new(ui.ctx().format_shortcut(shortcut));
+                                if conflict {
+                                    text.color(Color32::RED)
+                                } else {
+                                    text
+                                }
+                            };
+
+                            let r = ui.add(Button::new(text).min_size([120., 0.].into()));
+                            let r = if conflict {
+                                r.on_hover_text("Another hotkey uses the same shortcut")
+                            } else {
+                                r.on_hover_text("Click and press a new shortcut, Escape cancels")
+                            };
+                            if r.clicked() {
+                                self.capturing = Some(*name);
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+
+                CollapsingHeader::new("Field kinds")
+                    .id_salt("_change_kind_hotkeys")
+                    .show(ui, |ui| {
+                        ui.label(
+                            "Kinds the selected fields change to with the change kind hotkeys",
+                        );
+                        Grid::new("_change_kinds").num_columns(2).show(ui, |ui| {
+                            for (i, name) in CHANGE_KIND_HOTKEYS.into_iter().enumerate() {
+                                ui.label(state.hotkeys.format(name, ui.ctx()));
+
+                                let current = state.config.change_kind(i).cloned();
+                                let mut selected = current.clone();
+                                ComboBox::from_id_salt(name)
+                                    .selected_text(
+                                        current
+                                            .as_ref()
+                                            .map(|k| format!("{k:?}"))
+                                            .unwrap_or_default(),
+                                    )
+                                    .show_ui(ui, |ui| {
+                                        for kind in FieldKind::SIMPLE {
+                                            ui.selectable_value(
+                                                &mut selected,
+                                                Some(kind.clone()),
+                                                format!("{kind:?}"),
+                                            );
+                                        }
+                                    });
+                                if selected != current {
+                                    let mut kinds = (0..DEFAULT_CHANGE_KINDS.len())
+                                        .filter_map(|i| state.config.change_kind(i).cloned())
+                                        .collect::<Vec<_>>();
+                                    if let (Some(slot), Some(kind)) = (kinds.get_mut(i), selected) {
+                                        *slot = kind;
+                                    }
+                                    state.config.change_kinds = Some(kinds);
+                                    state.config.save();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                ui.separator();
+
+                if ui.button("Reset to defaults").clicked() {
+                    state.hotkeys.reset();
+                    state.config.hotkeys = None;
+                    state.config.change_kinds = None;
+                    state.config.save();
+                    self.capturing = None;
+                }
+            });
+        self.shown = shown;
+    }
+}
@@ -1,17 +1,84 @@
 use crate::{
-    address::parse_address, context::InspectionContext, field::FieldKind, field::FieldResponse,
-    state::StateRef, FID_M,
+    address::{evaluate_address, parse_address},
+    class::{ClassId, ClassList},
+    context::{Columns, FieldSearch, InspectionContext, Selection},
+    field::{
+        array_elements, display_class_fields, name_address_menu, walk_fields, ColorLayout, FieldId,
+        FieldKind, FieldResponse, GuidFormat, StringAbi, TimestampFormat, DEFAULT_VTABLE_COUNT,
+        MAX_ARRAY_ELEMENTS,
+    },
+    fonts::fid_m,
+    process::Process,
+    snapshot::{Change, Snapshot},
+    state::{GlobalState, StateRef},
 };
 use eframe::{
     egui::{
-        collapsing_header::CollapsingState, scroll_area::ScrollSource, Button, CentralPanel,
-        Context, Id, RichText, ScrollArea, Ui,
+        collapsing_header::CollapsingState, scroll_area::ScrollSource, Align, Button, CentralPanel,
+        Checkbox, ComboBox, Context, Id, InputState, Key, Label, Layout, RichText, ScrollArea,
+        Sense, SidePanel, TextEdit, Ui,
+    },
+    epaint::{
+        text::{LayoutJob, TextFormat},
+        vec2, Color32, CornerRadius, FontId,
     },
-    epaint::{vec2, Color32, CornerRadius, FontId},
 };
 use fastrand::Rng;
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::take,
+};
 
-use super::ToolBarResponse;
+use super::{size_warning, TextEditFromStrBind, ToolBarResponse};
+
+/// Number of previous addresses remembered per class.
+const ADDRESS_HISTORY_DEPTH: usize = 50;
+
+/// Addresses a class was inspected at, to go back to them like in a browser.
+#[derive(Default)]
+struct AddressHistory {
+    back: VecDeque<usize>,
+    forward: Vec<usize>,
+    // Address of the class the last frame, changes from anywhere are recorded.
+    current: Option<usize>,
+}
+
+impl AddressHistory {
+    fn visit(&mut self, address: usize) {
+        if let Some(current) = self.current.filter(|c| *c != address) {
+            if self.back.len() == ADDRESS_HISTORY_DEPTH {
+                self.back.pop_front();
+            }
+            self.back.push_back(current);
+            self.forward.clear();
+        }
+        self.current = Some(address);
+    }
+
+    fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    fn back(&mut self) -> Option<usize> {
+        let current = self.current?;
+        let address = self.back.pop_back()?;
+        self.forward.push(current);
+        self.current = Some(address);
+        Some(address)
+    }
+
+    fn forward(&mut self) -> Option<usize> {
+        let current = self.current?;
+        let address = self.forward.pop()?;
+        self.back.push_back(current);
+        self.current = Some(address);
+        Some(address)
+    }
+}
 
 macro_rules! create_change_field_type_group {
     ($ui:ident, $r:ident, $fg:ident, $bg:ident, $($size:ident),*) => {
@@ -31,7 +98,30 @@ macro_rules! create_change_field_type_group {
 }
 
 pub struct InspectorPanel {
+    array_count: TextEditFromStrBind<usize>,
+    array_kind: FieldKind,
+    inline_string_len: TextEditFromStrBind<usize>,
+    dynamic_array_kind: FieldKind,
     address_buffer: String,
+    // Why the last address expression couldn't be evaluated, shown next to it.
+    address_error: Option<String>,
+    address_history: HashMap<ClassId, AddressHistory>,
+    // Opened with Ctrl-F, closed with Escape.
+    field_search: Option<FieldSearch>,
+    // Hex offset typed after Ctrl-G, closed with Escape.
+    go_to_offset: Option<String>,
+    // Field jumped to, scrolled into view the next frame.
+    scroll_to: Option<FieldId>,
+    show_deltas: bool,
+    // Highlights fields that differ in the secondary process.
+    compare_secondary: bool,
+    // Index of the snapshot the inspected class is compared with.
+    diff_snapshot: Option<usize>,
+    snapshot_name: String,
+    // Hex size typed in the fill menu.
+    fill_size: String,
+    // Parts of field rows shown, only for this session.
+    columns: Columns,
     state: StateRef,
     allow_scroll: bool,
 }
@@ -41,7 +131,22 @@ impl InspectorPanel {
         Self {
             state,
             allow_scroll: true,
+            array_count: TextEditFromStrBind::new_from_str_with("4", Some(4)),
+            array_kind: FieldKind::F32,
+            inline_string_len: TextEditFromStrBind::new_from_str_with("32", Some(32)),
+            dynamic_array_kind: FieldKind::Ptr,
             address_buffer: format!("0x{:X}", 0),
+            address_error: None,
+            address_history: HashMap::new(),
+            field_search: None,
+            go_to_offset: None,
+            scroll_to: None,
+            show_deltas: false,
+            compare_secondary: false,
+            diff_snapshot: None,
+            snapshot_name: String::new(),
+            fill_size: String::new(),
+            columns: Columns::default(),
         }
     }
 
@@ -82,7 +187,7 @@ impl InspectorPanel {
                     create_add_remove_group!(ui, response, Remove, 1, 2, 4, 16, 64, 256, 1024);
                 })
                 .response
-                .on_hover_text("Removes N fields");
+                .on_hover_text("Removes N fields, or every selected field if several are selected");
 
                 ui.menu_button("Insert", |ui| {
                     ui.set_width(64.);
@@ -92,15 +197,97 @@ impl InspectorPanel {
                 .response
                 .on_hover_text("Inserts N bytes");
 
+                ui.menu_button("Fill to size...", |ui| {
+                    if let Some(target) = self.fill_menu(ui) {
+                        response = Some(ToolBarResponse::FillToSize(target));
+                        ui.close();
+                    }
+                })
+                .response
+                .on_hover_text("Appends padding until the class is the given size");
+
+                ui.menu_button("Freeze", |ui| {
+                    let state = &mut *self.state.borrow_mut();
+
+                    if ui
+                        .add_enabled(state.selection.is_some(), Button::new("Freeze selected"))
+                        .clicked()
+                    {
+                        state.freeze_selected();
+                        ui.close();
+                    }
+
+                    if ui
+                        .add_enabled(!state.frozen.is_empty(), Button::new("Unfreeze all"))
+                        .clicked()
+                    {
+                        state.frozen.clear();
+                        ui.close();
+                    }
+                })
+                .response
+                .on_hover_text("Writes values of frozen fields to the process every frame");
+
+                if ui
+                    .add_enabled(
+                        self.state.borrow().selection.is_some(),
+                        Button::new("Group"),
+                    )
+                    .on_hover_text(
+                        "Moves fields from the first to the last selected one into a new embedded class.\nShift or Ctrl click fields to select several",
+                    )
+                    .clicked()
+                {
+                    response = Some(ToolBarResponse::Group);
+                }
+
+                if ui
+                    .add_enabled(
+                        self.state.borrow().selection.is_some(),
+                        Button::new("Section"),
+                    )
+                    .on_hover_text(
+                        "Moves fields from the first to the last selected one into a collapsible section of the class",
+                    )
+                    .clicked()
+                {
+                    response = Some(ToolBarResponse::Section);
+                }
+
+                ui.menu_button("Snapshots", |ui| self.snapshots_menu(ui))
+                    .response
+                    .on_hover_text("Compares fields with memory as it was when the snapshot was taken");
+
+                if ui
+                    .toggle_value(&mut self.show_deltas, "Δ")
+                    .on_hover_text("Shows how much values changed since the last frame")
+                    .changed()
+                {
+                    self.state.borrow_mut().previous_values.clear();
+                }
+
+                let has_secondary = self.state.borrow().secondary_process.read().is_some();
+                ui.add_enabled_ui(has_secondary, |ui| {
+                    ui.toggle_value(&mut self.compare_secondary, "⇄")
+                        .on_hover_text("Compares fields with the secondary process at the same address")
+                        .on_disabled_hover_text("Attach a secondary process first");
+                });
+
                 ui.add_space(2.);
                 ui.separator();
                 ui.add_space(2.);
 
                 self.field_change_ui(ui, &mut response);
+
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    ui.menu_button("☰", |ui| self.columns_menu(ui))
+                        .response
+                        .on_hover_text("Columns shown in field rows");
+                });
             });
 
             ui.scope(|ui| {
-                ui.style_mut().override_font_id = Some(FontId::monospace(16.));
+                ui.style_mut().override_font_id = Some(fid_m());
 
                 {
                     let state = self.state.borrow();
@@ -124,57 +311,219 @@ impl InspectorPanel {
                         let state = &mut *self.state.borrow_mut();
                         let active_class = state.class_list.selected_class()?;
 
-                        ui.label(format!("{} - ", active_class.name));
+                        let size = state.class_list.class_size(active_class.id());
+                        ui.label(format!("{} [0x{size:X}]", active_class.name));
+                        if let Some((color, reason)) = size_warning(size, state.class_list.pointer_size()) {
+                            ui.label(RichText::new("⚠").color(color)).on_hover_text(reason);
+                        }
+                        ui.label(" - ");
                         ui.spacing_mut().text_edit_width = self
                             .address_buffer
                             .chars()
-                            .map(|c| ui.fonts(|f| f.glyph_width(&FID_M, c)))
+                            .map(|c| ui.fonts(|f| f.glyph_width(&fid_m(), c)))
                             .sum::<f32>()
                             .max(160.);
                         let selected_class = state.class_list.selected_class().unwrap();
 
+                        let history = self
+                            .address_history
+                            .entry(selected_class.id())
+                            .or_default();
+                        history.visit(selected_class.address.get());
+
+                        let input = ui.input(|i| i.clone());
+                        let mut navigated = None;
+                        if ui
+                            .add_enabled(history.can_go_back(), Button::new("⏴"))
+                            .on_hover_text("Previous address of the class (Alt-Left)")
+                            .clicked()
+                            || state.hotkeys.pressed("address_back", &input)
+                        {
+                            navigated = history.back();
+                        }
+
                         let r = ui.text_edit_singleline(&mut self.address_buffer);
+
+                        if ui
+                            .add_enabled(history.can_go_forward(), Button::new("⏵"))
+                            .on_hover_text("Next address of the class (Alt-Right)")
+                            .clicked()
+                            || state.hotkeys.pressed("address_forward", &input)
+                        {
+                            navigated = history.forward();
+                        }
+                        if let Some(address) = navigated {
+                            selected_class.address.set(address);
+                            self.address_error = None;
+                        }
                         if r.lost_focus() {
-                            if let Some(addr) = parse_address(&self.address_buffer) {
-                                selected_class.address.set(addr);
-                            } else {
-                                state.toasts.error("Address is in invalid format");
+                            let process = state.process.read();
+                            match evaluate_address(
+                                &self.address_buffer,
+                                process.as_ref()?,
+                                &state.named_addresses,
+                            ) {
+                                Ok(addr) => {
+                                    selected_class.address.set(addr);
+                                    self.address_error = None;
+                                }
+                                Err(e) => self.address_error = Some(e.to_string()),
                             }
                         }
 
-                        if !r.has_focus() {
+                        // Keep the invalid expression around so it can be fixed.
+                        if !r.has_focus() && self.address_error.is_none() {
                             self.address_buffer = format!("0x{:X}", selected_class.address.get());
                         }
 
+                        if let Some(error) = &self.address_error {
+                            ui.label(RichText::new(error).color(Color32::RED));
+                        }
+
+                        if let Some(name) = state.named_addresses.name(selected_class.address.get()) {
+                            ui.label(RichText::new(format!("({name})")).color(Color32::GOLD));
+                        }
+
+                        r.on_hover_text(
+                            "Hex number or an expression like `[game.exe+1A3F20]+0x10`",
+                        )
+                        .context_menu(|ui| {
+                            if ui.button("Add bookmark here").clicked() {
+                                let address = selected_class.address.get();
+                                state.bookmarks.add(
+                                    format!("{} 0x{address:X}", selected_class.name),
+                                    address,
+                                    Some(selected_class.id()),
+                                );
+                                ui.close();
+                            }
+
+                            name_address_menu(
+                                ui,
+                                &mut state.named_addresses,
+                                selected_class.address.get(),
+                            );
+
+                            if ui
+                                .button("Find references to address")
+                                .on_hover_text("Searches pointer fields of all classes")
+                                .clicked()
+                            {
+                                response = Some(ToolBarResponse::FindReferences(
+                                    selected_class.address.get(),
+                                ));
+                                ui.close();
+                            }
+                        });
+
                         Some(())
                     })
-                    .body(|ui| self.inspect(ui));
+                    .body(|ui| self.inspect(ui, &mut response));
             });
         });
 
         response
     }
 
-    fn inspect(&mut self, ui: &mut Ui) -> Option<()> {
+    fn columns_menu(&mut self, ui: &mut Ui) {
+        let columns = &mut self.columns;
+        ui.checkbox(&mut columns.offset, "Offset");
+        ui.checkbox(&mut columns.address, "Address");
+        ui.add_enabled(false, Checkbox::new(&mut true, "Bytes"))
+            .on_disabled_hover_text("Bytes of hex fields are always shown");
+        ui.checkbox(&mut columns.ascii, "ASCII");
+        ui.checkbox(&mut columns.int, "Integer");
+        ui.checkbox(&mut columns.float, "Float");
+        ui.checkbox(&mut columns.pointer, "Pointer");
+        ui.checkbox(&mut columns.string, "String");
+        ui.separator();
+        if ui.button("Reset").clicked() {
+            *columns = Columns::default();
+        }
+    }
+
+    fn inspect(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) -> Option<()> {
         let state = &mut *self.state.borrow_mut();
-        let rng = Rng::with_seed(0);
+
+        let input = ui.input(|i| i.clone());
+        self.field_search_ui(ui, state.hotkeys.pressed("find_field", &input));
+        let go_to_offset = state.hotkeys.pressed("go_to_offset", &input);
+        self.go_to_offset_ui(ui, state, go_to_offset);
+        if !ui.ctx().wants_keyboard_input() {
+            self.navigate_fields(state, &input);
+        }
 
         let process_lock = state.process.read();
+        let (process, cid) = (process_lock.as_ref()?, state.class_list.selected()?);
+        let address = state.class_list.selected_class()?.address.get();
+
+        let snapshot = self.diff_snapshot.and_then(|i| state.snapshots.get(i));
+        let changes = snapshot
+            .filter(|s| s.class_id == cid)
+            .map(|s| s.diff(&state.class_list, address, process));
+        if let Some(snapshot) = snapshot {
+            ui.horizontal(|ui| match &changes {
+                Some(changes) => {
+                    ui.label(format!(
+                        "{} fields changed since {}",
+                        changes.len(),
+                        snapshot.name
+                    ));
+                    for change in [Change::Changed, Change::FromZero, Change::ToZero] {
+                        ui.add_space(8.);
+                        ui.label(RichText::new("■").color(change.color()));
+                        ui.label(change.label());
+                    }
+                }
+                None => _ = ui.label(format!("{} is a snapshot of another class", snapshot.name)),
+            });
+        }
+
+        let secondary_lock = state.secondary_process.read();
+        let secondary = secondary_lock.as_ref().filter(|_| self.compare_secondary);
+        if let Some(secondary) = secondary {
+            let clicked = SidePanel::right("_secondary_process")
+                .resizable(true)
+                .default_width(280.)
+                .show_inside(ui, |ui| {
+                    secondary_column(ui, &state.class_list, cid, address, process, secondary)
+                })
+                .inner;
+            if let Some(selection) = clicked {
+                state.selection = Some(selection);
+                state.multi_selection.clear();
+            }
+        }
+
+        let rng = Rng::with_seed(0);
         let mut ctx = InspectionContext {
-            address: state.class_list.selected_class()?.address.get(),
-            current_container: state.class_list.selected()?,
-            process: process_lock.as_ref()?,
+            address,
+            current_container: cid,
+            process,
+            verify_writes: state.config.verify_writes(),
+            reader: &state.reader,
             class_list: &state.class_list,
             selection: state.selection,
+            multi_selection: &mut state.multi_selection,
+            extend_selection: ui.input(|i| i.modifiers.shift || i.modifiers.command),
+            frozen: &mut state.frozen,
+            recordings: &mut state.recordings,
             toasts: &mut state.toasts,
+            named_addresses: &mut state.named_addresses,
+            search: self.field_search.as_ref().filter(|s| !s.query.is_empty()),
+            previous_values: self.show_deltas.then_some(&mut state.previous_values),
+            changes: changes.as_ref(),
+            secondary,
+            columns: self.columns,
+            scroll_to: self.scroll_to.take(),
+            rename_selected: take(&mut state.rename_requested),
+            theme: state.config.theme(),
             current_id: Id::new(0),
             parent_id: Id::new(0),
             level_rng: &rng,
             offset: 0,
         };
 
-        let class = state.class_list.selected_class()?;
-
         let mut new_class = None;
         #[allow(clippy::single_match)]
         ScrollArea::vertical()
@@ -185,18 +534,36 @@ impl InspectorPanel {
             } else {
                 ScrollSource::NONE
             })
-            .show(ui, |ui| {
-                match class.fields.iter().fold(None, |r, f| {
-                    ctx.current_id = Id::new(rng.u64(..));
-                    r.or(f.draw(ui, &mut ctx))
-                }) {
-                    Some(FieldResponse::NewClass(name, id)) => new_class = Some((name, id)),
-                    Some(FieldResponse::LockScroll) => self.allow_scroll = false,
-                    Some(FieldResponse::UnlockScroll) => self.allow_scroll = true,
-                    None => {}
+            .show(ui, |ui| match display_class_fields(ui, &mut ctx, cid, 0) {
+                Some(FieldResponse::NewClass(name, id)) => new_class = Some((name, id)),
+                Some(FieldResponse::LockScroll) => self.allow_scroll = false,
+                Some(FieldResponse::UnlockScroll) => self.allow_scroll = true,
+                Some(FieldResponse::ChangeKind(kind)) => {
+                    // Field changes its own kind, the rest of the selection is left as is.
+                    ctx.multi_selection.clear();
+                    *response = Some(ToolBarResponse::ChangeKind(kind))
+                }
+                Some(FieldResponse::Reorder { from_id, to_id }) => {
+                    *response = Some(ToolBarResponse::Reorder { from_id, to_id })
+                }
+                Some(FieldResponse::FindReferences(address)) => {
+                    *response = Some(ToolBarResponse::FindReferences(address))
+                }
+                Some(FieldResponse::ViewHex(address)) => {
+                    *response = Some(ToolBarResponse::ViewHex(address))
+                }
+                Some(FieldResponse::BatchRename) => *response = Some(ToolBarResponse::BatchRename),
+                Some(FieldResponse::SwitchToClass(cid)) => {
+                    *response = Some(ToolBarResponse::SwitchToClass(cid))
                 }
+                #[cfg(feature = "disasm")]
+                Some(FieldResponse::ViewCode(address)) => {
+                    *response = Some(ToolBarResponse::ViewCode(address))
+                }
+                None => {}
             });
         state.selection = ctx.selection;
+        state.reader.finish_frame();
 
         if let Some((name, id)) = new_class {
             state.class_list.add_class_with_id(name, id);
@@ -205,6 +572,292 @@ impl InspectorPanel {
         Some(())
     }
 
+    fn fill_menu(&mut self, ui: &mut Ui) -> Option<usize> {
+        let state = self.state.borrow();
+        let Some(cid) = state.class_list.selected() else {
+            ui.label("Select a class first");
+            return None;
+        };
+        let size = state.class_list.class_size(cid);
+
+        let target = parse_address(&self.fill_size);
+        let edit = ui.add(
+            TextEdit::singleline(&mut self.fill_size)
+                .hint_text(format!("0x{size:X}"))
+                .desired_width(100.),
+        );
+        if !self.fill_size.trim().is_empty() && target.is_none() {
+            ui.colored_label(Color32::RED, "Size must be a hex number");
+        }
+
+        let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+        let clicked = ui
+            .add_enabled(target.is_some(), Button::new("Fill"))
+            .clicked();
+        (submitted || clicked).then_some(target).flatten()
+    }
+
+    fn snapshots_menu(&mut self, ui: &mut Ui) {
+        ui.set_min_width(200.);
+
+        let state = &mut *self.state.borrow_mut();
+        let Some(class) = state.class_list.selected_class() else {
+            ui.label("Select a class first");
+            return;
+        };
+        let (cid, address) = (class.id(), class.address.get());
+        let name = if self.snapshot_name.trim().is_empty() {
+            format!("{} #{}", class.name, state.snapshots.len() + 1)
+        } else {
+            self.snapshot_name.trim().to_owned()
+        };
+
+        ui.add(TextEdit::singleline(&mut self.snapshot_name).hint_text(&name));
+
+        let process_lock = state.process.read();
+        let mut snapshot = None;
+        if ui
+            .add_enabled(process_lock.is_some(), Button::new("Take snapshot"))
+            .clicked()
+        {
+            let process = process_lock.as_ref().unwrap();
+            snapshot = Some(Snapshot::take(
+                name.clone(),
+                &state.class_list,
+                cid,
+                address,
+                process,
+            ));
+        }
+
+        if ui
+            .button("Snapshot from minidump")
+            .on_hover_text("Reads the class at the same address from a minidump of the process")
+            .clicked()
+        {
+            if let Some(path) = rfd::FileDialog::new()
+                .set_title("Load minidump")
+                .add_filter("Minidump files", &["dmp", "DMP", "mdmp"])
+                .pick_file()
+            {
+                match Process::minidump(&path) {
+                    Ok(dump) => {
+                        snapshot =
+                            Some(Snapshot::take(name, &state.class_list, cid, address, &dump))
+                    }
+                    Err(e) => _ = state.toasts.error(format!("Failed to load minidump. {e}")),
+                }
+            }
+        }
+        drop(process_lock);
+
+        if let Some(snapshot) = snapshot {
+            state.snapshots.push(snapshot);
+            self.diff_snapshot = Some(state.snapshots.len() - 1);
+            self.snapshot_name.clear();
+            ui.close();
+        }
+
+        if state.snapshots.is_empty() {
+            return;
+        }
+
+        ui.separator();
+
+        let mut remove = None;
+        for (i, snapshot) in state.snapshots.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let compared = self.diff_snapshot == Some(i);
+                if ui
+                    .selectable_label(compared, &snapshot.name)
+                    .on_hover_text(format!("{:X}", snapshot.address))
+                    .clicked()
+                {
+                    self.diff_snapshot = (!compared).then_some(i);
+                }
+
+                if ui.button("🗑").on_hover_text("Delete").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = remove {
+            state.snapshots.remove(i);
+            self.diff_snapshot = match self.diff_snapshot {
+                Some(d) if d == i => None,
+                Some(d) if d > i => Some(d - 1),
+                d => d,
+            };
+        }
+    }
+
+    fn field_search_ui(&mut self, ui: &mut Ui, open: bool) {
+        if open && self.field_search.is_none() {
+            self.field_search = Some(FieldSearch::default());
+        }
+        let Some(search) = self.field_search.as_mut() else {
+            return;
+        };
+
+        let mut close = false;
+        ui.horizontal(|ui| {
+            let r = TextEdit::singleline(&mut search.query)
+                .hint_text("Search fields")
+                .desired_width(240.)
+                .show(ui)
+                .response;
+            if open {
+                r.request_focus();
+            }
+            if r.changed() {
+                search.scroll.set(true);
+            }
+            close = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Escape));
+
+            if !search.query.is_empty() {
+                ui.label(format!("{} matches", search.matches.get()));
+            }
+            close |= ui
+                .button("✖")
+                .on_hover_text("Close search (Escape)")
+                .clicked();
+        });
+        search.matches.set(0);
+
+        if close {
+            self.field_search = None;
+        }
+    }
+
+    fn go_to_offset_ui(&mut self, ui: &mut Ui, state: &mut GlobalState, open: bool) {
+        if open && self.go_to_offset.is_none() {
+            self.go_to_offset = Some(String::new());
+        }
+        let Some(buffer) = self.go_to_offset.as_mut() else {
+            return;
+        };
+
+        let (mut close, mut go) = (false, false);
+        ui.horizontal(|ui| {
+            let r = TextEdit::singleline(buffer)
+                .hint_text("Go to offset, i.e. 3A0")
+                .desired_width(240.)
+                .show(ui)
+                .response;
+            if open {
+                r.request_focus();
+            }
+            go = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            close = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Escape));
+
+            let valid = parse_address(buffer).is_some();
+            if !valid && !buffer.trim().is_empty() {
+                ui.label(RichText::new("Not a hex offset").color(Color32::RED));
+            }
+            go |= ui.add_enabled(valid, Button::new("Go")).clicked();
+            close |= ui.button("✖").on_hover_text("Close (Escape)").clicked();
+        });
+
+        let Some(offset) = parse_address(buffer).filter(|_| go) else {
+            if close {
+                self.go_to_offset = None;
+            }
+            return;
+        };
+        self.go_to_offset = None;
+
+        let Some(class) = state.class_list.selected_class() else {
+            return;
+        };
+        let (cid, address) = (class.id(), class.address.get());
+
+        // Fields of base classes come first, the innermost one containing the offset is jumped to.
+        let mut start = 0;
+        let mut target = None;
+        for container in state.class_list.bases_of(cid).into_iter().chain([cid]) {
+            let Some(class) = state.class_list.by_id(container) else {
+                continue;
+            };
+            for (field_start, field) in walk_fields(&class.fields, start) {
+                if field_start > offset {
+                    break;
+                }
+                target = Some((field_start, container, field.id()));
+            }
+            start += class.fields.iter().map(|f| f.size()).sum::<usize>();
+        }
+
+        let Some((start, container_id, field_id)) = target else {
+            state.toasts.error("The class has no fields");
+            return;
+        };
+        if start != offset {
+            state
+                .toasts
+                .info(format!("No field at 0x{offset:X}, jumped to 0x{start:X}"));
+        }
+        state.selection = Some(Selection {
+            address,
+            container_id,
+            field_id,
+        });
+        state.multi_selection.clear();
+        self.scroll_to = Some(field_id);
+    }
+
+    /// Moves the selection to the previous or next field of its container, `Tab` also starts
+    /// renaming it and `Enter` renames the selected field like `F2`.
+    fn navigate_fields(&mut self, state: &mut GlobalState, input: &InputState) {
+        if state.selection.is_some() && input.key_pressed(Key::Enter) && input.modifiers.is_none() {
+            state.rename_requested = true;
+        }
+
+        let rename_next = state.hotkeys.pressed("rename_next_field", input);
+        let forward = if state.hotkeys.pressed("previous_field", input) {
+            false
+        } else if rename_next || state.hotkeys.pressed("next_field", input) {
+            true
+        } else {
+            return;
+        };
+
+        // Without a selection the first field of the inspected class is selected.
+        let (container_id, address, current) = match state.selection {
+            Some(s) => (s.container_id, s.address, Some(s.field_id)),
+            None => match state.class_list.selected_class() {
+                Some(class) => (class.id(), class.address.get(), None),
+                None => return,
+            },
+        };
+        let Some(class) = state.class_list.by_id(container_id) else {
+            return;
+        };
+        let fields = walk_fields(&class.fields, state.class_list.base_size(container_id));
+        if fields.is_empty() {
+            return;
+        }
+
+        let current = current.and_then(|id| fields.iter().position(|(_, f)| f.id() == id));
+        let (start, index) = match current {
+            Some(i) if forward => (fields[i].0, (i + 1).min(fields.len() - 1)),
+            Some(i) => (fields[i].0, i.saturating_sub(1)),
+            None => (0, 0),
+        };
+        let (offset, field) = fields[index];
+
+        state.selection = Some(Selection {
+            address: address - start + offset,
+            container_id,
+            field_id: field.id(),
+        });
+        state.multi_selection.clear();
+        self.scroll_to = Some(field.id());
+        if rename_next {
+            state.rename_requested = true;
+        }
+    }
+
     fn field_change_ui(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
         create_change_field_type_group!(ui, response, BLACK, GOLD, Bool);
 
@@ -226,11 +879,363 @@ impl InspectorPanel {
         ui.separator();
         ui.add_space(2.);
 
+        create_change_field_type_group!(ui, response, BLACK, LIGHT_RED, Vec2, Vec3, Vec4, Mat4);
+
+        if ui
+            .add_sized(
+                vec2(24., ui.available_height()),
+                Button::new(RichText::new("GUID").color(Color32::BLACK)).fill(Color32::LIGHT_GRAY),
+            )
+            .clicked()
+        {
+            *response = Some(ToolBarResponse::ChangeKind(FieldKind::Guid(
+                GuidFormat::Windows,
+            )));
+        }
+        ui.add_space(2.);
+
+        ui.separator();
+        ui.add_space(2.);
+
         create_change_field_type_group!(ui, response, BLACK, GRAY, Unk8, Unk16, Unk32, Unk64);
 
         ui.separator();
         ui.add_space(2.);
 
-        create_change_field_type_group!(ui, response, BLACK, BROWN, Ptr, StrPtr, WStrPtr);
+        create_change_field_type_group!(
+            ui, response, BLACK, BROWN, Ptr, StrPtr, WStrPtr, RelPtr32, RelPtr64, FuncPtr
+        );
+
+        if ui
+            .add_sized(
+                vec2(24., ui.available_height()),
+                Button::new(RichText::new("VTable").color(Color32::BLACK)).fill(Color32::BROWN),
+            )
+            .clicked()
+        {
+            *response = Some(ToolBarResponse::ChangeKind(FieldKind::Vtable(
+                DEFAULT_VTABLE_COUNT,
+            )));
+        }
+        ui.add_space(2.);
+
+        if ui
+            .add_sized(
+                vec2(24., ui.available_height()),
+                Button::new(RichText::new("Chain").color(Color32::BLACK)).fill(Color32::BROWN),
+            )
+            .on_hover_text("Pointer followed through several offsets, edited by right clicking")
+            .clicked()
+        {
+            *response = Some(ToolBarResponse::ChangeKind(FieldKind::PointerChain(vec![
+                0,
+            ])));
+        }
+        ui.add_space(2.);
+
+        ui.separator();
+        ui.add_space(2.);
+
+        self.array_change_ui(ui, response);
+        self.dynamic_array_change_ui(ui, response);
+        Self::std_string_change_ui(ui, response);
+        self.inline_string_change_ui(ui, response);
+        Self::timestamp_change_ui(ui, response);
+        Self::color_change_ui(ui, response);
+        self.embed_change_ui(ui, response);
+        Self::enum_change_ui(ui, response);
+        Self::bitfield_change_ui(ui, response);
+        Self::flags_change_ui(ui, response);
+    }
+
+    fn bitfield_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Bitfield", |ui| {
+            for (size, label) in [(1, "8 bit"), (2, "16 bit"), (4, "32 bit"), (8, "64 bit")] {
+                if ui.button(label).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::Bitfield(
+                        size,
+                        vec![],
+                    )));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to a bitfield");
+    }
+
+    fn flags_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Flags", |ui| {
+            for (size, label) in [(1, "8 bit"), (2, "16 bit"), (4, "32 bit"), (8, "64 bit")] {
+                if ui.button(label).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::Flags(size)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to an integer of named bit flags");
     }
+
+    fn dynamic_array_change_ui(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Vector", |ui| {
+            ui.set_width(140.);
+
+            ComboBox::new("_dynamic_array_element_kind", "Element")
+                .selected_text(format!("{:?}", self.dynamic_array_kind))
+                .show_ui(ui, |ui| {
+                    // Elements are generated with their types, so unknown bytes don't fit.
+                    for kind in FieldKind::SIMPLE.iter().filter(|k| {
+                        !matches!(
+                            k,
+                            FieldKind::Unk8
+                                | FieldKind::Unk16
+                                | FieldKind::Unk32
+                                | FieldKind::Unk64
+                        )
+                    }) {
+                        if ui
+                            .selectable_label(*kind == self.dynamic_array_kind, format!("{kind:?}"))
+                            .clicked()
+                        {
+                            self.dynamic_array_kind = kind.clone();
+                        }
+                    }
+                });
+
+            let inner = Box::new(self.dynamic_array_kind.clone());
+            if ui.button("Pointer, size, capacity").clicked() {
+                *response = Some(ToolBarResponse::ChangeKind(FieldKind::DynamicArray(inner)));
+                ui.close();
+            } else if ui.button("std::vector").clicked() {
+                *response = Some(ToolBarResponse::ChangeKind(FieldKind::StdVector(inner)));
+                ui.close();
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to an array stored on the heap");
+    }
+
+    fn std_string_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("String", |ui| {
+            for abi in StringAbi::ALL {
+                if ui.button(abi.label()).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::StdString(abi)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to a std::string of the standard library");
+    }
+
+    fn inline_string_change_ui(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("char[N]", |ui| {
+            ui.set_width(140.);
+
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.inline_string_len).desired_width(60.));
+                ui.label("Length");
+            });
+
+            let len = self
+                .inline_string_len
+                .value()
+                .and_then(|v| v.ok())
+                .copied()
+                .filter(|len| *len > 0);
+            ui.add_enabled_ui(len.is_some(), |ui| {
+                if ui.button("as char[N]").clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::InlineStr(
+                        len.unwrap(),
+                    )));
+                    ui.close();
+                } else if ui.button("as wchar_t[N]").clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::InlineWStr(
+                        len.unwrap(),
+                    )));
+                    ui.close();
+                }
+            });
+        })
+        .response
+        .on_hover_text("Changes selected field to a string stored in place");
+    }
+
+    fn timestamp_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Time", |ui| {
+            for format in TimestampFormat::ALL {
+                if ui.button(format.label()).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::Timestamp(format)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to a timestamp");
+    }
+
+    fn color_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Color", |ui| {
+            for layout in ColorLayout::ALL {
+                if ui.button(layout.label()).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::Color(layout)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to a color");
+    }
+
+    fn embed_change_ui(&self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Embed", |ui| {
+            let state = self.state.borrow();
+            let Some(container) = state.selection.map(|s| s.container_id) else {
+                ui.label("Select a field first");
+                return;
+            };
+
+            for class in state.class_list.classes() {
+                // Classes that already contain the container would make it infinitely large.
+                if state.class_list.depends_on(class.id(), container) {
+                    continue;
+                }
+
+                if ui.button(&class.name).clicked() {
+                    *response = Some(ToolBarResponse::Embed(class.id()));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to an embedded class");
+    }
+
+    fn enum_change_ui(ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Enum", |ui| {
+            for (size, label) in [(1, "8 bit"), (2, "16 bit"), (4, "32 bit"), (8, "64 bit")] {
+                if ui.button(label).clicked() {
+                    *response = Some(ToolBarResponse::ChangeKind(FieldKind::Enum(size)));
+                    ui.close();
+                }
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to an enum");
+    }
+
+    fn array_change_ui(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
+        ui.menu_button("Array", |ui| {
+            ui.set_width(140.);
+
+            ComboBox::new("_array_element_kind", "Element")
+                .selected_text(format!("{:?}", self.array_kind))
+                .show_ui(ui, |ui| {
+                    for kind in FieldKind::SIMPLE {
+                        if ui
+                            .selectable_label(*kind == self.array_kind, format!("{kind:?}"))
+                            .clicked()
+                        {
+                            self.array_kind = kind.clone();
+                        }
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.array_count).desired_width(60.));
+                ui.label("Count");
+            });
+
+            let count = self.array_count.value().and_then(|v| v.ok()).copied();
+            let valid =
+                count.is_some_and(|c| c > 0 && array_elements(&self.array_kind, c).is_some());
+            if ui
+                .add_enabled(valid, Button::new("Apply"))
+                .on_disabled_hover_text(format!(
+                    "Arrays can have at most {MAX_ARRAY_ELEMENTS} elements"
+                ))
+                .clicked()
+            {
+                *response = Some(ToolBarResponse::ChangeKind(FieldKind::Array(
+                    Box::new(self.array_kind.clone()),
+                    count.unwrap(),
+                )));
+                ui.close();
+            }
+        })
+        .response
+        .on_hover_text("Changes selected field to an array");
+    }
+}
+
+/// Number of bytes of a field shown in the secondary process column.
+const SECONDARY_BYTES: usize = 8;
+
+/// Bytes of the class fields in the secondary process, bytes that differ from the inspected
+/// process are highlighted. Returns the clicked field.
+fn secondary_column(
+    ui: &mut Ui,
+    class_list: &ClassList,
+    cid: ClassId,
+    address: usize,
+    process: &Process,
+    secondary: &Process,
+) -> Option<Selection> {
+    let font_id = FontId::monospace(14.);
+    match secondary.name() {
+        Ok(name) => ui.label(format!("{name} - {}", secondary.id())),
+        Err(_) => ui.label(format!("Secondary process {}", secondary.id())),
+    };
+    ui.separator();
+
+    let mut clicked = None;
+    ScrollArea::vertical()
+        .id_salt("_secondary_column")
+        .show(ui, |ui| {
+            let mut offset = 0;
+            let classes = class_list.bases_of(cid).into_iter().chain([cid]);
+            for class in classes.filter_map(|id| class_list.by_id(id)) {
+                for field in &class.fields {
+                    let shown = field.size().min(SECONDARY_BYTES);
+                    let (mut primary_bytes, mut secondary_bytes) = (vec![0; shown], vec![0; shown]);
+                    process.read(address + offset, &mut primary_bytes);
+                    secondary.read(address + offset, &mut secondary_bytes);
+
+                    let format = |color| TextFormat {
+                        font_id: font_id.clone(),
+                        color,
+                        ..Default::default()
+                    };
+                    let mut job = LayoutJob::default();
+                    job.append(&format!("{offset:04X}"), 0., format(Color32::KHAKI));
+                    for (p, s) in primary_bytes.iter().zip(&secondary_bytes) {
+                        let color = if p == s {
+                            Color32::GRAY
+                        } else {
+                            Color32::LIGHT_RED
+                        };
+                        job.append(&format!("{s:02X}"), 6., format(color));
+                    }
+                    if field.size() > shown {
+                        job.append("…", 2., format(Color32::GRAY));
+                    }
+
+                    if ui
+                        .add(Label::new(job).sense(Sense::click()))
+                        .on_hover_text(field.name().unwrap_or_default())
+                        .clicked()
+                    {
+                        clicked = Some(Selection {
+                            address: address + offset,
+                            container_id: class.id(),
+                            field_id: field.id(),
+                        });
+                    }
+                    offset += field.size();
+                }
+            }
+        });
+
+    clicked
 }
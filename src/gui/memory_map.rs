@@ -0,0 +1,216 @@
+use crate::{
+    process::{Module, Process, Region},
+    state::StateRef,
+};
+use eframe::{
+    egui::{Context, Label, RichText, ScrollArea, Sense, Ui, Window},
+    epaint::{vec2, Color32, FontId},
+};
+use memflex::types::Protection;
+use std::time::{Duration, Instant};
+
+/// How often regions of an attached process are reloaded.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+const ROW_HEIGHT: f32 = 16.;
+
+pub struct MemoryMapWindow {
+    state: StateRef,
+    shown: bool,
+
+    regions: Vec<Region>,
+    modules: Vec<Module>,
+    // Why regions couldn't be loaded, i.e. plugins don't provide them.
+    error: Option<String>,
+    refreshed: Option<Instant>,
+}
+
+impl MemoryMapWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            regions: vec![],
+            modules: vec![],
+            error: None,
+            refreshed: None,
+            shown: false,
+            state,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Memory map").open(&mut shown).show(ctx, |ui| {
+            let state = self.state;
+            let state = &*state.borrow();
+
+            let process_lock = state.process.read();
+            let Some(process) = process_lock.as_ref() else {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Attach to a process first");
+                });
+                self.refreshed = None;
+                return;
+            };
+
+            if self
+                .refreshed
+                .is_none_or(|time| time.elapsed() >= REFRESH_INTERVAL)
+            {
+                self.refresh(process);
+            }
+            ui.ctx().request_repaint_after(REFRESH_INTERVAL);
+
+            ui.horizontal(|ui| {
+                if ui.button("Refresh").clicked() {
+                    self.refresh(process);
+                }
+
+                ui.separator();
+                ui.label(format!("{} regions", self.regions.len()));
+                ui.separator();
+                for (label, color) in [
+                    ("Executable", EXECUTABLE_COLOR),
+                    ("Writable", WRITABLE_COLOR),
+                    ("Read only", READ_ONLY_COLOR),
+                    ("No access", NO_ACCESS_COLOR),
+                ] {
+                    ui.label(RichText::new("■").color(color));
+                    ui.label(label);
+                }
+            });
+
+            ui.separator();
+
+            if let Some(error) = &self.error {
+                ui.centered_and_justified(|ui| {
+                    ui.label(RichText::new(error).color(Color32::RED));
+                });
+                return;
+            }
+
+            let selected_class = state.class_list.selected_class();
+            self.display_regions(ui, |base| {
+                if let Some(class) = selected_class {
+                    class.address.set(base);
+                }
+            });
+        });
+        self.shown = shown;
+    }
+
+    fn refresh(&mut self, process: &Process) {
+        match process.regions() {
+            Ok(regions) => {
+                self.regions = regions;
+                self.regions.sort_by_key(|region| region.range.start);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.modules = process.modules();
+        self.refreshed = Some(Instant::now());
+    }
+
+    fn display_regions(&self, ui: &mut Ui, mut inspect: impl FnMut(usize)) {
+        ui.style_mut().override_font_id = Some(FontId::monospace(14.));
+
+        // Sizes range from a page to gigabytes, so bars grow with the logarithm of the size
+        // to keep small regions visible.
+        let scale = |size: usize| (size.max(1) as f32).log2();
+        let largest = self
+            .regions
+            .iter()
+            .map(|region| scale(region.range.len()))
+            .fold(1., f32::max);
+
+        ScrollArea::vertical().auto_shrink([false, true]).show_rows(
+            ui,
+            ROW_HEIGHT,
+            self.regions.len(),
+            |ui, rows| {
+                for region in &self.regions[rows] {
+                    let module = self
+                        .modules
+                        .iter()
+                        .find(|m| (m.base..m.base + m.size).contains(&region.range.start));
+
+                    ui.horizontal(|ui| {
+                        ui.set_height(ROW_HEIGHT);
+                        ui.add(
+                            Label::new(format!(
+                                "{:016X} {}",
+                                region.range.start,
+                                format_protection(region.prot)
+                            ))
+                            .selectable(false),
+                        );
+
+                        let width = ui.available_width() * scale(region.range.len()) / largest;
+                        let (rect, r) = ui
+                            .allocate_exact_size(vec2(width.max(2.), ROW_HEIGHT - 4.), Sense::click());
+                        let color = protection_color(region.prot);
+                        ui.painter().rect_filled(
+                            rect,
+                            2.,
+                            if r.hovered() {
+                                color.gamma_multiply(1.5)
+                            } else {
+                                color
+                            },
+                        );
+
+                        let r = r.on_hover_text(format!(
+                            "{:X} - {:X}\nSize: {:X}\nProtection: {}{}\nClick to inspect the region in the selected class",
+                            region.range.start,
+                            region.range.end,
+                            region.range.len(),
+                            format_protection(region.prot),
+                            module
+                                .map(|m| format!("\nModule: {}", m.name))
+                                .unwrap_or_default()
+                        ));
+                        if r.clicked() {
+                            inspect(region.range.start);
+                        }
+                    });
+                }
+            },
+        );
+    }
+}
+
+const EXECUTABLE_COLOR: Color32 = Color32::from_rgb(220, 90, 90);
+const WRITABLE_COLOR: Color32 = Color32::from_rgb(90, 150, 220);
+const READ_ONLY_COLOR: Color32 = Color32::from_rgb(110, 180, 110);
+const NO_ACCESS_COLOR: Color32 = Color32::DARK_GRAY;
+
+fn protection_color(prot: Option<Protection>) -> Color32 {
+    match prot {
+        // Minidumps only contain readable memory.
+        None => READ_ONLY_COLOR,
+        Some(prot) if prot.execute() => EXECUTABLE_COLOR,
+        Some(prot) if prot.write() => WRITABLE_COLOR,
+        Some(prot) if prot.read() => READ_ONLY_COLOR,
+        Some(_) => NO_ACCESS_COLOR,
+    }
+}
+
+/// Formats protection as `rwx`, unknown protection of minidump regions is `r??`.
+fn format_protection(prot: Option<Protection>) -> String {
+    let Some(prot) = prot else {
+        return "r??".to_owned();
+    };
+
+    [
+        (prot.read(), 'r'),
+        (prot.write(), 'w'),
+        (prot.execute(), 'x'),
+    ]
+    .iter()
+    .map(|(set, c)| if *set { *c } else { '-' })
+    .collect()
+}
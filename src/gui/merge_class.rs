@@ -0,0 +1,196 @@
+use crate::{
+    address::parse_address,
+    class::{ClassId, ClassList, MergePart},
+    history::Command,
+    state::StateRef,
+};
+use eframe::{
+    egui::{Button, ComboBox, Context, Grid, RichText, ScrollArea, TextEdit, Ui, Window},
+    epaint::Color32,
+};
+
+/// Places fields of one class into another, e.g. once it turns out both describe the same struct.
+pub struct MergeClassWindow {
+    state: StateRef,
+    shown: bool,
+    primary: Option<ClassId>,
+    secondary: Option<ClassId>,
+    // Hex offset in the primary class, empty appends the fields.
+    offset: String,
+    delete_secondary: bool,
+}
+
+impl MergeClassWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            primary: None,
+            secondary: None,
+            offset: String::new(),
+            delete_secondary: true,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+        if self.shown {
+            self.primary = self.state.borrow().class_list.selected();
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Merge classes")
+            .open(&mut shown)
+            .default_width(360.)
+            .show(ctx, |ui| self.merge(ui));
+        self.shown = shown;
+    }
+
+    fn merge(&mut self, ui: &mut Ui) {
+        let state = &mut *self.state.borrow_mut();
+
+        Grid::new("_merge_classes").num_columns(2).show(ui, |ui| {
+            ui.label("Into");
+            class_combo(ui, "_merge_primary", &state.class_list, &mut self.primary);
+            ui.end_row();
+
+            ui.label("Merge");
+            class_combo(
+                ui,
+                "_merge_secondary",
+                &state.class_list,
+                &mut self.secondary,
+            );
+            ui.end_row();
+
+            ui.label("At offset");
+            let end = self
+                .primary
+                .map(|cid| state.class_list.class_size(cid))
+                .unwrap_or_default();
+            ui.add(
+                TextEdit::singleline(&mut self.offset)
+                    .hint_text(format!("0x{end:X}"))
+                    .desired_width(100.),
+            );
+            ui.end_row();
+        });
+        ui.checkbox(&mut self.delete_secondary, "Delete the merged class");
+        ui.separator();
+
+        let (Some(primary), Some(secondary)) = (self.primary, self.secondary) else {
+            ui.label("Pick both classes first");
+            return;
+        };
+        let offset = if self.offset.trim().is_empty() {
+            state.class_list.class_size(primary)
+        } else if let Some(offset) = parse_address(&self.offset) {
+            offset
+        } else {
+            ui.colored_label(Color32::RED, "Offset must be a hex number");
+            return;
+        };
+
+        let parts = match state.class_list.plan_merge(primary, secondary, offset) {
+            Ok(parts) => parts,
+            Err(e) => {
+                ui.colored_label(Color32::RED, e.to_string());
+                return;
+            }
+        };
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            preview(ui, &state.class_list, primary, secondary, &parts);
+        });
+        ui.separator();
+
+        if !ui.add(Button::new("Merge")).clicked() {
+            return;
+        }
+
+        let mut commands = vec![];
+        commands.extend(Command::edit_class(&state.class_list, primary));
+        if let Err(e) = state.class_list.merge(primary, secondary, offset) {
+            state.toasts.error(e.to_string());
+            return;
+        }
+        if self.delete_secondary {
+            commands.extend(Command::remove_class(&state.class_list, secondary));
+            state.class_list.delete_by_id(secondary);
+            self.secondary = None;
+        }
+        state.history.push(Command::Batch(commands));
+        state.dummy = false;
+    }
+}
+
+fn class_combo(ui: &mut Ui, id: &str, list: &ClassList, class: &mut Option<ClassId>) {
+    let name = class
+        .and_then(|cid| list.by_id(cid))
+        .map(|c| c.name.as_str())
+        .unwrap_or("None");
+
+    ComboBox::from_id_salt(id)
+        .selected_text(name)
+        .show_ui(ui, |ui| {
+            for c in list.classes() {
+                ui.selectable_value(class, Some(c.id()), &c.name);
+            }
+        });
+}
+
+/// Fields of the primary class after the merge, merged fields are highlighted.
+fn preview(
+    ui: &mut Ui,
+    list: &ClassList,
+    primary: ClassId,
+    secondary: ClassId,
+    parts: &[MergePart],
+) {
+    let (Some(class), Some(merged)) = (list.by_id(primary), list.by_id(secondary)) else {
+        return;
+    };
+
+    let mut offset = list.base_size(primary);
+    let mut row = |ui: &mut Ui, size: usize, name: String, kind: String, color: Color32| {
+        ui.label(RichText::new(format!("{offset:04X}")).color(color));
+        ui.label(RichText::new(name).color(color));
+        ui.label(RichText::new(kind).color(color));
+        ui.end_row();
+        offset += size;
+    };
+
+    Grid::new("_merge_preview")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for part in parts {
+                match *part {
+                    MergePart::Kept(i) => {
+                        let field = &class.fields[i];
+                        let name = field.name().unwrap_or_default();
+                        let kind = format!("{:?}", field.kind());
+                        row(ui, field.size(), name, kind, Color32::LIGHT_GRAY);
+                    }
+                    MergePart::Padding(size) => {
+                        row(
+                            ui,
+                            size,
+                            String::new(),
+                            format!("{size} bytes of padding"),
+                            Color32::GRAY,
+                        );
+                    }
+                    MergePart::Merged => {
+                        for field in &merged.fields {
+                            let name = field.name().unwrap_or_default();
+                            let kind = format!("{:?}", field.kind());
+                            row(ui, field.size(), name, kind, Color32::LIGHT_BLUE);
+                        }
+                    }
+                }
+            }
+        });
+}
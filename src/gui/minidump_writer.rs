@@ -0,0 +1,116 @@
+use crate::state::{GlobalState, StateRef};
+use eframe::{
+    egui::{Align2, Area, Context, Frame, Id, ProgressBar},
+    epaint::vec2,
+};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+};
+
+/// Writes a minidump of the attached process in the background, the progress is shown in the
+/// corner where toasts appear.
+pub struct MinidumpWriter {
+    state: StateRef,
+    path: PathBuf,
+    handle: Option<JoinHandle<eyre::Result<()>>>,
+    /// Written and total bytes of memory.
+    progress: Arc<(AtomicU64, AtomicU64)>,
+}
+
+impl MinidumpWriter {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            path: PathBuf::new(),
+            handle: None,
+            progress: Arc::default(),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /// Asks for the path of the minidump and starts writing it.
+    pub fn start(&mut self, state: &mut GlobalState) {
+        if self.is_running() {
+            state.toasts.warning("Already creating a minidump");
+            return;
+        }
+        if state.process.read().is_none() {
+            state.toasts.error("Attach to a process first");
+            return;
+        }
+
+        let name = state
+            .process
+            .read()
+            .as_ref()
+            .and_then(|p| p.name().ok())
+            .unwrap_or_else(|| "process".into());
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Create minidump")
+            .add_filter("Minidump files", &["dmp"])
+            .set_file_name(&format!("{name}.dmp"))
+            .save_file()
+        else {
+            return;
+        };
+
+        let (process, progress) = (state.process.clone(), self.progress.clone());
+        progress.0.store(0, Ordering::Relaxed);
+        progress.1.store(0, Ordering::Relaxed);
+        self.path = path.clone();
+        self.handle = Some(std::thread::spawn(move || {
+            let process = process.read();
+            let Some(process) = process.as_ref() else {
+                eyre::bail!("The process was detached");
+            };
+            process.create_minidump(&path, |written, total| {
+                progress.0.store(written, Ordering::Relaxed);
+                progress.1.store(total, Ordering::Relaxed);
+            })
+        }));
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        if self.handle.as_ref().is_some_and(|h| h.is_finished()) {
+            let result = self.handle.take().unwrap().join();
+            let toasts = &mut self.state.borrow_mut().toasts;
+            match result {
+                Ok(Ok(())) => _ = toasts.success(format!("Created {}", self.path.display())),
+                Ok(Err(e)) => _ = toasts.error(format!("Failed to create the minidump. {e}")),
+                Err(_) => _ = toasts.error("Failed to create the minidump"),
+            }
+        }
+        if self.handle.is_none() {
+            return;
+        }
+
+        let (written, total) = (
+            self.progress.0.load(Ordering::Relaxed),
+            self.progress.1.load(Ordering::Relaxed),
+        );
+        Area::new(Id::new("_minidump_progress"))
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-8., -8.))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(260.);
+                    ui.label(format!(
+                        "Creating minidump, {} of {} MiB",
+                        written >> 20,
+                        total >> 20
+                    ));
+                    ui.add(
+                        ProgressBar::new(written as f32 / total.max(1) as f32).show_percentage(),
+                    );
+                });
+            });
+        ctx.request_repaint();
+    }
+}
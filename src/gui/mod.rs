@@ -10,5 +10,47 @@ mod generator;
 pub use generator::*;
 mod spider;
 pub use spider::*;
+mod value_scan;
+pub use value_scan::*;
+mod aob_scan;
+pub use aob_scan::*;
+mod auto_tag;
+pub use auto_tag::*;
+mod batch_rename;
+pub use batch_rename::*;
+mod class_graph;
+pub use class_graph::*;
+mod field_stats;
+pub use field_stats::*;
+mod merge_class;
+mod minidump_writer;
+pub use merge_class::*;
+pub use minidump_writer::*;
+mod validation;
+pub use validation::*;
+mod vtable_scanner;
+pub use vtable_scanner::*;
+#[cfg(feature = "disasm")]
+mod disassembly;
+#[cfg(feature = "disasm")]
+pub use disassembly::*;
+mod module_list;
+pub use module_list::*;
+mod header_import;
+pub use header_import::*;
+mod hex_editor;
+pub use hex_editor::*;
+mod memory_map;
+pub use memory_map::*;
+mod settings;
+pub use settings::*;
+mod hotkeys;
+pub use hotkeys::*;
+mod recording_plot;
+pub use recording_plot::*;
+mod references;
+pub use references::*;
+mod symbols;
+pub use symbols::*;
 mod utils;
 pub use utils::*;
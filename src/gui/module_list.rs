@@ -0,0 +1,157 @@
+use crate::{
+    process::{Module, Process},
+    state::StateRef,
+};
+use eframe::{
+    egui::{Context, Label, Sense, TextEdit, Ui, Window},
+    epaint::FontId,
+};
+use egui_extras::{Column, TableBuilder};
+use std::time::{Duration, Instant};
+
+/// How often modules of an attached process are reloaded.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct ModuleListWindow {
+    state: StateRef,
+    shown: bool,
+
+    filter: String,
+    modules: Vec<Module>,
+    refreshed: Option<Instant>,
+}
+
+impl ModuleListWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            filter: String::new(),
+            modules: vec![],
+            refreshed: None,
+            shown: false,
+            state,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Modules").open(&mut shown).show(ctx, |ui| {
+            let state = self.state;
+            let state = &*state.borrow();
+
+            let process_lock = state.process.read();
+            let Some(process) = process_lock.as_ref() else {
+                ui.centered_and_justified(|ui| {
+                    ui.heading("Attach to a process first");
+                });
+                self.refreshed = None;
+                return;
+            };
+
+            if self
+                .refreshed
+                .is_none_or(|time| time.elapsed() >= REFRESH_INTERVAL)
+            {
+                self.refresh(process);
+            }
+            ui.ctx().request_repaint_after(REFRESH_INTERVAL);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut self.filter)
+                        .hint_text("Filter")
+                        .desired_width(ui.available_width() / 2.),
+                );
+
+                if ui.button("Refresh").clicked() {
+                    self.refresh(process);
+                }
+
+                ui.separator();
+                ui.label(format!("{} modules", self.modules.len()));
+            });
+
+            ui.separator();
+
+            if self.modules.is_empty() {
+                ui.centered_and_justified(|ui| {
+                    ui.label("N/A, the process doesn't provide modules");
+                });
+                return;
+            }
+
+            let selected_class = state.class_list.selected_class();
+            self.display_modules(ui, |base| {
+                if let Some(class) = selected_class {
+                    class.address.set(base);
+                }
+            });
+        });
+        self.shown = shown;
+    }
+
+    fn refresh(&mut self, process: &Process) {
+        self.modules = process.modules();
+        self.modules.sort_by_key(|m| m.base);
+        self.refreshed = Some(Instant::now());
+    }
+
+    fn display_modules(&self, ui: &mut Ui, mut inspect: impl FnMut(usize)) {
+        const DATA_HEIGHT: f32 = 14.;
+        ui.style_mut().override_font_id = Some(FontId::monospace(DATA_HEIGHT));
+
+        let filter = self.filter.to_lowercase();
+        let modules = self
+            .modules
+            .iter()
+            .filter(|m| m.file_name().to_lowercase().contains(&filter))
+            .collect::<Vec<_>>();
+
+        let w = ui.available_width() / 3.;
+        TableBuilder::new(ui)
+            .striped(true)
+            .columns(Column::initial(w).resizable(true), 2)
+            .column(Column::remainder())
+            .header(16., |mut row| {
+                row.col(|ui| _ = ui.label("Name"));
+                row.col(|ui| _ = ui.label("Base"));
+                row.col(|ui| _ = ui.label("Size"));
+            })
+            .body(|body| {
+                body.rows(DATA_HEIGHT, modules.len(), |mut row| {
+                    let module = modules[row.index()];
+
+                    row.col(|ui| {
+                        let r = ui
+                            .add(Label::new(module.file_name()).sense(Sense::click()))
+                            .on_hover_text(format!(
+                                "{}\nClick to inspect the module base in the selected class",
+                                module.name
+                            ));
+                        if r.clicked() {
+                            inspect(module.base);
+                        }
+
+                        r.context_menu(|ui| {
+                            if ui.button("Copy base address").clicked() {
+                                ui.ctx().copy_text(format!("{:X}", module.base));
+                                ui.close();
+                            }
+
+                            if ui.button("Copy path").clicked() {
+                                ui.ctx().copy_text(module.name.clone());
+                                ui.close();
+                            }
+                        });
+                    });
+
+                    row.col(|ui| _ = ui.label(format!("{:X}", module.base)));
+                    row.col(|ui| _ = ui.label(format!("{:X}", module.size)));
+                })
+            });
+    }
+}
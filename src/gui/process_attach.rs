@@ -1,88 +1,246 @@
-use crate::state::StateRef;
+use crate::{process::pointer_size_of_pid, state::StateRef};
 use eframe::{
-    egui::{Context, RichText, ScrollArea, TextEdit, Window},
+    egui::{Button, Context, Key, RichText, ScrollArea, TextEdit, Ui, Window},
     epaint::{vec2, FontId},
 };
 use memflex::external::{ProcessEntry, ProcessIterator};
+use std::collections::{HashMap, HashSet};
+
+/// Indentation of child processes in the tree.
+const TREE_INDENT: f32 = 16.;
+
+pub enum AttachTarget {
+    Process(u32),
+    /// `IP:Port` of a memory server.
+    Remote(String),
+}
 
 pub struct ProcessAttachWindow {
     shown: bool,
+    /// Picks the secondary process instead of the inspected one.
+    secondary: bool,
     request_focus: bool,
     filter: String,
+    remote_address: String,
     processes: Vec<ProcessEntry>,
+    /// Shows processes under their parents.
+    tree: bool,
+    /// Pointer sizes by process id, `None` if the process can't be inspected.
+    pointer_sizes: HashMap<u32, Option<usize>>,
 }
 
 impl ProcessAttachWindow {
-    pub fn new(_: StateRef) -> Self {
+    pub fn new(state: StateRef) -> Self {
         Self {
             processes: vec![],
+            tree: false,
+            pointer_sizes: HashMap::new(),
             shown: false,
+            secondary: false,
             request_focus: false,
             filter: "".to_owned(),
+            remote_address: state
+                .borrow()
+                .config
+                .last_remote_address
+                .clone()
+                .unwrap_or_default(),
         }
     }
 
     pub fn toggle(&mut self) {
         self.shown = !self.shown;
+        self.secondary = false;
 
         if self.shown {
-            self.processes = collect_processes();
+            self.refresh();
             self.request_focus = true;
         }
     }
 
-    pub fn show(&mut self, ctx: &Context) -> Option<u32> {
+    /// Opens the window to pick the process the inspected class is compared with.
+    pub fn toggle_secondary(&mut self) {
+        self.toggle();
+        self.secondary = self.shown;
+    }
+
+    pub fn is_secondary(&self) -> bool {
+        self.secondary
+    }
+
+    fn refresh(&mut self) {
+        self.processes = collect_processes();
+
+        // Ids of exited processes may be reused, their sizes are checked again.
+        let ids = self
+            .processes
+            .iter()
+            .map(|pe| pe.id)
+            .collect::<HashSet<_>>();
+        self.pointer_sizes.retain(|id, _| ids.contains(id));
+        for id in ids {
+            self.pointer_sizes
+                .entry(id)
+                .or_insert_with(|| pointer_size_of_pid(id));
+        }
+    }
+
+    fn matches_filter(&self, pe: &ProcessEntry) -> bool {
+        self.filter.is_empty()
+            || pe.name.to_lowercase().contains(&self.filter.to_lowercase())
+            || pe.id.to_string().contains(&self.filter)
+    }
+
+    /// Returns `true` if the process was clicked.
+    fn process_button(&self, ui: &mut Ui, pe: &ProcessEntry) -> bool {
+        let arch = match self.pointer_sizes.get(&pe.id) {
+            Some(Some(4)) => "x86",
+            Some(Some(_)) => "x64",
+            _ => "?",
+        };
+        ui.button(
+            RichText::new(format!("{} - {} ({arch})", pe.name, pe.id))
+                .font(FontId::proportional(16.)),
+        )
+        .clicked()
+    }
+
+    /// Processes grouped under their parents, parents of matching processes are shown too.
+    fn process_tree(&self, ui: &mut Ui) -> Option<u32> {
+        let by_id = self
+            .processes
+            .iter()
+            .map(|pe| (pe.id, pe))
+            .collect::<HashMap<_, _>>();
+        let mut children = HashMap::<u32, Vec<&ProcessEntry>>::new();
+        let mut roots = vec![];
+        for pe in &self.processes {
+            if pe.parent_id != pe.id && by_id.contains_key(&pe.parent_id) {
+                children.entry(pe.parent_id).or_default().push(pe);
+            } else {
+                roots.push(pe);
+            }
+        }
+
+        let mut visible = HashSet::new();
+        for pe in self.processes.iter().filter(|pe| self.matches_filter(pe)) {
+            let mut id = pe.id;
+            // Stops at processes already shown, reused ids can make parents loop.
+            while visible.insert(id) {
+                match by_id.get(&id) {
+                    Some(pe) if by_id.contains_key(&pe.parent_id) => id = pe.parent_id,
+                    _ => break,
+                }
+            }
+        }
+
+        let mut target = None;
+        let mut drawn = HashSet::new();
+        let mut stack = roots
+            .into_iter()
+            .rev()
+            .map(|pe| (pe, 0))
+            .collect::<Vec<_>>();
+        while let Some((pe, depth)) = stack.pop() {
+            if !visible.contains(&pe.id) || !drawn.insert(pe.id) {
+                continue;
+            }
+
+            ui.horizontal(|ui| {
+                ui.add_space(depth as f32 * TREE_INDENT);
+                if self.process_button(ui, pe) {
+                    target = Some(pe.id);
+                }
+            });
+            if let Some(children) = children.get(&pe.id) {
+                stack.extend(children.iter().rev().map(|pe| (*pe, depth + 1)));
+            }
+        }
+
+        target
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> Option<AttachTarget> {
         if !self.shown {
             return None;
         }
 
-        let mut attach_pid = None;
-        Window::new("Attach to process")
-            .collapsible(false)
-            .open(&mut self.shown)
-            .default_size(vec2(180., 320.))
-            .show(ctx, |ui| {
-                ui.vertical_centered_justified(|ui| {
-                    let r = TextEdit::singleline(&mut self.filter)
-                        .desired_width(f32::INFINITY)
-                        .hint_text("Filter by name")
-                        .show(ui)
-                        .response;
-
-                    if self.request_focus {
-                        r.request_focus();
-                        self.request_focus = false;
-                    }
+        let mut shown = self.shown;
+
+        let mut target = None;
+        Window::new(if self.secondary {
+            "Attach secondary process"
+        } else {
+            "Attach to process"
+        })
+        .id("_process_attach_window".into())
+        .collapsible(false)
+        .open(&mut shown)
+        .default_size(vec2(180., 320.))
+        .show(ctx, |ui| {
+            ui.vertical_centered_justified(|ui| {
+                let r = TextEdit::singleline(&mut self.filter)
+                    .desired_width(f32::INFINITY)
+                    .hint_text("Filter by name")
+                    .show(ui)
+                    .response;
 
+                if self.request_focus {
+                    r.request_focus();
+                    self.request_focus = false;
+                }
+
+                ui.horizontal(|ui| {
                     if ui.button("Refresh").clicked() || r.changed() {
-                        self.processes = collect_processes();
+                        self.refresh();
                     }
+                    ui.checkbox(&mut self.tree, "Tree")
+                        .on_hover_text("Shows child processes under their parents");
+                });
 
-                    ui.add_space(4.);
-                    ui.separator();
-                    ui.add_space(4.);
-
-                    ScrollArea::vertical().show(ui, |ui| {
-                        for pe in self.processes.iter().filter(|pe| {
-                            self.filter.is_empty()
-                                || pe.name.to_lowercase().contains(&self.filter.to_lowercase())
-                                || pe.id.to_string().contains(&self.filter)
-                        }) {
-                            if ui
-                                .button(
-                                    RichText::new(format!("{} - {}", pe.name, pe.id))
-                                        .font(FontId::proportional(16.)),
-                                )
-                                .clicked()
-                            {
-                                attach_pid = Some(pe.id);
-                            }
+                ui.horizontal(|ui| {
+                    let r = ui
+                        .add(
+                            TextEdit::singleline(&mut self.remote_address)
+                                .desired_width(ui.available_width() - 64.)
+                                .hint_text("IP:Port"),
+                        )
+                        .on_hover_text("Address of a memory server on another machine");
+                    let enter = r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+
+                    if (ui
+                        .add_enabled(!self.remote_address.is_empty(), Button::new("Connect"))
+                        .clicked()
+                        || enter)
+                        && !self.remote_address.is_empty()
+                    {
+                        target = Some(AttachTarget::Remote(self.remote_address.trim().to_owned()));
+                    }
+                });
+
+                ui.add_space(4.);
+                ui.separator();
+                ui.add_space(4.);
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    if self.tree {
+                        if let Some(id) = self.process_tree(ui) {
+                            target = Some(AttachTarget::Process(id));
                         }
-                    });
+                        return;
+                    }
+
+                    for pe in self.processes.iter().filter(|pe| self.matches_filter(pe)) {
+                        if self.process_button(ui, pe) {
+                            target = Some(AttachTarget::Process(pe.id));
+                        }
+                    }
                 });
             });
+        });
+        self.shown = shown;
 
-        attach_pid
+        target
     }
 }
 
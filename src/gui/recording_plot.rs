@@ -0,0 +1,116 @@
+use crate::{recording::Recording, state::StateRef};
+use eframe::{
+    egui::{Align2, Context, Id, Sense, Ui, Window},
+    epaint::{pos2, vec2, Color32, FontId, Shape, Stroke},
+};
+
+const PLOT_HEIGHT: f32 = 200.;
+
+/// Line charts of recorded values, one window per recording opened with `Plot`.
+pub struct RecordingPlotWindows {
+    state: StateRef,
+}
+
+impl RecordingPlotWindows {
+    pub fn new(state: StateRef) -> Self {
+        Self { state }
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let state = &mut *self.state.borrow_mut();
+
+        for (id, recording) in state.recordings.iter_mut().filter(|(_, r)| r.plotted) {
+            let mut open = true;
+            Window::new(format!("Plot of {}", recording.name))
+                .id(Id::new(("_recording_plot", *id)))
+                .open(&mut open)
+                .default_width(400.)
+                .show(ctx, |ui| plot(ui, recording));
+            recording.plotted = open;
+        }
+    }
+}
+
+fn plot(ui: &mut Ui, recording: &Recording) {
+    let points = recording
+        .samples
+        .iter()
+        .filter_map(|(time, bytes)| {
+            let secs = time.duration_since(recording.started).as_secs_f32();
+            Some((secs, recording.value(bytes)?.as_f64()))
+        })
+        .collect::<Vec<_>>();
+
+    ui.label(format!(
+        "{} values at {:X}{}",
+        points.len(),
+        recording.address,
+        if recording.active { ", recording" } else { "" }
+    ));
+
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        ui.label("Nothing was recorded yet");
+        return;
+    };
+
+    let (mut min, mut max) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+            (min.min(*v), max.max(*v))
+        });
+    if min == max {
+        min -= 1.;
+        max += 1.;
+    }
+    let duration = (last.0 - first.0).max(f32::EPSILON);
+
+    let (rect, r) = ui.allocate_exact_size(
+        vec2(ui.available_width().max(200.), PLOT_HEIGHT),
+        Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2., ui.visuals().extreme_bg_color);
+
+    let to_screen = |(secs, value): (f32, f64)| {
+        pos2(
+            rect.left() + rect.width() * (secs - first.0) / duration,
+            rect.bottom() - rect.height() * ((value - min) / (max - min)) as f32,
+        )
+    };
+    painter.add(Shape::line(
+        points.iter().copied().map(to_screen).collect(),
+        Stroke::new(1.5, Color32::LIGHT_BLUE),
+    ));
+
+    let font = FontId::monospace(11.);
+    let color = Color32::GRAY;
+    painter.text(rect.left_top(), Align2::LEFT_TOP, max, font.clone(), color);
+    painter.text(
+        rect.left_bottom(),
+        Align2::LEFT_BOTTOM,
+        min,
+        font.clone(),
+        color,
+    );
+    painter.text(
+        rect.right_bottom(),
+        Align2::RIGHT_BOTTOM,
+        format!("{duration:.2} s"),
+        font,
+        color,
+    );
+
+    // Value of the sample closest to the cursor.
+    if let Some(pos) = r.hover_pos() {
+        let secs = first.0 + (pos.x - rect.left()) / rect.width() * duration;
+        let closest = points
+            .iter()
+            .min_by(|a, b| (a.0 - secs).abs().total_cmp(&(b.0 - secs).abs()));
+        if let Some(&point) = closest {
+            let pos = to_screen(point);
+            painter.vline(pos.x, rect.y_range(), Stroke::new(1., Color32::DARK_GRAY));
+            painter.circle_filled(pos, 3., Color32::YELLOW);
+            r.on_hover_text(format!("{:.3} s\n{}", point.0, point.1));
+        }
+    }
+}
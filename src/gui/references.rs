@@ -0,0 +1,200 @@
+use crate::{
+    class::{ClassId, ClassList},
+    context::Selection,
+    field::{walk_fields, FieldId, FieldKind},
+    state::StateRef,
+};
+use eframe::{
+    egui::{Context, Label, ProgressBar, ScrollArea, Sense, Window},
+    epaint::FontId,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+};
+
+/// Pointer field of a class that points to the searched address.
+#[derive(Debug, Clone, Copy)]
+struct Reference {
+    class_id: ClassId,
+    /// Class that declares the field, either the class or one of its bases.
+    container_id: ClassId,
+    field_id: FieldId,
+    offset: usize,
+    /// Address of the field.
+    address: usize,
+}
+
+/// Lists pointer fields of all classes that point to an address.
+pub struct ReferencesWindow {
+    state: StateRef,
+    shown: bool,
+
+    target: usize,
+    search: Option<JoinHandle<Vec<Reference>>>,
+    progress: Arc<AtomicUsize>,
+    total: usize,
+    results: Vec<Reference>,
+}
+
+impl ReferencesWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            target: 0,
+            search: None,
+            progress: Arc::default(),
+            total: 0,
+            results: vec![],
+        }
+    }
+
+    /// Starts searching references to `target` in the background.
+    pub fn search(&mut self, target: usize) {
+        if self.search.is_some() {
+            self.state
+                .borrow_mut()
+                .toasts
+                .warning("Already searching for references");
+            return;
+        }
+
+        let state = self.state.borrow();
+        let candidates = pointer_fields(&state.class_list);
+        let (process, progress) = (state.process.clone(), self.progress.clone());
+
+        self.shown = true;
+        self.target = target;
+        self.total = candidates.len();
+        self.results.clear();
+        progress.store(0, Ordering::Relaxed);
+        self.search = Some(std::thread::spawn(move || {
+            let process = process.read();
+            let Some(process) = process.as_ref() else {
+                return vec![];
+            };
+
+            candidates
+                .into_iter()
+                .filter(|r| {
+                    progress.fetch_add(1, Ordering::Relaxed);
+                    process.read_pointer(r.address) == target
+                })
+                .collect()
+        }));
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        if self.search.as_ref().is_some_and(|h| h.is_finished()) {
+            self.results = self.search.take().unwrap().join().unwrap_or_default();
+            self.state.borrow_mut().toasts.info(format!(
+                "Found {} references to {:X}",
+                self.results.len(),
+                self.target
+            ));
+        }
+
+        let mut shown = self.shown;
+
+        Window::new(format!("References to {:X}", self.target))
+            .id("_references_window".into())
+            .open(&mut shown)
+            .show(ctx, |ui| {
+                if self.search.is_some() {
+                    ui.add(
+                        ProgressBar::new(
+                            self.progress.load(Ordering::Relaxed) as f32 / self.total.max(1) as f32,
+                        )
+                        .show_percentage(),
+                    );
+                    ui.ctx().request_repaint();
+                    return;
+                }
+
+                if self.results.is_empty() {
+                    ui.label("No pointer fields point to the address");
+                    return;
+                }
+
+                let state = &mut *self.state.borrow_mut();
+                let mut selected = None;
+                ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+                    ui.style_mut().override_font_id = Some(FontId::monospace(14.));
+
+                    for reference in &self.results {
+                        let Some(class) = state.class_list.by_id(reference.class_id) else {
+                            continue;
+                        };
+                        let field = state
+                            .class_list
+                            .by_id(reference.container_id)
+                            .and_then(|c| c.field(reference.field_id));
+                        let Some(field) = field else {
+                            continue;
+                        };
+
+                        let text = format!(
+                            "{}+{:X} {} at {:X}",
+                            class.name,
+                            reference.offset,
+                            field.name().unwrap_or_default(),
+                            reference.address
+                        );
+                        if ui
+                            .add(Label::new(text).sense(Sense::click()))
+                            .on_hover_text("Click to inspect the field")
+                            .clicked()
+                        {
+                            selected = Some(*reference);
+                        }
+                    }
+                });
+
+                if let Some(reference) = selected {
+                    *state.class_list.selected_mut() = Some(reference.class_id);
+                    state.multi_selection.clear();
+                    state.selection = Some(Selection {
+                        address: reference.address,
+                        container_id: reference.container_id,
+                        field_id: reference.field_id,
+                    });
+                }
+            });
+        self.shown = shown;
+    }
+}
+
+/// Pointer fields of all classes at the current addresses of the classes, including fields of
+/// base classes.
+fn pointer_fields(class_list: &ClassList) -> Vec<Reference> {
+    let mut references = vec![];
+    for class in class_list.classes() {
+        let mut offset = 0;
+        let classes = class_list
+            .bases_of(class.id())
+            .into_iter()
+            .chain([class.id()]);
+        for container in classes.filter_map(|id| class_list.by_id(id)) {
+            for (offset, field) in walk_fields(&container.fields, offset) {
+                if matches!(
+                    field.kind(),
+                    FieldKind::Ptr | FieldKind::StrPtr | FieldKind::WStrPtr
+                ) {
+                    references.push(Reference {
+                        class_id: class.id(),
+                        container_id: container.id(),
+                        field_id: field.id(),
+                        offset,
+                        address: class.address.get() + offset,
+                    });
+                }
+            }
+            offset += container.fields.iter().map(|f| f.size()).sum::<usize>();
+        }
+    }
+    references
+}
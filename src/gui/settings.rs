@@ -0,0 +1,437 @@
+use super::math_types_editor;
+use crate::{
+    config::YClassConfig,
+    field::{set_max_displayed_elements, DEFAULT_MAX_DISPLAYED_ELEMENTS},
+    fonts::{inspector_font, load_fonts, set_inspector_font_size, DEFAULT_INSPECTOR_FONT_SIZE},
+    history::DEFAULT_HISTORY_DEPTH,
+    recording::DEFAULT_MAX_RECORDED_VALUES,
+    state::{GlobalState, StateRef},
+};
+use eframe::{
+    egui::{
+        Button, ComboBox, Context, DragValue, Frame, Grid, RichText, ScrollArea, TextFormat, Ui,
+        Window,
+    },
+    epaint::{text::LayoutJob, Color32},
+};
+
+/// Default interval when auto-save is turned on.
+const DEFAULT_AUTO_SAVE_SECS: u64 = 300;
+
+/// Repaint intervals in milliseconds, `0` repaints only on input.
+const UPDATE_PRESETS: [(u64, &str); 4] = [
+    (16, "Real-time (16ms)"),
+    (100, "Normal (100ms)"),
+    (500, "Low power (500ms)"),
+    (0, "Manual (only on input)"),
+];
+
+/// Changes apply immediately but are only written to disk by `Save`, closing the window
+/// reverts them.
+pub struct SettingsWindow {
+    state: StateRef,
+    shown: bool,
+    // Config as it was when the window was opened or last saved.
+    original: Option<YClassConfig>,
+}
+
+impl SettingsWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            original: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        if !self.shown {
+            self.cancel(ctx);
+            return;
+        }
+
+        let mut open = true;
+        Window::new("Settings")
+            .open(&mut open)
+            .default_height(400.)
+            .show(ctx, |ui| {
+                let original = self
+                    .original
+                    .get_or_insert_with(|| self.state.borrow().config.clone());
+
+                let state = &mut *self.state.borrow_mut();
+                ScrollArea::vertical().max_height(400.).show(ui, |ui| {
+                    let mut config = state.config.clone();
+                    settings_ui(ui, &mut config);
+
+                    if config != state.config {
+                        let reload_fonts =
+                            config.inspector_font_family != state.config.inspector_font_family;
+                        state.config = config;
+                        apply(state);
+                        if reload_fonts {
+                            apply_fonts(ui.ctx(), state);
+                        }
+                    }
+                });
+                // Changing the scale while dragging would move the value under the cursor.
+                if !ui.input(|i| i.pointer.any_down()) {
+                    apply_dpi(ui.ctx(), &state.config);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let unsaved = *original != state.config;
+                    if ui.add_enabled(unsaved, Button::new("Save")).clicked() {
+                        state.config.save();
+                        *original = state.config.clone();
+                    }
+
+                    if ui.button("Cancel").clicked() {
+                        self.shown = false;
+                    }
+
+                    if unsaved {
+                        ui.label(RichText::new("Unsaved changes").color(Color32::YELLOW));
+                    }
+                });
+            });
+
+        if !open || !self.shown {
+            self.shown = false;
+            self.cancel(ctx);
+        }
+    }
+
+    /// Reverts unsaved changes.
+    fn cancel(&mut self, ctx: &Context) {
+        let Some(original) = self.original.take() else {
+            return;
+        };
+
+        let state = &mut *self.state.borrow_mut();
+        let config = &mut state.config;
+        // Other windows change the config too, only settings shown here are reverted.
+        config.dpi = original.dpi;
+        config.inspector_font_size = original.inspector_font_size;
+        let reload_fonts = config.inspector_font_family != original.inspector_font_family;
+        config.inspector_font_family = original.inspector_font_family;
+        config.theme = original.theme;
+        config.update_interval_ms = original.update_interval_ms;
+        config.plugin_path = original.plugin_path;
+        config.plugin_hot_reload = original.plugin_hot_reload;
+        config.auto_save_interval_secs = original.auto_save_interval_secs;
+        config.clear_history_on_save = original.clear_history_on_save;
+        config.reopen_last_project = original.reopen_last_project;
+        config.history_depth = original.history_depth;
+        config.max_dynamic_array_elements = original.max_dynamic_array_elements;
+        config.max_recorded_values = original.max_recorded_values;
+        config.verify_writes = original.verify_writes;
+        config.cpp_math_types = original.cpp_math_types;
+
+        apply(state);
+        apply_dpi(ctx, &state.config);
+        if reload_fonts {
+            apply_fonts(ctx, state);
+        }
+    }
+}
+
+/// Applies settings that are otherwise only read on startup.
+fn apply(state: &mut GlobalState) {
+    let config = &state.config;
+    state
+        .history
+        .set_max_depth(config.history_depth.unwrap_or(DEFAULT_HISTORY_DEPTH));
+    set_max_displayed_elements(
+        config
+            .max_dynamic_array_elements
+            .unwrap_or(DEFAULT_MAX_DISPLAYED_ELEMENTS),
+    );
+    set_inspector_font_size(
+        config
+            .inspector_font_size
+            .unwrap_or(DEFAULT_INSPECTOR_FONT_SIZE),
+    );
+}
+
+fn apply_fonts(ctx: &Context, state: &mut GlobalState) {
+    if let Err(e) = load_fonts(ctx, &state.config) {
+        state.toasts.error(e.to_string());
+    }
+}
+
+fn apply_dpi(ctx: &Context, config: &YClassConfig) {
+    let dpi = config.dpi.unwrap_or(1.);
+    if ctx.pixels_per_point() != dpi {
+        ctx.set_pixels_per_point(dpi);
+    }
+}
+
+/// Field rows the way the inspector draws them with the font settings.
+fn font_preview(ui: &mut Ui, config: &YClassConfig) {
+    let font = inspector_font(
+        config
+            .inspector_font_size
+            .unwrap_or(DEFAULT_INSPECTOR_FONT_SIZE),
+    );
+    let theme = config.theme();
+    let rows = [
+        ("0000", "7FF6A0001000", "Ptr", "owner", "-> 1F2A9C40"),
+        ("0008", "7FF6A0001008", "i32", "health", "100"),
+        ("000C", "7FF6A000100C", "f32", "speed", "4.5"),
+    ];
+
+    Frame::group(ui.style()).show(ui, |ui| {
+        for (offset, address, kind, name, value) in rows {
+            let mut job = LayoutJob::default();
+            let mut append = |text: &str, color| {
+                job.append(text, 8., TextFormat::simple(font.clone(), color));
+            };
+            append(offset, theme.offset);
+            append(address, theme.address);
+            append(kind, Color32::LIGHT_RED);
+            append(name, Color32::LIGHT_BLUE);
+            append(value, theme.foreground);
+            ui.label(job);
+        }
+    });
+}
+
+fn settings_ui(ui: &mut Ui, config: &mut YClassConfig) {
+    ui.heading("General");
+    Grid::new("_settings_general")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Zoom");
+            let mut dpi = config.dpi.unwrap_or(1.);
+            if ui
+                .add(DragValue::new(&mut dpi).range(0.5..=3.).speed(0.01))
+                .changed()
+            {
+                config.dpi = Some(dpi);
+            }
+            ui.end_row();
+
+            ui.label("Theme")
+                .on_hover_text("Custom themes are added to `themes` in the config file");
+            let mut theme = config.theme().name.clone();
+            ComboBox::from_id_salt("_theme")
+                .selected_text(&theme)
+                .show_ui(ui, |ui| {
+                    for t in config.themes() {
+                        ui.selectable_value(&mut theme, t.name.clone(), &t.name);
+                    }
+                });
+            if theme != config.theme().name {
+                config.theme = Some(theme);
+            }
+            ui.end_row();
+
+            ui.label("Update rate");
+            let mut update_interval = config.update_interval_ms.unwrap_or(100);
+            let selected = UPDATE_PRESETS
+                .iter()
+                .find(|(ms, _)| *ms == update_interval)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| format!("Custom ({update_interval}ms)"));
+            ComboBox::from_id_salt("_update_rate")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (ms, name) in UPDATE_PRESETS {
+                        ui.selectable_value(&mut update_interval, ms, name);
+                    }
+                });
+            if update_interval != config.update_interval_ms.unwrap_or(100) {
+                config.update_interval_ms = Some(update_interval);
+            }
+            ui.end_row();
+
+            ui.label("Plugin")
+                .on_hover_text("Used when attaching to a process");
+            ui.horizontal(|ui| {
+                let path = config
+                    .plugin_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "plugin.ycpl".to_owned());
+                ui.label(RichText::new(path).monospace());
+
+                if ui.button("Browse").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .set_title("Select plugin")
+                        .add_filter("Plugins", &["ycpl", "dll", "so"])
+                        .pick_file()
+                    {
+                        config.plugin_path = Some(path);
+                    }
+                }
+
+                if config.plugin_path.is_some() && ui.button("Default").clicked() {
+                    config.plugin_path = None;
+                }
+            });
+            ui.end_row();
+
+            ui.label("");
+            let mut hot_reload = config.plugin_hot_reload();
+            if ui
+                .checkbox(&mut hot_reload, "Reload when the file changes")
+                .on_hover_text("The plugin is loaded from a temporary copy, so it can be rebuilt while attached")
+                .changed()
+            {
+                config.plugin_hot_reload = Some(hot_reload);
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(8.);
+    ui.heading("Inspector font");
+    Grid::new("_settings_font").num_columns(2).show(ui, |ui| {
+        ui.label("Size")
+            .on_hover_text("In points, scaled by the zoom like the rest of the UI");
+        let mut size = config
+            .inspector_font_size
+            .unwrap_or(DEFAULT_INSPECTOR_FONT_SIZE);
+        if ui
+            .add(DragValue::new(&mut size).range(8. ..=40.).speed(0.1))
+            .changed()
+        {
+            config.inspector_font_size = Some(size);
+        }
+        ui.end_row();
+
+        ui.label("Font");
+        ui.horizontal(|ui| {
+            let path = config
+                .inspector_font_family
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "Roboto Mono".to_owned());
+            ui.label(RichText::new(path).monospace());
+
+            if ui.button("Browse").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Select font")
+                    .add_filter("Fonts", &["ttf", "otf", "ttc"])
+                    .pick_file()
+                {
+                    config.inspector_font_family = Some(path);
+                }
+            }
+
+            if config.inspector_font_family.is_some() && ui.button("Default").clicked() {
+                config.inspector_font_family = None;
+            }
+        });
+        ui.end_row();
+    });
+    font_preview(ui, config);
+
+    ui.add_space(8.);
+    ui.heading("Project");
+    Grid::new("_settings_project")
+        .num_columns(2)
+        .show(ui, |ui| {
+            let mut enabled = config.auto_save_interval_secs.is_some();
+            ui.checkbox(&mut enabled, "Auto-save")
+                .on_hover_text("Unnamed projects are saved to a temporary file");
+            let mut auto_save = enabled.then(|| {
+                config
+                    .auto_save_interval_secs
+                    .unwrap_or(DEFAULT_AUTO_SAVE_SECS)
+            });
+            if let Some(secs) = auto_save.as_mut() {
+                ui.add(DragValue::new(secs).range(10..=3600).suffix(" s"));
+            }
+            if auto_save != config.auto_save_interval_secs {
+                config.auto_save_interval_secs = auto_save;
+            }
+            ui.end_row();
+
+            let mut clear_history = config.clear_history_on_save.unwrap_or(false);
+            if ui
+                .checkbox(&mut clear_history, "Clear undo history on save")
+                .changed()
+            {
+                config.clear_history_on_save = Some(clear_history);
+            }
+            ui.end_row();
+
+            let mut reopen = config.reopen_last_project();
+            if ui
+                .checkbox(&mut reopen, "Reopen last project on startup")
+                .changed()
+            {
+                config.reopen_last_project = Some(reopen);
+            }
+            ui.end_row();
+
+            ui.label("Undo history depth");
+            let mut depth = config.history_depth.unwrap_or(DEFAULT_HISTORY_DEPTH);
+            if ui
+                .add(DragValue::new(&mut depth).range(1..=10000))
+                .changed()
+            {
+                config.history_depth = Some(depth);
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(8.);
+    ui.heading("Inspector");
+    Grid::new("_settings_inspector")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Max dynamic array elements");
+            let mut count = config
+                .max_dynamic_array_elements
+                .unwrap_or(DEFAULT_MAX_DISPLAYED_ELEMENTS);
+            if ui
+                .add(DragValue::new(&mut count).range(1..=65536))
+                .changed()
+            {
+                config.max_dynamic_array_elements = Some(count);
+            }
+            ui.end_row();
+
+            ui.label("Max recorded values");
+            let mut count = config
+                .max_recorded_values
+                .unwrap_or(DEFAULT_MAX_RECORDED_VALUES);
+            if ui
+                .add(DragValue::new(&mut count).range(10..=1_000_000))
+                .on_hover_text("Oldest values of a recorded field are dropped")
+                .changed()
+            {
+                config.max_recorded_values = Some(count);
+            }
+            ui.end_row();
+
+            ui.label("");
+            let mut verify = config.verify_writes();
+            if ui
+                .checkbox(&mut verify, "Verify frozen values")
+                .on_hover_text("Reads frozen values back after writing them, values that don't stick are unfrozen")
+                .changed()
+            {
+                config.verify_writes = Some(verify);
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(8.);
+    ui.heading("C++ math types");
+    let mut types = config.cpp_math_types.clone().unwrap_or_default();
+    math_types_editor(ui, &mut types);
+    if config.cpp_math_types.as_ref() != Some(&types) {
+        config.cpp_math_types = Some(types);
+    }
+
+    ui.add_space(8.);
+    ui.label(RichText::new("Hotkeys are changed in Edit > Hotkeys").color(Color32::GRAY));
+}
@@ -0,0 +1,141 @@
+use super::{bytes_to_value, SearchResult};
+use crate::{process::Process, value::Value};
+use std::fmt::Write;
+
+/// Spider result read from the process at the moment of exporting.
+pub(crate) struct ExportedResult {
+    /// Number of pointers followed from the base address, plus one for the value itself.
+    pub depth: usize,
+    /// Address of the value.
+    pub address: usize,
+    pub offsets: Vec<usize>,
+    pub value: Value,
+    pub bytes: Vec<u8>,
+}
+
+impl ExportedResult {
+    pub fn read(p: &Process, base: usize, result: &SearchResult) -> Self {
+        let mut address = base;
+        for offset in result.parent_offsets.iter() {
+            address = p.read_pointer(address.saturating_add(*offset));
+        }
+        address = address.saturating_add(result.offset);
+
+        let mut buf = [0; 8];
+        p.read(address, &mut buf[..]);
+        let kind = result.last_value.kind();
+        let size = kind.size(p.pointer_size());
+
+        Self {
+            depth: result.parent_offsets.len() + 1,
+            address,
+            offsets: result
+                .parent_offsets
+                .iter()
+                .copied()
+                .chain([result.offset])
+                .collect(),
+            value: bytes_to_value(&buf, kind),
+            bytes: buf[..size].to_vec(),
+        }
+    }
+
+    fn hex_value(&self) -> String {
+        self.bytes.iter().map(|b| format!("{b:02X}")).collect()
+    }
+
+    /// Printable ASCII characters of the value, others are replaced with `.`.
+    fn ascii_preview(&self) -> String {
+        self.bytes
+            .iter()
+            .map(|b| {
+                if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+
+    fn path(&self) -> String {
+        self.offsets
+            .iter()
+            .map(|o| format!("+{o:X}"))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+/// Quotes a CSV cell if it contains separators or quotes.
+fn csv_cell(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => _ = write!(out, "\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub(crate) fn to_csv(results: &[ExportedResult]) -> String {
+    let mut csv = "depth,address,hex_value,ascii_preview\n".to_owned();
+    for r in results {
+        _ = writeln!(
+            csv,
+            "{},{:X},{},{}",
+            r.depth,
+            r.address,
+            r.hex_value(),
+            csv_cell(&r.ascii_preview())
+        );
+    }
+    csv
+}
+
+/// Array of objects with `depth`, `address`, `offsets`, `path`, `kind`, `value`, `hex_value`
+/// and `ascii_preview`. Addresses and offsets are numbers, non finite floats become `null`.
+pub(crate) fn to_json(results: &[ExportedResult]) -> String {
+    let mut json = "[\n".to_owned();
+    for (i, r) in results.iter().enumerate() {
+        let value = match r.value {
+            Value::F32(v) if !v.is_finite() => "null".to_owned(),
+            Value::F64(v) if !v.is_finite() => "null".to_owned(),
+            v => v.to_string(),
+        };
+        let offsets = r
+            .offsets
+            .iter()
+            .map(|o| o.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        _ = write!(
+            json,
+            "  {{\"depth\": {}, \"address\": {}, \"offsets\": [{offsets}], \"path\": {}, \
+             \"kind\": {}, \"value\": {value}, \"hex_value\": {}, \"ascii_preview\": {}}}",
+            r.depth,
+            r.address,
+            json_string(&r.path()),
+            json_string(r.value.kind().label().unwrap_or_default()),
+            json_string(&r.hex_value()),
+            json_string(&r.ascii_preview()),
+        );
+        json.push_str(if i + 1 < results.len() { ",\n" } else { "\n" });
+    }
+    json.push(']');
+    json
+}
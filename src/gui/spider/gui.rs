@@ -1,4 +1,7 @@
-use super::{FilterMode, ScannerReport, ScannerState, SearchResult};
+use super::{
+    export::{self, ExportedResult},
+    FilterMode, ScannerReport, ScannerState, SearchResult,
+};
 use crate::{
     address::parse_address,
     field::FieldKind,
@@ -15,7 +18,12 @@ use eframe::{
     epaint::{vec2, FontId},
 };
 use egui_extras::{Column, TableBuilder};
-use std::{borrow::Cow, sync::Arc, time::Instant};
+use std::{borrow::Cow, fs, sync::Arc, time::Instant};
+
+enum ExportFormat {
+    Csv,
+    Json,
+}
 
 enum DisplayMode {
     Normal,
@@ -167,8 +175,9 @@ impl SpiderWindow {
                                     .selectable_label(*var == self.field_kind, *label)
                                     .clicked()
                                 {
-                                    self.field_kind = *var;
-                                    self.alignment.set(var.size(), var.size().to_string());
+                                    self.field_kind = var.clone();
+                                    let size = var.size(process.pointer_size());
+                                    self.alignment.set(size, size.to_string());
                                 }
                             }
                         });
@@ -228,7 +237,8 @@ impl SpiderWindow {
                                         })
                                     })
                                     .ok_or(eyre::eyre!("Base address is required"))??;
-                                let value = parse_kind_to_value(self.field_kind, &self.value_buf)?;
+                                let value =
+                                    parse_kind_to_value(self.field_kind.clone(), &self.value_buf)?;
 
                                 let time = Instant::now();
                                 self.results.retain_mut(|r| {
@@ -245,6 +255,20 @@ impl SpiderWindow {
                                 self.scanner_status = None;
                             }
 
+                            ui.menu_button("Export", |ui| {
+                                if ui.button("CSV").clicked() {
+                                    ui.close();
+                                    self.export(process, ExportFormat::Csv)?;
+                                }
+                                if ui.button("JSON").clicked() {
+                                    ui.close();
+                                    self.export(process, ExportFormat::Json)?;
+                                }
+                                eyre::Ok(())
+                            })
+                            .inner
+                            .transpose()?;
+
                             // This looks a bit nasty but *shrug*
                             let mut as_hex = matches!(self.display, DisplayMode::Hex);
                             if !matches!(self.field_kind, FieldKind::F32 | FieldKind::F64)
@@ -324,8 +348,7 @@ impl SpiderWindow {
                     let mut address = address;
                     let mut buf = [0; 8];
                     for offset in result.parent_offsets.iter() {
-                        process.read(address + offset, &mut buf[..]);
-                        address = usize::from_ne_bytes(buf);
+                        address = process.read_pointer(address + offset);
                     }
                     process.read(address + result.offset, &mut buf[..]);
 
@@ -343,6 +366,39 @@ impl SpiderWindow {
             });
     }
 
+    /// Writes results with their current values to a file picked by the user.
+    fn export(&self, process: &Process, format: ExportFormat) -> eyre::Result<()> {
+        let address = self
+            .base_address
+            .value_clone()
+            .map(|v| v.map_err(|_| eyre::eyre!("Base adderss is in invalid format")))
+            .ok_or(eyre::eyre!("Base address is required"))??;
+
+        let (name, extension) = match format {
+            ExportFormat::Csv => ("CSV", "csv"),
+            ExportFormat::Json => ("JSON", "json"),
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export spider results")
+            .set_file_name(&format!("spider.{extension}"))
+            .add_filter(name, &[extension])
+            .save_file()
+        else {
+            return Ok(());
+        };
+
+        let results = self
+            .results
+            .iter()
+            .map(|r| ExportedResult::read(process, address, r))
+            .collect::<Vec<_>>();
+        let data = match format {
+            ExportFormat::Csv => export::to_csv(&results),
+            ExportFormat::Json => export::to_json(&results),
+        };
+        fs::write(path, data).map_err(|e| eyre::eyre!("Failed to export the results. {e}"))
+    }
+
     fn collect_options(&self) -> eyre::Result<SearchOptions> {
         macro_rules! annotated {
             ($field:ident, $label:literal) => {
@@ -362,7 +418,7 @@ impl SpiderWindow {
             .map(|v| v.map_err(|_| eyre::eyre!("Base adderss is in invalid format")))
             .ok_or(eyre::eyre!("Base address is required"))??;
 
-        let value = parse_kind_to_value(self.field_kind, &self.value_buf)?;
+        let value = parse_kind_to_value(self.field_kind.clone(), &self.value_buf)?;
 
         Ok(SearchOptions {
             offsets: Arc::default(),
@@ -1,3 +1,4 @@
+mod export;
 mod gui;
 pub use gui::*;
 mod scanner;
@@ -19,7 +20,7 @@ pub(crate) enum FilterMode {
 }
 
 impl FilterMode {
-    const NAMED_VARIANTS: &'static [(Self, &'static str)] = &[
+    pub(crate) const NAMED_VARIANTS: &'static [(Self, &'static str)] = &[
         (Self::Greater, "Greater"),
         (Self::GreaterEq, "Greater or Equal"),
         (Self::Less, "Less"),
@@ -30,12 +31,30 @@ impl FilterMode {
         (Self::Unchanged, "Unchanged"),
     ];
 
-    fn label(&self) -> &'static str {
+    pub(crate) fn label(&self) -> &'static str {
         Self::NAMED_VARIANTS
             .iter()
             .find_map(|(v, s)| if v == self { Some(*s) } else { None })
             .unwrap()
     }
+
+    /// Returns `true` if filter should require the value from the previous scan.
+    pub(crate) fn compares_previous(&self) -> bool {
+        matches!(self, Self::Changed | Self::Unchanged)
+    }
+
+    pub(crate) fn matches(&self, current: Value, previous: Value, value: Value) -> bool {
+        match self {
+            Self::Less => current < value,
+            Self::LessEq => current <= value,
+            Self::Greater => current > value,
+            Self::GreaterEq => current >= value,
+            Self::Equal => current == value,
+            Self::NotEqual => current != value,
+            Self::Changed => current != previous,
+            Self::Unchanged => current == previous,
+        }
+    }
 }
 
 pub(crate) struct SearchOptions {
@@ -67,32 +86,21 @@ impl SearchResult {
         let mut buf = [0; 8];
 
         for offset in self.parent_offsets.iter() {
-            p.read(address.saturating_add(*offset), &mut buf[..]);
-            address = usize::from_ne_bytes(buf);
+            address = p.read_pointer(address.saturating_add(*offset));
         }
-        p.read(address.saturating_add(self.offset), &mut buf[..]);
-        address = usize::from_ne_bytes(buf);
+        address = p.read_pointer(address.saturating_add(self.offset));
 
         p.read(address, &mut buf[..]);
 
         let current_value = bytes_to_value(&buf, self.last_value.kind());
-        let result = match filter {
-            FilterMode::Less => current_value < new_value,
-            FilterMode::LessEq => current_value <= new_value,
-            FilterMode::Greater => current_value > new_value,
-            FilterMode::GreaterEq => current_value >= new_value,
-            FilterMode::Equal => current_value == new_value,
-            FilterMode::NotEqual => current_value != new_value,
-            FilterMode::Changed => current_value != self.last_value,
-            FilterMode::Unchanged => current_value == self.last_value,
-        };
+        let result = filter.matches(current_value, self.last_value, new_value);
 
         self.last_value = current_value;
         result
     }
 }
 
-fn bytes_to_value(arr: &[u8; 8], kind: FieldKind) -> Value {
+pub(crate) fn bytes_to_value(arr: &[u8; 8], kind: FieldKind) -> Value {
     macro_rules! into_value {
         ($s:ident, $type:ty) => {
             <$type>::from_ne_bytes(arr[..std::mem::size_of::<$type>()].try_into().unwrap()).into()
@@ -114,7 +122,7 @@ fn bytes_to_value(arr: &[u8; 8], kind: FieldKind) -> Value {
     }
 }
 
-fn parse_kind_to_value(kind: FieldKind, s: &str) -> eyre::Result<Value> {
+pub(crate) fn parse_kind_to_value(kind: FieldKind, s: &str) -> eyre::Result<Value> {
     macro_rules! into_value {
         ($s:ident, $type:ty) => {
             if let Some(tail) = $s.strip_prefix("0x") {
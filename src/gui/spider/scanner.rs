@@ -85,16 +85,18 @@ fn recursive_first_search(
             opts.alignment - opts.address % opts.alignment
         };
 
+    let pointer_size = process.read().as_ref().unwrap().pointer_size();
     for address in (start..start + opts.struct_size).step_by(opts.alignment) {
         let mut buf = [0; 8];
         process.read().as_ref().unwrap().read(address, &mut buf[..]);
 
-        if address % 8 == 0
-            && process
-                .read()
-                .as_ref()
-                .unwrap()
-                .can_read(usize::from_ne_bytes(buf))
+        // Zero extend in case of 32-bit pointers.
+        let mut pointer = [0; 8];
+        pointer[..pointer_size].copy_from_slice(&buf[..pointer_size]);
+        let pointer = usize::from_ne_bytes(pointer);
+
+        if address.is_multiple_of(pointer_size)
+            && process.read().as_ref().unwrap().can_read(pointer)
         {
             rayon::spawn({
                 let results = results.clone();
@@ -111,7 +113,7 @@ fn recursive_first_search(
                             offsets: Arc::new(
                                 offsets.iter().copied().chain([address - start]).collect(),
                             ),
-                            address: usize::from_ne_bytes(buf),
+                            address: pointer,
                             struct_size: opts.struct_size,
                             alignment: opts.alignment,
                             depth: opts.depth - 1,
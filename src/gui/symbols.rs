@@ -0,0 +1,117 @@
+use crate::{state::StateRef, symbols};
+use eframe::{
+    egui::{Context, Grid, RichText, TextEdit, Window},
+    epaint::Color32,
+};
+use std::collections::BTreeMap;
+
+/// Maps module names to PDB or ELF files, symbols are shown next to function pointers and vtables.
+pub struct SymbolsWindow {
+    state: StateRef,
+    shown: bool,
+    module: String,
+}
+
+impl SymbolsWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            module: String::new(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("Import symbols")
+            .open(&mut shown)
+            .show(ctx, |ui| {
+                let state = &mut *self.state.borrow_mut();
+                let files = state.config.symbol_files.get_or_insert_with(BTreeMap::new);
+
+                let mut remove = None;
+                if files.is_empty() {
+                    ui.label("No symbols are imported");
+                } else {
+                    Grid::new("_symbol_files")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (module, path) in files.iter() {
+                                ui.label(RichText::new(module).monospace());
+                                ui.label(path.file_name().unwrap_or_default().to_string_lossy())
+                                    .on_hover_text(path.display().to_string());
+                                match symbols::status(module) {
+                                    Some(Ok(count)) => _ = ui.label(format!("{count} symbols")),
+                                    Some(Err(e)) => {
+                                        ui.label(RichText::new("Failed").color(Color32::RED))
+                                            .on_hover_text(e);
+                                    }
+                                    None => _ = ui.spinner(),
+                                }
+                                if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                    remove = Some(module.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                if let Some(module) = remove {
+                    files.remove(&module);
+                    symbols::unload(&module);
+                    state.config.save();
+                    return;
+                }
+
+                ui.separator();
+                let imported = ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.module)
+                            .hint_text("Module, i.e. game.exe or libgame.so")
+                            .desired_width(150.),
+                    );
+
+                    let module = self.module.trim().to_lowercase();
+                    let clicked = ui
+                        .button("Browse")
+                        .on_hover_text("PDB file, or the ELF file of the module with symbols")
+                        .clicked();
+                    clicked
+                        .then(|| {
+                            rfd::FileDialog::new()
+                                .set_title("Import symbols")
+                                .add_filter("Symbols", &["pdb", "so", "elf", "debug"])
+                                .add_filter("All files", &["*"])
+                                .pick_file()
+                        })
+                        .flatten()
+                        .map(|path| {
+                            // ELF files are usually named after the module.
+                            let module = if module.is_empty() {
+                                path.file_name()
+                                    .unwrap_or_default()
+                                    .to_string_lossy()
+                                    .to_lowercase()
+                            } else {
+                                module
+                            };
+                            (module, path)
+                        })
+                });
+
+                if let Some((module, path)) = imported.inner {
+                    symbols::load(ctx, &module, path.clone());
+                    files.insert(module, path);
+                    state.config.save();
+                    self.module.clear();
+                }
+            });
+        self.shown = shown;
+    }
+}
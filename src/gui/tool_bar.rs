@@ -1,29 +1,92 @@
-use super::{GeneratorWindow, ProcessAttachWindow, SpiderWindow};
+use super::{
+    is_typing_hex, AobScanWindow, AttachTarget, AutoTagWindow, BatchRenameWindow, ClassGraphWindow,
+    FieldStatsWindow, GeneratorWindow, HeaderImportWindow, HexEditorWindow, HotkeysWindow,
+    MemoryMapWindow, MergeClassWindow, MinidumpWriter, ModuleListWindow, ProcessAttachWindow,
+    RecordingPlotWindows, ReferencesWindow, SettingsWindow, SpiderWindow, SymbolsWindow,
+    ValidationWindow, ValueScanWindow, VtableScannerWindow,
+};
 use crate::{
-    class::ClassList,
-    field::FieldKind,
+    bookmarks::BOOKMARK_HOTKEYS,
+    class::{ClassId, ClassList},
+    config::{CHANGE_KIND_HOTKEYS, RECENT_PROJECT_HOTKEYS},
+    field::{format_unix_seconds, FieldId, FieldKind},
     state::{GlobalState, StateRef},
 };
 use eframe::{
-    egui::{Button, Context, Frame, Margin, TopBottomPanel, Ui, WidgetText},
+    egui::{
+        containers::menu::{MenuButton, MenuConfig},
+        Button, Context, Event, Frame, Margin, PopupCloseBehavior, RichText, TopBottomPanel, Ui,
+        WidgetText,
+    },
     epaint::CornerRadius,
 };
 use memflex::external::ProcessIterator;
 
 pub enum ToolBarResponse {
     ProcessAttach(u32),
+    /// Connects to a memory server at `IP:Port`.
+    RemoteConnect(String),
     MinidumpLoad(std::path::PathBuf),
     ProcessDetach,
     Add(usize),
     Remove(usize),
     Insert(usize),
+    /// Appends padding to the selected class until it's the given size.
+    FillToSize(usize),
     ChangeKind(FieldKind),
+    /// Replaces the selected field with the class.
+    Embed(ClassId),
+    /// Moves the selected fields into a new class embedded in their place.
+    Group,
+    /// Moves the selected fields into a new section in their place.
+    Section,
+    /// Moves the field `from_id` to the position of `to_id` in the same class.
+    Reorder {
+        from_id: FieldId,
+        to_id: FieldId,
+    },
+    /// Searches pointer fields of all classes for the address.
+    FindReferences(usize),
+    /// Opens the hex editor at the address.
+    ViewHex(usize),
+    /// Opens the batch rename window for the selected fields.
+    BatchRename,
+    /// Inspects the class, selected fields belong to the previous one.
+    SwitchToClass(ClassId),
+    /// Opens the disassembly window at the address.
+    #[cfg(feature = "disasm")]
+    ViewCode(usize),
+    /// Attaches the process the inspected class is compared with.
+    SecondaryAttach(AttachTarget),
+    SecondaryDetach,
 }
 
 pub struct ToolBarPanel {
     ps_attach_window: ProcessAttachWindow,
     generator_window: GeneratorWindow,
     spider_window: SpiderWindow,
+    value_scan_window: ValueScanWindow,
+    aob_scan_window: AobScanWindow,
+    module_list_window: ModuleListWindow,
+    memory_map_window: MemoryMapWindow,
+    hex_editor_window: HexEditorWindow,
+    class_graph_window: ClassGraphWindow,
+    field_stats_window: FieldStatsWindow,
+    auto_tag_window: AutoTagWindow,
+    vtable_scanner_window: VtableScannerWindow,
+    minidump_writer: MinidumpWriter,
+    batch_rename_window: BatchRenameWindow,
+    merge_class_window: MergeClassWindow,
+    validation_window: ValidationWindow,
+    header_import_window: HeaderImportWindow,
+    symbols_window: SymbolsWindow,
+    references_window: ReferencesWindow,
+    #[cfg(feature = "disasm")]
+    disassembly_window: super::DisassemblyWindow,
+    settings_window: SettingsWindow,
+    hotkeys_window: HotkeysWindow,
+    recording_plots: RecordingPlotWindows,
+    renaming_bookmark: Option<usize>,
     state: StateRef,
 }
 
@@ -34,14 +97,59 @@ impl ToolBarPanel {
             ps_attach_window: ProcessAttachWindow::new(state),
             generator_window: GeneratorWindow::new(state),
             spider_window: SpiderWindow::new(state),
+            value_scan_window: ValueScanWindow::new(state),
+            aob_scan_window: AobScanWindow::new(state),
+            module_list_window: ModuleListWindow::new(state),
+            memory_map_window: MemoryMapWindow::new(state),
+            hex_editor_window: HexEditorWindow::new(state),
+            class_graph_window: ClassGraphWindow::new(state),
+            field_stats_window: FieldStatsWindow::new(state),
+            auto_tag_window: AutoTagWindow::new(state),
+            vtable_scanner_window: VtableScannerWindow::new(state),
+            minidump_writer: MinidumpWriter::new(state),
+            batch_rename_window: BatchRenameWindow::new(state),
+            merge_class_window: MergeClassWindow::new(state),
+            validation_window: ValidationWindow::new(state),
+            header_import_window: HeaderImportWindow::new(state),
+            symbols_window: SymbolsWindow::new(state),
+            references_window: ReferencesWindow::new(state),
+            #[cfg(feature = "disasm")]
+            disassembly_window: super::DisassemblyWindow::new(state),
+            settings_window: SettingsWindow::new(state),
+            hotkeys_window: HotkeysWindow::new(state),
+            recording_plots: RecordingPlotWindows::new(state),
+            renaming_bookmark: None,
         }
     }
 
+    pub fn find_references(&mut self, address: usize) {
+        self.references_window.search(address);
+    }
+
+    pub fn view_hex(&mut self, address: usize) {
+        self.hex_editor_window.open(address);
+    }
+
+    pub fn batch_rename(&mut self) {
+        self.batch_rename_window.open();
+    }
+
+    #[cfg(feature = "disasm")]
+    pub fn view_code(&mut self, address: usize) {
+        self.disassembly_window.open(address);
+    }
+
     pub fn show(&mut self, ctx: &Context) -> Option<ToolBarResponse> {
         let mut response = None;
 
-        if let Some(pid) = self.ps_attach_window.show(ctx) {
-            response = Some(ToolBarResponse::ProcessAttach(pid));
+        if let Some(target) = self.ps_attach_window.show(ctx) {
+            response = Some(match target {
+                _ if self.ps_attach_window.is_secondary() => {
+                    ToolBarResponse::SecondaryAttach(target)
+                }
+                AttachTarget::Process(pid) => ToolBarResponse::ProcessAttach(pid),
+                AttachTarget::Remote(address) => ToolBarResponse::RemoteConnect(address),
+            });
             self.ps_attach_window.toggle();
         }
 
@@ -49,6 +157,31 @@ impl ToolBarPanel {
         if let Err(e) = self.spider_window.show(ctx) {
             self.state.borrow_mut().toasts.error(e.to_string());
         }
+        if let Err(e) = self.value_scan_window.show(ctx) {
+            self.state.borrow_mut().toasts.error(e.to_string());
+        }
+        if let Err(e) = self.aob_scan_window.show(ctx) {
+            self.state.borrow_mut().toasts.error(e.to_string());
+        }
+        self.module_list_window.show(ctx);
+        self.memory_map_window.show(ctx);
+        self.hex_editor_window.show(ctx);
+        self.class_graph_window.show(ctx);
+        self.field_stats_window.show(ctx);
+        self.auto_tag_window.show(ctx);
+        self.vtable_scanner_window.show(ctx);
+        self.minidump_writer.show(ctx);
+        self.batch_rename_window.show(ctx);
+        self.merge_class_window.show(ctx);
+        self.validation_window.show(ctx);
+        self.header_import_window.show(ctx);
+        self.symbols_window.show(ctx);
+        self.references_window.show(ctx);
+        #[cfg(feature = "disasm")]
+        self.disassembly_window.show(ctx);
+        self.settings_window.show(ctx);
+        self.hotkeys_window.show(ctx);
+        self.recording_plots.show(ctx);
 
         self.run_hotkeys(ctx, &mut response);
 
@@ -69,7 +202,15 @@ impl ToolBarPanel {
                     ui.visuals_mut().widgets.inactive.corner_radius = CornerRadius::ZERO;
 
                     ui.menu_button("Project", |ui| self.project_menu(ui));
+                    ui.menu_button("Edit", |ui| self.edit_menu(ui));
                     ui.menu_button("Process", |ui| self.process_menu(ui, &mut response));
+                    // Renaming happens inside the menu so it shouldn't close on every click.
+                    MenuButton::new("Bookmarks")
+                        .config(
+                            MenuConfig::new()
+                                .close_behavior(PopupCloseBehavior::CloseOnClickOutside),
+                        )
+                        .ui(ui, |ui| self.bookmarks_menu(ui));
 
                     if ui.button("Generator").clicked() {
                         self.generator_window.toggle();
@@ -79,6 +220,50 @@ impl ToolBarPanel {
                         self.spider_window.toggle();
                     }
 
+                    if ui.button("Value scan").clicked() {
+                        self.value_scan_window.toggle();
+                    }
+
+                    if ui.button("AOB scan").clicked() {
+                        self.aob_scan_window.toggle();
+                    }
+
+                    if ui.button("Modules").clicked() {
+                        self.module_list_window.toggle();
+                    }
+
+                    if ui.button("Memory map").clicked() {
+                        self.memory_map_window.toggle();
+                    }
+
+                    if ui.button("Hex editor").clicked() {
+                        self.hex_editor_window.toggle();
+                    }
+
+                    if ui.button("Class graph").clicked() {
+                        self.class_graph_window.toggle();
+                    }
+
+                    if ui.button("Field stats").clicked() {
+                        self.field_stats_window.toggle();
+                    }
+
+                    if ui
+                        .button("Auto-tag")
+                        .on_hover_text("Suggests kinds of unknown fields from their values")
+                        .clicked()
+                    {
+                        self.auto_tag_window.toggle();
+                    }
+
+                    if ui
+                        .button("VTable scanner")
+                        .on_hover_text("Lists functions of a vtable")
+                        .clicked()
+                    {
+                        self.vtable_scanner_window.toggle();
+                    }
+
                     ui.add_space(4.);
                     ui.separator();
                     ui.add_space(4.);
@@ -87,6 +272,18 @@ impl ToolBarPanel {
                 });
             });
 
+        let state = &mut *self.state.borrow_mut();
+        if let Some((count, size)) = state.clipboard.as_ref().map(|c| (c.count(), c.size())) {
+            TopBottomPanel::bottom("_status_bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Clipboard: {count} fields, {size} bytes"));
+                    if ui.small_button("Clear").clicked() {
+                        state.clipboard = None;
+                    }
+                });
+            });
+        }
+
         response
     }
 
@@ -103,6 +300,62 @@ impl ToolBarPanel {
                 attach_to_process(state, &name, response);
             }
         }
+
+        for (i, name) in BOOKMARK_HOTKEYS.into_iter().enumerate() {
+            if state.hotkeys.pressed(name, input) {
+                state.jump_to_bookmark(i);
+            }
+        }
+
+        for (i, name) in RECENT_PROJECT_HOTKEYS.into_iter().enumerate() {
+            if state.hotkeys.pressed(name, input) {
+                state.open_recent_project(i);
+            }
+        }
+
+        // Text edits have their own undo.
+        if !ctx.wants_keyboard_input() {
+            if state.hotkeys.pressed("undo", input) {
+                state.undo();
+            }
+
+            if state.hotkeys.pressed("redo", input) {
+                state.redo();
+            }
+
+            if state.hotkeys.pressed("rename", input) {
+                state.rename_requested = true;
+            }
+
+            // Digits typed into hex editors are bytes, not kinds.
+            if state.selection.is_some() && !is_typing_hex(ctx) {
+                for (i, name) in CHANGE_KIND_HOTKEYS.into_iter().enumerate() {
+                    if state.hotkeys.pressed(name, input) {
+                        if let Some(kind) = state.config.change_kind(i) {
+                            *response = Some(ToolBarResponse::ChangeKind(kind.clone()));
+                        }
+                    }
+                }
+            }
+
+            if state.hotkeys.pressed("copy_fields", input) {
+                state.copy_selected_fields();
+                if let Some(clipboard) = state.clipboard.as_ref() {
+                    ctx.copy_text(clipboard.to_json());
+                }
+            }
+
+            if state.hotkeys.pressed("paste_fields", input) {
+                state.paste_fields();
+            }
+
+            // Ctrl-V arrives as a clipboard event instead of a key press.
+            for event in &input.events {
+                if let Event::Paste(text) = event {
+                    state.paste_fields_from(text);
+                }
+            }
+        }
     }
 
     fn project_menu(&mut self, ui: &mut Ui) {
@@ -111,6 +364,10 @@ impl ToolBarPanel {
         if ui.button("New project").clicked() {
             state.save_project(None);
             state.class_list = ClassList::default();
+            state.bookmarks.clear();
+            state.named_addresses.clear();
+            state.snapshots.clear();
+            state.history.clear();
             ui.close();
         }
 
@@ -119,28 +376,53 @@ impl ToolBarPanel {
             ui.close();
         }
 
-        if !state
-            .config
-            .recent_projects
-            .as_ref()
-            .map(|h| h.is_empty())
-            .unwrap_or(true)
-        {
+        if !state.config.recent_projects().is_empty() {
             ui.menu_button("Open recent...", |ui| {
                 let mut to_open = None;
-                for project in state.config.recent_projects.as_ref().unwrap().iter() {
-                    if let Some(name) = project.file_name().and_then(|name| name.to_str()) {
-                        if ui.button(name).clicked() {
-                            to_open = Some(project.to_owned());
-                        }
+                for (i, project) in state.config.recent_projects().iter().enumerate() {
+                    let name = project
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy())
+                        .unwrap_or_default();
+                    let mut details = vec![];
+                    if let Some(modified) = project.modified {
+                        details.push(format_unix_seconds(modified as i64));
                     }
+                    if let Some(classes) = project.classes {
+                        details.push(format!("{classes} classes"));
+                    }
+
+                    let mut button = Button::new(RichText::new(name).strong());
+                    if let Some(shortcut) = RECENT_PROJECT_HOTKEYS
+                        .get(i)
+                        .and_then(|name| state.hotkeys.try_format(name, ui.ctx()))
+                    {
+                        button = button.shortcut_text(shortcut);
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add(button)
+                            .on_hover_text(project.path.display().to_string())
+                            .clicked()
+                        {
+                            to_open = Some(i);
+                        }
+                        ui.weak(details.join(", "));
+                    });
+                }
+
+                ui.separator();
+                if ui.button("Clear history").clicked() {
+                    state.config.recent_projects = None;
+                    state.config.save();
+                    ui.close();
                 }
 
-                if let Some(path) = to_open {
-                    if state.open_project_path(&path) {
+                if let Some(i) = to_open {
+                    if state.open_recent_project(i) {
                         ui.close();
-                    } else {
-                        state.config.recent_projects.as_mut().unwrap().remove(&path);
                     }
                 }
             });
@@ -155,6 +437,122 @@ impl ToolBarPanel {
             state.save_project_as();
             ui.close();
         }
+
+        if ui.button("Save as JSON").clicked() {
+            state.save_project_as_json();
+            ui.close();
+        }
+
+        ui.separator();
+
+        if ui.button("Import from C header").clicked() {
+            self.header_import_window.toggle();
+            ui.close();
+        }
+
+        if ui.button("Import Cheat Engine table").clicked() {
+            state.import_cheat_table();
+            ui.close();
+        }
+
+        ui.separator();
+
+        if shortcut_button(ui, state, "undo", "Undo") {
+            state.undo();
+            ui.close();
+        }
+
+        if shortcut_button(ui, state, "redo", "Redo") {
+            state.redo();
+            ui.close();
+        }
+    }
+
+    fn edit_menu(&mut self, ui: &mut Ui) {
+        if ui.button("Batch rename").clicked() {
+            self.batch_rename_window.toggle();
+            ui.close();
+        }
+
+        if ui.button("Merge classes").clicked() {
+            self.merge_class_window.toggle();
+            ui.close();
+        }
+
+        if ui.button("Validate all").clicked() {
+            self.validation_window.toggle();
+            ui.close();
+        }
+
+        if ui.button("Hotkeys").clicked() {
+            self.hotkeys_window.toggle();
+            ui.close();
+        }
+
+        if ui.button("Settings").clicked() {
+            self.settings_window.toggle();
+            ui.close();
+        }
+    }
+
+    fn bookmarks_menu(&mut self, ui: &mut Ui) {
+        ui.set_min_width(200.);
+
+        let state = &mut *self.state.borrow_mut();
+        if state.bookmarks.entries().is_empty() {
+            ui.label("Right click the class address to add a bookmark");
+            return;
+        }
+
+        let mut jump_to = None;
+        let mut remove = None;
+        for i in 0..state.bookmarks.entries().len() {
+            ui.horizontal(|ui| {
+                if self.renaming_bookmark == Some(i) {
+                    let r = ui.text_edit_singleline(&mut state.bookmarks.entries_mut()[i].name);
+                    if r.lost_focus() {
+                        self.renaming_bookmark = None;
+                    } else {
+                        r.request_focus();
+                    }
+                } else {
+                    let bookmark = &state.bookmarks.entries()[i];
+                    let mut button = Button::new(&bookmark.name);
+                    if let Some(shortcut) = BOOKMARK_HOTKEYS
+                        .get(i)
+                        .and_then(|name| state.hotkeys.try_format(name, ui.ctx()))
+                    {
+                        button = button.shortcut_text(shortcut);
+                    }
+
+                    if ui
+                        .add(button)
+                        .on_hover_text(format!("0x{:X}", bookmark.address))
+                        .clicked()
+                    {
+                        jump_to = Some(i);
+                    }
+                }
+
+                if ui.button("✏").on_hover_text("Rename").clicked() {
+                    self.renaming_bookmark = Some(i);
+                }
+
+                if ui.button("🗑").on_hover_text("Delete").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = jump_to {
+            state.jump_to_bookmark(i);
+            ui.close();
+        }
+
+        if let Some(i) = remove {
+            state.bookmarks.remove(i);
+            self.renaming_bookmark = None;
+        }
     }
 
     fn process_menu(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
@@ -181,6 +579,28 @@ impl ToolBarPanel {
             ui.close();
         }
 
+        ui.separator();
+        if ui
+            .button("Attach secondary process")
+            .on_hover_text("Process the inspected class is compared with, i.e. an unmodded build")
+            .clicked()
+        {
+            self.ps_attach_window.toggle_secondary();
+            ui.close();
+        }
+
+        if ui
+            .add_enabled(
+                state.secondary_process.read().is_some(),
+                Button::new("Detach secondary process"),
+            )
+            .clicked()
+        {
+            *response = Some(ToolBarResponse::SecondaryDetach);
+            ui.close();
+        }
+        ui.separator();
+
         if ui.button("Load minidump").clicked() {
             if let Some(path) = rfd::FileDialog::new()
                 .set_title("Load minidump")
@@ -233,20 +653,46 @@ impl ToolBarPanel {
                 }
             });
         }
+
+        if ui
+            .add_enabled(
+                state.process.read().is_some() && !self.minidump_writer.is_running(),
+                Button::new("Create minidump"),
+            )
+            .on_hover_text("Writes the readable memory and modules of the process to a file")
+            .clicked()
+        {
+            self.minidump_writer.start(state);
+            ui.close();
+        }
+
+        ui.separator();
+        if ui.button("Import symbols").clicked() {
+            self.symbols_window.toggle();
+            ui.close();
+        }
     }
 
     fn status_ui(&mut self, ui: &mut Ui, response: &mut Option<ToolBarResponse>) {
-        if let Some((proc_name, proc_id)) = self
+        if let Some((proc_name, proc_id, connected)) = self
             .state
             .borrow()
             .process
             .as_ref()
             .read()
             .as_ref()
-            .map(|p| (p.name(), p.id()))
+            .map(|p| (p.name(), p.id(), p.is_connected()))
         {
             match proc_name {
-                Ok(name) => _ = ui.label(format!("Status: Attached to {} - {}", name, proc_id)),
+                Ok(name) if connected => {
+                    _ = ui.label(format!("Status: Attached to {} - {}", name, proc_id))
+                }
+                Ok(name) => {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!("Status: Disconnected from {name} - {proc_id}, reconnecting"),
+                    );
+                }
                 Err(e) => {
                     self.state
                         .borrow_mut()
@@ -1,6 +1,18 @@
-use eframe::egui::TextBuffer;
+use eframe::{egui::TextBuffer, epaint::Color32};
 use std::{ops::Range, str::FromStr};
 
+/// Color of the warning icon shown next to the class size and why, `None` if the size is fine.
+/// Sizes are aligned to the pointer width of the process.
+pub fn size_warning(size: usize, pointer_size: usize) -> Option<(Color32, &'static str)> {
+    if size == 0 {
+        Some((Color32::RED, "Class has no fields"))
+    } else if !size.is_multiple_of(pointer_size) {
+        Some((Color32::YELLOW, "Size not naturally aligned"))
+    } else {
+        None
+    }
+}
+
 pub type TextEditFromStrBind<T> = TextEditBind<T, <T as FromStr>::Err>;
 
 type ConvertFnBoxed<T, E> = Box<dyn Fn(&str) -> Result<T, E> + 'static>;
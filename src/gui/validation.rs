@@ -0,0 +1,77 @@
+use crate::{context::Selection, state::StateRef, validate::validate_class};
+use eframe::{
+    egui::{Context, Label, RichText, ScrollArea, Sense, Window},
+    epaint::Color32,
+};
+
+/// Layout problems of all classes, see [`validate_class`].
+pub struct ValidationWindow {
+    state: StateRef,
+    shown: bool,
+}
+
+impl ValidationWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let state = &mut *self.state.borrow_mut();
+
+        Window::new("Validation")
+            .open(&mut self.shown)
+            .default_size([420., 300.])
+            .show(ctx, |ui| {
+                let reports = state
+                    .class_list
+                    .classes()
+                    .iter()
+                    .map(|c| {
+                        let errors = validate_class(&state.class_list, c);
+                        (c.id(), c.name.clone(), c.address.get(), errors)
+                    })
+                    .filter(|(.., errors)| !errors.is_empty())
+                    .collect::<Vec<_>>();
+
+                if reports.is_empty() {
+                    ui.label(RichText::new("No problems found").color(Color32::LIGHT_GREEN));
+                    return;
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    for (cid, name, address, errors) in reports {
+                        ui.label(RichText::new(name).strong());
+                        for error in errors {
+                            if ui
+                                .add(
+                                    Label::new(
+                                        RichText::new(error.to_string()).color(Color32::YELLOW),
+                                    )
+                                    .sense(Sense::click()),
+                                )
+                                .on_hover_text("Click to select the field")
+                                .clicked()
+                            {
+                                let (field_id, offset) = error.location();
+                                *state.class_list.selected_mut() = Some(cid);
+                                state.clear_selection();
+                                state.selection = Some(Selection {
+                                    address: address + offset,
+                                    container_id: cid,
+                                    field_id,
+                                });
+                            }
+                        }
+                        ui.add_space(4.);
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,339 @@
+use super::{ScanOptions, ScanResult, ScannerReport, ValueScanner, MAX_RESULTS};
+use crate::{
+    context::Selection,
+    field::{allocate_padding, FieldKind},
+    gui::spider::{bytes_to_value, parse_kind_to_value, FilterMode},
+    process::Process,
+    state::{GlobalState, StateRef},
+};
+use eframe::{
+    egui::{Button, ComboBox, Context, ProgressBar, TextEdit, Ui, Window},
+    epaint::{vec2, Color32, FontId},
+};
+use egui_extras::{Column, TableBuilder};
+use std::borrow::Cow;
+
+pub struct ValueScanWindow {
+    state: StateRef,
+    shown: bool,
+
+    field_kind: FieldKind,
+    filter: FilterMode,
+    aligned: bool,
+    value_buf: String,
+
+    scanner_status: Option<Cow<'static, str>>,
+    results: Vec<ScanResult>,
+
+    scanner: ValueScanner,
+}
+
+impl ValueScanWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            field_kind: FieldKind::I32,
+            filter: FilterMode::Equal,
+            aligned: true,
+            value_buf: String::new(),
+
+            scanner_status: None,
+            results: vec![],
+
+            scanner: ValueScanner::new(),
+            shown: false,
+            state,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> eyre::Result<Option<()>> {
+        let mut shown = self.shown;
+
+        let mut progress = None;
+        match self.scanner.try_take() {
+            ScannerReport::Finished(time, results) => {
+                self.results = results;
+                self.scanner_status = Some(if self.results.len() >= MAX_RESULTS {
+                    format!(
+                        "Stopped after {MAX_RESULTS} results in: {:.2}",
+                        time.as_secs_f32()
+                    )
+                    .into()
+                } else {
+                    format!("Finished in: {:.2}", time.as_secs_f32()).into()
+                });
+            }
+            ScannerReport::InProgress(p) => {
+                self.scanner_status = Some("In progress".into());
+                progress = Some(p);
+            }
+            ScannerReport::Idle => {}
+        }
+
+        let result = Window::new("Value scan")
+            .open(&mut shown)
+            .show(ctx, |ui| {
+                let state = &mut *self.state.borrow_mut();
+
+                let process_lock = state.process.clone();
+                let process_lock = process_lock.read();
+                let Some(process) = process_lock.as_ref() else {
+                    ui.centered_and_justified(|ui| {
+                        ui.heading("Attach to a process first");
+                    });
+
+                    return Ok(());
+                };
+
+                let w = ui.available_width() / 2.;
+                let enabled = !self.scanner.active() && self.results.is_empty();
+                ui.add_enabled_ui(enabled, |ui| {
+                    ComboBox::new("_value_scan_select_kind", "Field type")
+                        .width(w + 8.)
+                        .selected_text(self.field_kind.label().unwrap())
+                        .show_ui(ui, |ui| {
+                            for (var, label) in FieldKind::NAMED_VARIANTS {
+                                if ui
+                                    .selectable_label(*var == self.field_kind, *label)
+                                    .clicked()
+                                {
+                                    self.field_kind = var.clone();
+                                }
+                            }
+                        });
+
+                    ui.checkbox(&mut self.aligned, "Aligned")
+                        .on_hover_text("Only check addresses aligned to the size of the type");
+                });
+
+                ui.separator();
+
+                ui.add_enabled_ui(!self.scanner.active(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(TextEdit::singleline(&mut self.value_buf).desired_width(w));
+                        ui.label("Value");
+                    });
+
+                    ComboBox::new("_value_scan_filter_box", "Filter")
+                        .width(w + 8.)
+                        .selected_text(self.filter.label())
+                        .show_ui(ui, |ui| {
+                            for (var, label) in FilterMode::NAMED_VARIANTS {
+                                if ui.selectable_label(*var == self.filter, *label).clicked() {
+                                    self.filter = *var;
+                                }
+                            }
+                        });
+                });
+
+                ui.separator();
+
+                let inner: eyre::Result<()> = ui
+                    .horizontal(|ui| {
+                        if self.scanner.active() {
+                            if ui.button("Cancel").clicked() {
+                                self.scanner.cancel();
+                            }
+                        } else if self.results.is_empty() {
+                            if ui
+                                .add_sized(vec2(w + 8., 12.), Button::new("First scan"))
+                                .clicked()
+                            {
+                                if self.filter.compares_previous() {
+                                    return Err(eyre::eyre!(
+                                        "First scan has no previous values to compare"
+                                    ));
+                                }
+
+                                let opts = self.collect_options(process)?;
+                                self.scanner.first_scan(
+                                    &state.process,
+                                    process.readable_regions()?,
+                                    opts,
+                                );
+                            }
+                        } else {
+                            if ui.button("Next scan").clicked() {
+                                let opts = self.collect_options(process)?;
+                                self.scanner.next_scan(
+                                    &state.process,
+                                    std::mem::take(&mut self.results),
+                                    opts,
+                                );
+                            }
+
+                            if ui.button("Clear results").clicked() {
+                                self.results.clear();
+                                self.scanner_status = None;
+                            }
+                        }
+
+                        if let Some(status) = self.scanner_status.as_deref() {
+                            ui.separator();
+                            ui.label(status);
+                        }
+
+                        if !self.scanner.active() && !self.results.is_empty() {
+                            ui.separator();
+                            ui.label(format!("Total count: {}", self.results.len()));
+                        }
+
+                        Ok(())
+                    })
+                    .inner;
+                inner?;
+
+                if let Some(progress) = progress {
+                    ui.add(ProgressBar::new(progress).show_percentage());
+                }
+
+                ui.separator();
+
+                if !self.scanner.active() {
+                    self.display_results(state, process, ui);
+                }
+
+                Ok(())
+            })
+            .and_then(|v| v.inner)
+            .transpose();
+        self.shown = shown;
+
+        result
+    }
+
+    fn display_results(&mut self, state: &mut GlobalState, process: &Process, ui: &mut Ui) {
+        const DATA_HEIGHT: f32 = 14.;
+        ui.style_mut().override_font_id = Some(FontId::monospace(DATA_HEIGHT));
+
+        let w = ui.available_width() / 3. - 4.;
+        let size = self.field_kind.size(process.pointer_size());
+        let mut add_to_inspector = None;
+        let mut bookmark = None;
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .columns(Column::initial(w).resizable(true), 2)
+            .column(Column::remainder())
+            .header(16., |mut row| {
+                row.col(|ui| _ = ui.label("Address"));
+                row.col(|ui| _ = ui.label("Previous"));
+                row.col(|ui| _ = ui.label("Current"));
+            })
+            .body(|body| {
+                body.rows(DATA_HEIGHT, self.results.len(), |mut row| {
+                    let result = &self.results[row.index()];
+
+                    let (_, r) = row.col(|ui| _ = ui.label(format!("{:X}", result.address)));
+                    r.context_menu(|ui| {
+                        if ui.button("Add to inspector at offset").clicked() {
+                            add_to_inspector = Some(result.address);
+                            ui.close();
+                        }
+
+                        if ui.button("Bookmark address").clicked() {
+                            bookmark = Some(result.address);
+                            ui.close();
+                        }
+
+                        if ui.button("Copy address").clicked() {
+                            ui.ctx().copy_text(format!("{:X}", result.address));
+                            ui.close();
+                        }
+                    });
+
+                    row.col(|ui| _ = ui.label(result.last_value.to_string()));
+
+                    let mut buf = [0; 8];
+                    process.read(result.address, &mut buf[..size]);
+                    let current = bytes_to_value(&buf, result.last_value.kind());
+                    row.col(|ui| {
+                        if current != result.last_value {
+                            ui.colored_label(Color32::KHAKI, current.to_string());
+                        } else {
+                            ui.label(current.to_string());
+                        }
+                    });
+                })
+            });
+
+        if let Some(address) = bookmark {
+            state.bookmarks.add(format!("0x{address:X}"), address, None);
+        }
+
+        if let Some(address) = add_to_inspector {
+            if let Err(e) = select_in_inspector(state, address, size) {
+                state.toasts.error(e.to_string());
+            }
+        }
+    }
+
+    fn collect_options(&self, process: &Process) -> eyre::Result<ScanOptions> {
+        let value = if self.filter.compares_previous() {
+            None
+        } else {
+            Some(parse_kind_to_value(
+                self.field_kind.clone(),
+                &self.value_buf,
+            )?)
+        };
+
+        Ok(ScanOptions {
+            kind: self.field_kind.clone(),
+            size: self.field_kind.size(process.pointer_size()),
+            filter: self.filter,
+            aligned: self.aligned,
+            value,
+        })
+    }
+}
+
+/// Selects the field of the selected class at `address`, growing the class if it's too small.
+fn select_in_inspector(state: &mut GlobalState, address: usize, size: usize) -> eyre::Result<()> {
+    let cid = state
+        .class_list
+        .selected()
+        .ok_or(eyre::eyre!("Select a class first"))?;
+    // Fields of the class start after its base class.
+    let base = state.class_list.by_id(cid).unwrap().address.get() + state.class_list.base_size(cid);
+    let offset = address
+        .checked_sub(base)
+        .ok_or(eyre::eyre!("Address is below the fields of the class"))?;
+
+    let class_size = state
+        .class_list
+        .by_id(cid)
+        .unwrap()
+        .fields
+        .iter()
+        .map(|f| f.size())
+        .sum::<usize>();
+    if offset + size > class_size {
+        state.record_edit(cid);
+        state
+            .class_list
+            .by_id_mut(cid)
+            .unwrap()
+            .fields
+            .extend(allocate_padding(offset + size - class_size));
+        state.dummy = false;
+    }
+
+    let mut field_offset = 0;
+    for field in state.class_list.by_id(cid).unwrap().fields.iter() {
+        if field_offset + field.size() > offset {
+            state.selection = Some(Selection {
+                address: base + field_offset,
+                container_id: cid,
+                field_id: field.id(),
+            });
+            break;
+        }
+        field_offset += field.size();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+mod gui;
+pub use gui::*;
+mod scanner;
+pub(crate) use scanner::*;
+
+use super::spider::FilterMode;
+use crate::{field::FieldKind, value::Value};
+
+#[derive(Clone)]
+pub(crate) struct ScanOptions {
+    kind: FieldKind,
+    /// Size of the values of `kind` in the scanned process.
+    size: usize,
+    filter: FilterMode,
+    /// Not required by filters that only compare against the previous value.
+    value: Option<Value>,
+    aligned: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScanResult {
+    address: usize,
+    last_value: Value,
+}
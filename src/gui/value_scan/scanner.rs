@@ -0,0 +1,207 @@
+use super::{ScanOptions, ScanResult};
+use crate::{gui::spider::bytes_to_value, process::Process};
+use parking_lot::RwLock;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// Memory is read in pieces of this size.
+const CHUNK_SIZE: usize = 0x10000;
+/// First scan stops after finding this many results.
+pub(crate) const MAX_RESULTS: usize = 1 << 24;
+
+type SharedProcess = Arc<RwLock<Option<Process>>>;
+
+pub(crate) struct ValueScanner {
+    handle: Option<JoinHandle<Vec<ScanResult>>>,
+    progress: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    total: usize,
+    start: Instant,
+}
+
+pub(crate) enum ScannerReport {
+    Finished(Duration, Vec<ScanResult>),
+    /// Holds progress from `0.` to `1.`.
+    InProgress(f32),
+    Idle,
+}
+
+impl ValueScanner {
+    pub fn new() -> Self {
+        Self {
+            handle: None,
+            progress: Arc::default(),
+            cancelled: Arc::default(),
+            total: 0,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn active(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn first_scan(
+        &mut self,
+        process: &SharedProcess,
+        regions: Vec<Range<usize>>,
+        opts: ScanOptions,
+    ) {
+        self.total = regions.iter().map(|r| r.len()).sum();
+        self.reset();
+
+        let (process, progress, cancelled) = (
+            process.clone(),
+            self.progress.clone(),
+            self.cancelled.clone(),
+        );
+        self.handle = Some(std::thread::spawn(move || {
+            first_scan(&process, &regions, &opts, &progress, &cancelled)
+        }));
+    }
+
+    pub fn next_scan(
+        &mut self,
+        process: &SharedProcess,
+        results: Vec<ScanResult>,
+        opts: ScanOptions,
+    ) {
+        self.total = results.len();
+        self.reset();
+
+        let (process, progress, cancelled) = (
+            process.clone(),
+            self.progress.clone(),
+            self.cancelled.clone(),
+        );
+        self.handle = Some(std::thread::spawn(move || {
+            next_scan(&process, results, &opts, &progress, &cancelled)
+        }));
+    }
+
+    pub fn try_take(&mut self) -> ScannerReport {
+        match self.handle.take() {
+            Some(handle) if handle.is_finished() => {
+                ScannerReport::Finished(self.start.elapsed(), handle.join().unwrap_or_default())
+            }
+            Some(handle) => {
+                self.handle = Some(handle);
+                ScannerReport::InProgress(
+                    self.progress.load(Ordering::Relaxed) as f32 / self.total.max(1) as f32,
+                )
+            }
+            None => ScannerReport::Idle,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.start = Instant::now();
+        self.progress.store(0, Ordering::SeqCst);
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+}
+
+fn first_scan(
+    process: &RwLock<Option<Process>>,
+    regions: &[Range<usize>],
+    opts: &ScanOptions,
+    progress: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> Vec<ScanResult> {
+    let Some(value) = opts.value else {
+        return vec![];
+    };
+
+    let size = opts.size;
+    let step = if opts.aligned { size } else { 1 };
+
+    let mut results = vec![];
+    // Reads a bit more than a chunk so values crossing chunk boundaries aren't missed.
+    let mut buf = vec![0; CHUNK_SIZE + size - 1];
+
+    for region in regions {
+        let mut address = region.start.next_multiple_of(step);
+
+        while address + size <= region.end {
+            if cancelled.load(Ordering::Relaxed) || results.len() >= MAX_RESULTS {
+                return results;
+            }
+
+            let buf = &mut buf[..(CHUNK_SIZE + size - 1).min(region.end - address)];
+            buf.fill(0);
+            match process.read().as_ref() {
+                Some(p) => p.read(address, buf),
+                None => return results,
+            }
+
+            let mut bytes = [0; 8];
+            for offset in (0..=buf.len() - size)
+                .step_by(step)
+                .take_while(|o| *o < CHUNK_SIZE)
+            {
+                bytes[..size].copy_from_slice(&buf[offset..offset + size]);
+
+                let current = bytes_to_value(&bytes, opts.kind.clone());
+                if opts.filter.matches(current, current, value) {
+                    results.push(ScanResult {
+                        address: address + offset,
+                        last_value: current,
+                    });
+                }
+            }
+
+            progress.fetch_add(buf.len().min(CHUNK_SIZE), Ordering::Relaxed);
+            address += CHUNK_SIZE;
+        }
+    }
+
+    results
+}
+
+fn next_scan(
+    process: &RwLock<Option<Process>>,
+    mut results: Vec<ScanResult>,
+    opts: &ScanOptions,
+    progress: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> Vec<ScanResult> {
+    let size = opts.size;
+
+    results.retain_mut(|result| {
+        progress.fetch_add(1, Ordering::Relaxed);
+
+        // Results that weren't checked yet are kept.
+        if cancelled.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        let mut bytes = [0; 8];
+        match process.read().as_ref() {
+            Some(p) => p.read(result.address, &mut bytes[..size]),
+            None => return true,
+        }
+
+        let current = bytes_to_value(&bytes, opts.kind.clone());
+        let remains = opts.filter.matches(
+            current,
+            result.last_value,
+            opts.value.unwrap_or(result.last_value),
+        );
+
+        result.last_value = current;
+        remains
+    });
+
+    results
+}
@@ -0,0 +1,203 @@
+use crate::{
+    address::evaluate_address,
+    field::{FieldKind, VtableField},
+    history::Command,
+    state::{GlobalState, StateRef},
+    vtable_scan::scan_vtable,
+};
+use eframe::{
+    egui::{Button, Context, Grid, Key, RichText, ScrollArea, TextEdit, Ui, Window},
+    epaint::Color32,
+};
+
+struct ScanResult {
+    /// Address of the object, `None` if the vtable address was entered directly.
+    object: Option<usize>,
+    vtable: usize,
+    functions: Vec<usize>,
+}
+
+/// Lists functions of a vtable, stopping at the first pointer outside of executable memory.
+pub struct VtableScannerWindow {
+    state: StateRef,
+    shown: bool,
+    address: String,
+    /// The entered address is an object that starts with a vtable pointer.
+    is_object: bool,
+    result: Option<ScanResult>,
+    error: Option<String>,
+}
+
+impl VtableScannerWindow {
+    pub fn new(state: StateRef) -> Self {
+        Self {
+            state,
+            shown: false,
+            address: String::new(),
+            is_object: true,
+            result: None,
+            error: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+        if self.shown && self.address.is_empty() {
+            if let Some(class) = self.state.borrow().class_list.selected_class() {
+                self.address = format!("{:X}", class.address.get());
+            }
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let mut shown = self.shown;
+
+        Window::new("VTable scanner")
+            .open(&mut shown)
+            .default_width(420.)
+            .show(ctx, |ui| self.scanner(ui));
+        self.shown = shown;
+    }
+
+    fn scanner(&mut self, ui: &mut Ui) {
+        let state = &mut *self.state.borrow_mut();
+
+        let mut scan = false;
+        ui.horizontal(|ui| {
+            ui.label("Address");
+            let r = ui.add(
+                TextEdit::singleline(&mut self.address)
+                    .hint_text("[game.exe+1A3F20]")
+                    .desired_width(200.),
+            );
+            scan |= r.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            scan |= ui.button("Scan").clicked();
+        });
+        ui.checkbox(
+            &mut self.is_object,
+            "Address of an object, its first pointer is the vtable",
+        );
+
+        if scan {
+            self.scan(state);
+        }
+        if let Some(error) = &self.error {
+            ui.colored_label(Color32::RED, error);
+        }
+        let Some(result) = &self.result else {
+            return;
+        };
+
+        ui.separator();
+        ui.label(format!(
+            "{} functions in the vtable at {:X}",
+            result.functions.len(),
+            result.vtable
+        ));
+        let process = state.process.read();
+        ScrollArea::vertical().max_height(300.).show(ui, |ui| {
+            Grid::new("_vtable_functions")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, &function) in result.functions.iter().enumerate() {
+                        ui.label(i.to_string());
+                        ui.monospace(format!("{function:X}"));
+                        let symbol = process
+                            .as_ref()
+                            .and_then(|p| p.resolve_symbol(function))
+                            .map(|s| s.to_string())
+                            .unwrap_or_default();
+                        ui.label(RichText::new(symbol).color(Color32::GRAY));
+                        ui.end_row();
+                    }
+                });
+        });
+        drop(process);
+        ui.separator();
+
+        // The vtable pointer is the first field of the object, fields of base classes come first.
+        let target = state
+            .class_list
+            .selected_class()
+            .filter(|c| Some(c.address.get()) == result.object)
+            .filter(|c| state.class_list.base_size(c.id()) == 0)
+            .map(|c| (c.id(), c.name.clone()));
+        let Some((cid, name)) = target else {
+            return;
+        };
+        let count = result.functions.len();
+        if !ui
+            .add_enabled(
+                count > 0,
+                Button::new(format!("Set as the vtable of {name}")),
+            )
+            .clicked()
+        {
+            return;
+        }
+
+        let edit = Command::edit_class(&state.class_list, cid);
+        let pointer_size = state.class_list.pointer_size();
+        let class = state.class_list.by_id_mut(cid).unwrap();
+        let Some(first) = class.fields.first() else {
+            return;
+        };
+        let replaceable = match first.kind() {
+            FieldKind::Vtable(_) => true,
+            FieldKind::Unk8 | FieldKind::Unk16 | FieldKind::Unk32 | FieldKind::Unk64 => {
+                first.size() == pointer_size
+            }
+            _ => false,
+        };
+        if !replaceable {
+            state.toasts.error(format!(
+                "The first field of {name} isn't an unknown field of pointer size"
+            ));
+            return;
+        }
+
+        let field_name = first.name().unwrap_or_else(|| "vtable".into());
+        class.fields[0] = Box::new(VtableField::new(field_name, count, pointer_size));
+        if let Some(edit) = edit {
+            state.history.push(edit);
+        }
+        state.dummy = false;
+    }
+
+    fn scan(&mut self, state: &GlobalState) {
+        self.result = None;
+        self.error = None;
+
+        let process = state.process.read();
+        let Some(process) = process.as_ref() else {
+            self.error = Some("Attach to a process first".into());
+            return;
+        };
+        let address = match evaluate_address(&self.address, process, &state.named_addresses) {
+            Ok(address) => address,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        let (object, vtable) = if self.is_object {
+            let mut buf = [0; 8];
+            let pointer_size = process.pointer_size();
+            if !process.read_checked(address, &mut buf[..pointer_size]) {
+                self.error = Some(format!("Can't read memory at {address:X}"));
+                return;
+            }
+            (Some(address), usize::from_ne_bytes(buf))
+        } else {
+            (None, address)
+        };
+
+        self.result = Some(ScanResult {
+            object,
+            vtable,
+            functions: scan_vtable(process, vtable),
+        });
+    }
+}
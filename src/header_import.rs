@@ -0,0 +1,579 @@
+use crate::{
+    class::{ClassId, ClassList},
+    field::{
+        allocate_padding, array_elements, EmbeddedClassField, Field, FieldKind, PointerField,
+        MAX_ARRAY_ELEMENTS,
+    },
+};
+use eyre::{bail, eyre};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(usize),
+    Punct(char),
+    /// Text of a `//` comment.
+    Comment(String),
+}
+
+struct Spanned {
+    token: Token,
+    line: usize,
+}
+
+/// Field as written in the header, its type is resolved once all structs are known.
+struct ParsedField {
+    name: String,
+    base: String,
+    /// Levels of indirection.
+    stars: usize,
+    /// Array dimensions, outermost first.
+    dims: Vec<usize>,
+    comment: Option<String>,
+    line: usize,
+}
+
+struct ParsedStruct {
+    name: String,
+    /// Tag of a typedef'd struct, it can be used to refer to the struct too.
+    tag: Option<String>,
+    fields: Vec<ParsedField>,
+    line: usize,
+}
+
+impl ParsedStruct {
+    fn names(&self) -> impl Iterator<Item = &String> {
+        [&self.name].into_iter().chain(self.tag.as_ref())
+    }
+}
+
+/// Parses structs in a simplified C syntax and adds them to the list as classes. Returns ids of
+/// the added classes, nothing is added if the header has an error.
+///
+/// Fields are laid out with natural alignment, gaps are filled with hex fields. A `//` comment
+/// on the line of a field or right above it becomes the comment of the field.
+pub fn import_header(source: &str, list: &mut ClassList) -> eyre::Result<Vec<ClassId>> {
+    let tokens = tokenize(source)?;
+    let structs = Parser::new(&tokens).parse()?;
+    if structs.is_empty() {
+        bail!("No structs found");
+    }
+
+    let mut ids = HashMap::new();
+    for s in &structs {
+        let id = fastrand::usize(..);
+        for name in s.names() {
+            if ids.insert(name.as_str(), id).is_some() || list.by_name(name).is_some() {
+                bail!("Line {}: class `{name}` already exists", s.line);
+            }
+        }
+    }
+
+    // Fields are built before any class is added so an error leaves the list untouched.
+    let mut layouts = HashMap::new();
+    let mut classes = vec![];
+    for s in &structs {
+        let id = ids[s.name.as_str()];
+        let layout = build_struct(s, &ids, &layouts, list)?;
+        layouts.insert(id, (layout.offset, layout.align));
+        classes.push((s.name.clone(), id, layout.fields));
+    }
+
+    Ok(classes
+        .into_iter()
+        .map(|(name, id, fields)| {
+            list.insert_empty_class(list.classes().len(), name, id);
+            list.by_id_mut(id).unwrap().fields = fields;
+            id
+        })
+        .collect())
+}
+
+fn tokenize(source: &str) -> eyre::Result<Vec<Spanned>> {
+    let mut tokens = vec![];
+    let mut in_block = false;
+
+    for (i, mut rest) in source.lines().enumerate() {
+        let line = i + 1;
+        let err = |msg: &str| eyre!("Line {line}: {msg}");
+
+        if !in_block && rest.trim_start().starts_with('#') {
+            return Err(err("preprocessor directives are not supported"));
+        }
+
+        loop {
+            if in_block {
+                let Some(end) = rest.find("*/") else {
+                    break;
+                };
+                rest = &rest[end + 2..];
+                in_block = false;
+            }
+
+            rest = rest.trim_start();
+            let Some(c) = rest.chars().next() else {
+                break;
+            };
+
+            if let Some(comment) = rest.strip_prefix("//") {
+                let comment = comment.trim_start_matches('/').trim();
+                if !comment.is_empty() {
+                    tokens.push(Spanned {
+                        token: Token::Comment(comment.to_owned()),
+                        line,
+                    });
+                }
+                break;
+            }
+            if let Some(after) = rest.strip_prefix("/*") {
+                rest = after;
+                in_block = true;
+                continue;
+            }
+
+            let word = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let token = if c.is_ascii_alphabetic() || c == '_' {
+                Token::Ident(rest[..word].to_owned())
+            } else if c.is_ascii_digit() {
+                parse_number(&rest[..word])
+                    .map(Token::Number)
+                    .ok_or_else(|| err(&format!("`{}` is not a valid number", &rest[..word])))?
+            } else {
+                match c {
+                    '{' | '}' | ';' | '*' | '[' | ']' | ',' => Token::Punct(c),
+                    '<' => return Err(err("templates are not supported")),
+                    '(' => return Err(err("functions and function pointers are not supported")),
+                    ':' => return Err(err("bit fields and base classes are not supported")),
+                    '=' => return Err(err("default values are not supported")),
+                    '&' => return Err(err("references are not supported")),
+                    c => return Err(err(&format!("unexpected character `{c}`"))),
+                }
+            };
+
+            let len = match token {
+                Token::Ident(_) | Token::Number(_) => word,
+                _ => c.len_utf8(),
+            };
+            rest = &rest[len..];
+            tokens.push(Spanned { token, line });
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Decimal or hexadecimal number with an optional integer suffix.
+fn parse_number(s: &str) -> Option<usize> {
+    let s = s.trim_end_matches(['u', 'U', 'l', 'L']);
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Spanned],
+    pos: usize,
+    /// Comments since the last field, the first one becomes the comment of the next field.
+    comments: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Spanned]) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            comments: vec![],
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn skip_comments(&mut self) {
+        while let Some(Spanned {
+            token: Token::Comment(comment),
+            ..
+        }) = self.tokens.get(self.pos)
+        {
+            self.comments.push(comment.clone());
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&'a Token> {
+        self.skip_comments();
+        self.tokens.get(self.pos).map(|t| &t.token)
+    }
+
+    fn next(&mut self) -> eyre::Result<&'a Token> {
+        self.skip_comments();
+        let token = self
+            .tokens
+            .get(self.pos)
+            .map(|t| &t.token)
+            .ok_or_else(|| eyre!("Line {}: unexpected end of the header", self.line()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> eyre::Result<()> {
+        let line = self.line();
+        match self.next()? {
+            Token::Punct(p) if *p == c => Ok(()),
+            t => bail!("Line {line}: expected `{c}`, found {}", describe(t)),
+        }
+    }
+
+    fn ident(&mut self) -> eyre::Result<&'a str> {
+        let line = self.line();
+        match self.next()? {
+            Token::Ident(name) => Ok(name),
+            t => bail!("Line {line}: expected a name, found {}", describe(t)),
+        }
+    }
+
+    fn parse(mut self) -> eyre::Result<Vec<ParsedStruct>> {
+        let mut structs = vec![];
+
+        while let Some(token) = self.peek() {
+            let line = self.line();
+            match token {
+                Token::Ident(kw) if kw == "struct" => {
+                    self.pos += 1;
+                    let name = self.ident()?.to_owned();
+                    // Forward declaration.
+                    if self.eat(';') {
+                        continue;
+                    }
+                    let fields = self.body()?;
+                    self.expect(';')?;
+                    structs.push(ParsedStruct {
+                        name,
+                        tag: None,
+                        fields,
+                        line,
+                    });
+                }
+                Token::Ident(kw) if kw == "typedef" => {
+                    self.pos += 1;
+                    if self.ident()? != "struct" {
+                        bail!("Line {line}: only structs can be typedef'd");
+                    }
+                    let tag = match self.peek() {
+                        Some(Token::Ident(_)) => Some(self.ident()?.to_owned()),
+                        _ => None,
+                    };
+                    let fields = self.body()?;
+                    let name = self.ident()?.to_owned();
+                    self.expect(';')?;
+                    structs.push(ParsedStruct {
+                        tag: tag.filter(|t| *t != name),
+                        name,
+                        fields,
+                        line,
+                    });
+                }
+                Token::Ident(kw) => bail!("Line {line}: {}", unsupported(kw)),
+                t => bail!("Line {line}: expected a struct, found {}", describe(t)),
+            }
+        }
+
+        Ok(structs)
+    }
+
+    fn body(&mut self) -> eyre::Result<Vec<ParsedField>> {
+        self.expect('{')?;
+        self.comments.clear();
+
+        let mut fields = vec![];
+        while !self.eat('}') {
+            self.field(&mut fields)?;
+        }
+        Ok(fields)
+    }
+
+    fn field(&mut self, fields: &mut Vec<ParsedField>) -> eyre::Result<()> {
+        let line = self.line();
+        let mut base = self.ident()?;
+        while matches!(base, "const" | "volatile" | "struct") {
+            base = self.ident()?;
+        }
+        match base {
+            "unsigned" | "signed" | "short" | "long" | "int" => bail!(
+                "Line {line}: `{base}` is not supported, use fixed width types like `int32_t`"
+            ),
+            kw @ ("union" | "enum" | "class" | "template" | "typedef" | "static" | "virtual") => {
+                bail!("Line {line}: {}", unsupported(kw))
+            }
+            _ => {}
+        }
+        if self.peek() == Some(&Token::Punct('{')) {
+            bail!("Line {line}: nested struct definitions are not supported, define `{base}` separately");
+        }
+
+        let first = fields.len();
+        loop {
+            let mut stars = 0;
+            loop {
+                if self.eat('*') {
+                    stars += 1;
+                } else if self.peek() == Some(&Token::Ident("const".into())) {
+                    self.pos += 1;
+                } else {
+                    break;
+                }
+            }
+
+            let name = self.ident()?.to_owned();
+            let mut dims = vec![];
+            while self.eat('[') {
+                let line = self.line();
+                match self.next()? {
+                    Token::Number(n) if *n > 0 => dims.push(*n),
+                    t => bail!("Line {line}: expected array length, found {}", describe(t)),
+                }
+                self.expect(']')?;
+            }
+
+            fields.push(ParsedField {
+                name,
+                base: base.to_owned(),
+                stars,
+                dims,
+                comment: None,
+                line,
+            });
+
+            if !self.eat(',') {
+                break;
+            }
+        }
+
+        let end = self.pos;
+        self.expect(';')?;
+        // Comments starting on the line of the `;` belong to the field, others to the next one.
+        let pending = std::mem::take(&mut self.comments);
+        let trailing = match self.tokens.get(self.pos) {
+            Some(Spanned {
+                token: Token::Comment(comment),
+                line,
+            }) if *line == self.tokens[end].line => {
+                self.pos += 1;
+                Some(comment.clone())
+            }
+            _ => None,
+        };
+        let comment = trailing.or_else(|| pending.into_iter().next());
+        for field in &mut fields[first..] {
+            field.comment = comment.clone();
+        }
+
+        Ok(())
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Ident(name) => format!("`{name}`"),
+        Token::Number(n) => format!("`{n}`"),
+        Token::Punct(c) => format!("`{c}`"),
+        Token::Comment(_) => "a comment".to_owned(),
+    }
+}
+
+fn unsupported(keyword: &str) -> String {
+    match keyword {
+        "union" => "unions are not supported".to_owned(),
+        "enum" => "enums are not supported".to_owned(),
+        "class" => "classes are not supported, use `struct`".to_owned(),
+        "template" => "templates are not supported".to_owned(),
+        "namespace" => "namespaces are not supported".to_owned(),
+        kw => format!("`{kw}` is not supported"),
+    }
+}
+
+fn builtin(name: &str) -> Option<FieldKind> {
+    Some(match name {
+        "uint8_t" => FieldKind::U8,
+        "uint16_t" => FieldKind::U16,
+        "uint32_t" => FieldKind::U32,
+        "uint64_t" => FieldKind::U64,
+        "int8_t" | "char" => FieldKind::I8,
+        "int16_t" => FieldKind::I16,
+        "int32_t" => FieldKind::I32,
+        "int64_t" => FieldKind::I64,
+        "wchar_t" => FieldKind::U16,
+        "float" => FieldKind::F32,
+        "double" => FieldKind::F64,
+        "bool" => FieldKind::Bool,
+        _ => return None,
+    })
+}
+
+fn alignment(kind: &FieldKind, pointer_size: usize) -> usize {
+    match kind {
+        FieldKind::Array(inner, _) => alignment(inner, pointer_size),
+        FieldKind::InlineStr(_) => 1,
+        FieldKind::InlineWStr(_) => 2,
+        kind => kind.size(pointer_size).max(1),
+    }
+}
+
+#[derive(Default)]
+struct Layout {
+    fields: Vec<Box<dyn Field>>,
+    offset: usize,
+    align: usize,
+}
+
+impl Layout {
+    fn push(&mut self, field: Box<dyn Field>, align: usize) {
+        self.pad_to(align);
+        self.offset += field.size();
+        self.align = self.align.max(align);
+        self.fields.push(field);
+    }
+
+    fn pad_to(&mut self, align: usize) {
+        let padding = self.offset.next_multiple_of(align) - self.offset;
+        self.fields.extend(allocate_padding(padding));
+        self.offset += padding;
+    }
+}
+
+/// `layouts` holds sizes and alignments of the structs built so far.
+fn build_struct(
+    s: &ParsedStruct,
+    ids: &HashMap<&str, ClassId>,
+    layouts: &HashMap<ClassId, (usize, usize)>,
+    list: &ClassList,
+) -> eyre::Result<Layout> {
+    let mut layout = Layout {
+        align: 1,
+        ..Default::default()
+    };
+    let mut names = HashSet::new();
+
+    for f in &s.fields {
+        let line = f.line;
+        if !names.insert(&f.name) {
+            bail!("Line {line}: field `{}` is declared twice", f.name);
+        }
+        let class = ids
+            .get(f.base.as_str())
+            .copied()
+            .or_else(|| list.by_name(&f.base).map(|c| c.id()));
+
+        let kind = match (f.base.as_str(), f.stars) {
+            ("char", 1) => FieldKind::StrPtr,
+            ("wchar_t", 1) => FieldKind::WStrPtr,
+            (_, 1) if class.is_some() && f.dims.is_empty() => {
+                let field = PointerField::new_with_class_id(
+                    f.name.clone(),
+                    class.unwrap(),
+                    list.pointer_size(),
+                );
+                field.set_comment(f.comment.clone());
+                layout.push(Box::new(field), list.pointer_size());
+                continue;
+            }
+            (base, 0) if builtin(base).is_none() => {
+                let Some(class) = class else {
+                    bail!(
+                        "Line {line}: unknown type `{base}`, supported are fixed width \
+                         integers, `float`, `double`, `bool`, `char*`, `wchar_t*` and structs"
+                    );
+                };
+                // Existing classes are laid out by hand, they are embedded as is.
+                let (size, align) = match (ids.get(base), layouts.get(&class)) {
+                    (Some(_), Some(layout)) => *layout,
+                    (Some(_), None) => {
+                        bail!("Line {line}: `{base}` is embedded before it's defined")
+                    }
+                    (None, _) => (list.class_size(class), 1),
+                };
+
+                let count = f.dims.iter().product::<usize>();
+                for i in 0..count {
+                    let name = if f.dims.is_empty() {
+                        f.name.clone()
+                    } else {
+                        format!("{}_{i}", f.name)
+                    };
+                    let field = EmbeddedClassField::new(name, class, size);
+                    field.set_comment(f.comment.clone());
+                    layout.push(Box::new(field), align);
+                }
+                continue;
+            }
+            ("void", 0) => bail!("Line {line}: `void` fields are not allowed"),
+            (base, 0) => builtin(base).unwrap(),
+            // Pointers to unknown structs and arrays of pointers don't keep the class.
+            _ => FieldKind::Ptr,
+        };
+
+        // Character arrays are inline strings, the innermost dimension is their length.
+        let (kind, dims) = match (&kind, f.dims.split_last()) {
+            (FieldKind::I8, Some((len, outer))) if f.base == "char" => {
+                (FieldKind::InlineStr(*len), outer)
+            }
+            (FieldKind::U16, Some((len, outer))) if f.base == "wchar_t" => {
+                (FieldKind::InlineWStr(*len), outer)
+            }
+            _ => (kind, &f.dims[..]),
+        };
+
+        let align = alignment(&kind, list.pointer_size());
+        let field = array(f, kind, dims, list.pointer_size())?;
+        field.set_comment(f.comment.clone());
+        layout.push(field, align);
+    }
+
+    // Tail padding so arrays of the struct stay aligned.
+    layout.pad_to(layout.align);
+    Ok(layout)
+}
+
+/// Creates the field wrapped in arrays of the dimensions.
+fn array(
+    f: &ParsedField,
+    kind: FieldKind,
+    dims: &[usize],
+    pointer_size: usize,
+) -> eyre::Result<Box<dyn Field>> {
+    let kind = dims
+        .iter()
+        .rev()
+        .fold(kind, |kind, n| FieldKind::Array(Box::new(kind), *n));
+    if let FieldKind::Array(inner, count) = &kind {
+        if array_elements(inner, *count).is_none() {
+            bail!(
+                "Line {}: array `{}` has more than {MAX_ARRAY_ELEMENTS} elements",
+                f.line,
+                f.name
+            );
+        }
+    }
+    if kind.size(pointer_size) == 0 {
+        bail!("Line {}: field `{}` has no size", f.line, f.name);
+    }
+    Ok(kind.into_field(Some(f.name.clone()), pointer_size))
+}
@@ -0,0 +1,130 @@
+use crate::{
+    class::{ClassId, ClassList},
+    project::ClassSnapshot,
+};
+use std::collections::VecDeque;
+
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// Reversible change of the class list.
+pub enum Command {
+    /// Class was edited, holds its state prior to the edit.
+    EditClass(ClassSnapshot),
+    AddClass(ClassId),
+    RemoveClass(ClassSnapshot, usize),
+    Batch(Vec<Command>),
+}
+
+impl Command {
+    pub fn edit_class(list: &ClassList, id: ClassId) -> Option<Self> {
+        ClassSnapshot::take(list, id).map(Self::EditClass)
+    }
+
+    pub fn remove_class(list: &ClassList, id: ClassId) -> Option<Self> {
+        Some(Self::RemoveClass(
+            ClassSnapshot::take(list, id)?,
+            list.position(id)?,
+        ))
+    }
+
+    /// Applies the command, returning the command that reverts it.
+    fn apply(self, list: &mut ClassList) -> Option<Self> {
+        match self {
+            Self::EditClass(snapshot) => {
+                let current = Self::edit_class(list, snapshot.id())?;
+                let index = list.position(snapshot.id())?;
+                snapshot.restore(list, index);
+                Some(current)
+            }
+            Self::AddClass(id) => {
+                let current = Self::remove_class(list, id)?;
+                list.delete_by_id(id);
+                Some(current)
+            }
+            Self::RemoveClass(snapshot, index) => {
+                let id = snapshot.id();
+                snapshot.restore(list, index);
+                Some(Self::AddClass(id))
+            }
+            Self::Batch(commands) => Some(Self::Batch(
+                commands
+                    .into_iter()
+                    .rev()
+                    .filter_map(|c| c.apply(list))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+pub struct History {
+    undo: VecDeque<Command>,
+    redo: Vec<Command>,
+    max_depth: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_DEPTH)
+    }
+}
+
+impl History {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            max_depth,
+        }
+    }
+
+    /// Changes the number of undoable commands, dropping the oldest ones that don't fit.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+        while self.undo.len() > self.max_depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Records a new command, discarding everything that could be redone.
+    pub fn push(&mut self, command: Command) {
+        self.redo.clear();
+        self.push_undo(command);
+    }
+
+    fn push_undo(&mut self, command: Command) {
+        self.undo.push_back(command);
+        while self.undo.len() > self.max_depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self, list: &mut ClassList) -> bool {
+        let Some(command) = self.undo.pop_back() else {
+            return false;
+        };
+
+        if let Some(inverse) = command.apply(list) {
+            self.redo.push(inverse);
+        }
+        true
+    }
+
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self, list: &mut ClassList) -> bool {
+        let Some(command) = self.redo.pop() else {
+            return false;
+        };
+
+        if let Some(inverse) = command.apply(list) {
+            self.push_undo(inverse);
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
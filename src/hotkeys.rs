@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{collections::BTreeMap, ops::Deref};
 
 use eframe::{
     egui::{Context, InputState, Key, KeyboardShortcut, Modifiers},
@@ -8,19 +8,62 @@ use eframe::{
 #[derive(Default)]
 pub struct HotkeyManager {
     names: HashMap<&'static str, KeyboardShortcut>,
+    // Shortcuts as they were registered, rebinding only changes `names`.
+    defaults: HashMap<&'static str, KeyboardShortcut>,
 }
 
 impl HotkeyManager {
     pub fn register(&mut self, name: &'static str, key: Key, modifiers: Modifiers) -> &mut Self {
-        self.names.insert(
-            name,
-            KeyboardShortcut {
+        let shortcut = KeyboardShortcut {
+            logical_key: key,
+            modifiers,
+        };
+        self.names.insert(name, shortcut);
+        self.defaults.insert(name, shortcut);
+
+        self
+    }
+
+    /// Changes the shortcut of an already registered hotkey.
+    pub fn rebind(&mut self, name: &str, key: Key, modifiers: Modifiers) {
+        if let Some(shortcut) = self.names.get_mut(name) {
+            *shortcut = KeyboardShortcut {
                 logical_key: key,
                 modifiers,
-            },
-        );
+            };
+        }
+    }
 
-        self
+    /// Rebinds hotkeys from the `hotkeys` config key, invalid shortcuts are ignored.
+    pub fn apply_overrides<'a>(
+        &mut self,
+        overrides: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) {
+        for (name, shortcut) in overrides {
+            if let Some((key, modifiers)) = parse_shortcut(shortcut) {
+                self.rebind(name, key, modifiers);
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.names = self.defaults.clone();
+    }
+
+    /// Every registered hotkey sorted by name.
+    pub fn entries(&self) -> Vec<(&'static str, KeyboardShortcut)> {
+        let mut entries = self.names.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+        entries.sort_by_key(|(name, _)| *name);
+        entries
+    }
+
+    /// Hotkeys that differ from their defaults, formatted for the config.
+    pub fn overrides(&self) -> BTreeMap<String, String> {
+        self.names
+            .iter()
+            .filter(|(name, shortcut)| self.defaults.get(*name) != Some(*shortcut))
+            .map(|(name, shortcut)| (name.to_string(), shortcut_to_string(shortcut)))
+            .collect()
     }
 
     pub fn pressed(&self, name: &'static str, input: impl Deref<Target = InputState>) -> bool {
@@ -32,10 +75,47 @@ impl HotkeyManager {
     }
 
     pub fn format(&self, name: &'static str, ctx: &Context) -> String {
-        let shortcut = self
-            .names
-            .get(name)
-            .expect("No shortcut registered for this name");
-        ctx.format_shortcut(shortcut)
+        self.try_format(name, ctx)
+            .expect("No shortcut registered for this name")
     }
+
+    /// Same as [`Self::format`] but for optional shortcuts.
+    pub fn try_format(&self, name: &'static str, ctx: &Context) -> Option<String> {
+        self.names.get(name).map(|s| ctx.format_shortcut(s))
+    }
+}
+
+/// Parses shortcuts like `Ctrl+Shift+1`.
+pub fn parse_shortcut(s: &str) -> Option<(Key, Modifiers)> {
+    let mut parts = s.split('+').map(str::trim).collect::<Vec<_>>();
+    let key = Key::from_name(parts.pop()?)?;
+
+    let mut modifiers = Modifiers::NONE;
+    for part in parts {
+        modifiers |= match part.to_ascii_lowercase().as_str() {
+            "ctrl" => Modifiers::CTRL,
+            "alt" => Modifiers::ALT,
+            "shift" => Modifiers::SHIFT,
+            _ => return None,
+        };
+    }
+
+    Some((key, modifiers))
+}
+
+/// Inverse of [`parse_shortcut`].
+pub fn shortcut_to_string(shortcut: &KeyboardShortcut) -> String {
+    let mut parts = vec![];
+    if shortcut.modifiers.ctrl {
+        parts.push("Ctrl");
+    }
+    if shortcut.modifiers.alt {
+        parts.push("Alt");
+    }
+    if shortcut.modifiers.shift {
+        parts.push("Shift");
+    }
+    parts.push(shortcut.logical_key.name());
+
+    parts.join("+")
 }
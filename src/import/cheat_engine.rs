@@ -0,0 +1,343 @@
+use crate::{
+    app::is_valid_ident,
+    class::{ClassId, ClassList},
+    field::{
+        allocate_padding, EmbeddedClassField, Field, FieldKind, PointerChainField, PointerField,
+    },
+};
+use eyre::{bail, eyre};
+use roxmltree::{Document, Node};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// Length of a string record without a `<Length>`, the default of Cheat Engine.
+const DEFAULT_STRING_LEN: usize = 10;
+
+/// Classes added by [`import_ct`].
+pub struct CtImport {
+    pub classes: Vec<ClassId>,
+    /// Fields created from memory records, padding isn't counted.
+    pub fields: usize,
+}
+
+/// Creates classes from the memory records of a Cheat Engine table, nothing is added if the
+/// table can't be read.
+///
+/// Records of a group become a class, those with addresses relative to the group (`+10`) are
+/// laid out at their offsets and the others are grouped by module into classes of their own.
+/// Records with pointer offsets become pointer chains, a pointer record whose children are
+/// relative to it becomes a pointer to the class of its children.
+pub fn import_ct(path: &Path, class_list: &mut ClassList) -> eyre::Result<CtImport> {
+    let source =
+        fs::read_to_string(path).map_err(|e| eyre!("Failed to read `{}`. {e}", path.display()))?;
+    let doc = Document::parse(&source).map_err(|e| eyre!("Invalid Cheat Engine table. {e}"))?;
+
+    let root = doc.root_element();
+    if !root.has_tag_name("CheatTable") {
+        bail!("`{}` isn't a Cheat Engine table", path.display());
+    }
+    let Some(entries) = child(root, "CheatEntries") else {
+        bail!("The table has no memory records");
+    };
+
+    let name = path
+        .file_stem()
+        .map(|s| ident(&s.to_string_lossy()))
+        .unwrap_or_else(|| "CheatTable".into());
+    let mut importer = Importer {
+        names: class_list
+            .classes()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect(),
+        classes: vec![],
+        fields: 0,
+        pointer_size: class_list.pointer_size(),
+    };
+    importer.group(&name, entries);
+    if importer.fields == 0 {
+        bail!("The table has no memory records of supported types");
+    }
+
+    let classes = importer
+        .classes
+        .into_iter()
+        .map(|(name, id, fields)| {
+            class_list.insert_empty_class(class_list.classes().len(), name, id);
+            class_list.by_id_mut(id).unwrap().fields = fields;
+            id
+        })
+        .collect();
+    Ok(CtImport {
+        classes,
+        fields: importer.fields,
+    })
+}
+
+/// Address of a memory record.
+enum Address {
+    /// Offset from the address of the parent record.
+    Relative(i64),
+    /// Offset from the module, or from zero without one.
+    Absolute(Option<String>, u64),
+}
+
+/// Name, id and fields of an imported class.
+type ImportedClass = (String, ClassId, Vec<Box<dyn Field>>);
+
+struct Importer {
+    /// Names of existing and imported classes.
+    names: HashSet<String>,
+    /// Classes in the order they are added, those a class refers to come before it.
+    classes: Vec<ImportedClass>,
+    fields: usize,
+    pointer_size: usize,
+}
+
+impl Importer {
+    /// Turns the records into classes. Returns the class and size of the records relative to
+    /// the parent.
+    fn group(&mut self, name: &str, entries: Node) -> Option<(ClassId, usize)> {
+        let mut relative = vec![];
+        let mut absolute = BTreeMap::<Option<String>, Vec<_>>::new();
+
+        for entry in entries.children().filter(|n| n.has_tag_name("CheatEntry")) {
+            let desc = ident(text(entry, "Description").unwrap_or_default());
+            let children = child(entry, "CheatEntries").and_then(|c| self.group(&desc, c));
+            let Some(address) = text(entry, "Address").and_then(parse_address) else {
+                continue;
+            };
+            let Some(offsets) = offsets(entry) else {
+                continue;
+            };
+
+            let pointer_size = self.pointer_size;
+            let field: Box<dyn Field> = match (offsets.as_slice(), children) {
+                ([0], Some((cid, _))) => {
+                    Box::new(PointerField::new_with_class_id(desc, cid, pointer_size))
+                }
+                ([], Some((cid, size))) => Box::new(EmbeddedClassField::new(desc, cid, size)),
+                ([], None) => match kind_of(entry) {
+                    Some(kind) => kind.into_field(Some(desc), pointer_size),
+                    None => continue,
+                },
+                (offsets, _) => {
+                    let field = PointerChainField::new(desc, offsets.to_vec(), pointer_size);
+                    field.set_comment(text(entry, "VariableType").map(str::to_owned));
+                    Box::new(field)
+                }
+            };
+
+            match address {
+                Address::Relative(offset) => relative.push((offset, field)),
+                Address::Absolute(module, offset) => {
+                    absolute.entry(module).or_default().push((offset, field))
+                }
+            }
+        }
+
+        for (module, records) in absolute {
+            let name = match &module {
+                Some(module) => ident(&format!("{name}_{module}")),
+                None => name.to_owned(),
+            };
+            let origin = records.iter().map(|(offset, _)| *offset).min().unwrap_or(0);
+            let records = records
+                .into_iter()
+                .map(|(offset, field)| ((offset - origin) as usize, field))
+                .collect();
+            self.add_class(&name, records);
+        }
+
+        // Records before the parent's address can't be laid out.
+        let relative = relative
+            .into_iter()
+            .filter_map(|(offset, field)| Some((usize::try_from(offset).ok()?, field)))
+            .collect();
+        self.add_class(name, relative)
+    }
+
+    fn add_class(
+        &mut self,
+        base: &str,
+        mut records: Vec<(usize, Box<dyn Field>)>,
+    ) -> Option<(ClassId, usize)> {
+        if records.is_empty() {
+            return None;
+        }
+        records.sort_by_key(|(offset, _)| *offset);
+
+        let mut fields = vec![];
+        let mut end = 0;
+        for (offset, field) in records {
+            // Overlapping records are views of the same memory, the first one wins.
+            if offset < end {
+                continue;
+            }
+            fields.extend(allocate_padding(offset - end));
+            end = offset + field.size();
+            fields.push(field);
+            self.fields += 1;
+        }
+
+        let mut name = base.to_owned();
+        for i in 2.. {
+            if !self.names.contains(&name) {
+                break;
+            }
+            name = format!("{base}_{i}");
+        }
+        self.names.insert(name.clone());
+
+        let id = fastrand::usize(..);
+        self.classes.push((name, id, fields));
+        Some((id, end))
+    }
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, tag: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    child(node, tag)?.text().map(str::trim)
+}
+
+fn flag(node: Node, tag: &str) -> bool {
+    text(node, tag) == Some("1")
+}
+
+/// Turns a description into an identifier, Cheat Engine wraps descriptions in quotes.
+fn ident(desc: &str) -> String {
+    let mut ident = String::new();
+    for c in desc.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            ident.push(c);
+        } else if !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+
+    let ident = ident.trim_matches('_');
+    if is_valid_ident(ident) {
+        ident.to_owned()
+    } else {
+        format!("_{ident}")
+    }
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    u64::from_str_radix(s.trim(), 16).ok()
+}
+
+/// Parses `+10`, `1A2B3C` and `"game.exe"+1A2B` addresses, others, like symbols and pointer
+/// expressions, are skipped.
+fn parse_address(address: &str) -> Option<Address> {
+    let address = address.replace('"', "");
+    if let Some(offset) = address.strip_prefix('+') {
+        return Some(Address::Relative(parse_hex(offset)? as i64));
+    }
+    if let Some(offset) = address.strip_prefix('-') {
+        return Some(Address::Relative(-(parse_hex(offset)? as i64)));
+    }
+
+    match address.rsplit_once('+') {
+        Some((module, offset)) => Some(Address::Absolute(
+            Some(module.trim().to_owned()),
+            parse_hex(offset)?,
+        )),
+        None => Some(Address::Absolute(None, parse_hex(&address)?)),
+    }
+}
+
+/// Pointer offsets in the order they're applied, Cheat Engine lists the last one first.
+fn offsets(entry: Node) -> Option<Vec<isize>> {
+    let Some(offsets) = child(entry, "Offsets") else {
+        return Some(vec![]);
+    };
+    let mut offsets = offsets
+        .children()
+        .filter(|n| n.has_tag_name("Offset"))
+        .map(|n| parse_hex(n.text()?).map(|o| o as isize))
+        .collect::<Option<Vec<_>>>()?;
+    offsets.reverse();
+    Some(offsets)
+}
+
+fn kind_of(entry: Node) -> Option<FieldKind> {
+    let hex = flag(entry, "ShowAsHex");
+    let signed = flag(entry, "ShowAsSigned");
+    let int = |unk, int, uint| {
+        if hex {
+            unk
+        } else if signed {
+            int
+        } else {
+            uint
+        }
+    };
+    let len = |tag| text(entry, tag).and_then(|s| s.parse::<usize>().ok());
+
+    Some(match text(entry, "VariableType")? {
+        "Byte" => int(FieldKind::Unk8, FieldKind::I8, FieldKind::U8),
+        "2 Bytes" => int(FieldKind::Unk16, FieldKind::I16, FieldKind::U16),
+        "4 Bytes" => int(FieldKind::Unk32, FieldKind::I32, FieldKind::U32),
+        "8 Bytes" => int(FieldKind::Unk64, FieldKind::I64, FieldKind::U64),
+        "Float" => FieldKind::F32,
+        "Double" => FieldKind::F64,
+        "String" => {
+            let len = len("Length").unwrap_or(DEFAULT_STRING_LEN).max(1);
+            if flag(entry, "Unicode") {
+                FieldKind::InlineWStr(len)
+            } else {
+                FieldKind::InlineStr(len)
+            }
+        }
+        "Array of byte" => FieldKind::Array(Box::new(FieldKind::U8), len("ByteLength")?.max(1)),
+        // Scripts, binary and custom types.
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::YClassConfig,
+        generator::{generate, AvailableGenerator},
+    };
+
+    const TABLE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<CheatTable>
+  <CheatEntries>
+    <CheatEntry>
+      <Description>"Signature"</Description>
+      <VariableType>Array of byte</VariableType>
+      <ByteLength>4</ByteLength>
+      <Address>+10</Address>
+    </CheatEntry>
+  </CheatEntries>
+</CheatTable>
+"#;
+
+    #[test]
+    fn array_of_byte_generates() {
+        let path = std::env::temp_dir().join(format!("yclass_aob_{}.ct", std::process::id()));
+        fs::write(&path, TABLE).unwrap();
+        let mut list = ClassList::empty();
+        let import = import_ct(&path, &mut list);
+        _ = fs::remove_file(&path);
+        assert_eq!(import.unwrap().fields, 1);
+
+        for available in AvailableGenerator::ALL {
+            let mut gen = available.generator(&YClassConfig::default(), list.pointer_size());
+            let output = generate(&mut *gen, &list);
+            if *available == AvailableGenerator::Rust {
+                assert!(output.contains("[u8; 4]"), "{output}");
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod cheat_engine;
@@ -8,31 +8,50 @@ compile_error!("Only X64 targets are supported.");
 
 mod address;
 mod app;
+mod background_read;
+mod bookmarks;
 mod class;
 mod config;
 mod context;
+#[cfg(feature = "disasm")]
+mod disasm;
+mod enums;
 mod field;
+mod fonts;
 mod generator;
 mod gui;
+mod header_import;
+mod history;
 mod hotkeys;
+mod import;
+mod json;
+mod minidump_writer;
+mod named_addresses;
+mod plugin_watcher;
 mod process;
 mod project;
+mod recording;
+mod remote;
+mod rtti;
+mod snapshot;
 mod state;
+mod suggest;
+mod symbols;
+mod theme;
+mod validate;
 mod value;
+mod vtable_scan;
+mod yaml;
 
-use config::YClassConfig;
+use config::{YClassConfig, CHANGE_KIND_HOTKEYS, RECENT_PROJECT_HOTKEYS};
 use eframe::{
-    egui::{FontData, FontDefinitions, Key, Modifiers},
-    epaint::{FontFamily, FontId},
+    egui::{Key, Modifiers},
     NativeOptions,
 };
 use hotkeys::HotkeyManager;
 use state::GlobalState;
 use std::cell::RefCell;
 
-/// Monospaced font id.
-const FID_M: FontId = FontId::monospace(16.);
-
 fn main() {
     eframe::run_native(
         "YClass",
@@ -40,31 +59,75 @@ fn main() {
         Box::new(|cc| {
             let config = YClassConfig::load_or_default();
             cc.egui_ctx.set_pixels_per_point(config.dpi.unwrap_or(1.));
+            if let Some(count) = config.max_dynamic_array_elements {
+                field::set_max_displayed_elements(count);
+            }
+            for (module, path) in config.symbol_files.iter().flatten() {
+                symbols::load(&cc.egui_ctx, module, path.clone());
+            }
 
-            let mut fonts = FontDefinitions::default();
-            fonts.font_data.insert(
-                "roboto-mono".into(),
-                FontData::from_static(include_bytes!("../fonts/RobotoMono-Regular.ttf")).into(),
-            );
-            fonts
-                .families
-                .get_mut(&FontFamily::Monospace)
-                .unwrap()
-                .insert(0, "roboto-mono".into());
-            cc.egui_ctx.set_fonts(fonts);
+            let font_error = fonts::load_fonts(&cc.egui_ctx, &config).err();
 
             let mut hotkeys = HotkeyManager::default();
             hotkeys.register("attach_process", Key::A, Modifiers::ALT);
             hotkeys.register("attach_recent", Key::A, Modifiers::ALT | Modifiers::CTRL);
             hotkeys.register("detach_process", Key::D, Modifiers::ALT);
+            hotkeys.register("undo", Key::Z, Modifiers::CTRL);
+            hotkeys.register("redo", Key::Y, Modifiers::CTRL);
+            hotkeys.register("find_field", Key::F, Modifiers::CTRL);
+            hotkeys.register("go_to_offset", Key::G, Modifiers::CTRL);
+            hotkeys.register("address_back", Key::ArrowLeft, Modifiers::ALT);
+            hotkeys.register("address_forward", Key::ArrowRight, Modifiers::ALT);
+            hotkeys.register("rename", Key::F2, Modifiers::NONE);
+            hotkeys.register("copy_fields", Key::C, Modifiers::ALT);
+            hotkeys.register("paste_fields", Key::V, Modifiers::ALT);
+            hotkeys.register("previous_field", Key::ArrowUp, Modifiers::NONE);
+            hotkeys.register("next_field", Key::ArrowDown, Modifiers::NONE);
+            hotkeys.register("rename_next_field", Key::Tab, Modifiers::NONE);
+
+            let bookmark_keys = [
+                Key::Num1,
+                Key::Num2,
+                Key::Num3,
+                Key::Num4,
+                Key::Num5,
+                Key::Num6,
+                Key::Num7,
+                Key::Num8,
+                Key::Num9,
+            ];
+            for (i, name) in bookmarks::BOOKMARK_HOTKEYS.into_iter().enumerate() {
+                let shortcut = match config.bookmark_hotkeys.as_ref() {
+                    Some(keys) => keys.get(i).and_then(|s| hotkeys::parse_shortcut(s)),
+                    None => Some((bookmark_keys[i], Modifiers::CTRL)),
+                };
+
+                if let Some((key, modifiers)) = shortcut {
+                    hotkeys.register(name, key, modifiers);
+                }
+            }
+            for (i, name) in RECENT_PROJECT_HOTKEYS.into_iter().enumerate() {
+                hotkeys.register(name, bookmark_keys[i], Modifiers::ALT);
+            }
+            for (i, name) in CHANGE_KIND_HOTKEYS.into_iter().enumerate() {
+                hotkeys.register(name, bookmark_keys[i], Modifiers::NONE);
+            }
+            if let Some(overrides) = config.hotkeys.as_ref() {
+                hotkeys.apply_overrides(overrides);
+            }
+
+            let state = Box::leak(Box::new(RefCell::new(GlobalState {
+                config,
+                hotkeys,
+                ..Default::default()
+            })));
+            if let Some(e) = font_error {
+                state.borrow_mut().toasts.error(e.to_string());
+            }
+            // Before the first frame, so the empty project is never shown.
+            state.borrow_mut().reopen_last_project();
 
-            Ok(Box::new(app::YClassApp::new(Box::leak(Box::new(
-                RefCell::new(GlobalState {
-                    config,
-                    hotkeys,
-                    ..Default::default()
-                }),
-            )))))
+            Ok(Box::new(app::YClassApp::new(state)))
         }),
     )
     .unwrap();
@@ -0,0 +1,176 @@
+use crate::process::Process;
+use eyre::{bail, eyre};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SIGNATURE: u32 = 0x504D444D; // "MDMP"
+const VERSION: u32 = 0xA793;
+const FLAGS_WITH_FULL_MEMORY: u64 = 2;
+
+const MODULE_LIST_STREAM: u32 = 4;
+const SYSTEM_INFO_STREAM: u32 = 7;
+const MEMORY64_LIST_STREAM: u32 = 9;
+
+const ARCH_X86: u16 = 0;
+const ARCH_AMD64: u16 = 9;
+#[cfg(windows)]
+const PLATFORM: u32 = 2; // VER_PLATFORM_WIN32_NT
+#[cfg(unix)]
+const PLATFORM: u32 = 0x8201; // Linux, as written by Breakpad
+
+const HEADER_SIZE: usize = 32;
+const DIRECTORY_ENTRY_SIZE: usize = 12;
+const STREAM_COUNT: usize = 3;
+/// `MINIDUMP_MODULE` without the name, version and debug records.
+const MODULE_SIZE: usize = 108;
+/// Memory is read and written in chunks of this size.
+const CHUNK_SIZE: usize = 0x100000;
+
+/// Writes the readable memory and the modules of the process to a minidump with full memory,
+/// memory that fails to read is written as zeroes. `progress` is called with the number of
+/// written and total bytes of memory after every chunk.
+pub fn write_minidump(
+    process: &Process,
+    path: &Path,
+    mut progress: impl FnMut(u64, u64),
+) -> eyre::Result<()> {
+    let regions = process.readable_regions()?;
+    if regions.is_empty() {
+        bail!("The process has no readable memory");
+    }
+    let modules = process.modules();
+
+    let mut out = vec![0; HEADER_SIZE + STREAM_COUNT * DIRECTORY_ENTRY_SIZE];
+    let mut directory = vec![];
+
+    // MINIDUMP_SYSTEM_INFO, the version string is empty.
+    let system_info = out.len();
+    put_u16(
+        &mut out,
+        if process.pointer_size() == 4 {
+            ARCH_X86
+        } else {
+            ARCH_AMD64
+        },
+    );
+    put_u16(&mut out, 0);
+    put_u16(&mut out, 0);
+    out.extend([1, 0]);
+    for value in [0, 0, 0, PLATFORM] {
+        put_u32(&mut out, value);
+    }
+    let csd_version = out.len();
+    put_u32(&mut out, 0);
+    out.resize(out.len() + 4 + 24, 0);
+    directory.push((SYSTEM_INFO_STREAM, system_info, out.len() - system_info));
+    let csd_rva = rva(out.len())?;
+    out[csd_version..csd_version + 4].copy_from_slice(&csd_rva.to_le_bytes());
+    put_string(&mut out, "");
+
+    let names = modules
+        .iter()
+        .map(|m| {
+            let rva = rva(out.len());
+            put_string(&mut out, &m.name);
+            rva
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    let module_list = out.len();
+    put_u32(&mut out, modules.len() as u32);
+    for (module, name) in modules.iter().zip(names) {
+        let start = out.len();
+        put_u64(&mut out, module.base as u64);
+        put_u32(&mut out, module.size as u32);
+        put_u32(&mut out, 0);
+        put_u32(&mut out, 0);
+        put_u32(&mut out, name);
+        out.resize(start + MODULE_SIZE, 0);
+    }
+    directory.push((MODULE_LIST_STREAM, module_list, out.len() - module_list));
+
+    // MINIDUMP_MEMORY64_LIST, the memory of all ranges follows it.
+    let memory_list = out.len();
+    let memory = memory_list + 16 + regions.len() * 16;
+    put_u64(&mut out, regions.len() as u64);
+    put_u64(&mut out, memory as u64);
+    for region in &regions {
+        put_u64(&mut out, region.start as u64);
+        put_u64(&mut out, region.len() as u64);
+    }
+    directory.push((MEMORY64_LIST_STREAM, memory_list, out.len() - memory_list));
+    // Only the memory itself may be past the 4 GiB 32-bit offsets can address.
+    rva(out.len())?;
+
+    let mut header = vec![];
+    put_u32(&mut header, SIGNATURE);
+    put_u32(&mut header, VERSION);
+    put_u32(&mut header, STREAM_COUNT as u32);
+    put_u32(&mut header, HEADER_SIZE as u32);
+    put_u32(&mut header, 0);
+    put_u32(
+        &mut header,
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as u32),
+    );
+    put_u64(&mut header, FLAGS_WITH_FULL_MEMORY);
+    for (kind, start, len) in directory {
+        put_u32(&mut header, kind);
+        put_u32(&mut header, len as u32);
+        put_u32(&mut header, start as u32);
+    }
+    out[..header.len()].copy_from_slice(&header);
+
+    let write_err = |e| eyre!("Failed to write `{}`. {e}", path.display());
+    let mut file = BufWriter::new(File::create(path).map_err(write_err)?);
+    file.write_all(&out).map_err(write_err)?;
+
+    let total = regions.iter().map(|r| r.len() as u64).sum();
+    let mut written = 0;
+    let mut buf = vec![0; CHUNK_SIZE];
+    for region in regions {
+        let mut address = region.start;
+        while address < region.end {
+            let buf = &mut buf[..CHUNK_SIZE.min(region.end - address)];
+            buf.fill(0);
+            process.read(address, buf);
+            file.write_all(buf).map_err(write_err)?;
+
+            address += buf.len();
+            written += buf.len() as u64;
+            progress(written, total);
+        }
+    }
+    file.flush().map_err(write_err)?;
+
+    Ok(())
+}
+
+fn rva(offset: usize) -> eyre::Result<u32> {
+    u32::try_from(offset).map_err(|_| eyre!("The minidump has too many modules"))
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend(value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend(value.to_le_bytes());
+}
+
+fn put_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend(value.to_le_bytes());
+}
+
+/// MINIDUMP_STRING, the length in bytes without the null terminator followed by UTF-16.
+fn put_string(out: &mut Vec<u8>, s: &str) {
+    let utf16 = s.encode_utf16().collect::<Vec<_>>();
+    put_u32(out, (utf16.len() * 2) as u32);
+    for c in utf16.into_iter().chain([0]) {
+        put_u16(out, c);
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+/// Names given to stable addresses, i.e. static globals and vtables. Names are shown next to
+/// the addresses in the inspector and can be used in address expressions.
+#[derive(Debug, Default)]
+pub struct NamedAddressRegistry {
+    names: HashMap<usize, String>,
+}
+
+impl NamedAddressRegistry {
+    pub fn from_entries(entries: impl IntoIterator<Item = (usize, String)>) -> Self {
+        Self {
+            names: entries.into_iter().collect(),
+        }
+    }
+
+    /// Entries sorted by address.
+    pub fn entries(&self) -> Vec<(usize, &str)> {
+        let mut entries = self
+            .names
+            .iter()
+            .map(|(address, name)| (*address, name.as_str()))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(address, _)| *address);
+        entries
+    }
+
+    pub fn name(&self, address: usize) -> Option<&str> {
+        self.names.get(&address).map(String::as_str)
+    }
+
+    pub fn address(&self, name: &str) -> Option<usize> {
+        self.names
+            .iter()
+            .find(|(_, n)| n.as_str() == name)
+            .map(|(address, _)| *address)
+    }
+
+    /// Names `address`, an empty name removes it.
+    pub fn set(&mut self, address: usize, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            self.names.remove(&address);
+        } else {
+            self.names.insert(address, name.to_owned());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+    }
+}
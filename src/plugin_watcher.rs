@@ -0,0 +1,58 @@
+use crate::{process::Process, state::GlobalState};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reloads the plugin of the attached process when its file is modified, i.e. rebuilt.
+/// Disabled with `plugin_hot_reload` config key.
+pub struct PluginWatcher {
+    /// Plugin file and its modification time when it was last checked.
+    modified: Option<(PathBuf, SystemTime)>,
+    last_check: Instant,
+}
+
+impl Default for PluginWatcher {
+    fn default() -> Self {
+        Self {
+            modified: None,
+            last_check: Instant::now(),
+        }
+    }
+}
+
+impl PluginWatcher {
+    pub fn update(&mut self, state: &mut GlobalState) {
+        if !state.config.plugin_hot_reload() || self.last_check.elapsed() < CHECK_INTERVAL {
+            return;
+        }
+        self.last_check = Instant::now();
+
+        let process = state.process.clone();
+        // Scans keep the process locked, the plugin is reloaded once they're done.
+        let Some(mut process) = process.try_write() else {
+            return;
+        };
+        let Some(Process::Managed(ext)) = process.as_mut() else {
+            self.modified = None;
+            return;
+        };
+        let Ok(modified) = fs::metadata(ext.path()).and_then(|m| m.modified()) else {
+            // The file may be in the middle of being replaced.
+            return;
+        };
+
+        match self.modified.replace((ext.path().to_owned(), modified)) {
+            Some((path, previous)) if path == ext.path() && previous != modified => {}
+            _ => return,
+        }
+
+        match ext.reload() {
+            Ok(_) => _ = state.toasts.success("Plugin reloaded"),
+            Err(e) => _ = state.toasts.error(format!("Failed to reload plugin.\n{e}")),
+        }
+    }
+}
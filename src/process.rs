@@ -1,19 +1,247 @@
-use crate::config::YClassConfig;
+use crate::{
+    config::YClassConfig,
+    minidump_writer::write_minidump,
+    remote::TcpRemoteProcess,
+    symbols::{self, ResolvedSymbol},
+};
 use libloading::Library;
-use memflex::external::{MemoryRegion, OwnedProcess};
-use std::fs;
+use memflex::{
+    external::{MemoryRegion, OwnedProcess},
+    types::Protection,
+};
+use std::{
+    ffi::{c_char, CStr},
+    fs, io,
+    mem::ManuallyDrop,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Module loaded into the inspected process.
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub name: String,
+    pub base: usize,
+    pub size: usize,
+}
+
+impl Module {
+    /// Name of the module without the directory.
+    pub fn file_name(&self) -> &str {
+        self.name.rsplit(['/', '\\']).next().unwrap_or(&self.name)
+    }
+}
+
+/// Range of memory in the inspected process.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub range: Range<usize>,
+    /// `None` for minidumps, which only contain readable memory.
+    pub prot: Option<Protection>,
+}
+
+/// Bits returned by `yc_capabilities`, functions of missing capabilities aren't loaded.
+pub mod capabilities {
+    pub const READ: u32 = 1;
+    pub const WRITE: u32 = 2;
+    pub const CAN_READ: u32 = 4;
+    pub const MAPS: u32 = 8;
+    pub const NAME: u32 = 16;
+
+    /// Assumed for plugins that don't export `yc_capabilities`.
+    pub const DEFAULT: u32 = READ | WRITE | CAN_READ;
+}
+
+/// Plugin library, optionally loaded from a copy so the original file can be replaced while
+/// it's loaded.
+struct PluginLibrary {
+    lib: ManuallyDrop<Library>,
+    copy: Option<PathBuf>,
+}
+
+impl PluginLibrary {
+    fn load(path: &Path, copy: bool) -> eyre::Result<Self> {
+        static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+        let copy = copy
+            .then(|| -> eyre::Result<_> {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let copy = std::env::temp_dir().join(format!(
+                    "yclass-{}-{}-{file_name}",
+                    std::process::id(),
+                    COPIES.fetch_add(1, Ordering::Relaxed)
+                ));
+                fs::copy(path, &copy)?;
+                Ok(copy)
+            })
+            .transpose()?;
+
+        let lib = unsafe { Library::new(copy.as_deref().unwrap_or(path)) };
+        match lib {
+            Ok(lib) => Ok(Self {
+                lib: ManuallyDrop::new(lib),
+                copy,
+            }),
+            Err(e) => {
+                if let Some(copy) = copy {
+                    _ = fs::remove_file(copy);
+                }
+                Err(e.into())
+            }
+        }
+    }
+}
+
+impl Drop for PluginLibrary {
+    fn drop(&mut self) {
+        // The copy can only be removed once the library is unloaded.
+        unsafe { ManuallyDrop::drop(&mut self.lib) };
+        if let Some(copy) = &self.copy {
+            _ = fs::remove_file(copy);
+        }
+    }
+}
 
 pub struct ManagedExtension {
     #[allow(dead_code)]
-    lib: Library,
+    lib: PluginLibrary,
+    path: PathBuf,
     // process id
     pid: u32,
+    capabilities: u32,
 
     attach: fn(u32) -> u32,
     read: fn(usize, *mut u8, usize) -> u32,
-    write: fn(usize, *const u8, usize) -> u32,
-    can_read: fn(usize) -> bool,
+    write: Option<fn(usize, *const u8, usize) -> u32>,
+    can_read: Option<fn(usize) -> bool>,
+    maps: Option<fn() -> *const u8>,
+    name: Option<fn() -> *const c_char>,
     detach: fn(),
+    pointer_size: usize,
+}
+
+impl ManagedExtension {
+    /// Loads the plugin at `path` without attaching to the process, `copy` loads it from a
+    /// temporary copy of the file.
+    fn load(path: &Path, pid: u32, copy: bool) -> eyre::Result<Self> {
+        let lib = PluginLibrary::load(path, copy)?;
+        let attach = unsafe { *lib.lib.get::<fn(u32) -> u32>(b"yc_attach")? };
+        let capabilities = unsafe { lib.lib.get::<fn() -> u32>(b"yc_capabilities") }
+            .map(|f| (*f)())
+            .unwrap_or(capabilities::DEFAULT);
+        let read = unsafe {
+            *lib.lib
+                .get::<fn(usize, *mut u8, usize) -> u32>(b"yc_read")?
+        };
+        // Required only if the capability is reported.
+        let optional = |capability: u32| capabilities & capability != 0;
+        let write = optional(capabilities::WRITE)
+            .then(|| unsafe {
+                lib.lib
+                    .get::<fn(usize, *const u8, usize) -> u32>(b"yc_write")
+            })
+            .transpose()?
+            .map(|f| *f);
+        let can_read = optional(capabilities::CAN_READ)
+            .then(|| unsafe { lib.lib.get::<fn(usize) -> bool>(b"yc_can_read") })
+            .transpose()?
+            .map(|f| *f);
+        let maps = optional(capabilities::MAPS)
+            .then(|| unsafe { lib.lib.get::<fn() -> *const u8>(b"yc_maps") })
+            .transpose()?
+            .map(|f| *f);
+        let name = optional(capabilities::NAME)
+            .then(|| unsafe { lib.lib.get::<fn() -> *const c_char>(b"yc_name") })
+            .transpose()?
+            .map(|f| *f);
+        let detach = unsafe { *lib.lib.get::<fn()>(b"yc_detach")? };
+        // Optional, plugins that don't export it are assumed to target 64-bit processes.
+        let pointer_size = unsafe { lib.lib.get::<fn() -> usize>(b"yc_pointer_size") }
+            .map(|f| (*f)())
+            .unwrap_or(8);
+
+        Ok(Self {
+            lib,
+            path: path.to_owned(),
+            pid,
+            capabilities,
+            attach,
+            read,
+            write,
+            can_read,
+            maps,
+            name,
+            detach,
+            pointer_size,
+        })
+    }
+
+    /// Path the plugin was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the plugin again from its path and attaches it to the same process. The current
+    /// plugin stays attached if the new one fails to load.
+    pub fn reload(&mut self) -> eyre::Result<()> {
+        // Loading the same path again would return the library that is already loaded.
+        let new = Self::load(&self.path, self.pid, true)?;
+        // Detaches the current plugin.
+        *self = new;
+
+        match (self.attach)(self.pid) {
+            0 => Ok(()),
+            status => Err(eyre::eyre!("yc_attach returned {status}")),
+        }
+    }
+
+    pub fn has(&self, capability: u32) -> bool {
+        self.capabilities & capability != 0
+    }
+
+    /// Parses the buffer returned by `yc_maps`: `count: u64` followed by `start: u64, end: u64,
+    /// prot: u64` for each region, native endian.
+    fn maps(&self) -> eyre::Result<Vec<Region>> {
+        let Some(maps) = self.maps else {
+            eyre::bail!("Plugin doesn't support enumerating memory regions");
+        };
+
+        let buf = maps() as *const u64;
+        if buf.is_null() {
+            eyre::bail!("Plugin failed to enumerate memory regions");
+        }
+
+        // SAFETY: The plugin guarantees the buffer holds `count` entries and stays valid until
+        // the next call.
+        unsafe {
+            let count = buf.read_unaligned() as usize;
+            Ok((0..count)
+                .map(|i| {
+                    let entry = buf.add(1 + i * 3);
+                    Region {
+                        range: entry.read_unaligned() as usize
+                            ..entry.add(1).read_unaligned() as usize,
+                        prot: Some(Protection::from_bits_truncate(
+                            entry.add(2).read_unaligned() as _,
+                        )),
+                    }
+                })
+                .collect())
+        }
+    }
+
+    fn name(&self) -> String {
+        let name = self.name.map(|name| name()).unwrap_or(std::ptr::null());
+        if name.is_null() {
+            return "[MANAGED]".into();
+        }
+
+        // SAFETY: The plugin returns a nul terminated string.
+        unsafe { CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned()
+    }
 }
 
 impl Drop for ManagedExtension {
@@ -23,16 +251,48 @@ impl Drop for ManagedExtension {
 }
 
 pub enum Process {
-    Internal((OwnedProcess, Vec<MemoryRegion>)),
+    /// Memory maps and modules are captured at the moment of attaching.
+    Internal((OwnedProcess, Vec<MemoryRegion>, usize, Vec<Module>)),
     Managed(ManagedExtension),
-    Minidump { segments: Vec<(u64, Vec<u8>)> },
+    Minidump {
+        segments: Vec<(u64, Vec<u8>)>,
+        modules: Vec<Module>,
+        pointer_size: usize,
+    },
+    TcpRemote(TcpRemoteProcess),
+}
+
+fn read_only() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "Process is read only")
 }
 
 impl Process {
     pub fn minidump(path: impl AsRef<std::path::Path>) -> eyre::Result<Self> {
+        use minidump::Module as _;
+
         let dump = minidump::Minidump::read_path(path)?;
 
         let mem = dump.get_memory().unwrap();
+        let pointer_size = match dump
+            .get_stream::<minidump::MinidumpSystemInfo>()
+            .map(|info| info.cpu.pointer_width())
+        {
+            Ok(minidump::system_info::PointerWidth::Bits32) => 4,
+            _ => 8,
+        };
+
+        let modules = dump
+            .get_stream::<minidump::MinidumpModuleList>()
+            .map(|list| {
+                list.iter()
+                    .map(|m| Module {
+                        name: m.code_file().into_owned(),
+                        base: m.base_address() as usize,
+                        size: m.size() as usize,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let mut segments = vec![];
         let mut chunk: Option<(&[u8], u64)> = None;
@@ -61,9 +321,24 @@ impl Process {
                 chunk = Some((bytes, mem.base_address()));
             }
         }
+        if let Some((slice, address)) = chunk {
+            segments.push((address, slice.to_vec()));
+        }
 
-        Ok(Self::Minidump { segments })
+        Ok(Self::Minidump {
+            segments,
+            modules,
+            pointer_size,
+        })
     }
+
+    /// Writes the memory and modules to a minidump that can be loaded with [`Process::minidump`],
+    /// plugins need `yc_maps` for their memory to be found. `progress` gets the written and total
+    /// bytes of memory.
+    pub fn create_minidump(&self, path: &Path, progress: impl FnMut(u64, u64)) -> eyre::Result<()> {
+        write_minidump(self, path, progress)
+    }
+
     pub fn attach(pid: u32, config: &YClassConfig) -> eyre::Result<Self> {
         let (path, modified) = (
             config
@@ -75,23 +350,7 @@ impl Process {
 
         let metadata = fs::metadata(&path);
         Ok(if metadata.is_ok() {
-            let lib = unsafe { Library::new(&path)? };
-            let attach = unsafe { *lib.get::<fn(u32) -> u32>(b"yc_attach")? };
-            let read = unsafe { *lib.get::<fn(usize, *mut u8, usize) -> u32>(b"yc_read")? };
-            let write = unsafe { *lib.get::<fn(usize, *const u8, usize) -> u32>(b"yc_write")? };
-            let can_read = unsafe { *lib.get::<fn(usize) -> bool>(b"yc_can_read")? };
-            let detach = unsafe { *lib.get::<fn()>(b"yc_detach")? };
-
-            let ext = ManagedExtension {
-                pid,
-                lib,
-                attach,
-                read,
-                write,
-                can_read,
-                detach,
-            };
-
+            let ext = ManagedExtension::load(&path, pid, config.plugin_hot_reload())?;
             (ext.attach)(pid);
 
             Self::Managed(ext)
@@ -115,52 +374,138 @@ impl Process {
             };
 
             let maps = proc.maps()?;
-            Self::Internal((proc, maps))
+            let pointer_size = detect_pointer_size(&proc);
+            let modules = load_modules(&proc);
+            Self::Internal((proc, maps, pointer_size, modules))
         })
     }
 
+    /// Connects to a memory server at `IP:Port`.
+    pub fn connect(address: &str) -> eyre::Result<Self> {
+        TcpRemoteProcess::connect(address).map(Self::TcpRemote)
+    }
+
     pub fn read(&self, address: usize, buf: &mut [u8]) {
+        _ = self.read_checked(address, buf);
+    }
+
+    /// Returns `false` if the memory couldn't be read, `buf` is left zeroed or partially filled.
+    pub fn read_checked(&self, address: usize, buf: &mut [u8]) -> bool {
         match self {
-            // TODO(ItsEthra): Proper error handling maybe?.
-            Self::Internal((op, _)) => _ = op.read_buf(address, buf),
-            Self::Managed(ext) => _ = (ext.read)(address, buf.as_mut_ptr(), buf.len()),
-            Self::Minidump { segments } => {
+            Self::Internal((op, ..)) => op.read_buf(address, buf).is_ok(),
+            Self::Managed(ext) => (ext.read)(address, buf.as_mut_ptr(), buf.len()) == 0,
+            Self::Minidump { segments, .. } => {
                 let address = address as u64;
                 for (addr, mem) in segments {
                     if (*addr..*addr + mem.len() as u64).contains(&address) {
+                        // Reads past the end of the segment are truncated.
                         let base = (address - addr) as usize;
-                        buf.copy_from_slice(&mem[base..base + buf.len()]);
-                        break;
+                        let end = (base + buf.len()).min(mem.len());
+                        buf[..end - base].copy_from_slice(&mem[base..end]);
+                        return end - base == buf.len();
                     }
                 }
+                false
             }
-        };
+            Self::TcpRemote(remote) => remote.read(address, buf).is_ok(),
+        }
+    }
+
+    /// Reads a pointer of the process' width at `address`.
+    /// 32-bit pointers are zero extended.
+    pub fn read_pointer(&self, address: usize) -> usize {
+        let mut buf = [0; 8];
+        self.read(address, &mut buf[..self.pointer_size()]);
+        usize::from_ne_bytes(buf)
     }
 
-    pub fn write(&self, address: usize, buf: &[u8]) {
+    /// Writes the buffer, with `verify` it's read back as well, see [`Self::write_verified`].
+    pub fn write(&self, address: usize, buf: &[u8], verify: bool) -> io::Result<()> {
+        if verify {
+            self.write_verified(address, buf)
+        } else {
+            self.try_write(address, buf)
+        }
+    }
+
+    pub fn try_write(&self, address: usize, buf: &[u8]) -> io::Result<()> {
         match self {
-            // TODO(ItsEthra): Proper error handling maybe?.
-            Self::Internal((op, _)) => _ = op.write_buf(address, buf),
-            Self::Managed(ext) => _ = (ext.write)(address, buf.as_ptr(), buf.len()),
-            Self::Minidump { .. } => { /* read only */ }
+            Self::Internal((op, ..)) => _ = op.write_buf(address, buf).map_err(io::Error::other)?,
+            Self::Managed(ext) => match ext.write {
+                Some(write) => match write(address, buf.as_ptr(), buf.len()) {
+                    0 => {}
+                    status => {
+                        return Err(io::Error::other(format!(
+                            "yc_write failed with status {status}"
+                        )))
+                    }
+                },
+                None => return Err(read_only()),
+            },
+            Self::Minidump { .. } => return Err(read_only()),
+            Self::TcpRemote(remote) => remote.write(address, buf).map_err(io::Error::other)?,
         };
+        Ok(())
+    }
+
+    /// Writes the buffer and reads it back, fails if the memory doesn't hold it afterwards,
+    /// e.g. because the page is write protected.
+    pub fn write_verified(&self, address: usize, buf: &[u8]) -> io::Result<()> {
+        self.try_write(address, buf)?;
+
+        let mut written = vec![0; buf.len()];
+        self.read(address, &mut written);
+        if written != buf {
+            return Err(io::Error::other(format!(
+                "Memory at {address:X} doesn't hold the written value"
+            )));
+        }
+        Ok(())
     }
 
     pub fn id(&self) -> u32 {
         match self {
-            Self::Internal((op, _)) => op.id(),
+            Self::Internal((op, ..)) => op.id(),
             Self::Managed(ext) => ext.pid,
             Self::Minidump { .. } => 0,
+            Self::TcpRemote(remote) => remote.pid(),
+        }
+    }
+
+    /// Returns `true` if writes are ignored, i.e. for minidumps or plugins without `yc_write`.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Self::Managed(ext) => !ext.has(capabilities::WRITE),
+            _ => matches!(self, Self::Minidump { .. }),
+        }
+    }
+
+    /// Returns `false` while a remote process is reconnecting, reads fail until then.
+    pub fn is_connected(&self) -> bool {
+        match self {
+            Self::TcpRemote(remote) => remote.is_connected(),
+            _ => true,
+        }
+    }
+
+    /// Pointer size of the inspected process in bytes, either `4` or `8`.
+    pub fn pointer_size(&self) -> usize {
+        match self {
+            Self::Internal((_, _, size, _)) => *size,
+            Self::Managed(ext) => ext.pointer_size,
+            Self::Minidump { pointer_size, .. } => *pointer_size,
+            Self::TcpRemote(remote) => remote.pointer_size(),
         }
     }
 
     pub fn can_read(&self, address: usize) -> bool {
         match self {
-            Self::Internal((_, maps)) => maps
+            Self::Internal((_, maps, ..)) => maps
                 .iter()
                 .any(|map| map.from <= address && map.to >= address && map.prot.read()),
-            Self::Managed(ext) => (ext.can_read)(address),
-            Self::Minidump { segments } => {
+            // Without `yc_can_read` any non-null address is assumed to be readable.
+            Self::Managed(ext) => ext.can_read.map_or(address != 0, |f| f(address)),
+            Self::Minidump { segments, .. } => {
                 let address = address as u64;
                 for (addr, mem) in segments {
                     if (*addr..*addr + mem.len() as u64).contains(&address) {
@@ -169,14 +514,298 @@ impl Process {
                 }
                 false
             }
+            Self::TcpRemote(remote) => remote.can_read(address),
         }
     }
 
+    /// Memory regions of the process, refreshed on every call for attached processes.
+    pub fn regions(&self) -> eyre::Result<Vec<Region>> {
+        match self {
+            Self::Internal((op, maps, ..)) => Ok(op
+                .maps()
+                .as_ref()
+                .unwrap_or(maps)
+                .iter()
+                .map(|map| Region {
+                    range: map.from..map.to,
+                    prot: Some(map.prot),
+                })
+                .collect()),
+            Self::Managed(ext) => ext.maps(),
+            Self::Minidump { segments, .. } => Ok(segments
+                .iter()
+                .map(|(addr, mem)| Region {
+                    range: *addr as usize..*addr as usize + mem.len(),
+                    prot: None,
+                })
+                .collect()),
+            Self::TcpRemote(remote) => Ok(remote
+                .maps()?
+                .into_iter()
+                .map(|map| Region {
+                    range: map.start..map.end,
+                    prot: Some(Protection::from_bits_truncate(map.prot)),
+                })
+                .collect()),
+        }
+    }
+
+    /// Address ranges of readable memory, refreshed on every call for attached processes.
+    pub fn readable_regions(&self) -> eyre::Result<Vec<Range<usize>>> {
+        Ok(self
+            .regions()?
+            .into_iter()
+            .filter(|region| region.prot.is_none_or(|prot| prot.read()))
+            .map(|region| region.range)
+            .collect())
+    }
+
+    /// Returns addresses of all occurrences of `pattern` in readable memory,
+    /// `None` matches any byte.
+    pub fn scan_pattern(&self, pattern: &[Option<u8>]) -> Vec<usize> {
+        const CHUNK_SIZE: usize = 0x10000;
+
+        let Ok(regions) = self.readable_regions() else {
+            return vec![];
+        };
+        if pattern.is_empty() {
+            return vec![];
+        }
+
+        let mut results = vec![];
+        // Reads a bit more than a chunk so matches crossing chunk boundaries aren't missed.
+        let mut buf = vec![0; CHUNK_SIZE + pattern.len() - 1];
+        for region in regions {
+            let mut address = region.start;
+            while address + pattern.len() <= region.end {
+                let buf = &mut buf[..(CHUNK_SIZE + pattern.len() - 1).min(region.end - address)];
+                buf.fill(0);
+                self.read(address, buf);
+
+                results.extend(
+                    buf.windows(pattern.len())
+                        .take(CHUNK_SIZE)
+                        .enumerate()
+                        .filter(|(_, window)| matches_pattern(window, pattern))
+                        .map(|(i, _)| address + i),
+                );
+                address += CHUNK_SIZE;
+            }
+        }
+
+        results
+    }
+
+    /// Modules loaded into the process, empty for plugins and remote processes.
+    /// Modules of the process, refreshed on every call for attached processes.
+    pub fn modules(&self) -> Vec<Module> {
+        match self {
+            Self::Internal((op, ..)) => load_modules(op),
+            Self::Managed(_) | Self::TcpRemote(_) => vec![],
+            Self::Minidump { modules, .. } => modules.clone(),
+        }
+    }
+
+    /// Resolves the address to its module and the closest symbol imported for the module,
+    /// modules loaded after attaching aren't known.
+    pub fn resolve_symbol(&self, address: usize) -> Option<ResolvedSymbol> {
+        let (module, rva) = self.address_to_rva(address)?;
+        Some(symbols::resolve(self.id(), &module, address, rva))
+    }
+
+    /// File name of the module containing the address and the offset into it, modules loaded
+    /// after attaching aren't known.
+    pub fn address_to_rva(&self, address: usize) -> Option<(String, usize)> {
+        let modules = match self {
+            Self::Internal((.., modules)) => modules,
+            Self::Managed(_) | Self::TcpRemote(_) => return None,
+            Self::Minidump { modules, .. } => modules,
+        };
+
+        modules
+            .iter()
+            .find(|m| (m.base..m.base + m.size).contains(&address))
+            .map(|m| (m.file_name().to_owned(), address - m.base))
+    }
+
+    /// Base address of the module with the file name `name`, ignoring case.
+    pub fn module_base(&self, name: &str) -> Option<usize> {
+        self.modules()
+            .into_iter()
+            .find(|m| m.file_name().eq_ignore_ascii_case(name))
+            .map(|m| m.base)
+    }
+
     pub fn name(&self) -> eyre::Result<String> {
         match self {
-            Self::Internal((op, _)) => op.name().map_err(Into::into),
-            Self::Managed(_) => Ok("[MANAGED]".into()),
+            Self::Internal((op, ..)) => op.name().map_err(Into::into),
+            Self::Managed(ext) => Ok(ext.name()),
             Self::Minidump { .. } => Ok("[minidump]".into()),
+            Self::TcpRemote(remote) => remote.name(),
         }
     }
 }
+
+fn load_modules(proc: &OwnedProcess) -> Vec<Module> {
+    proc.modules()
+        .map(|modules| {
+            modules
+                .map(|m| Module {
+                    name: m.name,
+                    base: m.base as usize,
+                    size: m.size,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn matches_pattern(bytes: &[u8], pattern: &[Option<u8>]) -> bool {
+    bytes
+        .iter()
+        .zip(pattern)
+        .all(|(b, p)| p.is_none_or(|p| p == *b))
+}
+
+/// Checks the class of the process' executable image, falls back to `8` if it can't be determined.
+#[cfg(unix)]
+fn detect_pointer_size(proc: &OwnedProcess) -> usize {
+    pointer_size_of_pid(proc.id()).unwrap_or(8)
+}
+
+/// Pointer size of the process with the id without attaching to it, `None` if the process
+/// can't be inspected.
+#[cfg(unix)]
+pub fn pointer_size_of_pid(pid: u32) -> Option<usize> {
+    use std::io::Read;
+
+    // `EI_CLASS` byte of the ELF header, `1` means 32-bit.
+    let mut ident = [0; 5];
+    fs::File::open(format!("/proc/{pid}/exe"))
+        .and_then(|mut f| f.read_exact(&mut ident))
+        .ok()?;
+    Some(if ident[4] == 1 { 4 } else { 8 })
+}
+
+/// Pointer size of the process with the id without attaching to it, `None` if the process
+/// can't be opened.
+#[cfg(windows)]
+pub fn pointer_size_of_pid(pid: u32) -> Option<usize> {
+    use memflex::types::win::{PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+
+    let proc = memflex::external::open_process_by_id(
+        pid,
+        false,
+        PROCESS_QUERY_INFORMATION | PROCESS_VM_READ,
+    )
+    .ok()?;
+    Some(detect_pointer_size(&proc))
+}
+
+/// Checks the machine type of the main module, falls back to `8` if it can't be determined.
+#[cfg(windows)]
+fn detect_pointer_size(proc: &OwnedProcess) -> usize {
+    // IMAGE_FILE_MACHINE_I386
+    const MACHINE_I386: u16 = 0x14C;
+
+    let Some(base) = proc
+        .modules()
+        .ok()
+        .and_then(|mut modules| modules.next())
+        .map(|m| m.base as usize)
+    else {
+        return 8;
+    };
+
+    match proc
+        .read::<u32>(base + 0x3C)
+        .and_then(|e_lfanew| proc.read::<u16>(base + e_lfanew as usize + 4))
+    {
+        Ok(MACHINE_I386) => 4,
+        _ => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// Builds `examples/plugin.rs` into `dir` and returns the path of the library.
+    fn build_plugin(dir: &Path) -> PathBuf {
+        let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into()))
+            .args([
+                "--edition",
+                "2021",
+                "--crate-type",
+                "cdylib",
+                "--crate-name",
+            ])
+            .arg("yclass_plugin")
+            .arg("--out-dir")
+            .arg(dir)
+            .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/plugin.rs"))
+            .output()
+            .expect("Failed to run rustc");
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| {
+                p.file_stem()
+                    .is_some_and(|s| s.to_string_lossy().contains("yclass_plugin"))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn example_plugin_loads() {
+        let dir = std::env::temp_dir().join(format!("yclass_plugin_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = YClassConfig {
+            plugin_path: Some(build_plugin(&dir)),
+            plugin_hot_reload: Some(false),
+            ..Default::default()
+        };
+        let process = Process::attach(42, &config);
+        _ = fs::remove_dir_all(&dir);
+        let process = process.unwrap();
+
+        let Process::Managed(ext) = &process else {
+            panic!("The plugin wasn't loaded");
+        };
+        assert!(ext.has(capabilities::READ | capabilities::WRITE));
+        for capability in [
+            capabilities::CAN_READ,
+            capabilities::MAPS,
+            capabilities::NAME,
+        ] {
+            assert!(ext.has(capability));
+        }
+        assert_eq!(process.id(), 42);
+        assert_eq!(process.pointer_size(), 4);
+        assert_eq!(process.name().unwrap(), "example");
+        assert!(!process.is_read_only());
+
+        let regions = process.regions().unwrap();
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].range, 0x10000..0x10100);
+        assert_eq!(regions[0].prot, Some(Protection::R | Protection::W));
+
+        assert!(process.can_read(0x10000));
+        assert!(!process.can_read(0x10100));
+        assert_eq!(process.read_pointer(0x10004), 0x07060504);
+        let mut buf = [0; 2];
+        assert!(!process.read_checked(0x100FF, &mut buf));
+
+        process.write_verified(0x10010, &[0xAA, 0xBB]).unwrap();
+        assert!(process.read_checked(0x1000F, &mut buf));
+        assert_eq!(buf, [0x0F, 0xAA]);
+        assert!(process.try_write(0x20000, &[0]).is_err());
+    }
+}
@@ -1,10 +1,20 @@
 /// This module contains structures that serialize/deserialize project data(i.e. classes).
 use crate::{
-    class::{Class, ClassList},
-    field::{allocate_padding, CodegenData, Field, FieldKind, PointerField},
+    bookmarks::{Bookmark, Bookmarks},
+    class::{Class, ClassId, ClassList, ColorOverride, DEFAULT_POINTER_SIZE},
+    enums::{EnumDefinition, EnumRegistry},
+    field::{
+        allocate_padding, array_elements, walk_fields, CodegenData, EmbeddedClassField, EnumField,
+        Field, FieldKind, FlagsField, PointerField, SectionField,
+    },
     generator::Generator,
+    json,
+    named_addresses::NamedAddressRegistry,
+    yaml,
 };
+use eframe::epaint::Color32;
 use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DataField {
@@ -12,25 +22,147 @@ struct DataField {
     offset: usize,
     kind: FieldKind,
     metadata: Option<String>,
+    #[serde(default)]
+    comment: Option<String>,
+    /// Fields of a section, their offsets are relative to the class as well.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<DataField>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DataClass {
     name: String,
+    /// Offsets of the fields are relative to the end of the base class.
     fields: Vec<DataField>,
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    colors: Option<DataColors>,
+    /// Address the class is inspected at.
+    #[serde(default)]
+    address: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum DataColors {
+    ByKind(Vec<(FieldKind, [u8; 3])>),
+    /// Colors of the fields at the offsets, hex fields aren't stored so their ids can't be used.
+    PerField(Vec<(usize, [u8; 3])>),
+    Solid([u8; 3]),
+}
+
+fn rgb(color: Color32) -> [u8; 3] {
+    [color.r(), color.g(), color.b()]
+}
+
+impl DataColors {
+    fn store(class: &Class) -> Option<Self> {
+        Some(match class.colors.borrow().as_ref()? {
+            ColorOverride::ByKind(colors) => Self::ByKind(
+                colors
+                    .iter()
+                    .map(|(kind, color)| (kind.clone(), rgb(*color)))
+                    .collect(),
+            ),
+            ColorOverride::PerField(colors) => Self::PerField(
+                offsets(class)
+                    .filter_map(|(offset, f)| Some((offset, rgb(*colors.get(&f.id())?))))
+                    .collect(),
+            ),
+            ColorOverride::Solid(color) => Self::Solid(rgb(*color)),
+        })
+    }
+
+    /// Sets the colors of the class, which must have its fields loaded.
+    fn load(self, class: &mut Class) {
+        let color = |[r, g, b]: [u8; 3]| Color32::from_rgb(r, g, b);
+        let colors = match self {
+            Self::ByKind(colors) => ColorOverride::ByKind(
+                colors
+                    .into_iter()
+                    .map(|(kind, c)| (kind, color(c)))
+                    .collect(),
+            ),
+            Self::PerField(colors) => {
+                let ids = offsets(class)
+                    .map(|(offset, f)| (offset, f.id()))
+                    .collect::<HashMap<_, _>>();
+                ColorOverride::PerField(
+                    colors
+                        .into_iter()
+                        .filter_map(|(offset, c)| Some((*ids.get(&offset)?, color(c))))
+                        .collect(),
+                )
+            }
+            Self::Solid(c) => ColorOverride::Solid(color(c)),
+        };
+        *class.colors.get_mut() = Some(colors);
+    }
+}
+
+/// Fields of the class with their offsets from the end of the base class, sections are skipped
+/// since they share the offset with their first field.
+fn offsets(class: &Class) -> impl Iterator<Item = (usize, &dyn Field)> {
+    walk_fields(&class.fields, 0)
+        .into_iter()
+        .filter(|(_, f)| f.section_fields().is_none())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DataBookmark {
+    name: String,
+    address: usize,
+    /// Classes are referenced by name because ids aren't preserved between sessions.
+    class: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DataNamedAddress {
+    name: String,
+    address: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct ProjectData {
     classes: Vec<DataClass>,
+    #[serde(default)]
+    enums: Vec<EnumDefinition>,
+    #[serde(default)]
+    bookmarks: Vec<DataBookmark>,
+    #[serde(default)]
+    named_addresses: Vec<DataNamedAddress>,
+    /// Pointer width of the process the classes were made for, offsets of the fields depend on it.
+    #[serde(default = "default_pointer_size")]
+    pointer_size: usize,
+}
+
+fn default_pointer_size() -> usize {
+    DEFAULT_POINTER_SIZE
 }
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 struct ProjectDataGenerator {
     classes: Vec<DataClass>,
+    comment: Option<String>,
     offset: usize,
     last_offset: usize,
+    /// Open sections along with fields of the class or section they're in.
+    sections: Vec<(DataField, Vec<DataField>)>,
+    pointer_size: usize,
+}
+
+impl ProjectDataGenerator {
+    fn new(list: &ClassList) -> Self {
+        Self {
+            classes: vec![],
+            comment: None,
+            offset: 0,
+            last_offset: 0,
+            sections: vec![],
+            pointer_size: list.pointer_size(),
+        }
+    }
 }
 
 impl Generator for &mut ProjectDataGenerator {
@@ -38,23 +170,42 @@ impl Generator for &mut ProjectDataGenerator {
         self.classes.push(DataClass {
             name: name.into(),
             fields: vec![],
+            base: None,
+            colors: None,
+            address: 0,
         });
     }
 
+    fn add_comment(&mut self, comment: &str) {
+        self.comment = Some(comment.to_owned());
+    }
+
     fn add_field(&mut self, name: &str, kind: FieldKind, metadata: Option<&str>) {
-        let size = kind.size();
+        let size = kind.size(self.pointer_size);
 
         self.classes.last_mut().unwrap().fields.push(DataField {
             metadata: metadata.map(|s| s.to_owned()),
+            comment: self.comment.take(),
             name: name.to_owned(),
             offset: self.offset,
             kind,
+            fields: vec![],
         });
 
         self.offset += size;
         self.last_offset = self.offset;
     }
 
+    fn add_array_field(
+        &mut self,
+        name: &str,
+        kind: FieldKind,
+        count: usize,
+        metadata: Option<&str>,
+    ) {
+        self.add_field(name, FieldKind::Array(Box::new(kind), count), metadata);
+    }
+
     fn add_offset(&mut self, offset: usize) {
         self.offset += offset;
     }
@@ -64,103 +215,635 @@ impl Generator for &mut ProjectDataGenerator {
         self.last_offset = 0;
     }
 
+    fn begin_section(&mut self, name: &str) {
+        let section = DataField {
+            name: name.to_owned(),
+            offset: self.offset,
+            kind: FieldKind::Section(0),
+            metadata: None,
+            comment: self.comment.take(),
+            fields: vec![],
+        };
+        let outer = std::mem::take(&mut self.classes.last_mut().unwrap().fields);
+        self.sections.push((section, outer));
+    }
+
+    fn end_section(&mut self) {
+        let (mut section, outer) = self.sections.pop().unwrap();
+        let class = self.classes.last_mut().unwrap();
+        section.fields = std::mem::replace(&mut class.fields, outer);
+        section.kind = FieldKind::Section(self.offset - section.offset);
+        class.fields.push(section);
+        self.last_offset = self.offset;
+    }
+
     fn finilize(&mut self) -> String {
         unimplemented!()
     }
 }
 
+/// Creates fields stored from `start` onwards, returns them along with the offset after the last one.
+fn load_fields(
+    list: &mut ClassList,
+    mut fields: Vec<DataField>,
+    start: usize,
+) -> (Vec<Box<dyn Field>>, usize) {
+    fields.sort_by_key(|f| f.offset);
+    let (mut loaded, mut current_offset) = (Vec::<Box<dyn Field>>::new(), start);
+
+    for DataField {
+        offset: field_offset,
+        name,
+        kind,
+        metadata,
+        comment,
+        fields,
+    } in fields
+    {
+        // Arrays too large to create, only found in hand edited files, are dropped.
+        if let FieldKind::Array(inner, count) = &kind {
+            if array_elements(inner, *count).is_none() {
+                continue;
+            }
+        }
+
+        let size = kind.size(list.pointer_size());
+        if field_offset > current_offset {
+            loaded.extend(allocate_padding(field_offset - current_offset));
+        }
+
+        let field: Box<dyn Field> = match kind {
+            FieldKind::Ptr | FieldKind::RelPtr32 | FieldKind::RelPtr64 => {
+                let classname = metadata.as_deref();
+                let refid = if let Some(refclass) = classname.and_then(|name| list.by_name(name)) {
+                    refclass.id()
+                } else {
+                    list.add_class(
+                        classname
+                            .map(str::to_owned)
+                            .unwrap_or_else(|| format!("C{:X}", field_offset)),
+                    )
+                };
+
+                if kind == FieldKind::Ptr {
+                    Box::new(PointerField::new_with_class_id(
+                        name,
+                        refid,
+                        list.pointer_size(),
+                    ))
+                } else {
+                    Box::new(PointerField::new_relative_with_class_id(name, size, refid))
+                }
+            }
+            FieldKind::Flags(size) => Box::new(FlagsField::new_with_flags(name, size, metadata)),
+            FieldKind::Enum(size) => Box::new(EnumField::new_with_enum(name, size, metadata)),
+            FieldKind::Embedded(size) => {
+                let refid = metadata.as_deref().and_then(|name| list.by_name(name));
+                match refid.map(|c| c.id()) {
+                    Some(refid) => Box::new(EmbeddedClassField::new(name, refid, size)),
+                    None => FieldKind::Embedded(size).into_field(Some(name), list.pointer_size()),
+                }
+            }
+            FieldKind::Section(size) => {
+                let (mut children, end) = load_fields(list, fields, field_offset);
+                if end < field_offset + size {
+                    children.extend(allocate_padding(field_offset + size - end));
+                }
+                Box::new(SectionField::new(name, children))
+            }
+            other => other.into_field(Some(name), list.pointer_size()),
+        };
+        field.set_comment(comment);
+
+        current_offset = field_offset + field.size();
+        loaded.push(field);
+    }
+
+    (loaded, current_offset)
+}
+
+/// State of a single class, used to roll back edits.
+pub struct ClassSnapshot {
+    id: ClassId,
+    name: String,
+    address: usize,
+    size: usize,
+    base: Option<ClassId>,
+    fields: Vec<DataField>,
+    colors: Option<DataColors>,
+}
+
+impl ClassSnapshot {
+    pub fn take(list: &ClassList, id: ClassId) -> Option<Self> {
+        let class = list.by_id(id)?;
+
+        let mut datagen = ProjectDataGenerator::new(list);
+        let dynam = &mut &mut datagen as &mut dyn Generator;
+        let data = CodegenData {
+            classes: list.classes(),
+            enums: list.enums(),
+        };
+
+        dynam.begin_class(&class.name);
+        for f in class.fields.iter() {
+            if let Some(comment) = f.comment() {
+                dynam.add_comment(&comment);
+            }
+            f.codegen(dynam, &data);
+        }
+
+        Some(Self {
+            id,
+            name: class.name.clone(),
+            address: class.address.get(),
+            size: class.fields.iter().map(|f| f.size()).sum(),
+            base: list.base_of(id),
+            fields: datagen.classes.pop().unwrap().fields,
+            colors: DataColors::store(class),
+        })
+    }
+
+    pub fn id(&self) -> ClassId {
+        self.id
+    }
+
+    /// Makes the snapshot restore a different class.
+    pub fn renamed(self, id: ClassId, name: String) -> Self {
+        Self { id, name, ..self }
+    }
+
+    /// Restores the class in place, or at `index` if it doesn't exist anymore.
+    pub fn restore(self, list: &mut ClassList, index: usize) {
+        if list.by_id(self.id).is_none() {
+            list.insert_empty_class(index, self.name.clone(), self.id);
+        }
+
+        let class = list.by_id_mut(self.id).unwrap();
+        class.name = self.name;
+        class.address.set(self.address);
+        class.fields.clear();
+        *class.colors.get_mut() = None;
+        // Base was valid when the snapshot was taken.
+        _ = list.set_base(self.id, self.base);
+
+        let (fields, current_offset) = load_fields(list, self.fields, 0);
+        list.by_id_mut(self.id).unwrap().fields.extend(fields);
+        if current_offset < self.size {
+            list.by_id_mut(self.id)
+                .unwrap()
+                .fields
+                .extend(allocate_padding(self.size - current_offset));
+        }
+        if let Some(colors) = self.colors {
+            colors.load(list.by_id_mut(self.id).unwrap());
+        }
+    }
+}
+
+/// Fields copied from a class, stored like snapshots so every paste creates new fields.
+#[derive(Serialize, Deserialize)]
+pub struct FieldClipboard {
+    fields: Vec<DataField>,
+    count: usize,
+    size: usize,
+}
+
+impl FieldClipboard {
+    pub fn copy(list: &ClassList, fields: &[&dyn Field]) -> Self {
+        let mut datagen = ProjectDataGenerator::new(list);
+        let dynam = &mut &mut datagen as &mut dyn Generator;
+        let data = CodegenData {
+            classes: list.classes(),
+            enums: list.enums(),
+        };
+
+        dynam.begin_class("");
+        for f in fields {
+            if let Some(comment) = f.comment() {
+                dynam.add_comment(&comment);
+            }
+            f.codegen(dynam, &data);
+        }
+
+        Self {
+            fields: datagen.classes.pop().unwrap().fields,
+            count: fields.len(),
+            size: fields.iter().map(|f| f.size()).sum(),
+        }
+    }
+
+    /// Number of copied fields, not counting fields of sections.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Text put into the system clipboard, so fields can be pasted into another session.
+    pub fn to_json(&self) -> String {
+        json::to_string(self).unwrap()
+    }
+
+    /// `None` if the text wasn't copied from fields.
+    pub fn from_json(text: &str) -> Option<Self> {
+        json::from_str(text).ok()
+    }
+
+    /// Creates the copied fields, classes they point to are created if they don't exist anymore.
+    pub fn paste(&self, list: &mut ClassList) -> Vec<Box<dyn Field>> {
+        let (mut fields, end) = load_fields(list, self.fields.clone(), 0);
+        if end < self.size {
+            fields.extend(allocate_padding(self.size - end));
+        }
+        fields
+    }
+}
+
 impl ProjectData {
-    pub fn store(classes: &[Class]) -> Self {
-        let mut datagen = ProjectDataGenerator::default();
+    pub fn store(
+        list: &ClassList,
+        bookmarks: &Bookmarks,
+        named_addresses: &NamedAddressRegistry,
+    ) -> Self {
+        let mut datagen = ProjectDataGenerator::new(list);
         let dynam = &mut &mut datagen as &mut dyn Generator;
-        let data = CodegenData { classes };
+        let classes = list.classes();
+        let data = CodegenData {
+            enums: list.enums(),
+            classes,
+        };
 
         for class in classes {
             dynam.begin_class(&class.name);
             for f in class.fields.iter() {
+                if let Some(comment) = f.comment() {
+                    dynam.add_comment(&comment);
+                }
                 f.codegen(dynam, &data);
             }
             dynam.end_class();
         }
 
+        for (dataclass, class) in datagen.classes.iter_mut().zip(classes) {
+            dataclass.base = list
+                .base_of(class.id())
+                .and_then(|id| list.by_id(id))
+                .map(|c| c.name.clone());
+            dataclass.colors = DataColors::store(class);
+            dataclass.address = class.address.get();
+        }
+
         Self {
             classes: datagen.classes,
+            enums: list.enums().definitions().to_vec(),
+            bookmarks: bookmarks
+                .entries()
+                .iter()
+                .map(|b| DataBookmark {
+                    name: b.name.clone(),
+                    address: b.address,
+                    class: b
+                        .class_id
+                        .and_then(|id| list.by_id(id))
+                        .map(|c| c.name.clone()),
+                })
+                .collect(),
+            named_addresses: named_addresses
+                .entries()
+                .into_iter()
+                .map(|(address, name)| DataNamedAddress {
+                    name: name.to_owned(),
+                    address,
+                })
+                .collect(),
+            pointer_size: list.pointer_size(),
         }
     }
 
-    pub fn load(self) -> ClassList {
-        let mut list = ClassList::EMPTY;
+    pub fn load(self) -> (ClassList, Bookmarks, NamedAddressRegistry) {
+        let mut list = ClassList::empty();
+        list.set_pointer_size(self.pointer_size);
+        list.set_enums(EnumRegistry::from_definitions(self.enums));
 
         self.classes
             .iter()
             .for_each(|cl| _ = list.add_empty_class(cl.name.to_string()));
 
-        self.classes.into_iter().for_each(|mut dataclass| {
-            dataclass.fields.sort_by_key(|f| f.offset);
-
-            let cid = list.by_name(&dataclass.name).unwrap().id();
-            let mut current_offset = 0;
-
-            for DataField {
-                offset: field_offset,
-                name,
-                kind,
-                metadata,
-            } in dataclass.fields
+        for dataclass in self.classes.iter() {
+            if let Some(base) = dataclass
+                .base
+                .as_deref()
+                .and_then(|name| list.by_name(name))
             {
-                let class = list.by_id_mut(cid).unwrap();
-                if field_offset > current_offset {
-                    class
-                        .fields
-                        .extend(allocate_padding(field_offset - current_offset));
-                }
-
-                match kind {
-                    FieldKind::Ptr => {
-                        let classname = metadata.as_deref();
-                        if let Some(refclass) = classname.and_then(|name| list.by_name(name)) {
-                            let refid = refclass.id();
-                            let class = list.by_id_mut(cid).unwrap();
-                            class
-                                .fields
-                                .push(Box::new(PointerField::new_with_class_id(name, refid))
-                                    as Box<dyn Field>);
-                        } else {
-                            let new_cid = list.add_class(
-                                classname
-                                    .map(str::to_owned)
-                                    .unwrap_or_else(|| format!("C{:X}", field_offset)),
-                            );
-                            let class = list.by_id_mut(cid).unwrap();
-                            class
-                                .fields
-                                .push(Box::new(PointerField::new_with_class_id(name, new_cid))
-                                    as Box<dyn Field>);
-                        }
-                    }
-                    other => class.fields.push(other.into_field(Some(name))),
-                }
-
-                current_offset = field_offset + kind.size();
+                let (base, cid) = (base.id(), list.by_name(&dataclass.name).unwrap().id());
+                // Circular inheritance in a hand edited file is dropped.
+                _ = list.set_base(cid, Some(base));
             }
+        }
+
+        self.classes.into_iter().for_each(|dataclass| {
+            let cid = list.by_name(&dataclass.name).unwrap().id();
+            let (fields, current_offset) = load_fields(&mut list, dataclass.fields, 0);
 
-            if current_offset % 8 != 0 {
-                list.by_id_mut(cid)
-                    .unwrap()
+            let class = list.by_id_mut(cid).unwrap();
+            class.fields.extend(fields);
+            if !current_offset.is_multiple_of(8) {
+                class
                     .fields
                     .extend(allocate_padding(8 - (current_offset % 8)));
             }
+            class.address.set(dataclass.address);
+            if let Some(colors) = dataclass.colors {
+                colors.load(class);
+            }
         });
 
-        list
+        let bookmarks = self
+            .bookmarks
+            .into_iter()
+            .map(|b| Bookmark {
+                class_id: b.class.and_then(|name| list.by_name(&name)).map(|c| c.id()),
+                name: b.name,
+                address: b.address,
+            })
+            .collect();
+
+        let named_addresses = NamedAddressRegistry::from_entries(
+            self.named_addresses
+                .into_iter()
+                .map(|n| (n.address, n.name)),
+        );
+
+        (list, Bookmarks::from_entries(bookmarks), named_addresses)
+    }
+
+    pub fn from_str(text: &str, format: ProjectFormat) -> eyre::Result<Self> {
+        Ok(match format {
+            ProjectFormat::Ron => ron::from_str(text)?,
+            ProjectFormat::Json => json::from_str(text)?,
+            ProjectFormat::Yaml => yaml::from_str(text)?,
+        })
+    }
+
+    pub fn to_string(&self, format: ProjectFormat) -> String {
+        match format {
+            ProjectFormat::Ron => ron::to_string(self).unwrap(),
+            ProjectFormat::Json => json::to_string(self).unwrap(),
+            ProjectFormat::Yaml => yaml::to_string(self).unwrap(),
+        }
+    }
+}
+
+/// Serialization of a project file, picked by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectFormat {
+    Ron,
+    /// Indented JSON that is easier to diff and fix by hand.
+    Json,
+    /// Block style YAML with a line per field, it has the same structure as JSON.
+    Yaml,
+}
+
+impl ProjectFormat {
+    pub const EXTENSION: &'static str = "yclass";
+    pub const JSON_EXTENSION: &'static str = "yclsj";
+    pub const YAML_EXTENSION: &'static str = "yclsy";
+
+    pub fn of(path: &Path) -> Self {
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case(Self::JSON_EXTENSION) => Self::Json,
+            Some(ext) if ext.eq_ignore_ascii_case(Self::YAML_EXTENSION) => Self::Yaml,
+            _ => Self::Ron,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ProjectData {
+    /// Project with every field kind, strings that need escaping, an empty class and nested
+    /// arrays and sections, used by round-trip tests of the formats.
+    pub(crate) fn sample() -> Self {
+        let names = [
+            "plain",
+            "",
+            "quote \" and backslash \\",
+            "line\nbreak\ttab\r",
+            "key: value # not a comment",
+            "- item",
+            "null",
+            "true",
+            "123",
+            "-0x10",
+            "  padded  ",
+            "[flow], {map}",
+            "ünïcödé ✓ \u{1}",
+        ];
+        Self::with_names(
+            "Every \"kind\"",
+            ("Flags: \"A|B\"", &[(-1, "All"), (0, ""), (i64::MAX, "Max")]),
+            |i| names[i % names.len()].to_owned(),
+        )
     }
 
-    pub fn from_str(text: &str) -> Option<Self> {
-        ron::from_str(text).ok()
+    /// [`ProjectData::sample`] with names that are valid identifiers, used to compile generated
+    /// code.
+    pub(crate) fn sample_idents() -> Self {
+        Self::with_names(
+            "EveryKind",
+            ("Flags", &[(1, "A"), (2, "B"), (4, "C")]),
+            |i| format!("field{i}"),
+        )
     }
 
-    #[allow(clippy::inherent_to_string)]
-    pub fn to_string(&self) -> String {
-        ron::to_string(self).unwrap()
+    fn with_names(
+        class: &str,
+        (enum_name, variants): (&str, &[(i64, &str)]),
+        name: impl Fn(usize) -> String,
+    ) -> Self {
+        use crate::field::{BitSlice, ColorLayout, GuidFormat, StringAbi, TimestampFormat};
+
+        let kinds = [
+            FieldKind::Unk8,
+            FieldKind::Unk16,
+            FieldKind::Unk32,
+            FieldKind::Unk64,
+            FieldKind::I8,
+            FieldKind::I16,
+            FieldKind::I32,
+            FieldKind::I64,
+            FieldKind::U8,
+            FieldKind::U16,
+            FieldKind::U32,
+            FieldKind::U64,
+            FieldKind::F32,
+            FieldKind::F64,
+            FieldKind::Ptr,
+            FieldKind::StrPtr,
+            FieldKind::WStrPtr,
+            FieldKind::RelPtr32,
+            FieldKind::RelPtr64,
+            FieldKind::FuncPtr,
+            FieldKind::Bool,
+            FieldKind::Vec2,
+            FieldKind::Vec3,
+            FieldKind::Vec4,
+            FieldKind::Mat4,
+            FieldKind::Array(Box::new(FieldKind::U16), 3),
+            FieldKind::Array(Box::new(FieldKind::Array(Box::new(FieldKind::F32), 4)), 2),
+            FieldKind::Enum(4),
+            FieldKind::Bitfield(
+                2,
+                vec![
+                    BitSlice {
+                        name: "low".into(),
+                        bit_offset: 0,
+                        bit_width: 3,
+                    },
+                    BitSlice {
+                        name: name(2),
+                        bit_offset: 3,
+                        bit_width: 13,
+                    },
+                ],
+            ),
+            FieldKind::Flags(1),
+            FieldKind::Vtable(12),
+            FieldKind::Embedded(0x10),
+            FieldKind::PointerChain(vec![0x10, -8, 0]),
+            FieldKind::DynamicArray(Box::new(FieldKind::Array(Box::new(FieldKind::U8), 2))),
+            FieldKind::StdVector(Box::new(FieldKind::Ptr)),
+            FieldKind::StdString(StringAbi::Libcxx),
+            FieldKind::Guid(GuidFormat::Unreal),
+            FieldKind::Timestamp(TimestampFormat::UnixF64),
+            FieldKind::InlineStr(16),
+            FieldKind::InlineWStr(8),
+            FieldKind::Color(ColorLayout::RgbaF32),
+            FieldKind::Section(4),
+        ];
+        // Fails to compile once a kind is added, it has to be added above as well.
+        for kind in &kinds {
+            match kind {
+                FieldKind::Unk8
+                | FieldKind::Unk16
+                | FieldKind::Unk32
+                | FieldKind::Unk64
+                | FieldKind::I8
+                | FieldKind::I16
+                | FieldKind::I32
+                | FieldKind::I64
+                | FieldKind::U8
+                | FieldKind::U16
+                | FieldKind::U32
+                | FieldKind::U64
+                | FieldKind::F32
+                | FieldKind::F64
+                | FieldKind::Ptr
+                | FieldKind::StrPtr
+                | FieldKind::WStrPtr
+                | FieldKind::RelPtr32
+                | FieldKind::RelPtr64
+                | FieldKind::FuncPtr
+                | FieldKind::Bool
+                | FieldKind::Vec2
+                | FieldKind::Vec3
+                | FieldKind::Vec4
+                | FieldKind::Mat4
+                | FieldKind::Array(..)
+                | FieldKind::Enum(_)
+                | FieldKind::Bitfield(..)
+                | FieldKind::Flags(_)
+                | FieldKind::Vtable(_)
+                | FieldKind::Embedded(_)
+                | FieldKind::PointerChain(_)
+                | FieldKind::DynamicArray(_)
+                | FieldKind::StdVector(_)
+                | FieldKind::StdString(_)
+                | FieldKind::Guid(_)
+                | FieldKind::Timestamp(_)
+                | FieldKind::InlineStr(_)
+                | FieldKind::InlineWStr(_)
+                | FieldKind::Color(_)
+                | FieldKind::Section(_) => {}
+            }
+        }
+
+        let mut offset = 0;
+        let fields = kinds
+            .into_iter()
+            .enumerate()
+            .map(|(i, kind)| {
+                let field = DataField {
+                    name: name(i),
+                    offset,
+                    metadata: match kind {
+                        FieldKind::Ptr | FieldKind::Embedded(_) => Some("Empty".into()),
+                        FieldKind::Enum(_) | FieldKind::Flags(_) => Some(enum_name.into()),
+                        _ => None,
+                    },
+                    comment: (i % 3 == 0).then(|| name(i + 5)),
+                    fields: match kind {
+                        FieldKind::Section(_) => vec![DataField {
+                            name: "inner".into(),
+                            offset,
+                            kind: FieldKind::U32,
+                            metadata: None,
+                            comment: Some("nested".into()),
+                            fields: vec![],
+                        }],
+                        _ => vec![],
+                    },
+                    kind,
+                };
+                offset += 8;
+                field
+            })
+            .collect();
+
+        Self {
+            classes: vec![
+                DataClass {
+                    name: "Empty".into(),
+                    fields: vec![],
+                    base: None,
+                    colors: None,
+                    address: 0,
+                },
+                DataClass {
+                    name: class.into(),
+                    fields,
+                    base: Some("Empty".into()),
+                    colors: Some(DataColors::ByKind(vec![
+                        (FieldKind::Array(Box::new(FieldKind::U8), 4), [255, 0, 128]),
+                        (FieldKind::Ptr, [0, 0, 0]),
+                    ])),
+                    address: 0x7FF6_1234_5678,
+                },
+                DataClass {
+                    name: "Solid".into(),
+                    fields: vec![],
+                    base: None,
+                    colors: Some(DataColors::Solid([1, 2, 3])),
+                    address: usize::MAX,
+                },
+            ],
+            enums: vec![EnumDefinition {
+                name: enum_name.into(),
+                size: 4,
+                variants: variants.iter().map(|&(v, n)| (v, n.to_owned())).collect(),
+                flags: true,
+            }],
+            bookmarks: vec![DataBookmark {
+                name: "# bookmark".into(),
+                address: 0xFFFF_FFFF_FFFF_FFF0,
+                class: Some(class.into()),
+            }],
+            named_addresses: vec![DataNamedAddress {
+                name: "Player::vtable".into(),
+                address: 0x1400_0000,
+            }],
+            pointer_size: 4,
+        }
     }
 }
@@ -0,0 +1,87 @@
+use crate::{
+    field::{Field, FieldKind},
+    gui::bytes_to_value,
+    value::Value,
+};
+use std::{collections::VecDeque, fmt::Write, time::Instant};
+
+/// Number of values kept by default, configured with `max_recorded_values`.
+pub const DEFAULT_MAX_RECORDED_VALUES: usize = 10000;
+
+/// Values of a field read every frame while the recording is active.
+pub struct Recording {
+    pub name: String,
+    pub address: usize,
+    pub size: usize,
+    pub kind: FieldKind,
+    pub active: bool,
+    /// Whether the plot window of the recording is shown.
+    pub plotted: bool,
+    pub started: Instant,
+    pub samples: VecDeque<(Instant, Vec<u8>)>,
+}
+
+impl Recording {
+    pub fn new(field: &dyn Field, address: usize) -> Self {
+        Self {
+            name: field.name().unwrap_or_else(|| format!("{address:X}")),
+            address,
+            size: field.size(),
+            kind: field.kind(),
+            active: true,
+            plotted: false,
+            started: Instant::now(),
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn push(&mut self, bytes: Vec<u8>, max_len: usize) {
+        self.samples.push_back((Instant::now(), bytes));
+        while self.samples.len() > max_len {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Only integers, floats and booleans have a numeric value that can be plotted.
+    pub fn is_numeric(&self) -> bool {
+        self.value(&[0; 8]).is_some()
+    }
+
+    pub fn value(&self, bytes: &[u8]) -> Option<Value> {
+        let kind = match self.kind {
+            FieldKind::Bool => FieldKind::U8,
+            FieldKind::I8
+            | FieldKind::I16
+            | FieldKind::I32
+            | FieldKind::I64
+            | FieldKind::U8
+            | FieldKind::U16
+            | FieldKind::U32
+            | FieldKind::U64
+            | FieldKind::F32
+            | FieldKind::F64 => self.kind.clone(),
+            _ => return None,
+        };
+
+        let mut buf = [0; 8];
+        let len = bytes.len().min(8);
+        buf[..len].copy_from_slice(&bytes[..len]);
+        Some(bytes_to_value(&buf, kind))
+    }
+
+    /// Values as `timestamp_ms,value` rows, values that aren't numbers are written as hex bytes.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "timestamp_ms,value\n".to_owned();
+        for (time, bytes) in &self.samples {
+            let ms = time.duration_since(self.started).as_millis();
+            match self.value(bytes) {
+                Some(value) => _ = writeln!(csv, "{ms},{value}"),
+                None => {
+                    let hex = bytes.iter().map(|b| format!("{b:02X}")).collect::<String>();
+                    _ = writeln!(csv, "{ms},{hex}");
+                }
+            }
+        }
+        csv
+    }
+}
@@ -0,0 +1,320 @@
+//! Client of a memory server running on another machine, i.e. a VM or an emulator.
+//! See [`Request`] for the protocol.
+
+mod protocol;
+pub use protocol::*;
+
+use eyre::{bail, eyre, Result};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+/// Delay between attempts to reconnect after the connection was lost.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+/// How long answers of `CanRead` are reused, they're asked for every frame.
+const CAN_READ_TTL: Duration = Duration::from_secs(1);
+const PAGE_SIZE: usize = 0x1000;
+
+pub struct TcpRemoteProcess {
+    connection: Arc<Connection>,
+    pid: u32,
+    pointer_size: usize,
+    // Asked for every frame, so it's only requested once.
+    name: String,
+    /// Readability of pages and when it was asked for.
+    readable: Mutex<HashMap<usize, (bool, Instant)>>,
+}
+
+struct Connection {
+    address: String,
+    // Requests and responses have to stay paired, scans read from several threads.
+    stream: Mutex<TcpStream>,
+    // Set after a failed request until a new connection is made, the stream can't be trusted.
+    broken: AtomicBool,
+}
+
+impl TcpRemoteProcess {
+    /// Connects to `IP:Port`.
+    pub fn connect(address: &str) -> Result<Self> {
+        let connection = Arc::new(Connection {
+            address: address.to_owned(),
+            stream: Mutex::new(open_stream(address)?),
+            broken: AtomicBool::new(false),
+        });
+        let (pid, pointer_size) = connection.info()?;
+        if pointer_size != 4 && pointer_size != 8 {
+            bail!("Unsupported pointer size {pointer_size}");
+        }
+        let name = match connection.call(&Request::Name)? {
+            Response::Name(name) => name,
+            r => bail!("Unexpected response {r:?}"),
+        };
+
+        Ok(Self {
+            connection,
+            pid,
+            pointer_size,
+            name,
+            readable: Mutex::default(),
+        })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+
+    /// `false` while reconnecting after the connection was lost, requests fail until then.
+    pub fn is_connected(&self) -> bool {
+        !self.connection.broken.load(Ordering::Relaxed)
+    }
+
+    fn call(&self, request: &Request) -> Result<Response> {
+        let connection = &self.connection;
+        if connection.broken.load(Ordering::Relaxed) {
+            bail!(
+                "Connection to {} was lost, reconnecting",
+                connection.address
+            );
+        }
+
+        let result = connection.call(request);
+        // Only the request that broke the stream starts reconnecting.
+        if result.as_ref().is_err_and(|e| e.is::<io::Error>())
+            && !connection.broken.swap(true, Ordering::Relaxed)
+        {
+            self.reconnect();
+        }
+        result
+    }
+
+    /// Reconnects on a background thread, the server has to report the same process.
+    fn reconnect(&self) {
+        let connection = Arc::downgrade(&self.connection);
+        let (pid, pointer_size) = (self.pid, self.pointer_size);
+        thread::spawn(move || loop {
+            thread::sleep(RECONNECT_INTERVAL);
+            let Some(connection) = Weak::upgrade(&connection) else {
+                return;
+            };
+
+            let Ok(stream) = open_stream(&connection.address) else {
+                continue;
+            };
+            *connection.stream.lock() = stream;
+            if connection.info().ok() == Some((pid, pointer_size)) {
+                connection.broken.store(false, Ordering::Relaxed);
+                return;
+            }
+        });
+    }
+
+    /// Fails unless the whole buffer was read.
+    pub fn read(&self, address: usize, buf: &mut [u8]) -> Result<()> {
+        match self.call(&Request::Read {
+            address,
+            len: buf.len(),
+        })? {
+            Response::Read(data) if data.len() == buf.len() => {
+                buf.copy_from_slice(&data);
+                Ok(())
+            }
+            Response::Read(data) => bail!("Read {} of {} bytes", data.len(), buf.len()),
+            r => bail!("Unexpected response {r:?}"),
+        }
+    }
+
+    pub fn write(&self, address: usize, data: &[u8]) -> Result<()> {
+        self.call(&Request::Write { address, data }).map(|_| ())
+    }
+
+    /// Answers are cached per page for [`CAN_READ_TTL`].
+    pub fn can_read(&self, address: usize) -> bool {
+        let page = address & !(PAGE_SIZE - 1);
+        if let Some((readable, time)) = self.readable.lock().get(&page) {
+            if time.elapsed() < CAN_READ_TTL {
+                return *readable;
+            }
+        }
+
+        let readable = matches!(
+            self.call(&Request::CanRead { address }),
+            Ok(Response::CanRead(true))
+        );
+        let cache = &mut *self.readable.lock();
+        cache.retain(|_, (_, time)| time.elapsed() < CAN_READ_TTL);
+        cache.insert(page, (readable, Instant::now()));
+        readable
+    }
+
+    pub fn maps(&self) -> Result<Vec<RemoteRegion>> {
+        match self.call(&Request::Maps)? {
+            Response::Maps(regions) => Ok(regions),
+            r => bail!("Unexpected response {r:?}"),
+        }
+    }
+
+    pub fn name(&self) -> Result<String> {
+        Ok(self.name.clone())
+    }
+}
+
+impl Connection {
+    /// Sends the request regardless of `broken`.
+    fn call(&self, request: &Request) -> Result<Response> {
+        let stream = &mut *self.stream.lock();
+        let payload = request.encode();
+        stream.write_all(&(payload.len() as u32).to_le_bytes())?;
+        stream.write_all(&payload)?;
+
+        let mut len = [0; 4];
+        stream.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_MESSAGE_LEN {
+            // The rest of the response is left in the stream.
+            return Err(io::Error::other(format!("Response of {len} bytes is too large")).into());
+        }
+
+        let mut buf = vec![0; len];
+        stream.read_exact(&mut buf)?;
+        Response::decode(request, &buf)
+    }
+
+    fn info(&self) -> Result<(u32, usize)> {
+        match self.call(&Request::Info)? {
+            Response::Info { pid, pointer_size } => Ok((pid, pointer_size)),
+            r => bail!("Unexpected response {r:?}"),
+        }
+    }
+}
+
+fn open_stream(address: &str) -> Result<TcpStream> {
+    let socket = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| eyre!("Couldn't resolve {address}"))?;
+    let stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    stream.set_nodelay(true)?;
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{net::TcpListener, sync::atomic::AtomicUsize};
+
+    /// Serves every connection on its own thread, the first connection is closed on its first
+    /// read. Reads return half of the requested bytes if `short_reads` is set.
+    fn serve(short_reads: bool, can_reads: Arc<AtomicUsize>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        thread::spawn(move || {
+            for (i, stream) in listener.incoming().enumerate() {
+                let (mut stream, can_reads) = (stream.unwrap(), can_reads.clone());
+                thread::spawn(move || loop {
+                    let mut len = [0; 4];
+                    if stream.read_exact(&mut len).is_err() {
+                        return;
+                    }
+                    let mut request = vec![0; u32::from_le_bytes(len) as usize];
+                    stream.read_exact(&mut request).unwrap();
+
+                    let mut response = vec![request[0]];
+                    match request[0] {
+                        0 => {
+                            response.extend(42u32.to_le_bytes());
+                            response.push(8);
+                        }
+                        1 if i == 0 => return,
+                        1 => {
+                            let len = u32::from_le_bytes(request[9..13].try_into().unwrap());
+                            let len = if short_reads { len / 2 } else { len };
+                            response.extend(len.to_le_bytes());
+                            response.extend((0..len).map(|b| b as u8));
+                        }
+                        3 => {
+                            can_reads.fetch_add(1, Ordering::Relaxed);
+                            response.push(1);
+                        }
+                        5 => {
+                            response.extend(4u32.to_le_bytes());
+                            response.extend(b"game");
+                        }
+                        _ => unreachable!(),
+                    }
+                    stream
+                        .write_all(&(response.len() as u32).to_le_bytes())
+                        .unwrap();
+                    stream.write_all(&response).unwrap();
+                });
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn reconnects_after_losing_the_connection() {
+        let address = serve(false, Arc::default());
+        let remote = TcpRemoteProcess::connect(&address).unwrap();
+        assert_eq!((remote.pid(), remote.name().unwrap()), (42, "game".into()));
+
+        let mut buf = [0; 4];
+        assert!(remote.read(0x1000, &mut buf).is_err());
+        assert!(!remote.is_connected());
+        // The name stays available, it's shown while reconnecting.
+        assert_eq!(remote.name().unwrap(), "game");
+
+        let start = Instant::now();
+        while !remote.is_connected() {
+            assert!(start.elapsed() < TIMEOUT * 2, "didn't reconnect");
+            thread::sleep(Duration::from_millis(50));
+        }
+        remote.read(0x1000, &mut buf).unwrap();
+        assert_eq!(buf, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn short_reads_fail() {
+        let address = serve(true, Arc::default());
+        let remote = TcpRemoteProcess::connect(&address).unwrap();
+
+        let mut buf = [0; 8];
+        // The first connection is closed on its first read.
+        assert!(remote.read(0x1000, &mut buf).is_err());
+        while !remote.is_connected() {
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert!(remote.read(0x1000, &mut buf).is_err());
+        // A short response isn't a broken stream.
+        assert!(remote.is_connected());
+    }
+
+    #[test]
+    fn can_read_is_cached_per_page() {
+        let can_reads = Arc::new(AtomicUsize::new(0));
+        let address = serve(false, can_reads.clone());
+        let remote = TcpRemoteProcess::connect(&address).unwrap();
+
+        assert!(remote.can_read(0x1000));
+        assert!(remote.can_read(0x1FF8));
+        assert!(remote.can_read(0x2000));
+        assert_eq!(can_reads.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,154 @@
+use eyre::{bail, eyre, Result};
+
+/// Messages are rejected if they are larger than this, i.e. reads bigger than 16 MiB.
+pub const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// Requests sent by yclass, every message is prefixed with its length as `u32`.
+/// Numbers are little endian, byte arrays and strings are prefixed with their length as `u32`.
+///
+/// | Tag | Request   | Fields                       | Response                                    |
+/// |-----|-----------|------------------------------|---------------------------------------------|
+/// | 0   | `Info`    |                              | `pid: u32, pointer_size: u8`                |
+/// | 1   | `Read`    | `address: u64, len: u32`     | `data: bytes`                               |
+/// | 2   | `Write`   | `address: u64, data: bytes`  |                                             |
+/// | 3   | `CanRead` | `address: u64`               | `readable: u8`                              |
+/// | 4   | `Maps`    |                              | `count: u32`, `start: u64, end: u64, prot: u8` each |
+/// | 5   | `Name`    |                              | `name: string`                              |
+///
+/// Responses start with the tag of the request, or `0xFF` followed by an error string.
+/// Protection has `1` for read, `2` for write and `4` for execute set.
+#[derive(Debug)]
+pub enum Request<'a> {
+    Info,
+    Read { address: usize, len: usize },
+    Write { address: usize, data: &'a [u8] },
+    CanRead { address: usize },
+    Maps,
+    Name,
+}
+
+#[derive(Debug)]
+pub enum Response {
+    Info { pid: u32, pointer_size: usize },
+    Read(Vec<u8>),
+    Write,
+    CanRead(bool),
+    Maps(Vec<RemoteRegion>),
+    Name(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteRegion {
+    pub start: usize,
+    pub end: usize,
+    pub prot: u8,
+}
+
+const ERROR_TAG: u8 = 0xFF;
+
+impl Request<'_> {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Info => 0,
+            Self::Read { .. } => 1,
+            Self::Write { .. } => 2,
+            Self::CanRead { .. } => 3,
+            Self::Maps => 4,
+            Self::Name => 5,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![self.tag()];
+        match self {
+            Self::Read { address, len } => {
+                buf.extend((*address as u64).to_le_bytes());
+                buf.extend((*len as u32).to_le_bytes());
+            }
+            Self::Write { address, data } => {
+                buf.extend((*address as u64).to_le_bytes());
+                buf.extend((data.len() as u32).to_le_bytes());
+                buf.extend_from_slice(data);
+            }
+            Self::CanRead { address } => buf.extend((*address as u64).to_le_bytes()),
+            Self::Info | Self::Maps | Self::Name => {}
+        }
+        buf
+    }
+}
+
+impl Response {
+    /// Decodes the response to `request`, errors reported by the server are returned as `Err`.
+    pub fn decode(request: &Request, buf: &[u8]) -> Result<Self> {
+        let mut reader = Reader { buf };
+
+        let tag = reader.u8()?;
+        if tag == ERROR_TAG {
+            bail!("Remote error: {}", reader.string()?);
+        }
+        if tag != request.tag() {
+            bail!("Expected response to {request:?}, got tag {tag}");
+        }
+
+        Ok(match request {
+            Request::Info => Self::Info {
+                pid: reader.u32()?,
+                pointer_size: reader.u8()? as usize,
+            },
+            Request::Read { .. } => Self::Read(reader.bytes()?.to_vec()),
+            Request::Write { .. } => Self::Write,
+            Request::CanRead { .. } => Self::CanRead(reader.u8()? != 0),
+            Request::Maps => {
+                let count = reader.u32()?;
+                let regions = (0..count)
+                    .map(|_| {
+                        Ok(RemoteRegion {
+                            start: reader.u64()? as usize,
+                            end: reader.u64()? as usize,
+                            prot: reader.u8()?,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Self::Maps(regions)
+            }
+            Request::Name => Self::Name(reader.string()?.to_owned()),
+        })
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < len {
+            return Err(eyre!("Response is too short"));
+        }
+
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<&'a str> {
+        Ok(std::str::from_utf8(self.bytes()?)?)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::process::Process;
+use msvc_demangler::DemangleFlags;
+
+/// Longest mangled type name that is read from a type descriptor.
+const MAX_NAME_LEN: usize = 256;
+
+/// Name of the C++ class of the object from MSVC RTTI, e.g. `ns::Player`. Follows the vtable
+/// pointer at the start of the object to its `RTTICompleteObjectLocator` and from there to the
+/// `TypeDescriptor` with the mangled name.
+pub fn detect_rtti_name(process: &Process, object_address: usize) -> Option<String> {
+    let pointer_size = process.pointer_size();
+    let read_pointer = |address: usize| {
+        let mut buf = [0; 8];
+        process
+            .read_checked(address, &mut buf[..pointer_size])
+            .then(|| usize::from_ne_bytes(buf))
+            .filter(|p| *p != 0)
+    };
+
+    let vtable = read_pointer(object_address)?;
+    let locator = read_pointer(vtable.checked_sub(pointer_size)?)?;
+
+    // signature, offset, cdOffset, pTypeDescriptor, pClassDescriptor and on 64 bit pSelf.
+    let mut raw = [0; 24];
+    if !process.read_checked(locator, &mut raw) {
+        return None;
+    }
+    let dword = |i: usize| u32::from_ne_bytes(raw[i * 4..i * 4 + 4].try_into().unwrap()) as usize;
+    let type_descriptor = match (pointer_size, dword(0)) {
+        // Offsets are relative to the image base, found through the locator's own offset.
+        (8, 1) => locator.checked_sub(dword(5))?.checked_add(dword(3))?,
+        (4, 0) => dword(3),
+        _ => return None,
+    };
+
+    // The name follows the vtable pointer and the spare pointer of the type descriptor.
+    let mut name = [0; MAX_NAME_LEN];
+    process.read(type_descriptor.checked_add(2 * pointer_size)?, &mut name);
+    let len = name.iter().position(|b| *b == 0)?;
+    let mangled = std::str::from_utf8(&name[..len]).ok()?;
+
+    // `.?AV` for classes and `.?AU` for structs, demangled as the name of the vtable.
+    let rest = mangled
+        .strip_prefix(".?AV")
+        .or_else(|| mangled.strip_prefix(".?AU"))?;
+    let demangled =
+        msvc_demangler::demangle(&format!("??_7{rest}6B@"), DemangleFlags::NAME_ONLY).ok()?;
+    demangled.strip_suffix("::`vftable'").map(str::to_owned)
+}
+
+/// Turns a demangled C++ name into a class name, e.g. `TArray<class UObject>` into
+/// `TArray_UObject`.
+pub fn class_name_from_rtti(name: &str) -> String {
+    let name = name.replace("class ", "").replace("struct ", "");
+    let mut ident = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            ident.push(c);
+        } else if !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+    ident.trim_matches('_').to_owned()
+}
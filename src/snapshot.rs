@@ -0,0 +1,107 @@
+use crate::{
+    class::{ClassId, ClassList},
+    field::{walk_fields, FieldId},
+    process::Process,
+};
+use eframe::epaint::Color32;
+use std::collections::HashMap;
+
+/// Raw bytes of a class instance, compared against memory to find fields that changed.
+pub struct Snapshot {
+    pub name: String,
+    pub class_id: ClassId,
+    pub address: usize,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Changed,
+    FromZero,
+    ToZero,
+}
+
+impl Change {
+    pub fn of(old: &[u8], new: &[u8]) -> Option<Self> {
+        if old == new {
+            None
+        } else if old.iter().all(|b| *b == 0) {
+            Some(Self::FromZero)
+        } else if new.iter().all(|b| *b == 0) {
+            Some(Self::ToZero)
+        } else {
+            Some(Self::Changed)
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Changed => Color32::from_rgb(0x55, 0x1E, 0x1E),
+            Self::FromZero => Color32::from_rgb(0x14, 0x48, 0x4C),
+            Self::ToZero => Color32::from_rgb(0x44, 0x44, 0x1A),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Changed => "Changed",
+            Self::FromZero => "Was zero",
+            Self::ToZero => "Became zero",
+        }
+    }
+}
+
+impl Snapshot {
+    /// Reads the whole class including its base classes at `address`.
+    pub fn take(
+        name: String,
+        class_list: &ClassList,
+        class_id: ClassId,
+        address: usize,
+        process: &Process,
+    ) -> Self {
+        let mut bytes = vec![0; class_list.class_size(class_id)];
+        process.read(address, &mut bytes);
+
+        Self {
+            name,
+            class_id,
+            address,
+            bytes,
+        }
+    }
+
+    /// Compares fields of the class at `address` with the snapshot, fields that start past the end
+    /// of the snapshot are skipped.
+    pub fn diff(
+        &self,
+        class_list: &ClassList,
+        address: usize,
+        process: &Process,
+    ) -> HashMap<FieldId, Change> {
+        let mut current = vec![0; self.bytes.len()];
+        process.read(address, &mut current);
+
+        let mut changes = HashMap::new();
+        let mut offset = 0;
+        let classes = class_list
+            .bases_of(self.class_id)
+            .into_iter()
+            .chain([self.class_id]);
+        for class in classes.filter_map(|id| class_list.by_id(id)) {
+            for (start, field) in walk_fields(&class.fields, offset) {
+                let range = start..(start + field.size()).min(self.bytes.len());
+                if range.is_empty() {
+                    continue;
+                }
+
+                if let Some(change) = Change::of(&self.bytes[range.clone()], &current[range]) {
+                    changes.insert(field.id(), change);
+                }
+            }
+            offset += class.fields.iter().map(|f| f.size()).sum::<usize>();
+        }
+
+        changes
+    }
+}
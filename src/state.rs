@@ -1,27 +1,62 @@
 use crate::{
-    class::ClassList, config::YClassConfig, context::Selection, hotkeys::HotkeyManager,
-    process::Process, project::ProjectData,
+    background_read::BackgroundReader,
+    bookmarks::Bookmarks,
+    class::ClassList,
+    config::YClassConfig,
+    context::{FrozenValue, Selection},
+    field::FieldId,
+    history::{Command, History, DEFAULT_HISTORY_DEPTH},
+    hotkeys::HotkeyManager,
+    import::cheat_engine::import_ct,
+    named_addresses::NamedAddressRegistry,
+    process::Process,
+    project::{FieldClipboard, ProjectData, ProjectFormat},
+    recording::{Recording, DEFAULT_MAX_RECORDED_VALUES},
+    snapshot::Snapshot,
+    validate::validate_class,
 };
 use egui_notify::Toasts;
 use parking_lot::RwLock;
 use std::{
     cell::RefCell,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fs,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 pub type StateRef = &'static RefCell<GlobalState>;
+/// Attached process, shared with background scans.
+pub type ProcessSlot = Arc<RwLock<Option<Process>>>;
 
 pub struct GlobalState {
     pub last_opened_project: Option<PathBuf>,
     pub selection: Option<Selection>,
-    pub process: Arc<RwLock<Option<Process>>>,
+    /// Fields selected in addition to `selection`, see [`InspectionContext::multi_selection`].
+    pub multi_selection: HashSet<FieldId>,
+    pub process: ProcessSlot,
+    /// Reads values of fields drawn in the inspector from `process`.
+    pub reader: BackgroundReader,
+    /// Process the inspected class is compared with, read at the same addresses.
+    pub secondary_process: ProcessSlot,
     pub hotkeys: HotkeyManager,
     pub class_list: ClassList,
+    pub history: History,
+    pub bookmarks: Bookmarks,
+    pub named_addresses: NamedAddressRegistry,
+    /// Not saved in the project file.
+    pub frozen: HashMap<FieldId, FrozenValue>,
+    /// Values of fields as they were read the previous frame, used to display deltas.
+    pub previous_values: HashMap<FieldId, Vec<u8>>,
+    pub recordings: HashMap<FieldId, Recording>,
+    pub snapshots: Vec<Snapshot>,
     pub config: YClassConfig,
     pub toasts: Toasts,
+    /// Fields copied with Ctrl-C, not saved in the project file.
+    pub clipboard: Option<FieldClipboard>,
+    /// Set by F2, the selected field or class if no field is selected starts being renamed.
+    pub rename_requested: bool,
     /// `true` means project was just created and contains no useful
     /// information
     pub dummy: bool,
@@ -30,14 +65,27 @@ pub struct GlobalState {
 impl Default for GlobalState {
     fn default() -> Self {
         let config = YClassConfig::load_or_default();
+        let process = ProcessSlot::default();
 
         Self {
             hotkeys: HotkeyManager::default(),
             class_list: ClassList::default(),
+            history: History::new(config.history_depth.unwrap_or(DEFAULT_HISTORY_DEPTH)),
+            bookmarks: Bookmarks::default(),
+            named_addresses: NamedAddressRegistry::default(),
+            frozen: HashMap::new(),
+            previous_values: HashMap::new(),
+            recordings: HashMap::new(),
+            snapshots: vec![],
             last_opened_project: None,
             toasts: Toasts::default(),
-            process: Arc::default(),
+            reader: BackgroundReader::new(process.clone()),
+            process,
+            secondary_process: Arc::default(),
             selection: None,
+            multi_selection: HashSet::new(),
+            clipboard: None,
+            rename_requested: false,
             dummy: true,
             config,
         }
@@ -45,48 +93,380 @@ impl Default for GlobalState {
 }
 
 impl GlobalState {
+    /// Records the state of the class prior to editing it.
+    pub fn record_edit(&mut self, class_id: usize) {
+        if let Some(command) = Command::edit_class(&self.class_list, class_id) {
+            self.history.push(command);
+        }
+    }
+
+    pub fn undo(&mut self) {
+        if self.history.undo(&mut self.class_list) {
+            self.clear_selection();
+            self.dummy = false;
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if self.history.redo(&mut self.class_list) {
+            self.clear_selection();
+            self.dummy = false;
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+        self.multi_selection.clear();
+    }
+
+    /// Ids of the selected field and the multi-selection in the order they appear in the class.
+    pub fn selected_fields(&self) -> Vec<FieldId> {
+        let Some(selection) = self.selection else {
+            return vec![];
+        };
+
+        self.class_list
+            .by_id(selection.container_id)
+            .and_then(|class| class.fields_with(selection.field_id))
+            .map(|fields| {
+                fields
+                    .iter()
+                    .map(|f| f.id())
+                    .filter(|id| *id == selection.field_id || self.multi_selection.contains(id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Copies the selected fields to the clipboard, nothing is copied if no field is selected.
+    pub fn copy_selected_fields(&mut self) {
+        let (Some(selection), ids) = (self.selection, self.selected_fields()) else {
+            return;
+        };
+        let Some(class) = self.class_list.by_id(selection.container_id) else {
+            return;
+        };
+
+        let fields = ids
+            .into_iter()
+            .filter_map(|id| class.field(id))
+            .collect::<Vec<_>>();
+        self.clipboard = Some(FieldClipboard::copy(&self.class_list, &fields));
+    }
+
+    /// Pastes fields copied in another session, nothing happens unless `text` holds copied fields.
+    pub fn paste_fields_from(&mut self, text: &str) {
+        if let Some(clipboard) = FieldClipboard::from_json(text) {
+            self.clipboard = Some(clipboard);
+            self.paste_fields();
+        }
+    }
+
+    /// Inserts fields of the clipboard before the selected field, or at the end of the selected
+    /// class if no field is selected.
+    pub fn paste_fields(&mut self) {
+        let Some(clipboard) = self.clipboard.as_ref() else {
+            return;
+        };
+        let (container_id, field_id) = match self.selection {
+            Some(selection) => (selection.container_id, Some(selection.field_id)),
+            None => match self.class_list.selected() {
+                Some(cid) => (cid, None),
+                None => return,
+            },
+        };
+
+        let edit = Command::edit_class(&self.class_list, container_id);
+        let classes = self.class_list.classes().len();
+        let fields = clipboard.paste(&mut self.class_list);
+        if fields
+            .iter()
+            .filter_map(|f| f.embedded_class())
+            .any(|cid| self.class_list.depends_on(cid, container_id))
+        {
+            // Classes created for pointers of the pasted fields are removed again.
+            let created = self.class_list.classes()[classes..]
+                .iter()
+                .map(|c| c.id())
+                .collect::<Vec<_>>();
+            for id in created {
+                self.class_list.delete_by_id(id);
+            }
+            self.toasts.error("Class can't be embedded into itself");
+            return;
+        }
+
+        let class = self.class_list.by_id_mut(container_id).unwrap();
+        match field_id.and_then(|id| Some((id, class.fields_with_mut(id)?))) {
+            Some((id, list)) => {
+                let pos = list.iter().position(|f| f.id() == id).unwrap();
+                list.splice(pos..pos, fields);
+            }
+            None => class.fields.extend(fields),
+        }
+
+        if let Some(edit) = edit {
+            self.history.push(edit);
+        }
+        self.dummy = false;
+    }
+
+    /// Freezes the selected field, its size is looked up in the class it belongs to.
+    pub fn freeze_selected(&mut self) {
+        let Some(Selection {
+            address,
+            container_id,
+            field_id,
+        }) = self.selection
+        else {
+            return;
+        };
+
+        let process = self.process.read();
+        let Some(process) = process.as_ref() else {
+            return;
+        };
+
+        if process.is_read_only() {
+            self.toasts
+                .error("Can't freeze values of a read-only process");
+            return;
+        }
+
+        if let Some(field) = self
+            .class_list
+            .by_id(container_id)
+            .and_then(|c| c.field(field_id))
+        {
+            self.frozen
+                .insert(field_id, FrozenValue::read(process, address, field.size()));
+        }
+    }
+
+    /// Writes frozen values to the process, values of fields that no longer exist or that failed
+    /// to be written are dropped.
+    pub fn write_frozen(&mut self) {
+        if self.frozen.is_empty() {
+            return;
+        }
+
+        let classes = self.class_list.classes();
+        self.frozen.retain(|id, _| {
+            classes
+                .iter()
+                .any(|c| c.fields.iter().any(|f| f.contains(*id)))
+        });
+
+        if let Some(process) = self.process.read().as_ref() {
+            let verify = self.config.verify_writes();
+            let toasts = &mut self.toasts;
+            self.frozen.retain(|_, frozen| {
+                let result = process.write(frozen.address, &frozen.bytes, verify);
+                if let Err(e) = &result {
+                    toasts.error(format!(
+                        "Failed to write the frozen value at {:X}, it was unfrozen. {e}",
+                        frozen.address
+                    ));
+                }
+                result.is_ok()
+            });
+        }
+    }
+
+    /// Reads a new value of every active recording.
+    pub fn record_values(&mut self) {
+        if self.recordings.is_empty() {
+            return;
+        }
+
+        let max_len = self
+            .config
+            .max_recorded_values
+            .unwrap_or(DEFAULT_MAX_RECORDED_VALUES);
+        if let Some(process) = self.process.read().as_ref() {
+            for recording in self.recordings.values_mut().filter(|r| r.active) {
+                let mut bytes = vec![0; recording.size];
+                process.read(recording.address, &mut bytes);
+                recording.push(bytes, max_len);
+            }
+        }
+    }
+
+    /// Moves the inspector to the bookmark, selecting its class if it still exists.
+    pub fn jump_to_bookmark(&mut self, index: usize) {
+        let Some(bookmark) = self.bookmarks.get(index) else {
+            return;
+        };
+
+        let cid = bookmark
+            .class_id
+            .filter(|id| self.class_list.by_id(*id).is_some())
+            .or(self.class_list.selected());
+        let Some(cid) = cid else {
+            self.toasts.error("Select a class first");
+            return;
+        };
+
+        self.class_list
+            .by_id(cid)
+            .unwrap()
+            .address
+            .set(bookmark.address);
+        *self.class_list.selected_mut() = Some(cid);
+        self.clear_selection();
+    }
+
     pub fn save_project_as(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
+        if let Some(path) = project_dialog()
             .set_title("Save current project")
-            .add_filter("YClass project", &["yclass"])
             .save_file()
         {
             self.save_project(Some(&path));
         }
     }
 
+    /// Saves the project in JSON format next to the current one, the JSON file is opened after.
+    pub fn save_project_as_json(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .set_title("Save current project as JSON")
+            .add_filter("YClass JSON project", &[ProjectFormat::JSON_EXTENSION]);
+        if let Some(last) = &self.last_opened_project {
+            if let Some(dir) = last.parent() {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(stem) = last.file_stem().and_then(|s| s.to_str()) {
+                dialog = dialog.set_file_name(&format!("{stem}.{}", ProjectFormat::JSON_EXTENSION));
+            }
+        }
+
+        if let Some(mut path) = dialog.save_file() {
+            if ProjectFormat::of(&path) != ProjectFormat::Json {
+                path.set_extension(ProjectFormat::JSON_EXTENSION);
+            }
+            self.save_project(Some(&path));
+        }
+    }
+
+    /// Adds classes from the memory records of a Cheat Engine table picked in a dialog.
+    pub fn import_cheat_table(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Import Cheat Engine table")
+            .add_filter("Cheat Engine table", &["ct"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        match import_ct(&path, &mut self.class_list) {
+            Ok(import) => {
+                let file = path.file_name().unwrap_or_default().to_string_lossy();
+                self.toasts.success(format!(
+                    "Imported {} classes, {} fields from {file}",
+                    import.classes.len(),
+                    import.fields
+                ));
+                *self.class_list.selected_mut() = import.classes.last().copied();
+                self.clear_selection();
+                self.history.push(Command::Batch(
+                    import.classes.into_iter().map(Command::AddClass).collect(),
+                ));
+                self.dummy = false;
+            }
+            Err(e) => {
+                self.toasts.error(e.to_string());
+            }
+        }
+    }
+
     pub fn save_project(&mut self, path: Option<&Path>) {
         if let Some(path) = path {
-            let pd = ProjectData::store(self.class_list.classes()).to_string();
+            let pd = ProjectData::store(&self.class_list, &self.bookmarks, &self.named_addresses)
+                .to_string(ProjectFormat::of(path));
             if let Err(e) = fs::write(path, pd.as_bytes()) {
                 self.toasts
                     .error(format!("Failed to save the project. {e}"));
             } else {
                 self.last_opened_project = Some(path.to_owned());
                 self.dummy = false;
+                self.on_project_saved();
             }
         } else if let Some(ref last) = self.last_opened_project {
-            let pd = ProjectData::store(self.class_list.classes()).to_string();
+            let pd = ProjectData::store(&self.class_list, &self.bookmarks, &self.named_addresses)
+                .to_string(ProjectFormat::of(last));
             if let Err(e) = fs::write(last, pd.as_bytes()) {
                 self.toasts
                     .error(format!("Failed to save the project. {e}"));
             } else {
                 self.last_opened_project = Some(last.to_owned());
                 self.dummy = false;
+                self.on_project_saved();
             }
-        } else if let Some(path) = rfd::FileDialog::new()
+        } else if let Some(path) = project_dialog()
             .set_title("Save current project")
-            .add_filter("YClass project", &["yclass"])
             .save_file()
         {
             self.save_project(Some(&path));
         }
     }
 
+    /// Saves the project without asking for a path, unnamed projects are saved to a temporary file.
+    pub fn auto_save(&mut self) {
+        if self.dummy {
+            return;
+        }
+
+        let (path, message) = match &self.last_opened_project {
+            Some(path) => (path.clone(), "Auto-saved.".to_owned()),
+            None => {
+                let path = std::env::temp_dir().join("yclass_autosave.yclass");
+                let message = format!(
+                    "Auto-saved to {}, use \"Save project as\" to keep it.",
+                    path.display()
+                );
+                (path, message)
+            }
+        };
+
+        let pd = ProjectData::store(&self.class_list, &self.bookmarks, &self.named_addresses)
+            .to_string(ProjectFormat::of(&path));
+        if let Err(e) = fs::write(&path, pd.as_bytes()) {
+            self.toasts
+                .error(format!("Failed to auto-save the project. {e}"));
+        } else {
+            self.toasts
+                .info(message)
+                .duration(Some(Duration::from_secs(2)));
+        }
+    }
+
+    fn on_project_saved(&mut self) {
+        if let Some(path) = self.last_opened_project.clone() {
+            self.config
+                .add_recent_project(&path, self.class_list.classes().len());
+            self.config.save();
+        }
+
+        // Saved anyway, the layout may be unfinished on purpose.
+        let problems = self
+            .class_list
+            .classes()
+            .iter()
+            .map(|c| validate_class(&self.class_list, c).len())
+            .sum::<usize>();
+        if problems > 0 {
+            self.toasts.warning(format!(
+                "Saved with {problems} layout problems, see `Edit > Validate all`"
+            ));
+        }
+
+        if self.config.clear_history_on_save.unwrap_or(false) {
+            self.history.clear();
+        }
+    }
+
     pub fn open_project(&mut self) -> bool {
-        if let Some(path) = rfd::FileDialog::new()
+        if let Some(path) = project_dialog()
             .set_title("Open existing project")
-            .add_filter("YClass project", &["yclass"])
             .pick_file()
         {
             self.open_project_path(&path)
@@ -95,32 +475,67 @@ impl GlobalState {
         }
     }
 
+    /// Opens the project opened or saved last time, unless it's turned off in the config.
+    pub fn reopen_last_project(&mut self) {
+        if !self.config.reopen_last_project() {
+            return;
+        }
+        let Some(path) = self
+            .config
+            .recent_projects()
+            .first()
+            .map(|p| p.path.clone())
+        else {
+            return;
+        };
+
+        if path.exists() {
+            self.open_project_path(&path);
+        } else {
+            self.toasts.warning("Last project not found");
+        }
+    }
+
+    /// Opens the N-th of the recent projects, it's forgotten if it can't be opened.
+    pub fn open_recent_project(&mut self, i: usize) -> bool {
+        let Some(path) = self.config.recent_projects().get(i).map(|p| p.path.clone()) else {
+            return false;
+        };
+
+        if self.open_project_path(&path) {
+            true
+        } else {
+            self.config.remove_recent_project(&path);
+            self.config.save();
+            false
+        }
+    }
+
     pub fn open_project_path(&mut self, path: &Path) -> bool {
         if !self.class_list.classes().is_empty() && !self.dummy {
             self.save_project(None);
         }
 
         match fs::read_to_string(path) {
-            Ok(data) => {
-                if let Some(pd) = ProjectData::from_str(&data) {
-                    self.class_list = pd.load();
+            Ok(data) => match ProjectData::from_str(&data, ProjectFormat::of(path)) {
+                Ok(pd) => {
+                    (self.class_list, self.bookmarks, self.named_addresses) = pd.load();
+                    self.history.clear();
                     self.dummy = false;
                     self.last_opened_project = Some(path.to_path_buf());
 
-                    if let Some(recent) = self.config.recent_projects.as_mut() {
-                        recent.insert(path.to_path_buf());
-                    } else {
-                        self.config.recent_projects =
-                            Some(HashSet::from_iter([path.to_path_buf()]));
-                    }
+                    self.config
+                        .add_recent_project(path, self.class_list.classes().len());
                     self.config.save();
 
                     true
-                } else {
-                    self.toasts.error("Project file is in invalid format");
+                }
+                Err(e) => {
+                    self.toasts
+                        .error(format!("Project file is in invalid format. {e}"));
                     false
                 }
-            }
+            },
             Err(e) => {
                 self.toasts
                     .error(format!("Failed to open the project. {e}"));
@@ -129,3 +544,18 @@ impl GlobalState {
         }
     }
 }
+
+/// File dialog that shows projects of both formats.
+fn project_dialog() -> rfd::FileDialog {
+    rfd::FileDialog::new()
+        .add_filter(
+            "YClass project",
+            &[
+                ProjectFormat::EXTENSION,
+                ProjectFormat::JSON_EXTENSION,
+                ProjectFormat::YAML_EXTENSION,
+            ],
+        )
+        .add_filter("YClass JSON project", &[ProjectFormat::JSON_EXTENSION])
+        .add_filter("YClass YAML project", &[ProjectFormat::YAML_EXTENSION])
+}
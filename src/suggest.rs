@@ -0,0 +1,62 @@
+use crate::{field::FieldKind, process::Process};
+
+/// Bytes read at a pointer to check if it points to a string.
+const STRING_PROBE: usize = 32;
+/// Printable characters a string needs before its terminator.
+const MIN_STRING_LEN: usize = 4;
+
+/// Guesses the kind of an unknown field at `address` from its `bytes`, only returns kinds the
+/// value is very likely to be of. Zeroes and values that fit several kinds are left unknown.
+pub fn suggest_field_type(bytes: &[u8], address: usize, process: &Process) -> Option<FieldKind> {
+    let pointer_size = process.pointer_size();
+    if bytes.len() == pointer_size && address.is_multiple_of(pointer_size) {
+        let mut raw = [0; 8];
+        raw[..bytes.len()].copy_from_slice(bytes);
+        let pointer = usize::from_ne_bytes(raw);
+
+        if pointer != 0 && process.can_read(pointer) {
+            let mut target = [0; STRING_PROBE];
+            process.read(pointer, &mut target);
+            return Some(if is_c_string(&target) {
+                FieldKind::StrPtr
+            } else if is_wide_string(&target) {
+                FieldKind::WStrPtr
+            } else {
+                FieldKind::Ptr
+            });
+        }
+    }
+
+    match *bytes {
+        [a, b, c, d] if address.is_multiple_of(4) => {
+            is_plausible_float(f32::from_ne_bytes([a, b, c, d]) as f64).then_some(FieldKind::F32)
+        }
+        [a, b, c, d, e, f, g, h] if address.is_multiple_of(8) => {
+            is_plausible_float(f64::from_ne_bytes([a, b, c, d, e, f, g, h]))
+                .then_some(FieldKind::F64)
+        }
+        _ => None,
+    }
+}
+
+/// Integers and flags read as floats are denormal or far out of this range.
+fn is_plausible_float(value: f64) -> bool {
+    value.is_normal() && (1e-4..=1e6).contains(&value.abs())
+}
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' '
+}
+
+fn is_c_string(bytes: &[u8]) -> bool {
+    let len = bytes.iter().take_while(|b| is_printable(**b)).count();
+    len >= MIN_STRING_LEN && bytes.get(len) == Some(&0)
+}
+
+fn is_wide_string(bytes: &[u8]) -> bool {
+    let len = bytes
+        .chunks_exact(2)
+        .take_while(|c| is_printable(c[0]) && c[1] == 0)
+        .count();
+    len >= MIN_STRING_LEN && bytes.get(len * 2..len * 2 + 2) == Some(&[0, 0])
+}
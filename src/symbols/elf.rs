@@ -0,0 +1,202 @@
+//! Reader of ELF symbol tables, both `.symtab` and `.dynsym` are read.
+
+use super::{SortedSymbols, SymbolStore};
+use eyre::{bail, eyre, Result};
+use std::{fs, path::Path};
+
+const MAGIC: &[u8] = b"\x7fELF";
+const PT_LOAD: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+
+/// Function and object symbols of an ELF shared library or executable, DWARF line info isn't
+/// read.
+pub struct ElfSymbolStore(SortedSymbols);
+
+impl ElfSymbolStore {
+    pub fn load(path: &Path) -> Result<Self> {
+        read_symbols(&fs::read(path)?).map(|symbols| Self(SortedSymbols::new(symbols)))
+    }
+}
+
+impl SymbolStore for ElfSymbolStore {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn resolve(&self, rva: usize) -> Option<(&str, usize)> {
+        self.0.resolve(rva)
+    }
+}
+
+/// Reads integers of the file's class and byte order.
+struct Reader<'a> {
+    data: &'a [u8],
+    is_64: bool,
+    is_le: bool,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N]> {
+        offset
+            .checked_add(N)
+            .and_then(|end| self.data.get(offset..end))
+            .map(|b| b.try_into().unwrap())
+            .ok_or_else(|| eyre!("ELF file is truncated"))
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8> {
+        Ok(self.bytes::<1>(offset)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16> {
+        let b = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u16::from_le_bytes(b)
+        } else {
+            u16::from_be_bytes(b)
+        })
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32> {
+        let b = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+
+    fn u64(&self, offset: usize) -> Result<u64> {
+        let b = self.bytes(offset)?;
+        Ok(if self.is_le {
+            u64::from_le_bytes(b)
+        } else {
+            u64::from_be_bytes(b)
+        })
+    }
+
+    /// Address or offset sized field, `offset` is picked by the class of the file.
+    fn word(&self, offset32: usize, offset64: usize) -> Result<usize> {
+        if self.is_64 {
+            Ok(self.u64(offset64)? as usize)
+        } else {
+            Ok(self.u32(offset32)? as usize)
+        }
+    }
+
+    fn string(&self, offset: usize) -> Result<String> {
+        let data = self
+            .data
+            .get(offset..)
+            .ok_or_else(|| eyre!("ELF file is truncated"))?;
+        let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+        Ok(String::from_utf8_lossy(&data[..len]).into_owned())
+    }
+}
+
+struct Section {
+    kind: u32,
+    offset: usize,
+    size: usize,
+    link: usize,
+    entry_size: usize,
+}
+
+/// Reads defined function and object symbols as `(RVA, name)` pairs. RVAs are relative to the
+/// lowest loaded segment, which is where the module base is.
+fn read_symbols(data: &[u8]) -> Result<Vec<(usize, String)>> {
+    if !data.starts_with(MAGIC) {
+        bail!("Not an ELF file");
+    }
+
+    let r = Reader {
+        data,
+        // EI_CLASS and EI_DATA
+        is_64: match data.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => bail!("Unknown ELF class"),
+        },
+        is_le: data.get(5) != Some(&2),
+    };
+
+    let ph_offset = r.word(0x1C, 0x20)?;
+    let sh_offset = r.word(0x20, 0x28)?;
+    let (ph_size, ph_count, sh_size, sh_count) = if r.is_64 {
+        (r.u16(0x36)?, r.u16(0x38)?, r.u16(0x3A)?, r.u16(0x3C)?)
+    } else {
+        (r.u16(0x2A)?, r.u16(0x2C)?, r.u16(0x2E)?, r.u16(0x30)?)
+    };
+
+    let mut base = usize::MAX;
+    for i in 0..ph_count as usize {
+        let header = ph_offset + i * ph_size as usize;
+        if r.u32(header)? != PT_LOAD {
+            continue;
+        }
+
+        let vaddr = r.word(header + 8, header + 16)?;
+        let align = r.word(header + 28, header + 48)?.max(1);
+        base = base.min(vaddr & !(align - 1));
+    }
+    if base == usize::MAX {
+        base = 0;
+    }
+
+    let sections = (0..sh_count as usize)
+        .map(|i| {
+            let header = sh_offset + i * sh_size as usize;
+            Ok(Section {
+                kind: r.u32(header + 4)?,
+                offset: r.word(header + 16, header + 24)?,
+                size: r.word(header + 20, header + 32)?,
+                link: r.u32(header + if r.is_64 { 40 } else { 24 })? as usize,
+                entry_size: r.word(header + 36, header + 56)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut symbols = vec![];
+    for table in sections
+        .iter()
+        .filter(|s| matches!(s.kind, SHT_SYMTAB | SHT_DYNSYM) && s.entry_size > 0)
+    {
+        let Some(strings) = sections.get(table.link) else {
+            continue;
+        };
+
+        for i in 0..table.size / table.entry_size {
+            let symbol = table.offset + i * table.entry_size;
+            let (name, value, info, section) = if r.is_64 {
+                (
+                    r.u32(symbol)?,
+                    r.u64(symbol + 8)? as usize,
+                    r.u8(symbol + 4)?,
+                    r.u16(symbol + 6)?,
+                )
+            } else {
+                (
+                    r.u32(symbol)?,
+                    r.u32(symbol + 4)? as usize,
+                    r.u8(symbol + 12)?,
+                    r.u16(symbol + 14)?,
+                )
+            };
+
+            // Undefined symbols are imports of other modules.
+            if section == 0 || value < base || !matches!(info & 0xF, STT_FUNC | STT_OBJECT) {
+                continue;
+            }
+
+            let name = r.string(strings.offset + name as usize)?;
+            if !name.is_empty() {
+                symbols.push((value - base, name));
+            }
+        }
+    }
+
+    Ok(symbols)
+}
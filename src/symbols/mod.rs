@@ -0,0 +1,213 @@
+//! Symbols of modules loaded from PDB or ELF files, imported in `Process > Import symbols`.
+
+mod elf;
+pub use elf::*;
+mod pdb;
+pub use pdb::*;
+
+use eframe::egui::Context;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+    thread,
+};
+
+/// Number of cached resolutions, vtables resolve every function each frame.
+const CACHE_CAPACITY: usize = 4096;
+
+type LoadedStore = Result<Arc<dyn SymbolStore>, String>;
+
+/// Loaded symbols by lowercase module name, or why they couldn't be loaded.
+static STORES: RwLock<BTreeMap<String, LoadedStore>> = RwLock::new(BTreeMap::new());
+/// Resolutions by process id and address, checked against the module at the address.
+static CACHE: LazyLock<Mutex<LruCache<(u32, usize), ResolvedSymbol>>> =
+    LazyLock::new(|| Mutex::new(LruCache::new(CACHE_CAPACITY)));
+
+/// Symbols of a single module.
+pub trait SymbolStore: Send + Sync {
+    fn len(&self) -> usize;
+
+    /// Closest symbol at or before `rva` and the distance to it.
+    fn resolve(&self, rva: usize) -> Option<(&str, usize)>;
+
+    /// Source file and line of the code at `rva`.
+    fn line(&self, _rva: usize) -> Option<(&str, u32)> {
+        None
+    }
+}
+
+/// Loads a PDB file or an ELF file, depending on the extension of `path`.
+pub fn load_store(path: &Path) -> eyre::Result<Arc<dyn SymbolStore>> {
+    let is_pdb = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdb"));
+    Ok(if is_pdb {
+        Arc::new(PdbSymbolStore::load(path)?)
+    } else {
+        Arc::new(ElfSymbolStore::load(path)?)
+    })
+}
+
+/// Symbols sorted by their RVA.
+struct SortedSymbols {
+    symbols: Vec<(usize, String)>,
+}
+
+impl SortedSymbols {
+    fn new(mut symbols: Vec<(usize, String)>) -> Self {
+        symbols.sort_by_key(|(rva, _)| *rva);
+        symbols.dedup_by_key(|(rva, _)| *rva);
+        Self { symbols }
+    }
+
+    fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    fn resolve(&self, rva: usize) -> Option<(&str, usize)> {
+        let i = self.symbols.partition_point(|(start, _)| *start <= rva);
+        let (start, name) = &self.symbols[i.checked_sub(1)?];
+        Some((name, rva - start))
+    }
+}
+
+/// Address resolved to the module it falls in and the closest symbol if the module has symbols.
+#[derive(Debug, Clone)]
+pub struct ResolvedSymbol {
+    pub module: String,
+    pub rva: usize,
+    pub symbol: Option<(String, usize)>,
+    /// Source file of the code at the address if the module has line info.
+    pub source_file: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl ResolvedSymbol {
+    /// `module+rva` followed by the source line if it's known, shown in tooltips.
+    pub fn details(&self) -> String {
+        let mut details = format!("{}+{:X}", self.module, self.rva);
+        if let (Some(file), Some(line)) = (&self.source_file, self.line) {
+            details.push_str(&format!("\n{file}:{line}"));
+        }
+        details
+    }
+}
+
+impl fmt::Display for ResolvedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.symbol {
+            Some((name, 0)) => write!(f, "{}!{name}", self.module),
+            Some((name, offset)) => write!(f, "{}!{name}+{offset:X}", self.module),
+            None => write!(f, "{}+{:X}", self.module, self.rva),
+        }
+    }
+}
+
+/// Loads symbols of `module` from `path` in the background, replacing ones loaded before.
+pub fn load(ctx: &Context, module: &str, path: PathBuf) {
+    let ctx = ctx.clone();
+    let module = module.to_lowercase();
+    STORES.write().remove(&module);
+
+    thread::spawn(move || {
+        let store = load_store(&path).map_err(|e| e.to_string());
+        STORES.write().insert(module, store);
+        CACHE.lock().clear();
+        ctx.request_repaint();
+    });
+}
+
+pub fn unload(module: &str) {
+    STORES.write().remove(&module.to_lowercase());
+    CACHE.lock().clear();
+}
+
+/// Number of symbols of `module` or the error that occurred while loading them, `None` while
+/// they are loading.
+pub fn status(module: &str) -> Option<Result<usize, String>> {
+    STORES
+        .read()
+        .get(&module.to_lowercase())
+        .map(|store| store.as_ref().map(|s| s.len()).map_err(Clone::clone))
+}
+
+/// Resolves `rva` of the module `module` of the process `pid`.
+pub fn resolve(pid: u32, module: &str, address: usize, rva: usize) -> ResolvedSymbol {
+    // Another module may be loaded at the address by now, i.e. in a different minidump.
+    if let Some(symbol) = CACHE
+        .lock()
+        .get(&(pid, address))
+        .filter(|s| s.module == module && s.rva == rva)
+    {
+        return symbol.clone();
+    }
+
+    let (symbol, source) = match STORES.read().get(&module.to_lowercase()) {
+        Some(Ok(store)) => (
+            store
+                .resolve(rva)
+                .map(|(name, offset)| (name.to_owned(), offset)),
+            store.line(rva).map(|(file, line)| (file.to_owned(), line)),
+        ),
+        _ => (None, None),
+    };
+    let (source_file, line) = source.unzip();
+    let symbol = ResolvedSymbol {
+        module: module.to_owned(),
+        rva,
+        symbol,
+        source_file,
+        line,
+    };
+
+    CACHE.lock().insert((pid, address), symbol.clone());
+    symbol
+}
+
+/// Map that drops the least recently used entry once it's full.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(value)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.tick += 1;
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
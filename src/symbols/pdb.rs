@@ -0,0 +1,424 @@
+//! Minimal reader of PDB files, only public symbols and the line info of modules are read.
+//! Layout of the file is described in <https://llvm.org/docs/PDB/index.html>.
+
+use super::{SortedSymbols, SymbolStore};
+use eyre::{bail, eyre, Result};
+use std::{collections::HashMap, fs, path::Path};
+
+const MAGIC: &[u8] = b"Microsoft C/C++ MSF 7.00\r\n\x1aDS\0\0\0";
+const INFO_STREAM: usize = 1;
+const INFO_HEADER_SIZE: usize = 28;
+const DBI_STREAM: usize = 3;
+const DBI_HEADER_SIZE: usize = 64;
+const MODULE_INFO_SIZE: usize = 64;
+/// Index of the section header stream in the optional debug header of the DBI stream.
+const SECTION_HEADER_STREAM: usize = 5;
+const SECTION_HEADER_SIZE: usize = 40;
+const NO_STREAM: u16 = 0xFFFF;
+const S_PUB32: u16 = 0x110E;
+const DEBUG_S_LINES: u32 = 0xF2;
+const DEBUG_S_FILECHKSMS: u32 = 0xF4;
+/// Lines from `0xF00F00` on mark code without a source line.
+const MAX_LINE: u32 = 0xF00F00;
+
+fn slice(buf: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| buf.get(offset..end))
+        .ok_or_else(|| eyre!("PDB file is truncated"))
+}
+
+fn u16_at(buf: &[u8], offset: usize) -> Result<u16> {
+    Ok(u16::from_le_bytes(
+        slice(buf, offset, 2)?.try_into().unwrap(),
+    ))
+}
+
+fn u32_at(buf: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(
+        slice(buf, offset, 4)?.try_into().unwrap(),
+    ))
+}
+
+/// Null terminated string at `offset`.
+fn str_at(buf: &[u8], offset: usize) -> Result<&[u8]> {
+    let s = buf
+        .get(offset..)
+        .ok_or_else(|| eyre!("PDB file is truncated"))?;
+    Ok(&s[..s.iter().position(|b| *b == 0).unwrap_or(s.len())])
+}
+
+/// Multi-stream file, the container of PDB streams.
+struct Msf<'a> {
+    data: &'a [u8],
+    block_size: usize,
+    /// Size and blocks of every stream, `None` for deleted streams.
+    streams: Vec<Option<(usize, Vec<usize>)>>,
+}
+
+impl<'a> Msf<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        if !data.starts_with(MAGIC) {
+            bail!("Not a PDB file");
+        }
+
+        let block_size = u32_at(data, 32)? as usize;
+        if !block_size.is_power_of_two() {
+            bail!("Invalid block size {block_size}");
+        }
+        let directory_size = u32_at(data, 44)? as usize;
+        let block_map = u32_at(data, 52)? as usize;
+
+        let mut msf = Self {
+            data,
+            block_size,
+            streams: vec![],
+        };
+
+        let directory_blocks = (0..directory_size.div_ceil(block_size))
+            .map(|i| Ok(u32_at(msf.block(block_map)?, i * 4)? as usize))
+            .collect::<Result<Vec<_>>>()?;
+        let directory = msf.read(directory_size, &directory_blocks)?;
+
+        let count = u32_at(&directory, 0)? as usize;
+        let mut offset = 4 + count * 4;
+        for i in 0..count {
+            let size = u32_at(&directory, 4 + i * 4)?;
+            if size == u32::MAX {
+                msf.streams.push(None);
+                continue;
+            }
+
+            let size = size as usize;
+            let blocks = (0..size.div_ceil(block_size))
+                .map(|j| Ok(u32_at(&directory, offset + j * 4)? as usize))
+                .collect::<Result<Vec<_>>>()?;
+            offset += blocks.len() * 4;
+            msf.streams.push(Some((size, blocks)));
+        }
+
+        Ok(msf)
+    }
+
+    fn block(&self, index: usize) -> Result<&'a [u8]> {
+        slice(self.data, index * self.block_size, self.block_size)
+    }
+
+    fn read(&self, size: usize, blocks: &[usize]) -> Result<Vec<u8>> {
+        let mut buf = Vec::with_capacity(blocks.len() * self.block_size);
+        for block in blocks {
+            buf.extend_from_slice(self.block(*block)?);
+        }
+        buf.truncate(size);
+        Ok(buf)
+    }
+
+    fn stream(&self, index: usize) -> Result<Vec<u8>> {
+        match self.streams.get(index) {
+            Some(Some((size, blocks))) => self.read(*size, blocks),
+            _ => bail!("PDB file doesn't contain stream {index}"),
+        }
+    }
+}
+
+/// Public symbols of a PDB file and source lines of its modules, names are not demangled.
+pub struct PdbSymbolStore {
+    symbols: SortedSymbols,
+    lines: LineTable,
+}
+
+impl PdbSymbolStore {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read(path)?;
+        let msf = Msf::parse(&data)?;
+        let dbi = msf.stream(DBI_STREAM)?;
+        let sections = read_sections(&msf, &dbi)?;
+
+        Ok(Self {
+            symbols: SortedSymbols::new(read_publics(&msf, &dbi, &sections)?),
+            lines: read_lines(&msf, &dbi, &sections)?,
+        })
+    }
+}
+
+impl SymbolStore for PdbSymbolStore {
+    fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    fn resolve(&self, rva: usize) -> Option<(&str, usize)> {
+        self.symbols.resolve(rva)
+    }
+
+    fn line(&self, rva: usize) -> Option<(&str, u32)> {
+        self.lines.resolve(rva)
+    }
+}
+
+/// Reads virtual addresses of sections, symbols and lines are stored as section index and offset.
+fn read_sections(msf: &Msf, dbi: &[u8]) -> Result<Vec<u32>> {
+    if dbi.len() < DBI_HEADER_SIZE {
+        bail!("PDB file has no DBI stream");
+    }
+
+    // Module info, section contributions, section map, file info, type server map and EC
+    // substreams come before the optional debug header.
+    let mut offset = DBI_HEADER_SIZE;
+    for header_offset in [24, 28, 32, 36, 40, 52] {
+        offset += u32_at(dbi, header_offset)? as usize;
+    }
+    let sections_stream = u16_at(dbi, offset + SECTION_HEADER_STREAM * 2)?;
+    if sections_stream == NO_STREAM {
+        bail!("PDB file has no section headers");
+    }
+
+    msf.stream(sections_stream as usize)?
+        .chunks_exact(SECTION_HEADER_SIZE)
+        .map(|header| u32_at(header, 12))
+        .collect()
+}
+
+/// RVA of `offset` into the section with the 1 based `index`.
+fn section_rva(sections: &[u32], index: u16, offset: u32) -> Option<u32> {
+    let address = sections.get((index as usize).checked_sub(1)?)?;
+    Some(address.wrapping_add(offset))
+}
+
+/// Reads public symbols as `(RVA, name)` pairs.
+fn read_publics(msf: &Msf, dbi: &[u8], sections: &[u32]) -> Result<Vec<(usize, String)>> {
+    let records_stream = u16_at(dbi, 20)?;
+    if records_stream == NO_STREAM {
+        bail!("PDB file has no symbol records");
+    }
+
+    let records = msf.stream(records_stream as usize)?;
+    let mut symbols = vec![];
+    let mut offset = 0;
+    while offset + 4 <= records.len() {
+        let len = u16_at(&records, offset)? as usize;
+        let kind = u16_at(&records, offset + 2)?;
+        let record = slice(&records, offset + 4, len.saturating_sub(2))?;
+        offset += 2 + len;
+
+        if kind != S_PUB32 {
+            continue;
+        }
+
+        let symbol_offset = u32_at(record, 4)?;
+        let section = u16_at(record, 8)?;
+        let name = str_at(record, 10.min(record.len()))?;
+        if let Some(rva) = section_rva(sections, section, symbol_offset) {
+            symbols.push((rva as usize, String::from_utf8_lossy(name).into_owned()));
+        }
+    }
+
+    Ok(symbols)
+}
+
+/// Source lines of code, read from the C13 line info of every module.
+#[derive(Default)]
+struct LineTable {
+    files: Vec<String>,
+    /// Start and end RVA, index of the file and line of the code, sorted by the start.
+    lines: Vec<(u32, u32, usize, u32)>,
+}
+
+impl LineTable {
+    fn resolve(&self, rva: usize) -> Option<(&str, u32)> {
+        let rva = u32::try_from(rva).ok()?;
+        let i = self.lines.partition_point(|(start, ..)| *start <= rva);
+        let (_, end, file, line) = self.lines[i.checked_sub(1)?];
+        (rva < end && line < MAX_LINE).then(|| (self.files[file].as_str(), line))
+    }
+
+    /// Adds lines of the C13 subsections of a module, `names` is the string table file names
+    /// point into.
+    fn add_module(&mut self, c13: &[u8], sections: &[u32], names: &[u8]) -> Result<()> {
+        let subsections = || {
+            let mut offset = 0;
+            std::iter::from_fn(move || {
+                let kind = u32_at(c13, offset).ok()?;
+                let len = u32_at(c13, offset + 4).ok()? as usize;
+                let data = slice(c13, offset + 8, len).ok()?;
+                offset = (offset + 8 + len).next_multiple_of(4);
+                Some((kind, data))
+            })
+        };
+
+        // Blocks of lines refer to their file by the offset of its checksum entry.
+        let Some((_, checksums)) = subsections().find(|(kind, _)| *kind == DEBUG_S_FILECHKSMS)
+        else {
+            return Ok(());
+        };
+        let mut files = HashMap::new();
+
+        for (_, data) in subsections().filter(|(kind, _)| *kind == DEBUG_S_LINES) {
+            let code_offset = u32_at(data, 0)?;
+            let section = u16_at(data, 4)?;
+            let code_size = u32_at(data, 8)?;
+            // Offsets of lines are relative to the start of the code.
+            let Some(base) = section_rva(sections, section, code_offset) else {
+                continue;
+            };
+
+            let mut offset = 12;
+            while offset + 12 <= data.len() {
+                let checksum = u32_at(data, offset)? as usize;
+                let count = u32_at(data, offset + 4)? as usize;
+                let block_size = u32_at(data, offset + 8)? as usize;
+
+                let file = match files.get(&checksum) {
+                    Some(file) => *file,
+                    None => {
+                        let name = str_at(names, u32_at(checksums, checksum)? as usize)?;
+                        self.files.push(String::from_utf8_lossy(name).into_owned());
+                        files.insert(checksum, self.files.len() - 1);
+                        self.files.len() - 1
+                    }
+                };
+
+                let entries = slice(data, offset + 12, count * 8)?;
+                for i in 0..count {
+                    let start = u32_at(entries, i * 8)?;
+                    let line = u32_at(entries, i * 8 + 4)? & 0xFFFFFF;
+                    let end = if i + 1 < count {
+                        u32_at(entries, i * 8 + 8)?
+                    } else {
+                        code_size
+                    };
+                    self.lines
+                        .push((base.wrapping_add(start), base.wrapping_add(end), file, line));
+                }
+                // The size includes the header and the columns following the lines.
+                offset += block_size.max(12);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the source lines of all modules, files without a string table have none.
+fn read_lines(msf: &Msf, dbi: &[u8], sections: &[u32]) -> Result<LineTable> {
+    let mut table = LineTable::default();
+    let Some(names_stream) = find_named_stream(&msf.stream(INFO_STREAM)?, b"/names")? else {
+        return Ok(table);
+    };
+    // The string table starts with its signature, hash version and size.
+    let names = msf.stream(names_stream)?;
+    let names = slice(&names, 12, u32_at(&names, 8)? as usize)?;
+
+    let modules = slice(dbi, DBI_HEADER_SIZE, u32_at(dbi, 24)? as usize)?;
+    let mut offset = 0;
+    while offset + MODULE_INFO_SIZE <= modules.len() {
+        let stream = u16_at(modules, offset + 34)?;
+        let symbols_size = u32_at(modules, offset + 36)? as usize;
+        let c11_size = u32_at(modules, offset + 40)? as usize;
+        let c13_size = u32_at(modules, offset + 44)? as usize;
+
+        // The module name and object file name follow.
+        let name_len = str_at(modules, offset + MODULE_INFO_SIZE)?.len() + 1;
+        let object_len = str_at(modules, offset + MODULE_INFO_SIZE + name_len)?.len() + 1;
+        offset = (offset + MODULE_INFO_SIZE + name_len + object_len).next_multiple_of(4);
+
+        if stream != NO_STREAM && c13_size > 0 {
+            let module = msf.stream(stream as usize)?;
+            let c13 = slice(&module, symbols_size + c11_size, c13_size)?;
+            table.add_module(c13, sections, names)?;
+        }
+    }
+
+    table.lines.sort_by_key(|(start, ..)| *start);
+    Ok(table)
+}
+
+/// Finds the stream with the name in the named stream map of the PDB info stream.
+fn find_named_stream(info: &[u8], name: &[u8]) -> Result<Option<usize>> {
+    let names_len = u32_at(info, INFO_HEADER_SIZE)? as usize;
+    let names = slice(info, INFO_HEADER_SIZE + 4, names_len)?;
+    let mut offset = INFO_HEADER_SIZE + 4 + names_len;
+
+    // Hash table of name offsets to streams, present buckets are marked in a bit vector
+    // followed by one of deleted buckets.
+    offset += 8;
+    let present_words = u32_at(info, offset)? as usize;
+    let present = slice(info, offset + 4, present_words * 4)?;
+    offset += 4 + present_words * 4;
+    offset += 4 + u32_at(info, offset)? as usize * 4;
+
+    for bucket in 0..present_words * 32 {
+        if present[bucket / 8] & (1 << (bucket % 8)) == 0 {
+            continue;
+        }
+        let key = u32_at(info, offset)? as usize;
+        let stream = u32_at(info, offset + 4)? as usize;
+        offset += 8;
+        if str_at(names, key)? == name {
+            return Ok(Some(stream));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subsection(kind: u32, data: &[u8]) -> Vec<u8> {
+        let mut buf = [kind.to_le_bytes(), (data.len() as u32).to_le_bytes()].concat();
+        buf.extend_from_slice(data);
+        buf.resize(buf.len().next_multiple_of(4), 0);
+        buf
+    }
+
+    fn dwords(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn lines_resolve_within_their_block() {
+        let names = b"\0main.cpp\0util.cpp\0";
+        // Name offset, checksum size and kind, padded to 4 bytes.
+        let checksums = [dwords(&[10, 0]), dwords(&[1, 0])].concat();
+
+        // Code at offset 0x100 of section 2, 0x20 bytes long.
+        let mut lines = dwords(&[0x100]);
+        lines.extend_from_slice(&2u16.to_le_bytes());
+        lines.extend_from_slice(&0u16.to_le_bytes());
+        lines.extend(dwords(&[0x20]));
+        lines.extend(dwords(&[0, 2, 12 + 2 * 8, 0, 12 | 1 << 31, 0x10, 14]));
+        lines.extend(dwords(&[8, 1, 12 + 8, 0x18, MAX_LINE]));
+
+        let c13 = [
+            subsection(DEBUG_S_FILECHKSMS, &checksums),
+            subsection(DEBUG_S_LINES, &lines),
+        ]
+        .concat();
+
+        let mut table = LineTable::default();
+        table.add_module(&c13, &[0x1000, 0x2000], names).unwrap();
+        table.lines.sort_by_key(|(start, ..)| *start);
+
+        assert_eq!(table.resolve(0x20FF), None);
+        assert_eq!(table.resolve(0x2100), Some(("util.cpp", 12)));
+        assert_eq!(table.resolve(0x210F), Some(("util.cpp", 12)));
+        assert_eq!(table.resolve(0x2110), Some(("util.cpp", 14)));
+        assert_eq!(table.resolve(0x2118), None);
+        assert_eq!(table.resolve(0x2120), None);
+    }
+
+    #[test]
+    fn named_streams_are_found() {
+        let mut info = vec![0; INFO_HEADER_SIZE];
+        let names = b"/LinkInfo\0/names\0";
+        info.extend(dwords(&[names.len() as u32]));
+        info.extend_from_slice(names);
+        // Size, capacity, present buckets 1 and 3, no deleted buckets, then the entries.
+        info.extend(dwords(&[2, 4, 1, 0b1010, 0, 0, 5, 10, 12]));
+
+        assert_eq!(find_named_stream(&info, b"/names").unwrap(), Some(12));
+        assert_eq!(find_named_stream(&info, b"/LinkInfo").unwrap(), Some(5));
+        assert_eq!(find_named_stream(&info, b"/src/headerblock").unwrap(), None);
+    }
+}
@@ -0,0 +1,181 @@
+use eframe::{
+    egui::{Context, Visuals},
+    epaint::Color32,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+pub const DEFAULT_THEME: &str = "Dark";
+
+static BUILTIN_THEMES: LazyLock<[Theme; 3]> =
+    LazyLock::new(|| [Theme::dark(), Theme::light(), Theme::solarized_dark()]);
+
+/// Colors of the UI and of the inspected fields. Custom themes are stored in the config with
+/// colors as `"#RRGGBB"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    /// Starts from the dark egui visuals instead of the light ones.
+    pub dark: bool,
+    #[serde(with = "hex_color")]
+    pub background: Color32,
+    /// Labels and other text of the UI.
+    #[serde(with = "hex_color")]
+    pub text: Color32,
+    /// Links and highlighted UI elements.
+    #[serde(with = "hex_color")]
+    pub accent: Color32,
+    #[serde(with = "hex_color")]
+    pub toast_background: Color32,
+    #[serde(with = "hex_color")]
+    pub toast_text: Color32,
+    /// Values of fields.
+    #[serde(with = "hex_color")]
+    pub foreground: Color32,
+    /// Types, hints and other secondary text of fields.
+    #[serde(with = "hex_color")]
+    pub dim: Color32,
+    /// Underline of selected fields.
+    #[serde(with = "hex_color")]
+    pub selection: Color32,
+    /// Zero bytes of unknown fields.
+    #[serde(with = "hex_color")]
+    pub unknown: Color32,
+    #[serde(with = "hex_color")]
+    pub pointer: Color32,
+    /// Offsets of naturally aligned fields.
+    #[serde(with = "hex_color")]
+    pub offset: Color32,
+    #[serde(with = "hex_color")]
+    pub address: Color32,
+    #[serde(with = "hex_color")]
+    pub named_address: Color32,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: DEFAULT_THEME.into(),
+            dark: true,
+            background: Color32::from_gray(27),
+            text: Color32::from_gray(140),
+            accent: Color32::from_rgb(90, 170, 255),
+            toast_background: Color32::from_rgb(0x10, 0x10, 0x10),
+            toast_text: Color32::LIGHT_GRAY,
+            foreground: Color32::WHITE,
+            dim: Color32::GRAY,
+            selection: Color32::LIGHT_GRAY,
+            unknown: Color32::DARK_GRAY,
+            pointer: Color32::YELLOW,
+            offset: Color32::from_rgb(0x9A, 0xCD, 0x32),
+            address: Color32::LIGHT_GREEN,
+            named_address: Color32::GOLD,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "Light".into(),
+            dark: false,
+            background: Color32::from_gray(248),
+            text: Color32::from_gray(80),
+            accent: Color32::from_rgb(0, 155, 255),
+            toast_background: Color32::from_gray(230),
+            toast_text: Color32::from_gray(40),
+            foreground: Color32::BLACK,
+            dim: Color32::from_gray(110),
+            selection: Color32::from_gray(60),
+            unknown: Color32::from_gray(170),
+            pointer: Color32::from_rgb(0xA0, 0x78, 0x00),
+            offset: Color32::from_rgb(0x3C, 0x78, 0x14),
+            address: Color32::from_rgb(0x00, 0x82, 0x3C),
+            named_address: Color32::from_rgb(0xAA, 0x6E, 0x00),
+        }
+    }
+
+    pub fn solarized_dark() -> Self {
+        Self {
+            name: "Solarized Dark".into(),
+            dark: true,
+            background: Color32::from_rgb(0x00, 0x2B, 0x36),
+            text: Color32::from_rgb(0x83, 0x94, 0x96),
+            accent: Color32::from_rgb(0x26, 0x8B, 0xD2),
+            toast_background: Color32::from_rgb(0x07, 0x36, 0x42),
+            toast_text: Color32::from_rgb(0x93, 0xA1, 0xA1),
+            foreground: Color32::from_rgb(0xEE, 0xE8, 0xD5),
+            dim: Color32::from_rgb(0x58, 0x6E, 0x75),
+            selection: Color32::from_rgb(0x93, 0xA1, 0xA1),
+            unknown: Color32::from_rgb(0x58, 0x6E, 0x75),
+            pointer: Color32::from_rgb(0xB5, 0x89, 0x00),
+            offset: Color32::from_rgb(0x85, 0x99, 0x00),
+            address: Color32::from_rgb(0x2A, 0xA1, 0x98),
+            named_address: Color32::from_rgb(0xCB, 0x4B, 0x16),
+        }
+    }
+
+    pub fn builtin() -> &'static [Theme] {
+        &*BUILTIN_THEMES
+    }
+
+    pub fn visuals(&self) -> Visuals {
+        let mut visuals = if self.dark {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+        visuals.panel_fill = self.background;
+        visuals.window_fill = self.background;
+        visuals.widgets.noninteractive.bg_fill = self.background;
+        visuals.widgets.noninteractive.fg_stroke.color = self.text;
+        visuals.hyperlink_color = self.accent;
+        visuals
+    }
+
+    /// Sets the visuals of the theme unless they're already set.
+    pub fn apply(&self, ctx: &Context) {
+        let visuals = self.visuals();
+        if ctx.style().visuals != visuals {
+            ctx.set_visuals(visuals);
+        }
+    }
+
+    /// Visuals of toasts, they're drawn over the other panels and need more contrast.
+    pub fn toast_visuals(&self) -> Visuals {
+        let mut visuals = self.visuals();
+        visuals.widgets.noninteractive.bg_fill = self.toast_background;
+        visuals.widgets.noninteractive.fg_stroke.color = self.toast_text;
+        visuals
+    }
+}
+
+mod hex_color {
+    use eframe::epaint::Color32;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!(
+            "#{:02X}{:02X}{:02X}",
+            color.r(),
+            color.g(),
+            color.b()
+        ))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color32, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let hex = text.trim_start_matches('#');
+        if hex.len() != 6 {
+            return Err(D::Error::custom(format!(
+                "`{text}` isn't a `#RRGGBB` color"
+            )));
+        }
+
+        let rgb = u32::from_str_radix(hex, 16)
+            .map_err(|_| D::Error::custom(format!("`{text}` isn't a `#RRGGBB` color")))?;
+        Ok(Color32::from_rgb(
+            (rgb >> 16) as u8,
+            (rgb >> 8) as u8,
+            rgb as u8,
+        ))
+    }
+}
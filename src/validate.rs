@@ -0,0 +1,177 @@
+use crate::{
+    app::is_valid_ident,
+    class::{Class, ClassList},
+    field::{walk_fields, FieldId, FieldKind},
+};
+use std::{collections::HashSet, fmt};
+
+/// Problem with the layout of a class, generated code of the class would be wrong because of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Embedded class changed size since the field was created, fields after it overlap it or
+    /// leave a gap.
+    SizeMismatch {
+        field: FieldId,
+        name: String,
+        offset: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// Pointer isn't aligned to the pointer size of the process.
+    UnalignedPointer {
+        field: FieldId,
+        name: String,
+        offset: usize,
+        alignment: usize,
+    },
+    /// Embedded or pointed to class was deleted.
+    MissingClass {
+        field: FieldId,
+        name: String,
+        offset: usize,
+    },
+    InvalidName {
+        field: FieldId,
+        name: String,
+        offset: usize,
+    },
+    /// Name was already used by a field at `first_offset`.
+    DuplicateName {
+        field: FieldId,
+        name: String,
+        offset: usize,
+        first_offset: usize,
+    },
+}
+
+impl ValidationError {
+    /// Field with the problem and its offset.
+    pub fn location(&self) -> (FieldId, usize) {
+        match self {
+            Self::SizeMismatch { field, offset, .. }
+            | Self::UnalignedPointer { field, offset, .. }
+            | Self::MissingClass { field, offset, .. }
+            | Self::InvalidName { field, offset, .. }
+            | Self::DuplicateName { field, offset, .. } => (*field, *offset),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SizeMismatch {
+                name,
+                offset,
+                expected,
+                actual,
+                ..
+            } => write!(
+                f,
+                "`{name}` at 0x{offset:X} is 0x{actual:X} bytes but its class is 0x{expected:X}"
+            ),
+            Self::UnalignedPointer {
+                name,
+                offset,
+                alignment,
+                ..
+            } => write!(
+                f,
+                "Pointer `{name}` at 0x{offset:X} isn't aligned to {alignment} bytes"
+            ),
+            Self::MissingClass { name, offset, .. } => {
+                write!(f, "Class of `{name}` at 0x{offset:X} doesn't exist")
+            }
+            Self::InvalidName { name, offset, .. } => {
+                write!(f, "`{name}` at 0x{offset:X} isn't a valid identifier")
+            }
+            Self::DuplicateName {
+                name,
+                offset,
+                first_offset,
+                ..
+            } => write!(
+                f,
+                "`{name}` at 0x{offset:X} is already the name of the field at 0x{first_offset:X}"
+            ),
+        }
+    }
+}
+
+/// Checks fields of the class, including fields of its sections. Offsets include the base class.
+pub fn validate_class(list: &ClassList, class: &Class) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    let mut names = HashSet::new();
+    let fields = walk_fields(&class.fields, list.base_size(class.id()));
+
+    for &(offset, field) in &fields {
+        let Some(name) = field.name() else {
+            continue;
+        };
+        let id = field.id();
+
+        if !is_valid_ident(&name) {
+            errors.push(ValidationError::InvalidName {
+                field: id,
+                name: name.clone(),
+                offset,
+            });
+        } else if !names.insert(name.clone()) {
+            let first_offset = fields
+                .iter()
+                .find(|(_, f)| f.name().as_deref() == Some(&name))
+                .map(|(offset, _)| *offset)
+                .unwrap_or_default();
+            errors.push(ValidationError::DuplicateName {
+                field: id,
+                name: name.clone(),
+                offset,
+                first_offset,
+            });
+        }
+
+        let kind = field.kind();
+        let alignment = list.pointer_size();
+        if matches!(
+            kind,
+            FieldKind::Ptr
+                | FieldKind::StrPtr
+                | FieldKind::WStrPtr
+                | FieldKind::FuncPtr
+                | FieldKind::Vtable(_)
+                | FieldKind::PointerChain(_)
+        ) && !offset.is_multiple_of(alignment)
+        {
+            errors.push(ValidationError::UnalignedPointer {
+                field: id,
+                name: name.clone(),
+                offset,
+                alignment,
+            });
+        }
+
+        let class_id = field.embedded_class().or(field.pointed_class());
+        match class_id.map(|cid| list.by_id(cid).map(|c| c.id())) {
+            Some(None) => errors.push(ValidationError::MissingClass {
+                field: id,
+                name,
+                offset,
+            }),
+            Some(Some(cid)) if field.embedded_class().is_some() => {
+                let expected = list.class_size(cid);
+                if expected != field.size() {
+                    errors.push(ValidationError::SizeMismatch {
+                        field: id,
+                        name,
+                        offset,
+                        expected,
+                        actual: field.size(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    errors
+}
@@ -16,6 +16,22 @@ pub enum Value {
 }
 
 impl Value {
+    /// Lossy conversion used for plotting.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Value::U8(v) => v as f64,
+            Value::I8(v) => v as f64,
+            Value::U16(v) => v as f64,
+            Value::I16(v) => v as f64,
+            Value::U32(v) => v as f64,
+            Value::I32(v) => v as f64,
+            Value::U64(v) => v as f64,
+            Value::I64(v) => v as f64,
+            Value::F32(v) => v as f64,
+            Value::F64(v) => v,
+        }
+    }
+
     pub const fn kind(&self) -> FieldKind {
         match self {
             Value::U8(_) => FieldKind::U8,
@@ -0,0 +1,33 @@
+use crate::process::Process;
+
+/// Longest vtable that is scanned, memory of minidumps doesn't tell code from data.
+pub const MAX_VTABLE_LEN: usize = 1024;
+
+/// Functions of the vtable at `vtable_addr`, pointers are read until the first one that doesn't
+/// point to executable memory.
+pub fn scan_vtable(process: &Process, vtable_addr: usize) -> Vec<usize> {
+    // Minidumps don't have the protection of their memory, any readable memory counts as code.
+    let code = process
+        .regions()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.prot.is_none_or(|p| p.execute()))
+        .map(|r| r.range)
+        .collect::<Vec<_>>();
+
+    let pointer_size = process.pointer_size();
+    let mut functions = vec![];
+    for i in 0..MAX_VTABLE_LEN {
+        let mut buf = [0; 8];
+        if !process.read_checked(vtable_addr + i * pointer_size, &mut buf[..pointer_size]) {
+            break;
+        }
+
+        let function = usize::from_ne_bytes(buf);
+        if function == 0 || !code.iter().any(|r| r.contains(&function)) {
+            break;
+        }
+        functions.push(function);
+    }
+    functions
+}
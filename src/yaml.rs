@@ -0,0 +1,491 @@
+use crate::json::{self, Error, Value};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Write;
+
+/// Serializes the value as block style YAML where every list item and mapping entry is on its own
+/// line. The document has the same structure as the JSON one, enums are externally tagged.
+pub fn to_string<T: Serialize>(value: &T) -> eyre::Result<String> {
+    let value = json::to_value(value)?;
+    let mut out = String::new();
+    if is_block(&value) {
+        write_block(&mut out, &value, 0);
+    } else {
+        write_scalar(&mut out, &value);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Reads block and flow style YAML without anchors, tags and multi-line strings.
+pub fn from_str<T: DeserializeOwned>(text: &str) -> eyre::Result<T> {
+    let mut parser = Parser {
+        lines: lines(text)?,
+        pos: 0,
+    };
+    let value = match parser.lines.first() {
+        Some(_) => parser.node()?,
+        None => Value::Null,
+    };
+    if let Some(line) = parser.lines.get(parser.pos) {
+        return Err(Error(format!("Line {}: unexpected indentation", line.number)).into());
+    }
+    Ok(json::from_value(value)?)
+}
+
+fn is_block(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(entries) => !entries.is_empty(),
+        _ => false,
+    }
+}
+
+fn pad(out: &mut String, indent: usize) {
+    out.extend(std::iter::repeat_n(' ', indent));
+}
+
+fn write_block(out: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Object(entries) => {
+            for (key, value) in entries {
+                pad(out, indent);
+                write_string(out, key);
+                out.push(':');
+                if is_block(value) {
+                    out.push('\n');
+                    write_block(out, value, indent + 2);
+                } else {
+                    out.push(' ');
+                    write_scalar(out, value);
+                    out.push('\n');
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                pad(out, indent);
+                out.push_str("- ");
+                if is_block(item) {
+                    // The first line of the item goes right after the dash.
+                    let mut nested = String::new();
+                    write_block(&mut nested, item, indent + 2);
+                    out.push_str(&nested[indent + 2..]);
+                } else {
+                    write_scalar(out, item);
+                    out.push('\n');
+                }
+            }
+        }
+        value => {
+            write_scalar(out, value);
+            out.push('\n');
+        }
+    }
+}
+
+fn write_scalar(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => _ = write!(out, "{b}"),
+        Value::Unsigned(n) => _ = write!(out, "{n}"),
+        Value::Signed(n) => _ = write!(out, "{n}"),
+        Value::Float(f) if f.is_nan() => out.push_str(".nan"),
+        Value::Float(f) if f.is_infinite() => out.push_str(if *f > 0. { ".inf" } else { "-.inf" }),
+        Value::Float(f) => _ = write!(out, "{f:?}"),
+        Value::String(s) => write_string(out, s),
+        Value::Array(_) => out.push_str("[]"),
+        Value::Object(_) => out.push_str("{}"),
+    }
+}
+
+/// Strings that could be read as another type or contain special characters are quoted.
+fn write_string(out: &mut String, s: &str) {
+    let plain = s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '_' | ' ' | '.' | '-' | '/' | '(' | ')' | '$')
+        })
+        && !s.ends_with(' ')
+        // YAML 1.1 reads these as booleans.
+        && !matches!(
+            s.to_ascii_lowercase().as_str(),
+            "y" | "n" | "yes" | "no" | "on" | "off"
+        )
+        && matches!(resolve(s), Value::String(_));
+
+    if plain {
+        out.push_str(s);
+    } else {
+        json::write_string(out, s);
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+/// Splits the text into lines without comments and blank lines.
+fn lines(text: &str) -> Result<Vec<Line>, Error> {
+    let mut lines = vec![];
+    for (i, raw) in text.lines().enumerate() {
+        let number = i + 1;
+        let content = strip_comment(raw).trim_end();
+        let text = content.trim_start_matches(' ');
+        match text {
+            "" | "---" => continue,
+            "..." => break,
+            _ if text.starts_with('\t') => {
+                return Err(Error(format!(
+                    "Line {number}: tabs can't be used for indentation"
+                )))
+            }
+            _ if text.starts_with('%') => {
+                return Err(Error(format!(
+                    "Line {number}: directives are not supported"
+                )))
+            }
+            _ => {}
+        }
+
+        lines.push(Line {
+            number,
+            indent: content.len() - text.len(),
+            text: text.to_owned(),
+        });
+    }
+    Ok(lines)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    let mut prev = ' ';
+
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '#' && prev.is_whitespace() => return &line[..i],
+            // Quotes in the middle of a plain string like `it's` don't start a quoted string.
+            None if matches!(c, '"' | '\'') && (prev.is_whitespace() || "[{,:-".contains(prev)) => {
+                quote = Some(c)
+            }
+            None => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Splits `key: value` into the key and the rest of the line.
+fn split_key(text: &str, number: usize) -> Result<Option<(String, &str)>, Error> {
+    let (key, rest) = if text.starts_with(['"', '\'']) {
+        let (key, len) = quoted(text, number)?;
+        (key, text[len..].trim_start())
+    } else if text.starts_with(['[', '{']) {
+        return Ok(None);
+    } else {
+        match text.find(": ").or(text.strip_suffix(':').map(str::len)) {
+            Some(end) => (text[..end].trim_end().to_owned(), &text[end..]),
+            None => return Ok(None),
+        }
+    };
+
+    Ok(rest.strip_prefix(':').map(|rest| (key, rest.trim_start())))
+}
+
+/// Reads a single or double quoted string, returns it along with the length of the quoted text.
+fn quoted(text: &str, number: usize) -> Result<(String, usize), Error> {
+    let invalid = || Error(format!("Line {number}: invalid quoted string"));
+
+    if text.starts_with('"') {
+        return json::parse_string(text).map_err(|_| invalid());
+    }
+
+    // Single quotes are escaped by doubling them.
+    let mut out = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '\'' {
+            out.push(c);
+        } else if chars.next_if(|(_, c)| *c == '\'').is_some() {
+            out.push('\'');
+        } else {
+            return Ok((out, i + 1));
+        }
+    }
+    Err(invalid())
+}
+
+/// Reads a plain scalar as the type it looks like.
+fn resolve(s: &str) -> Value {
+    match s {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        ".inf" | "+.inf" | ".Inf" | "+.Inf" => Value::Float(f64::INFINITY),
+        "-.inf" | "-.Inf" => Value::Float(f64::NEG_INFINITY),
+        ".nan" | ".NaN" => Value::Float(f64::NAN),
+        _ => {
+            if let Some(hex) = s.strip_prefix("0x") {
+                if let Ok(n) = u64::from_str_radix(hex, 16) {
+                    return Value::Unsigned(n);
+                }
+            }
+            if let Ok(n) = s.parse() {
+                return Value::Unsigned(n);
+            }
+            if let Ok(n) = s.parse() {
+                return Value::Signed(n);
+            }
+
+            let numeric = s.bytes().any(|b| b.is_ascii_digit())
+                && s.bytes()
+                    .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b));
+            match s.parse() {
+                Ok(f) if numeric => Value::Float(f),
+                _ => Value::String(s.to_owned()),
+            }
+        }
+    }
+}
+
+struct Parser {
+    lines: Vec<Line>,
+    pos: usize,
+}
+
+impl Parser {
+    /// Reads the node starting at the current line, which must exist.
+    fn node(&mut self) -> Result<Value, Error> {
+        let line = &self.lines[self.pos];
+        if is_item(&line.text) {
+            self.sequence(line.indent)
+        } else if split_key(&line.text, line.number)?.is_some() {
+            self.mapping(line.indent)
+        } else {
+            self.pos += 1;
+            scalar(&line.text, line.number)
+        }
+    }
+
+    /// Value that starts on the line after a key or a dash indented by `indent`. Sequences in
+    /// mappings may be at the same indentation as their key.
+    fn child(&mut self, indent: usize, in_mapping: bool) -> Result<Value, Error> {
+        match self.lines.get(self.pos) {
+            Some(line) if line.indent > indent => self.node(),
+            Some(line) if in_mapping && line.indent == indent && is_item(&line.text) => {
+                self.sequence(indent)
+            }
+            _ => Ok(Value::Null),
+        }
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value, Error> {
+        let mut items = vec![];
+        while let Some(line) = self.lines.get_mut(self.pos) {
+            if line.indent > indent {
+                return Err(Error(format!(
+                    "Line {}: unexpected indentation",
+                    line.number
+                )));
+            }
+            if line.indent < indent || !is_item(&line.text) {
+                break;
+            }
+
+            let rest = line.text[1..].trim_start().to_owned();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.child(indent, false)?);
+            } else {
+                // The item starts on the line of the dash as if it was on its own line.
+                line.indent += line.text.len() - rest.len();
+                line.text = rest;
+                items.push(self.node()?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value, Error> {
+        let mut entries = vec![];
+        while let Some(line) = self.lines.get(self.pos) {
+            let number = line.number;
+            if line.indent > indent {
+                return Err(Error(format!("Line {number}: unexpected indentation")));
+            }
+            if line.indent < indent {
+                break;
+            }
+            let Some((key, rest)) = split_key(&line.text, number)? else {
+                return Err(Error(format!("Line {number}: expected `key: value`")));
+            };
+
+            let rest = rest.to_owned();
+            self.pos += 1;
+            let value = if rest.is_empty() {
+                self.child(indent, true)?
+            } else {
+                scalar(&rest, number)?
+            };
+            entries.push((key, value));
+        }
+        Ok(Value::Object(entries))
+    }
+}
+
+/// Reads the text as a single value: a plain or quoted scalar or a flow collection.
+fn scalar(text: &str, number: usize) -> Result<Value, Error> {
+    let mut flow = Flow {
+        text,
+        pos: 0,
+        number,
+    };
+    let value = flow.value(false)?;
+    flow.skip_whitespace();
+    if flow.pos < text.len() {
+        return Err(flow.error(&format!("unexpected `{}`", &text[flow.pos..])));
+    }
+    Ok(value)
+}
+
+/// Parser of `[a, b]` and `{key: value}` collections.
+struct Flow<'a> {
+    text: &'a str,
+    pos: usize,
+    number: usize,
+}
+
+impl Flow<'_> {
+    fn error(&self, msg: &str) -> Error {
+        Error(format!("Line {}: {msg}", self.number))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.text[self.pos..].starts_with(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Separator between items, returns `true` once the collection is closed.
+    fn next_or_end(&mut self, end: char) -> Result<bool, Error> {
+        if self.eat(end) {
+            Ok(true)
+        } else if self.eat(',') {
+            Ok(self.eat(end))
+        } else {
+            Err(self.error(&format!("expected `,` or `{end}`")))
+        }
+    }
+
+    fn value(&mut self, in_flow: bool) -> Result<Value, Error> {
+        self.skip_whitespace();
+        let rest = &self.text[self.pos..];
+
+        match rest.chars().next() {
+            Some('[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                if !self.eat(']') {
+                    loop {
+                        items.push(self.value(true)?);
+                        if self.next_or_end(']')? {
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut entries = vec![];
+                if !self.eat('}') {
+                    loop {
+                        let key = match self.value(true)? {
+                            Value::String(s) => s,
+                            Value::Unsigned(n) => n.to_string(),
+                            Value::Signed(n) => n.to_string(),
+                            _ => return Err(self.error("mapping keys must be strings")),
+                        };
+                        if !self.eat(':') {
+                            return Err(self.error("expected `:`"));
+                        }
+                        entries.push((key, self.value(true)?));
+                        if self.next_or_end('}')? {
+                            break;
+                        }
+                    }
+                }
+                Ok(Value::Object(entries))
+            }
+            Some('"' | '\'') => {
+                let (s, len) = quoted(rest, self.number)?;
+                self.pos += len;
+                Ok(Value::String(s))
+            }
+            Some('|' | '>') => Err(self.error("multi-line strings are not supported")),
+            Some('&' | '*' | '!') => Err(self.error("anchors, aliases and tags are not supported")),
+            _ => {
+                let end = if in_flow {
+                    rest.find([',', ']', '}', ':']).unwrap_or(rest.len())
+                } else {
+                    rest.len()
+                };
+                self.pos += end;
+                Ok(resolve(rest[..end].trim_end()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectData;
+
+    #[test]
+    fn project_round_trip() {
+        let project = ProjectData::sample();
+        let text = to_string(&project).unwrap();
+        let back = from_str::<ProjectData>(&text).unwrap();
+        assert_eq!(
+            ron::to_string(&back).unwrap(),
+            ron::to_string(&project).unwrap(),
+            "{text}"
+        );
+        assert_eq!(to_string(&back).unwrap(), text);
+
+        let (list, ..) = back.load();
+        let address = |name| list.by_name(name).unwrap().address.get();
+        assert_eq!(address("Empty"), 0);
+        assert_eq!(address("Solid"), usize::MAX);
+    }
+
+    #[test]
+    fn empty_project_round_trip() {
+        let empty = ron::from_str::<ProjectData>("(classes: [])").unwrap();
+        let text = to_string(&empty).unwrap();
+        let back = from_str::<ProjectData>(&text).unwrap();
+        assert_eq!(
+            ron::to_string(&back).unwrap(),
+            ron::to_string(&empty).unwrap()
+        );
+    }
+}